@@ -67,6 +67,8 @@ pub enum Token<'input> {
 
     Uint(u16),
     Int(u16),
+    Ufixed(u16, u8),
+    Fixed(u16, u8),
     Bytes(u8),
     // prior to 0.8.0 `byte` used to be an alias for `bytes1`
     Byte,
@@ -200,6 +202,8 @@ impl<'input> fmt::Display for Token<'input> {
             Token::HexNumber(n) => write!(f, "{}", n),
             Token::Uint(w) => write!(f, "uint{}", w),
             Token::Int(w) => write!(f, "int{}", w),
+            Token::Ufixed(m, n) => write!(f, "ufixed{}x{}", m, n),
+            Token::Fixed(m, n) => write!(f, "fixed{}x{}", m, n),
             Token::Bytes(w) => write!(f, "bytes{}", w),
             Token::Byte => write!(f, "byte"),
             Token::DynamicBytes => write!(f, "bytes"),
@@ -560,6 +564,48 @@ static KEYWORDS: phf::Map<&'static str, Token> = phf_map! {
     "let" => Token::Let,
 };
 
+/// Fixed point types are named fixedMxN or ufixedMxN, where M is the number of bits and
+/// N the number of decimals. There are too many of them for the keywords table.
+fn fixed_type(id: &str) -> Option<Token<'static>> {
+    let (signed, dimensions) = if let Some(dimensions) = id.strip_prefix("ufixed") {
+        (false, dimensions)
+    } else if let Some(dimensions) = id.strip_prefix("fixed") {
+        (true, dimensions)
+    } else {
+        return None;
+    };
+
+    let (m, n) = if dimensions.is_empty() {
+        (128, 18)
+    } else {
+        let (m, n) = dimensions.split_once('x')?;
+
+        // leading zeros or signs are not permitted
+        if [m, n].iter().any(|d| {
+            d.is_empty()
+                || !d.chars().all(|c| c.is_ascii_digit())
+                || (d.len() > 1 && d.starts_with('0'))
+        }) {
+            return None;
+        }
+
+        let m: u16 = m.parse().ok()?;
+        let n: u8 = n.parse().ok()?;
+
+        if m == 0 || m > 256 || m % 8 != 0 || n > 80 {
+            return None;
+        }
+
+        (m, n)
+    };
+
+    Some(if signed {
+        Token::Fixed(m, n)
+    } else {
+        Token::Ufixed(m, n)
+    })
+}
+
 impl<'input> Lexer<'input> {
     pub fn new(input: &'input str, file_no: usize, comments: &'input mut Vec<Comment>) -> Self {
         Lexer {
@@ -839,6 +885,8 @@ impl<'input> Lexer<'input> {
 
                     return if let Some(w) = KEYWORDS.get(id) {
                         Some(Ok((start, *w, end)))
+                    } else if let Some(w) = fixed_type(id) {
+                        Some(Ok((start, w, end)))
                     } else {
                         Some(Ok((start, Token::Identifier(id), end)))
                     };
@@ -1225,6 +1273,31 @@ fn lexertest() {
 
     assert_eq!(tokens, vec!(Ok((0, Token::Uint(8), 5))));
 
+    let tokens = Lexer::new("fixed ufixed64x10 fixed256x80", 0, &mut comments)
+        .collect::<Vec<Result<(usize, Token, usize), LexicalError>>>();
+
+    assert_eq!(
+        tokens,
+        vec!(
+            Ok((0, Token::Fixed(128, 18), 5)),
+            Ok((6, Token::Ufixed(64, 10), 17)),
+            Ok((18, Token::Fixed(256, 80), 29))
+        )
+    );
+
+    let tokens = Lexer::new("fixed7x1 ufixed8x81 fixed08x1 fixed8", 0, &mut comments)
+        .collect::<Vec<Result<(usize, Token, usize), LexicalError>>>();
+
+    assert_eq!(
+        tokens,
+        vec!(
+            Ok((0, Token::Identifier("fixed7x1"), 8)),
+            Ok((9, Token::Identifier("ufixed8x81"), 19)),
+            Ok((20, Token::Identifier("fixed08x1"), 29)),
+            Ok((30, Token::Identifier("fixed8"), 36))
+        )
+    );
+
     let tokens = Lexer::new("hex", 0, &mut comments)
         .collect::<Vec<Result<(usize, Token, usize), LexicalError>>>();
 
@@ -118,6 +118,16 @@ contract dynNFT is ERC721, ERC721URIStorage, Ownable {
     pretty_test(src);
 }
 
+#[test]
+fn using_operators() {
+    let src = r#"
+contract C {
+    using {add as +, sub as -, neg as -, eq as ==, Math.lt as <} for Fixed18;
+}
+"#;
+    pretty_test(src);
+}
+
 #[test]
 fn list() {
     let src = r#"
@@ -698,3 +708,16 @@ contract Contract {
 }"#;
     pretty_test(src);
 }
+
+#[test]
+fn array_slice() {
+    let src = r#"
+contract proxy {
+    function forward(bytes calldata data) public returns (bytes memory) {
+        bytes memory args = data[4:];
+        bytes memory selector = data[:4];
+        return data[a:b];
+    }
+}"#;
+    pretty_test(src);
+}
@@ -377,6 +377,8 @@ pub enum Type {
     String,
     Int(u16),
     Uint(u16),
+    Fixed(u16, u8),
+    Ufixed(u16, u8),
     Bytes(u8),
     Rational,
     DynamicBytes,
@@ -398,6 +400,8 @@ impl Docable for Type {
             Type::String => text!("string"),
             Type::Int(size) => text!("int").append(size.to_string()),
             Type::Uint(size) => text!("uint").append(size.to_string()),
+            Type::Fixed(m, n) => text!(format!("fixed{}x{}", m, n)),
+            Type::Ufixed(m, n) => text!(format!("ufixed{}x{}", m, n)),
             Type::Bytes(size) => text!("bytes").append(size.to_string()),
             Type::DynamicBytes => text!("bytes"),
             Type::Mapping(_, from_expr, to_expr) => text!("mapping(")
@@ -537,17 +541,101 @@ impl ContractPart {
     }
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum UserDefinedOperator {
+    BitwiseAnd,
+    BitwiseNot,
+    Negate,
+    BitwiseOr,
+    BitwiseXor,
+    Add,
+    Divide,
+    Modulo,
+    Multiply,
+    Subtract,
+    Equal,
+    More,
+    MoreEqual,
+    Less,
+    LessEqual,
+    NotEqual,
+}
+
+impl UserDefinedOperator {
+    /// Number of arguments the function bound to this operator must have
+    pub fn args(&self) -> usize {
+        match self {
+            UserDefinedOperator::BitwiseNot | UserDefinedOperator::Negate => 1,
+            _ => 2,
+        }
+    }
+
+    /// Does this operator return a bool rather than the user defined type
+    pub fn is_comparison(&self) -> bool {
+        matches!(
+            self,
+            UserDefinedOperator::Equal
+                | UserDefinedOperator::More
+                | UserDefinedOperator::MoreEqual
+                | UserDefinedOperator::Less
+                | UserDefinedOperator::LessEqual
+                | UserDefinedOperator::NotEqual
+        )
+    }
+}
+
+impl fmt::Display for UserDefinedOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            UserDefinedOperator::BitwiseAnd => "&",
+            UserDefinedOperator::BitwiseNot => "~",
+            UserDefinedOperator::Negate => "-",
+            UserDefinedOperator::BitwiseOr => "|",
+            UserDefinedOperator::BitwiseXor => "^",
+            UserDefinedOperator::Add => "+",
+            UserDefinedOperator::Divide => "/",
+            UserDefinedOperator::Modulo => "%",
+            UserDefinedOperator::Multiply => "*",
+            UserDefinedOperator::Subtract => "-",
+            UserDefinedOperator::Equal => "==",
+            UserDefinedOperator::More => ">",
+            UserDefinedOperator::MoreEqual => ">=",
+            UserDefinedOperator::Less => "<",
+            UserDefinedOperator::LessEqual => "<=",
+            UserDefinedOperator::NotEqual => "!=",
+        };
+
+        write!(f, "{}", s)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct UsingFunction {
+    pub loc: Loc,
+    pub path: IdentifierPath,
+    pub oper: Option<UserDefinedOperator>,
+}
+
+impl Docable for UsingFunction {
+    fn to_doc(&self) -> RcDoc<()> {
+        match &self.oper {
+            Some(oper) => self.path.to_doc().append(format!(" as {}", oper)),
+            None => self.path.to_doc(),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum UsingList {
     Library(IdentifierPath),
-    Functions(Vec<IdentifierPath>),
+    Functions(Vec<UsingFunction>),
 }
 
 impl Docable for UsingList {
     fn to_doc(&self) -> RcDoc<()> {
         match self {
             UsingList::Library(ip) => ip.to_doc(),
-            UsingList::Functions(ips) => text!("{").append(list_to_doc(ips)).append("}"),
+            UsingList::Functions(funcs) => text!("{").append(list_to_doc(funcs)).append("}"),
         }
     }
 }
@@ -952,7 +1040,13 @@ impl Docable for Expression {
                 .append("[")
                 .append(option_box_to_doc(mexpr))
                 .append("]"),
-            Expression::ArraySlice(..) => panic!("Array slice not supported: {:#?}", self),
+            Expression::ArraySlice(_, expr, start, end) => expr
+                .to_doc()
+                .append("[")
+                .append(option_box_to_doc(start))
+                .append(":")
+                .append(option_box_to_doc(end))
+                .append("]"),
             Expression::Parenthesis(_, expr) => text!("(").append(expr.to_doc()).append(")"),
             Expression::FunctionCall(_, fun, args) => fun.to_doc().append(paren_list_to_doc(args)),
             Expression::MemberAccess(_, contract, field) => {
@@ -1371,7 +1465,7 @@ impl Docable for Statement {
 #[derive(Debug, PartialEq, Clone)]
 pub enum CatchClause {
     Simple(Loc, Option<Parameter>, Statement),
-    Named(Loc, Identifier, Parameter, Statement),
+    Named(Loc, IdentifierPath, Vec<Parameter>, Statement),
 }
 
 #[derive(Debug, PartialEq, Clone)]
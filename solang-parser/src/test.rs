@@ -233,11 +233,14 @@ fn parse_test() {
                         ),
                         CatchClause::Named(
                             Loc::File(0, 951, 1046),
-                            Identifier {
+                            IdentifierPath {
                                 loc: Loc::File(0, 957, 962),
-                                name: "Error".to_string(),
+                                identifiers: vec![Identifier {
+                                    loc: Loc::File(0, 957, 962),
+                                    name: "Error".to_string(),
+                                }],
                             },
-                            Parameter {
+                            vec![Parameter {
                                 loc: Loc::File(0, 963, 982),
                                 ty: Expression::Type(Loc::File(0, 963, 969), Type::String),
                                 storage: Some(StorageLocation::Memory(Loc::File(0, 970, 976))),
@@ -245,7 +248,7 @@ fn parse_test() {
                                     loc: Loc::File(0, 977, 982),
                                     name: "error".to_string(),
                                 }),
-                            },
+                            }],
                             Statement::Block {
                                 loc: Loc::File(0, 984, 1046),
                                 unchecked: false,
@@ -261,11 +264,14 @@ fn parse_test() {
                         ),
                         CatchClause::Named(
                             Loc::File(0, 1047, 1129),
-                            Identifier {
+                            IdentifierPath {
                                 loc: Loc::File(0, 1053, 1058),
-                                name: "Panic".to_string(),
+                                identifiers: vec![Identifier {
+                                    loc: Loc::File(0, 1053, 1058),
+                                    name: "Panic".to_string(),
+                                }],
                             },
-                            Parameter {
+                            vec![Parameter {
                                 loc: Loc::File(0, 1059, 1065),
                                 ty: Expression::Type(Loc::File(0, 1059, 1063), Type::Uint(256)),
                                 storage: None,
@@ -273,7 +279,7 @@ fn parse_test() {
                                     loc: Loc::File(0, 1064, 1065),
                                     name: "x".to_string(),
                                 }),
-                            },
+                            }],
                             Statement::Block {
                                 loc: Loc::File(0, 1067, 1129),
                                 unchecked: false,
@@ -0,0 +1,504 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Anchor style IDLs for Solana
+use crate::codegen::SOLANA_FIRST_OFFSET;
+use crate::sema::ast::{
+    ArrayLength, Function, Mutability, Namespace, Parameter, StructType, Tag, Type,
+};
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+use serde::Serialize;
+use solang_parser::pt;
+use std::collections::HashSet;
+
+#[derive(Serialize)]
+pub struct Idl {
+    pub version: String,
+    pub name: String,
+    pub instructions: Vec<IdlInstruction>,
+    pub accounts: Vec<IdlTypeDefinition>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub types: Vec<IdlTypeDefinition>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<IdlEvent>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<IdlErrorCode>,
+    pub metadata: IdlMetadata,
+}
+
+#[derive(Serialize)]
+pub struct IdlInstruction {
+    pub name: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub docs: Vec<String>,
+    pub accounts: Vec<IdlAccount>,
+    pub args: Vec<IdlField>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub returns: Option<IdlType>,
+}
+
+#[derive(Serialize)]
+pub struct IdlAccount {
+    pub name: String,
+    #[serde(rename = "isMut")]
+    pub is_mut: bool,
+    #[serde(rename = "isSigner")]
+    pub is_signer: bool,
+}
+
+#[derive(Serialize)]
+pub struct IdlField {
+    pub name: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub docs: Vec<String>,
+    #[serde(rename = "type")]
+    pub ty: IdlType,
+}
+
+#[derive(Serialize)]
+pub struct IdlTypeDefinition {
+    pub name: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub docs: Vec<String>,
+    #[serde(rename = "type")]
+    pub ty: IdlTypeDefinitionTy,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum IdlTypeDefinitionTy {
+    Struct { fields: Vec<IdlField> },
+    Enum { variants: Vec<IdlEnumVariant> },
+}
+
+#[derive(Serialize)]
+pub struct IdlEnumVariant {
+    pub name: String,
+}
+
+#[derive(Serialize)]
+pub struct IdlEvent {
+    pub name: String,
+    pub fields: Vec<IdlEventField>,
+}
+
+#[derive(Serialize)]
+pub struct IdlEventField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: IdlType,
+    pub index: bool,
+}
+
+#[derive(Serialize)]
+pub struct IdlErrorCode {
+    pub code: u32,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub msg: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct IdlMetadata {
+    /// Size of the fixed part of the account data; the heap follows it
+    #[serde(rename = "fixedLayoutSize")]
+    pub fixed_layout_size: u64,
+}
+
+/// Types as Anchor describes them; these are the Borsh encoded types
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+#[serde(untagged)]
+pub enum IdlType {
+    Primitive(String),
+    Array { array: (Box<IdlType>, usize) },
+    Vec { vec: Box<IdlType> },
+    Defined { defined: String },
+}
+
+impl IdlType {
+    fn primitive(name: &str) -> Self {
+        IdlType::Primitive(name.to_owned())
+    }
+
+    fn bytes(len: usize) -> Self {
+        IdlType::Array {
+            array: (Box::new(IdlType::primitive("u8")), len),
+        }
+    }
+}
+
+/// Collects the user defined types (structs and enums) used by the IDL
+struct TypeCollector<'a> {
+    ns: &'a Namespace,
+    types: Vec<IdlTypeDefinition>,
+    seen: HashSet<String>,
+}
+
+impl<'a> TypeCollector<'a> {
+    fn new(ns: &'a Namespace) -> Self {
+        TypeCollector {
+            ns,
+            types: Vec::new(),
+            seen: HashSet::new(),
+        }
+    }
+
+    /// Map a Solidity type to the Anchor type which matches its Borsh encoding
+    fn idl_type(&mut self, ty: &Type) -> IdlType {
+        match ty {
+            Type::Bool => IdlType::primitive("bool"),
+            // Fixed point values are encoded as their scaled underlying integer
+            Type::Int(n) | Type::Uint(n) | Type::Fixed(n, _) | Type::Ufixed(n, _) => {
+                let signed = if ty.is_signed_numeric() { "i" } else { "u" };
+
+                match n {
+                    8 | 16 | 32 | 64 | 128 | 256 => IdlType::Primitive(format!("{}{}", signed, n)),
+                    // Borsh encodes integers in as many bytes as they need, which
+                    // does not match any Anchor integer type
+                    _ => IdlType::bytes(*n as usize / 8),
+                }
+            }
+            Type::Value => IdlType::Primitive(format!("u{}", self.ns.value_length * 8)),
+            Type::Address(_) | Type::Contract(_) => IdlType::primitive("publicKey"),
+            Type::Bytes(n) => IdlType::bytes(*n as usize),
+            Type::DynamicBytes => IdlType::primitive("bytes"),
+            Type::String => IdlType::primitive("string"),
+            Type::Array(elem, dims) => {
+                let mut idl_ty = self.idl_type(elem);
+
+                for dim in dims {
+                    idl_ty = match dim {
+                        ArrayLength::Fixed(len) => IdlType::Array {
+                            array: (Box::new(idl_ty), len.to_usize().unwrap()),
+                        },
+                        ArrayLength::Dynamic => IdlType::Vec {
+                            vec: Box::new(idl_ty),
+                        },
+                        ArrayLength::AnyFixed => unreachable!(),
+                    };
+                }
+
+                idl_ty
+            }
+            Type::Enum(enum_no) => {
+                let decl = &self.ns.enums[*enum_no];
+
+                if self.seen.insert(decl.name.clone()) {
+                    let mut values: Vec<_> = decl.values.iter().collect();
+
+                    values.sort_by_key(|(_, (_, no))| *no);
+
+                    self.types.push(IdlTypeDefinition {
+                        name: decl.name.clone(),
+                        docs: docs(&decl.tags),
+                        ty: IdlTypeDefinitionTy::Enum {
+                            variants: values
+                                .into_iter()
+                                .map(|(name, _)| IdlEnumVariant { name: name.clone() })
+                                .collect(),
+                        },
+                    });
+                }
+
+                IdlType::Defined {
+                    defined: decl.name.clone(),
+                }
+            }
+            Type::Struct(struct_ty) => {
+                let decl = struct_ty.definition(self.ns);
+
+                if self.seen.insert(decl.name.clone()) {
+                    let fields = self.fields(&decl.fields, "field");
+
+                    self.types.push(IdlTypeDefinition {
+                        name: decl.name.clone(),
+                        docs: docs(&decl.tags),
+                        ty: IdlTypeDefinitionTy::Struct { fields },
+                    });
+                }
+
+                IdlType::Defined {
+                    defined: decl.name.clone(),
+                }
+            }
+            Type::ExternalFunction { .. } => {
+                let name = StructType::ExternalFunction
+                    .definition(self.ns)
+                    .name
+                    .clone();
+
+                if self.seen.insert(name.clone()) {
+                    // Borsh encodes the selector before the address
+                    self.types.push(IdlTypeDefinition {
+                        name: name.clone(),
+                        docs: Vec::new(),
+                        ty: IdlTypeDefinitionTy::Struct {
+                            fields: vec![
+                                IdlField {
+                                    name: "selector".to_owned(),
+                                    docs: Vec::new(),
+                                    ty: IdlType::bytes(4),
+                                },
+                                IdlField {
+                                    name: "address".to_owned(),
+                                    docs: Vec::new(),
+                                    ty: IdlType::primitive("publicKey"),
+                                },
+                            ],
+                        },
+                    });
+                }
+
+                IdlType::Defined { defined: name }
+            }
+            Type::UserType(user_no) => self.idl_type(&self.ns.user_types[*user_no].ty),
+            Type::Ref(ty) | Type::StorageRef(_, ty) => self.idl_type(ty),
+            _ => unreachable!("{} cannot be abi encoded", ty.to_string(self.ns)),
+        }
+    }
+
+    /// The type of a variable as it is laid out in the account data. Only simple types are stored
+    /// like their Borsh encoding; anything else is described as an opaque array of bytes.
+    fn storage_type(&mut self, ty: &Type) -> IdlType {
+        match ty {
+            Type::Bool
+            | Type::Int(_)
+            | Type::Uint(_)
+            | Type::Value
+            | Type::Address(_)
+            | Type::Contract(_)
+            | Type::Bytes(_)
+            | Type::Enum(_) => self.idl_type(ty),
+            Type::UserType(user_no) => self.storage_type(&self.ns.user_types[*user_no].ty),
+            _ => IdlType::bytes(ty.storage_slots(self.ns).to_usize().unwrap()),
+        }
+    }
+
+    fn fields(&mut self, params: &[Parameter], prefix: &str) -> Vec<IdlField> {
+        params
+            .iter()
+            .enumerate()
+            .map(|(no, param)| IdlField {
+                name: field_name(param, no, prefix),
+                docs: Vec::new(),
+                ty: self.idl_type(&param.ty),
+            })
+            .collect()
+    }
+
+    /// Anchor allows a single return type, so multiple return values are returned as a struct
+    fn returns(&mut self, func: &Function) -> Option<IdlType> {
+        match func.returns.len() {
+            0 => None,
+            1 => Some(self.idl_type(&func.returns[0].ty)),
+            _ => {
+                let name = format!("{}Returns", func.name);
+
+                if self.seen.insert(name.clone()) {
+                    let fields = self.fields(&func.returns, "return");
+
+                    self.types.push(IdlTypeDefinition {
+                        name: name.clone(),
+                        docs: Vec::new(),
+                        ty: IdlTypeDefinitionTy::Struct { fields },
+                    });
+                }
+
+                Some(IdlType::Defined { defined: name })
+            }
+        }
+    }
+}
+
+fn field_name(param: &Parameter, no: usize, prefix: &str) -> String {
+    match &param.id {
+        Some(id) => id.name.clone(),
+        None => format!("{}{}", prefix, no),
+    }
+}
+
+fn docs(tags: &[Tag]) -> Vec<String> {
+    tags.iter()
+        .filter(|tag| tag.tag == "notice" || tag.tag == "dev")
+        .map(|tag| tag.value.clone())
+        .collect()
+}
+
+fn padding(no: usize, len: usize) -> IdlField {
+    IdlField {
+        name: format!("padding{}", no),
+        docs: Vec::new(),
+        ty: IdlType::bytes(len),
+    }
+}
+
+/// Generate the Anchor IDL for a contract
+pub fn gen_idl(contract_no: usize, ns: &Namespace) -> Idl {
+    let contract = &ns.contracts[contract_no];
+    let mut collector = TypeCollector::new(ns);
+
+    let instructions = contract
+        .all_functions
+        .keys()
+        .filter_map(|function_no| {
+            let func = &ns.functions[*function_no];
+
+            if let Some(base_contract_no) = func.contract_no {
+                if ns.contracts[base_contract_no].is_library() {
+                    return None;
+                }
+
+                if func.ty == pt::FunctionTy::Constructor && base_contract_no != contract_no {
+                    return None;
+                }
+            }
+
+            if !matches!(
+                func.visibility,
+                pt::Visibility::Public(_) | pt::Visibility::External(_)
+            ) {
+                return None;
+            }
+
+            if !matches!(
+                func.ty,
+                pt::FunctionTy::Function | pt::FunctionTy::Constructor
+            ) || !func.has_body
+            {
+                return None;
+            }
+
+            Some(func)
+        })
+        .map(|func| IdlInstruction {
+            name: if func.is_constructor() {
+                "new".to_owned()
+            } else {
+                func.name.clone()
+            },
+            docs: docs(&func.tags),
+            accounts: vec![IdlAccount {
+                name: "dataAccount".to_owned(),
+                is_mut: !matches!(func.mutability, Mutability::View(_) | Mutability::Pure(_)),
+                is_signer: false,
+            }],
+            args: collector.fields(&func.params, "arg"),
+            returns: collector.returns(func),
+        })
+        .collect();
+
+    let events = contract
+        .sends_events
+        .iter()
+        .map(|event_no| {
+            let event = &ns.events[*event_no];
+
+            IdlEvent {
+                name: event.name.clone(),
+                fields: event
+                    .fields
+                    .iter()
+                    .enumerate()
+                    .map(|(no, field)| IdlEventField {
+                        name: field_name(field, no, "field"),
+                        ty: collector.idl_type(&field.ty),
+                        index: field.indexed,
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+
+    // Solidity errors have no error code, so the selector is used to identify them
+    let errors = contract
+        .reverts_errors
+        .iter()
+        .map(|error_no| {
+            let error = &ns.errors[*error_no];
+
+            IdlErrorCode {
+                code: error.selector(),
+                name: error.name.clone(),
+                msg: Some(error.signature.clone()),
+            }
+        })
+        .collect();
+
+    // The account data starts with a header, followed by the contract storage
+    let mut fields: Vec<IdlField> = [
+        "magic",
+        "returnDataLength",
+        "returnDataOffset",
+        "heapOffset",
+    ]
+    .iter()
+    .map(|name| IdlField {
+        name: name.to_string(),
+        docs: Vec::new(),
+        ty: IdlType::primitive("u32"),
+    })
+    .collect();
+
+    let mut offset = BigInt::from(SOLANA_FIRST_OFFSET);
+
+    if let Some(lamports_offset) = &contract.lamports_offset {
+        fields.push(IdlField {
+            name: "lamports".to_owned(),
+            docs: vec!["Lamports balance recorded after the last call".to_owned()],
+            ty: IdlType::primitive("u64"),
+        });
+
+        offset = lamports_offset + 8;
+    }
+
+    let mut layout: Vec<_> = contract
+        .layout
+        .iter()
+        .chain(contract.immutable_layout.iter())
+        .collect();
+
+    layout.sort_by(|a, b| a.slot.cmp(&b.slot));
+
+    let mut padding_no = 0;
+
+    for var in layout {
+        if var.slot > offset {
+            fields.push(padding(
+                padding_no,
+                (&var.slot - &offset).to_usize().unwrap(),
+            ));
+
+            padding_no += 1;
+        }
+
+        let variable = &ns.contracts[var.contract_no].variables[var.var_no];
+
+        fields.push(IdlField {
+            name: variable.name.clone(),
+            docs: docs(&variable.tags),
+            ty: collector.storage_type(&var.ty),
+        });
+
+        offset = &var.slot + var.ty.storage_slots(ns);
+    }
+
+    let accounts = vec![IdlTypeDefinition {
+        name: contract.name.clone(),
+        docs: vec!["Layout of the data account; dynamic storage lives on the heap which follows the fixed layout".to_owned()],
+        ty: IdlTypeDefinitionTy::Struct { fields },
+    }];
+
+    Idl {
+        // there is no version in the solidity source code
+        version: "0.0.1".to_owned(),
+        name: contract.name.clone(),
+        instructions,
+        accounts,
+        types: collector.types,
+        events,
+        errors,
+        metadata: IdlMetadata {
+            fixed_layout_size: contract.fixed_layout_size.to_u64().unwrap(),
+        },
+    }
+}
@@ -153,5 +153,28 @@ pub fn gen_abi(contract_no: usize, ns: &Namespace) -> Vec<ABI> {
                     }
                 }),
         )
+        .chain(
+            ns.contracts[contract_no]
+                .reverts_errors
+                .iter()
+                .map(|error_no| {
+                    let error = &ns.errors[*error_no];
+
+                    ABI {
+                        name: error.name.to_owned(),
+                        mutability: String::new(),
+                        inputs: Some(
+                            error
+                                .fields
+                                .iter()
+                                .map(|p| parameter_to_abi(p, ns))
+                                .collect(),
+                        ),
+                        outputs: None,
+                        ty: "error".to_owned(),
+                        anonymous: None,
+                    }
+                }),
+        )
         .collect()
 }
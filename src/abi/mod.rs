@@ -3,6 +3,7 @@
 use crate::sema::ast::Namespace;
 use crate::Target;
 
+pub mod anchor;
 pub mod ethereum;
 pub mod substrate;
 
@@ -39,3 +40,17 @@ pub fn generate_abi(
         }
     }
 }
+
+/// Generate an Anchor IDL for a Solana contract, rather than an Ethereum ABI
+pub fn generate_idl(contract_no: usize, ns: &Namespace, verbose: bool) -> (String, &'static str) {
+    if verbose {
+        eprintln!(
+            "info: Generating Anchor IDL for contract {}",
+            ns.contracts[contract_no].name
+        );
+    }
+
+    let idl = anchor::gen_idl(contract_no, ns);
+
+    (serde_json::to_string_pretty(&idl).unwrap(), "json")
+}
@@ -142,11 +142,21 @@ pub struct Event {
     args: Vec<ParamIndexed>,
 }
 
+#[derive(Deserialize, Serialize)]
+pub struct Error {
+    docs: Vec<String>,
+    name: String,
+    selector: String,
+    args: Vec<Param>,
+}
+
 #[derive(Deserialize, Serialize)]
 pub struct Spec {
     pub constructors: Vec<Constructor>,
     pub messages: Vec<Message>,
     pub events: Vec<Event>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<Error>,
 }
 
 #[derive(Deserialize, Serialize)]
@@ -363,6 +373,7 @@ fn gen_abi(contract_no: usize, ns: &ast::Namespace) -> Abi {
             constructors: Vec::new(),
             messages: Vec::new(),
             events: Vec::new(),
+            errors: Vec::new(),
         },
     };
 
@@ -507,10 +518,35 @@ fn gen_abi(contract_no: usize, ns: &ast::Namespace) -> Abi {
         })
         .collect();
 
+    let errors = ns.contracts[contract_no]
+        .reverts_errors
+        .iter()
+        .map(|error_no| {
+            let error = &ns.errors[*error_no];
+
+            let name = error.name.to_owned();
+            let selector = format!("0x{}", hex::encode(error.selector().to_be_bytes()));
+            let args = error
+                .fields
+                .iter()
+                .map(|p| parameter_to_abi(p, ns, &mut abi))
+                .collect();
+            let docs = vec![render(&error.tags)];
+
+            Error {
+                docs,
+                name,
+                selector,
+                args,
+            }
+        })
+        .collect();
+
     abi.spec = Spec {
         constructors,
         messages,
         events,
+        errors,
     };
 
     abi
@@ -552,13 +588,20 @@ fn ty_to_abi(ty: &ast::Type, ns: &ast::Namespace, registry: &mut Abi) -> ParamTy
         ast::Type::StorageRef(_, ty) => ty_to_abi(ty, ns, registry),
         ast::Type::Ref(ty) => ty_to_abi(ty, ns, registry),
         ast::Type::UserType(no) => ty_to_abi(&ns.user_types[*no].ty, ns, registry),
-        ast::Type::Bool | ast::Type::Uint(_) | ast::Type::Int(_) => {
+        ast::Type::Bool
+        | ast::Type::Uint(_)
+        | ast::Type::Int(_)
+        | ast::Type::Fixed(..)
+        | ast::Type::Ufixed(..) => {
             let scalety = match ty {
                 ast::Type::Bool => "bool".into(),
                 // Substrate doesn't like primitive types which aren't a power of 2
-                // The abi encoder/decoder fixes this automatically
-                ast::Type::Uint(n) => format!("u{}", n.next_power_of_two()),
-                ast::Type::Int(n) => format!("i{}", n.next_power_of_two()),
+                // The abi encoder/decoder fixes this automatically. Fixed point numbers
+                // are encoded as their scaled integer value
+                ast::Type::Uint(n) | ast::Type::Ufixed(n, _) => {
+                    format!("u{}", n.next_power_of_two())
+                }
+                ast::Type::Int(n) | ast::Type::Fixed(n, _) => format!("i{}", n.next_power_of_two()),
                 _ => unreachable!(),
             };
 
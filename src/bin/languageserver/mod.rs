@@ -353,6 +353,11 @@ impl SolangServer {
                     SolangServer::construct_expr(arg, lookup_tbl, symtab, fnc_map, ns);
                 }
             }
+            ast::Statement::Revert { args, .. } => {
+                for arg in args {
+                    SolangServer::construct_expr(arg, lookup_tbl, symtab, fnc_map, ns);
+                }
+            }
             ast::Statement::TryCatch(_, _, try_stmt) => {
                 SolangServer::construct_expr(&try_stmt.expr, lookup_tbl, symtab, fnc_map, ns);
                 for vecstmt in &try_stmt.catch_stmt {
@@ -361,8 +366,8 @@ impl SolangServer {
                 for vecstmt in &try_stmt.ok_stmt {
                     SolangServer::construct_stmt(vecstmt, lookup_tbl, symtab, fnc_map, ns);
                 }
-                for okstmt in &try_stmt.errors {
-                    for stmts in &okstmt.2 {
+                for clause in &try_stmt.errors {
+                    for stmts in &clause.stmt {
                         SolangServer::construct_stmt(stmts, lookup_tbl, symtab, fnc_map, ns);
                     }
                 }
@@ -475,11 +480,15 @@ impl SolangServer {
                 SolangServer::construct_expr(expr1, lookup_tbl, symtab, fnc_map, ns);
                 SolangServer::construct_expr(expr2, lookup_tbl, symtab, fnc_map, ns);
             }
-            ast::Expression::Divide(locs, ty, expr1, expr2) => {
+            ast::Expression::Divide(locs, ty, unchecked, expr1, expr2) => {
                 lookup_tbl.push(HoverEntry {
                     start: locs.start(),
                     stop: locs.end(),
-                    val: format!("{} divide", ty.to_string(ns)),
+                    val: format!(
+                        "{} {} divide",
+                        if *unchecked { "unchecked " } else { "" },
+                        ty.to_string(ns)
+                    ),
                 });
 
                 SolangServer::construct_expr(expr1, lookup_tbl, symtab, fnc_map, ns);
@@ -669,6 +678,17 @@ impl SolangServer {
                 SolangServer::construct_expr(expr1, lookup_tbl, symtab, fnc_map, ns);
                 SolangServer::construct_expr(expr2, lookup_tbl, symtab, fnc_map, ns);
             }
+            ast::Expression::ArraySlice {
+                array, start, end, ..
+            } => {
+                SolangServer::construct_expr(array, lookup_tbl, symtab, fnc_map, ns);
+                if let Some(start) = start {
+                    SolangServer::construct_expr(start, lookup_tbl, symtab, fnc_map, ns);
+                }
+                if let Some(end) = end {
+                    SolangServer::construct_expr(end, lookup_tbl, symtab, fnc_map, ns);
+                }
+            }
 
             ast::Expression::StructMember(_locs, _typ, expr1, _val) => {
                 SolangServer::construct_expr(expr1, lookup_tbl, symtab, fnc_map, ns);
@@ -1024,6 +1044,18 @@ impl SolangServer {
             });
         }
 
+        for errdcl in &ns.errors {
+            for filds in &errdcl.fields {
+                SolangServer::construct_strct(filds, lookup_tbl, ns);
+            }
+            let val = render(&errdcl.tags[..]);
+            lookup_tbl.push(HoverEntry {
+                start: errdcl.loc.start(),
+                stop: errdcl.loc.start() + errdcl.name.len(),
+                val,
+            });
+        }
+
         for lookup in lookup_tbl.iter_mut() {
             if let Some(msg) = ns
                 .hover_overrides
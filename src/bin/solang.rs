@@ -100,6 +100,11 @@ fn main() {
                 .takes_value(true)
                 .default_value("16"),
         )
+        .arg(
+            Arg::new("ANCHOR_IDL")
+                .help("Generate Anchor IDL rather than Ethereum ABI on Solana")
+                .long("anchor-idl"),
+        )
         .arg(
             Arg::new("STD-JSON")
                 .help("mimic solidity json output on stdout")
@@ -171,11 +176,32 @@ fn main() {
                 .action(ArgAction::SetFalse)
                 .display_order(5),
         )
+        .arg(
+            Arg::new("FUNCTIONINLINING")
+                .help("Disable function inlining codegen optimization")
+                .long("no-inline")
+                .action(ArgAction::SetFalse)
+                .display_order(6),
+        )
+        .arg(
+            Arg::new("LOOPINVARIANTCODEMOTION")
+                .help("Disable loop-invariant code motion")
+                .long("no-licm")
+                .action(ArgAction::SetFalse)
+                .display_order(7),
+        )
+        .arg(
+            Arg::new("DEADCODEELIMINATION")
+                .help("Disable dead code elimination")
+                .long("no-dce")
+                .action(ArgAction::SetFalse)
+                .display_order(8),
+        )
         .arg(
             Arg::new("MATHOVERFLOW")
                 .help("Enable math overflow checking")
                 .long("math-overflow")
-                .display_order(6),
+                .display_order(9),
         )
         .arg(
             Arg::new("LANGUAGESERVER")
@@ -224,6 +250,14 @@ fn main() {
         std::process::exit(1);
     }
 
+    if target != solang::Target::Solana && matches.contains_id("ANCHOR_IDL") {
+        eprintln!(
+            "error: anchor idl cannot be generated for target '{}'",
+            target
+        );
+        std::process::exit(1);
+    }
+
     if matches.contains_id("LANGUAGESERVER") {
         languageserver::start_server(target, matches);
     }
@@ -326,6 +360,11 @@ fn main() {
             common_subexpression_elimination: *matches
                 .get_one::<bool>("COMMONSUBEXPRESSIONELIMINATION")
                 .unwrap(),
+            function_inlining: *matches.get_one::<bool>("FUNCTIONINLINING").unwrap(),
+            loop_invariant_code_motion: *matches
+                .get_one::<bool>("LOOPINVARIANTCODEMOTION")
+                .unwrap(),
+            dead_code_elimination: *matches.get_one::<bool>("DEADCODEELIMINATION").unwrap(),
             opt_level,
         };
 
@@ -361,13 +400,8 @@ fn main() {
         if target == solang::Target::Solana {
             let context = inkwell::context::Context::create();
 
-            let binary = solang::compile_many(
-                &context,
-                &namespaces,
-                "bundle.sol",
-                opt_level.into(),
-                math_overflow_check,
-            );
+            let binary =
+                solang::compile_many(&context, &namespaces, "bundle.sol", opt_level.into());
 
             if !save_intermediates(&binary, &matches) {
                 let bin_filename = output_file(&matches, "bundle", target.file_extension());
@@ -412,8 +446,11 @@ fn main() {
                                 continue;
                             }
 
-                            let (abi_bytes, abi_ext) =
-                                abi::generate_abi(contract_no, ns, &code, verbose);
+                            let (abi_bytes, abi_ext) = if matches.contains_id("ANCHOR_IDL") {
+                                abi::generate_idl(contract_no, ns, verbose)
+                            } else {
+                                abi::generate_abi(contract_no, ns, &code, verbose)
+                            };
                             let abi_filename = output_file(&matches, &contract.name, abi_ext);
 
                             if verbose {
@@ -543,13 +580,7 @@ fn process_file(
         let context = inkwell::context::Context::create();
         let filename_string = filename.to_string_lossy();
 
-        let binary = resolved_contract.emit(
-            &ns,
-            &context,
-            &filename_string,
-            opt.opt_level.into(),
-            opt.math_overflow_check,
-        );
+        let binary = resolved_contract.emit(&ns, &context, &filename_string, opt.opt_level.into());
 
         if save_intermediates(&binary, matches) {
             continue;
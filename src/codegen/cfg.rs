@@ -2,9 +2,9 @@
 
 use super::statements::{statement, LoopScopes};
 use super::{
-    constant_folding, dead_storage,
+    constant_folding, dead_code_elimination, dead_storage,
     expression::expression,
-    reaching_definitions, strength_reduce,
+    loop_invariant_code_motion, reaching_definitions, strength_reduce,
     vartable::{Vars, Vartable},
     vector_to_slice, Options,
 };
@@ -18,7 +18,7 @@ use crate::sema::{contracts::collect_base_args, diagnostics::Diagnostics, Recurs
 use crate::{sema::ast, Target};
 use indexmap::IndexMap;
 use num_bigint::BigInt;
-use num_traits::One;
+use num_traits::{One, ToPrimitive};
 use solang_parser::pt;
 use solang_parser::pt::CodeLocation;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
@@ -55,9 +55,15 @@ pub enum Instr {
         true_block: usize,
         false_block: usize,
     },
+    /// Jump to the block of the case which matches the condition, or else to the default block
+    Switch {
+        cond: Expression,
+        cases: Vec<(Expression, usize)>,
+        default: usize,
+    },
     /// Set array element in memory
     Store { dest: Expression, data: Expression },
-    /// Abort execution
+    /// Abort execution, optionally with ABI encoded return data (selector and arguments)
     AssertFailure { expr: Option<Expression> },
     /// Print to log message
     Print { expr: Expression },
@@ -76,6 +82,14 @@ pub enum Instr {
         value: Expression,
         storage: Expression,
     },
+    /// Load an immutable variable from the immutables area
+    LoadImmutable { res: usize, ty: Type, offset: usize },
+    /// Set an immutable variable; only the constructor does this
+    SetImmutable {
+        ty: Type,
+        value: Expression,
+        offset: usize,
+    },
     /// In storage slot, set the value at the offset
     SetStorageBytes {
         value: Expression,
@@ -187,6 +201,7 @@ impl Instr {
             | Instr::PopStorage { storage: expr, .. }
             | Instr::AbiDecode { data: expr, .. }
             | Instr::SelfDestruct { recipient: expr }
+            | Instr::SetImmutable { value: expr, .. }
             | Instr::Set { expr, .. } => {
                 expr.recurse(cx, f);
             }
@@ -300,13 +315,163 @@ impl Instr {
                 bytes.recurse(cx, f);
             }
 
+            Instr::Switch { cond, cases, .. } => {
+                cond.recurse(cx, f);
+                for (case, _) in cases {
+                    case.recurse(cx, f);
+                }
+            }
+
             Instr::AssertFailure { expr: None }
             | Instr::Unreachable
             | Instr::Nop
             | Instr::Branch { .. }
+            | Instr::LoadImmutable { .. }
             | Instr::PopMemory { .. } => {}
         }
     }
+
+    /// Apply a function to all the expressions in the instruction, so they can be modified
+    pub fn update_expressions<F>(&mut self, mut update: F)
+    where
+        F: FnMut(&mut Expression),
+    {
+        match self {
+            Instr::Call {
+                args: exprs, call, ..
+            } => {
+                if let InternalCallTy::Dynamic(expr) = call {
+                    update(expr);
+                }
+                exprs.iter_mut().for_each(&mut update);
+            }
+            Instr::Return { value: exprs } => {
+                exprs.iter_mut().for_each(&mut update);
+            }
+            Instr::Constructor {
+                args,
+                value,
+                gas,
+                salt,
+                space,
+                ..
+            } => {
+                args.iter_mut().for_each(&mut update);
+                value.iter_mut().for_each(&mut update);
+                update(gas);
+                salt.iter_mut().for_each(&mut update);
+                space.iter_mut().for_each(&mut update);
+            }
+            Instr::BranchCond { cond: expr, .. }
+            | Instr::Print { expr }
+            | Instr::AssertFailure { expr: Some(expr) }
+            | Instr::LoadStorage { storage: expr, .. }
+            | Instr::ClearStorage { storage: expr, .. }
+            | Instr::SetImmutable { value: expr, .. }
+            | Instr::PopStorage { storage: expr, .. }
+            | Instr::AbiDecode { data: expr, .. }
+            | Instr::SelfDestruct { recipient: expr }
+            | Instr::Set { expr, .. } => update(expr),
+            Instr::PushMemory { value, .. } => update(value.as_mut()),
+            Instr::Switch { cond, cases, .. } => {
+                update(cond);
+                cases.iter_mut().for_each(|(case, _)| update(case));
+            }
+            Instr::Store {
+                dest: left,
+                data: right,
+            }
+            | Instr::SetStorage {
+                value: left,
+                storage: right,
+                ..
+            }
+            | Instr::ValueTransfer {
+                address: left,
+                value: right,
+                ..
+            } => {
+                update(left);
+                update(right);
+            }
+            Instr::PushStorage { value, storage, .. } => {
+                value.iter_mut().for_each(&mut update);
+                update(storage);
+            }
+            Instr::SetStorageBytes {
+                value: first,
+                storage: second,
+                offset: third,
+            }
+            | Instr::WriteBuffer {
+                buf: first,
+                offset: second,
+                value: third,
+            }
+            | Instr::MemCopy {
+                source: first,
+                destination: second,
+                bytes: third,
+            } => {
+                update(first);
+                update(second);
+                update(third);
+            }
+            Instr::ExternalCall {
+                address,
+                accounts,
+                payload,
+                value,
+                gas,
+                ..
+            } => {
+                address.iter_mut().for_each(&mut update);
+                accounts.iter_mut().for_each(&mut update);
+                update(payload);
+                update(value);
+                update(gas);
+            }
+            Instr::EmitEvent { data, topics, .. } => {
+                data.iter_mut().for_each(&mut update);
+                topics.iter_mut().for_each(&mut update);
+            }
+            Instr::AssertFailure { expr: None }
+            | Instr::Branch { .. }
+            | Instr::LoadImmutable { .. }
+            | Instr::PopMemory { .. }
+            | Instr::Unreachable
+            | Instr::Nop => (),
+        }
+    }
+
+    /// Change the block numbers of all the branches in the instruction
+    pub fn update_blocks<F>(&mut self, update: F)
+    where
+        F: Fn(usize) -> usize,
+    {
+        match self {
+            Instr::Branch { block } => *block = update(*block),
+            Instr::BranchCond {
+                true_block,
+                false_block,
+                ..
+            } => {
+                *true_block = update(*true_block);
+                *false_block = update(*false_block);
+            }
+            Instr::Switch { cases, default, .. } => {
+                for (_, block) in cases {
+                    *block = update(*block);
+                }
+                *default = update(*default);
+            }
+            Instr::AbiDecode {
+                exception_block: Some(block),
+                ..
+            } => *block = update(*block),
+            _ => (),
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -346,6 +511,8 @@ pub struct BasicBlock {
     pub defs: reaching_definitions::VarDefs,
     pub loop_reaching_variables: HashSet<usize>,
     pub transfers: Vec<Vec<reaching_definitions::Transfer>>,
+    /// The block was copied from another function by function inlining
+    pub inlined: bool,
 }
 
 impl BasicBlock {
@@ -433,6 +600,7 @@ impl ControlFlowGraph {
             transfers: Vec::new(),
             defs: IndexMap::new(),
             loop_reaching_variables: HashSet::new(),
+            inlined: false,
         });
 
         pos
@@ -469,7 +637,7 @@ impl ControlFlowGraph {
                 Expression::Subtract(
                     pt::Loc::Codegen,
                     Type::Uint(32),
-                    false,
+                    true,
                     Box::new(Expression::Variable(
                         pt::Loc::Codegen,
                         Type::Uint(32),
@@ -485,7 +653,7 @@ impl ControlFlowGraph {
                 Expression::Add(
                     pt::Loc::Codegen,
                     Type::Uint(32),
-                    false,
+                    true,
                     Box::new(Expression::Variable(
                         pt::Loc::Codegen,
                         Type::Uint(32),
@@ -684,6 +852,19 @@ impl ControlFlowGraph {
                 self.expr_to_string(contract, ns, a),
                 self.expr_to_string(contract, ns, i)
             ),
+            Expression::ArraySlice {
+                ty,
+                array,
+                start,
+                end,
+                ..
+            } => format!(
+                "(slice {} {}[{}:{}])",
+                ty.to_string(ns),
+                self.expr_to_string(contract, ns, array),
+                self.expr_to_string(contract, ns, start),
+                self.expr_to_string(contract, ns, end)
+            ),
             Expression::StorageArrayLength { array, elem_ty, .. } => format!(
                 "(storage array length {}[{}])",
                 self.expr_to_string(contract, ns, array),
@@ -842,6 +1023,23 @@ impl ControlFlowGraph {
                 true_block,
                 false_block,
             ),
+            Instr::Switch {
+                cond,
+                cases,
+                default,
+            } => format!(
+                "switch {}:{}\n\t\tdefault: block{}",
+                self.expr_to_string(contract, ns, cond),
+                cases
+                    .iter()
+                    .map(|(case, block)| format!(
+                        "\n\t\tcase: {} => block{}",
+                        self.expr_to_string(contract, ns, case),
+                        block
+                    ))
+                    .collect::<String>(),
+                default,
+            ),
             Instr::LoadStorage { ty, res, storage } => format!(
                 "%{} = load storage slot({}) ty:{}",
                 self.vars[res].id.name,
@@ -859,6 +1057,18 @@ impl ControlFlowGraph {
                 ty.to_string(ns),
                 self.expr_to_string(contract, ns, value),
             ),
+            Instr::LoadImmutable { res, ty, offset } => format!(
+                "%{} = load immutable offset({}) ty:{}",
+                self.vars[res].id.name,
+                offset,
+                ty.to_string(ns),
+            ),
+            Instr::SetImmutable { ty, value, offset } => format!(
+                "store immutable offset({}) ty:{} = {}",
+                offset,
+                ty.to_string(ns),
+                self.expr_to_string(contract, ns, value),
+            ),
             Instr::SetStorageBytes {
                 value,
                 storage,
@@ -1297,17 +1507,6 @@ pub fn generate_cfg(
         cfg.selector = func.selector();
     }
 
-    optimize_and_check_cfg(
-        &mut cfg,
-        ns,
-        if let Some(func_no) = function_no {
-            ASTFunction::SolidityFunction(func_no)
-        } else {
-            ASTFunction::None
-        },
-        opt,
-    );
-
     all_cfgs[cfg_no] = cfg;
 }
 
@@ -1337,20 +1536,21 @@ fn resolve_modifier_call<'a>(
     panic!("modifier should resolve to internal call");
 }
 
-/// Detect undefined variables and run codegen optimizer passess
-pub fn optimize_and_check_cfg(
+/// Detect undefined variables. If there are any, an error is raised and false is returned, and the
+/// cfg should not be optimized
+pub fn check_cfg(cfg: &mut ControlFlowGraph, ns: &mut Namespace, func_no: ASTFunction) -> bool {
+    reaching_definitions::find(cfg);
+
+    func_no == ASTFunction::None || !undefined_variable::find_undefined_variables(cfg, ns, func_no)
+}
+
+/// Run codegen optimizer passess. The reaching definitions of the cfg must be up to date.
+pub fn optimize_cfg(
     cfg: &mut ControlFlowGraph,
     ns: &mut Namespace,
     func_no: ASTFunction,
     opt: &Options,
 ) {
-    reaching_definitions::find(cfg);
-    if func_no != ASTFunction::None {
-        // If there are undefined variables, we raise an error and don't run optimizations
-        if undefined_variable::find_undefined_variables(cfg, ns, func_no) {
-            return;
-        }
-    }
     if opt.constant_folding {
         constant_folding::constant_folding(cfg, ns);
     }
@@ -1363,11 +1563,17 @@ pub fn optimize_and_check_cfg(
     if opt.dead_storage {
         dead_storage::dead_storage(cfg, ns);
     }
+    if opt.loop_invariant_code_motion {
+        loop_invariant_code_motion::loop_invariant_code_motion(cfg, ns);
+    }
 
     // If the function is a default constructor, there is nothing to optimize.
     if opt.common_subexpression_elimination && func_no != ASTFunction::None {
         common_sub_expression_elimination(cfg, ns);
     }
+    if opt.dead_code_elimination {
+        dead_code_elimination::dead_code_elimination(cfg, opt);
+    }
 }
 
 /// Generate the CFG for a function. If function_no is None, generate the implicit default
@@ -1879,6 +2085,19 @@ impl Contract {
             panic!("get_storage_slot called on non-storage variable");
         }
     }
+
+    /// Get the offset of an immutable variable in the immutables area, possibly from base contract
+    pub fn get_immutable_offset(&self, var_contract_no: usize, var_no: usize) -> usize {
+        if let Some(layout) = self
+            .immutable_layout
+            .iter()
+            .find(|l| l.contract_no == var_contract_no && l.var_no == var_no)
+        {
+            layout.slot.to_usize().unwrap()
+        } else {
+            panic!("get_immutable_offset called on non-immutable variable");
+        }
+    }
 }
 
 impl Namespace {
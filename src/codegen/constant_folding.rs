@@ -21,6 +21,15 @@ pub fn constant_folding(cfg: &mut ControlFlowGraph, ns: &mut Namespace) {
     for block_no in 0..cfg.blocks.len() {
         let mut vars = cfg.blocks[block_no].defs.clone();
 
+        // the arguments of an inlined function may reach code which is never executed with
+        // those values, e.g. a division guarded by a check for zero. The function itself is
+        // folded too, so anything wrong with it is reported there.
+        let diagnostics = if cfg.blocks[block_no].inlined {
+            Some(std::mem::take(&mut ns.diagnostics))
+        } else {
+            None
+        };
+
         for instr_no in 0..cfg.blocks[block_no].instr.len() {
             match &cfg.blocks[block_no].instr[instr_no] {
                 Instr::Set { loc, res, expr, .. } => {
@@ -81,6 +90,39 @@ pub fn constant_folding(cfg: &mut ControlFlowGraph, ns: &mut Namespace) {
                         };
                     }
                 }
+                Instr::Switch {
+                    cond,
+                    cases,
+                    default,
+                } => {
+                    let (cond, _) = expression(cond, Some(&vars), cfg, ns);
+
+                    cfg.blocks[block_no].instr[instr_no] = match &cond {
+                        Expression::NumberLiteral(..) | Expression::BoolLiteral(..) => {
+                            let block = cases
+                                .iter()
+                                .find(|(case, _)| match (case, &cond) {
+                                    (
+                                        Expression::NumberLiteral(_, _, left),
+                                        Expression::NumberLiteral(_, _, right),
+                                    ) => left == right,
+                                    (
+                                        Expression::BoolLiteral(_, left),
+                                        Expression::BoolLiteral(_, right),
+                                    ) => left == right,
+                                    _ => false,
+                                })
+                                .map_or(*default, |(_, block)| *block);
+
+                            Instr::Branch { block }
+                        }
+                        _ => Instr::Switch {
+                            cond,
+                            cases: cases.clone(),
+                            default: *default,
+                        },
+                    };
+                }
                 Instr::Store { dest, data } => {
                     let (dest, _) = expression(dest, Some(&vars), cfg, ns);
                     let (data, _) = expression(data, Some(&vars), cfg, ns);
@@ -116,6 +158,15 @@ pub fn constant_folding(cfg: &mut ControlFlowGraph, ns: &mut Namespace) {
                         value,
                     };
                 }
+                Instr::SetImmutable { ty, value, offset } => {
+                    let (value, _) = expression(value, Some(&vars), cfg, ns);
+
+                    cfg.blocks[block_no].instr[instr_no] = Instr::SetImmutable {
+                        ty: ty.clone(),
+                        value,
+                        offset: *offset,
+                    };
+                }
                 Instr::LoadStorage { ty, storage, res } => {
                     let (storage, _) = expression(storage, Some(&vars), cfg, ns);
 
@@ -304,6 +355,10 @@ pub fn constant_folding(cfg: &mut ControlFlowGraph, ns: &mut Namespace) {
                 &mut vars,
             );
         }
+
+        if let Some(diagnostics) = diagnostics {
+            ns.diagnostics = diagnostics;
+        }
     }
 }
 
@@ -319,46 +374,56 @@ fn expression(
 ) -> (Expression, bool) {
     match expr {
         Expression::Add(loc, ty, unchecked, left, right) => {
+            let literals = literal_operands(left, right);
             let left = expression(left, vars, cfg, ns);
             let right = expression(right, vars, cfg, ns);
 
             if let (Expression::NumberLiteral(_, _, left), Expression::NumberLiteral(_, _, right)) =
                 (&left.0, &right.0)
             {
-                bigint_to_expression(loc, ty, left.add(right))
-            } else {
-                (
-                    Expression::Add(
-                        *loc,
-                        ty.clone(),
-                        *unchecked,
-                        Box::new(left.0),
-                        Box::new(right.0),
-                    ),
-                    left.1 && right.1,
-                )
+                let res = left.add(right);
+
+                if *unchecked || !overflows(loc, ty, &res, literals, ns) {
+                    return bigint_to_expression(loc, ty, res);
+                }
             }
+
+            (
+                Expression::Add(
+                    *loc,
+                    ty.clone(),
+                    *unchecked,
+                    Box::new(left.0),
+                    Box::new(right.0),
+                ),
+                left.1 && right.1,
+            )
         }
         Expression::Subtract(loc, ty, unchecked, left, right) => {
+            let literals = literal_operands(left, right);
             let left = expression(left, vars, cfg, ns);
             let right = expression(right, vars, cfg, ns);
 
             if let (Expression::NumberLiteral(_, _, left), Expression::NumberLiteral(_, _, right)) =
                 (&left.0, &right.0)
             {
-                bigint_to_expression(loc, ty, left.sub(right))
-            } else {
-                (
-                    Expression::Subtract(
-                        *loc,
-                        ty.clone(),
-                        *unchecked,
-                        Box::new(left.0),
-                        Box::new(right.0),
-                    ),
-                    left.1 && right.1,
-                )
+                let res = left.sub(right);
+
+                if *unchecked || !overflows(loc, ty, &res, literals, ns) {
+                    return bigint_to_expression(loc, ty, res);
+                }
             }
+
+            (
+                Expression::Subtract(
+                    *loc,
+                    ty.clone(),
+                    *unchecked,
+                    Box::new(left.0),
+                    Box::new(right.0),
+                ),
+                left.1 && right.1,
+            )
         }
         Expression::AdvancePointer {
             loc,
@@ -379,25 +444,30 @@ fn expression(
             )
         }
         Expression::Multiply(loc, ty, unchecked, left, right) => {
+            let literals = literal_operands(left, right);
             let left = expression(left, vars, cfg, ns);
             let right = expression(right, vars, cfg, ns);
 
             if let (Expression::NumberLiteral(_, _, left), Expression::NumberLiteral(_, _, right)) =
                 (&left.0, &right.0)
             {
-                bigint_to_expression(loc, ty, left.mul(right))
-            } else {
-                (
-                    Expression::Multiply(
-                        *loc,
-                        ty.clone(),
-                        *unchecked,
-                        Box::new(left.0),
-                        Box::new(right.0),
-                    ),
-                    left.1 && right.1,
-                )
+                let res = left.mul(right);
+
+                if *unchecked || !overflows(loc, ty, &res, literals, ns) {
+                    return bigint_to_expression(loc, ty, res);
+                }
             }
+
+            (
+                Expression::Multiply(
+                    *loc,
+                    ty.clone(),
+                    *unchecked,
+                    Box::new(left.0),
+                    Box::new(right.0),
+                ),
+                left.1 && right.1,
+            )
         }
         Expression::BitwiseAnd(loc, ty, left, right) => {
             let left = expression(left, vars, cfg, ns);
@@ -498,6 +568,7 @@ fn expression(
             )
         }
         Expression::Power(loc, ty, unchecked, left, right) => {
+            let literals = literal_operands(left, right);
             let left = expression(left, vars, cfg, ns);
             let right = expression(right, vars, cfg, ns);
 
@@ -511,8 +582,11 @@ fn expression(
                     ));
                 } else {
                     let right: u32 = right.to_u32().unwrap();
+                    let res = left.pow(right);
 
-                    return bigint_to_expression(loc, ty, left.pow(right));
+                    if *unchecked || !overflows(loc, ty, &res, literals, ns) {
+                        return bigint_to_expression(loc, ty, res);
+                    }
                 }
             }
 
@@ -586,6 +660,11 @@ fn expression(
             let expr = expression(expr, vars, cfg, ns);
             if let Expression::NumberLiteral(_, _, n) = expr.0 {
                 (Expression::NumberLiteral(*loc, ty.clone(), n), true)
+            } else if let Expression::BoolLiteral(_, b) = expr.0 {
+                (
+                    Expression::NumberLiteral(*loc, ty.clone(), BigInt::from(b as u8)),
+                    true,
+                )
             } else {
                 (
                     Expression::ZeroExt(*loc, ty.clone(), Box::new(expr.0)),
@@ -646,7 +725,11 @@ fn expression(
 
                         for def in defs.keys() {
                             if let Some(expr) = get_definition(def, cfg) {
+                                // any diagnostics are reported where the definition itself
+                                // is folded, so do not report them again
+                                let diagnostics = std::mem::take(&mut ns.diagnostics);
                                 let expr = expression(expr, None, cfg, ns);
+                                ns.diagnostics = diagnostics;
 
                                 if expr.1 {
                                     if let Some(last) = &v {
@@ -775,6 +858,36 @@ fn expression(
                 )
             }
         }
+        Expression::Builtin(loc, tys, Builtin::ByteSwap, args) => {
+            let arg = expression(&args[0], vars, cfg, ns);
+
+            match arg.0 {
+                Expression::NumberLiteral(_, ty, n) => {
+                    let len = ty.bits(ns) as usize / 8;
+                    let mut bs = n.to_signed_bytes_le();
+                    let fill = if n.sign() == Sign::Minus { 0xff } else { 0 };
+                    bs.resize(len, fill);
+                    bs.reverse();
+
+                    let n = if ty.is_signed_numeric() {
+                        BigInt::from_signed_bytes_le(&bs)
+                    } else {
+                        BigInt::from_bytes_le(Sign::Plus, &bs)
+                    };
+
+                    (Expression::NumberLiteral(*loc, ty, n), true)
+                }
+                Expression::BytesLiteral(_, ty, mut bs) => {
+                    bs.reverse();
+
+                    (Expression::BytesLiteral(*loc, ty, bs), true)
+                }
+                arg => (
+                    Expression::Builtin(*loc, tys.clone(), Builtin::ByteSwap, vec![arg]),
+                    false,
+                ),
+            }
+        }
         Expression::Keccak256(loc, ty, args) => {
             let mut all_constant = true;
             let mut hasher = Keccak::v256();
@@ -794,8 +907,10 @@ fn expression(
                                 let (sign, mut bs) = n.to_bytes_le();
 
                                 match ty {
-                                    Type::Uint(bits) => bs.resize(*bits as usize / 8, 0),
-                                    Type::Int(bits) => {
+                                    Type::Uint(bits) | Type::Ufixed(bits, _) => {
+                                        bs.resize(*bits as usize / 8, 0)
+                                    }
+                                    Type::Int(bits) | Type::Fixed(bits, _) => {
                                         let v = if sign == Sign::Minus { 0xffu8 } else { 0 };
 
                                         bs.resize(*bits as usize / 8, v);
@@ -975,6 +1090,28 @@ fn expression(
                 false,
             )
         }
+        Expression::ArraySlice {
+            loc,
+            ty,
+            array,
+            start,
+            end,
+        } => {
+            let array = expression(array, vars, cfg, ns);
+            let start = expression(start, vars, cfg, ns);
+            let end = expression(end, vars, cfg, ns);
+
+            (
+                Expression::ArraySlice {
+                    loc: *loc,
+                    ty: ty.clone(),
+                    array: Box::new(array.0),
+                    start: Box::new(start.0),
+                    end: Box::new(end.0),
+                },
+                false,
+            )
+        }
         Expression::StructMember(loc, ty, strct, member) => {
             let strct = expression(strct, vars, cfg, ns);
 
@@ -1107,7 +1244,7 @@ fn expression(
 
 fn bigint_to_expression(loc: &Loc, ty: &Type, n: BigInt) -> (Expression, bool) {
     let n = match ty {
-        Type::Uint(bits) => {
+        Type::Uint(bits) | Type::Ufixed(bits, _) => {
             if n.bits() > *bits as u64 {
                 let (_, mut bs) = n.to_bytes_le();
                 bs.truncate(*bits as usize / 8);
@@ -1117,7 +1254,7 @@ fn bigint_to_expression(loc: &Loc, ty: &Type, n: BigInt) -> (Expression, bool) {
                 n
             }
         }
-        Type::Int(bits) => {
+        Type::Int(bits) | Type::Fixed(bits, _) => {
             if n.bits() > *bits as u64 {
                 let mut bs = n.to_signed_bytes_le();
                 bs.truncate(*bits as usize / 8);
@@ -1134,6 +1271,51 @@ fn bigint_to_expression(loc: &Loc, ty: &Type, n: BigInt) -> (Expression, bool) {
     (Expression::NumberLiteral(*loc, ty.clone(), n), true)
 }
 
+/// Are both operands of an arithmetic operation literals in the source code, rather than values
+/// which are only known once variable definitions have been propagated?
+fn literal_operands(left: &Expression, right: &Expression) -> bool {
+    matches!(
+        (left, right),
+        (Expression::NumberLiteral(..), Expression::NumberLiteral(..))
+    )
+}
+
+/// If the result of a checked arithmetic operation does not fit into its type, then the
+/// operation would fail at runtime. If both operands are literals, report this as an error at
+/// compile time. Otherwise the operation may be on a path which is never taken, so only warn;
+/// the operation is not folded and will revert if it is executed.
+fn overflows(loc: &Loc, ty: &Type, n: &BigInt, literals: bool, ns: &mut Namespace) -> bool {
+    let (min, max) = match ty {
+        Type::Uint(bits) | Type::Ufixed(bits, _) => {
+            (BigInt::zero(), BigInt::from(1).shl(*bits as usize))
+        }
+        Type::Int(bits) | Type::Fixed(bits, _) => {
+            let max = BigInt::from(1).shl(*bits as usize - 1);
+
+            (-max.clone(), max)
+        }
+        _ => return false,
+    };
+
+    if n < &min || n >= &max {
+        let message = format!(
+            "arithmetic overflow: {} does not fit into type '{}'",
+            n,
+            ty.to_string(ns)
+        );
+
+        ns.diagnostics.push(if literals {
+            Diagnostic::error(*loc, message)
+        } else {
+            Diagnostic::warning(*loc, format!("{}, this will revert at runtime", message))
+        });
+
+        true
+    } else {
+        false
+    }
+}
+
 fn get_definition<'a>(
     def: &reaching_definitions::Def,
     cfg: &'a ControlFlowGraph,
@@ -0,0 +1,233 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::cfg::{ControlFlowGraph, Instr, InternalCallTy};
+use super::reaching_definitions::{block_edges, find};
+use super::{OptimizationLevel, Options};
+use crate::codegen::Expression;
+use crate::sema::Recurse;
+use std::collections::{HashMap, HashSet};
+
+/// Remove the code which can never be executed, or which has no effect. Constant folding replaces
+/// branches on constant conditions with unconditional branches, so the blocks on the other side
+/// of the branch become unreachable.
+///
+/// - Blocks which are not reachable from the entry block are removed
+/// - A block which is only branched to from one other block is merged into that block
+/// - Nop instructions are removed
+/// - Set instructions without side effects are removed, if the variable is never used. Like
+///   unused variable elimination, this is not done when optimizations are disabled.
+pub fn dead_code_elimination(cfg: &mut ControlFlowGraph, opt: &Options) {
+    remove_unreachable_blocks(cfg);
+    merge_blocks(cfg);
+    remove_unreachable_blocks(cfg);
+    remove_dead_instructions(cfg, opt);
+
+    // the block numbers and instructions have changed, so the reaching definitions are stale
+    for block in &mut cfg.blocks {
+        block.defs.clear();
+        block.loop_reaching_variables.clear();
+    }
+
+    find(cfg);
+}
+
+/// Remove the blocks which cannot be reached from the entry block, and renumber the rest
+fn remove_unreachable_blocks(cfg: &mut ControlFlowGraph) {
+    let mut reachable = vec![false; cfg.blocks.len()];
+    let mut todo = vec![0];
+
+    reachable[0] = true;
+
+    while let Some(block_no) = todo.pop() {
+        for edge in block_edges(&cfg.blocks[block_no]) {
+            if !reachable[edge] {
+                reachable[edge] = true;
+                todo.push(edge);
+            }
+        }
+    }
+
+    if reachable.iter().all(|reachable| *reachable) {
+        return;
+    }
+
+    let mut renumber = Vec::with_capacity(cfg.blocks.len());
+    let mut next = 0;
+
+    for reachable in &reachable {
+        renumber.push(next);
+
+        if *reachable {
+            next += 1;
+        }
+    }
+
+    let mut block_no = 0;
+
+    cfg.blocks.retain(|_| {
+        block_no += 1;
+
+        reachable[block_no - 1]
+    });
+
+    for instr in cfg
+        .blocks
+        .iter_mut()
+        .flat_map(|block| block.instr.iter_mut())
+    {
+        instr.update_blocks(|block| renumber[block]);
+    }
+}
+
+/// If a block ends with a branch to a block which has no other predecessors, then the two blocks
+/// can be joined. The merged blocks are left empty and unreachable.
+fn merge_blocks(cfg: &mut ControlFlowGraph) {
+    let mut preds = vec![0; cfg.blocks.len()];
+
+    for block in &cfg.blocks {
+        let mut edges = block_edges(block);
+
+        edges.sort_unstable();
+        edges.dedup();
+
+        for edge in edges {
+            preds[edge] += 1;
+        }
+    }
+
+    for block_no in 0..cfg.blocks.len() {
+        while let Some(Instr::Branch { block: next }) = cfg.blocks[block_no].instr.last() {
+            let next = *next;
+
+            // the merged block must not branch to itself, reaching definitions cannot handle
+            // a block which is its own successor
+            if next == 0
+                || next == block_no
+                || preds[next] != 1
+                || block_edges(&cfg.blocks[next]).contains(&block_no)
+            {
+                break;
+            }
+
+            let instrs = std::mem::take(&mut cfg.blocks[next].instr);
+
+            let block = &mut cfg.blocks[block_no];
+
+            block.instr.pop();
+            block.instr.extend(instrs);
+        }
+    }
+}
+
+/// Remove Nop instructions, and the Set instructions of variables which are never used
+fn remove_dead_instructions(cfg: &mut ControlFlowGraph, opt: &Options) {
+    if opt.opt_level == OptimizationLevel::None {
+        for block in &mut cfg.blocks {
+            block.instr.retain(|instr| !matches!(instr, Instr::Nop));
+        }
+
+        return;
+    }
+
+    // find the variables which are used by anything other than a Set which can be removed,
+    // and then the variables used to calculate those
+    let mut used = HashSet::new();
+    let mut todo = Vec::new();
+    let mut sets: HashMap<usize, Vec<usize>> = HashMap::new();
+
+    for instr in cfg.blocks.iter().flat_map(|block| block.instr.iter()) {
+        if let Some(var_no) = removable(instr) {
+            used_variables(instr, sets.entry(var_no).or_default());
+        } else {
+            used_variables(instr, &mut todo);
+
+            match instr {
+                Instr::PushMemory { array, .. } | Instr::PopMemory { array, .. } => {
+                    todo.push(*array);
+                }
+                // the return values of a call may be stored to the memory the variables point to
+                Instr::Call { res, .. } => {
+                    todo.extend(res);
+                }
+                _ => (),
+            }
+        }
+    }
+
+    while let Some(var_no) = todo.pop() {
+        if used.insert(var_no) {
+            if let Some(vars) = sets.get(&var_no) {
+                todo.extend(vars);
+            }
+        }
+    }
+
+    for block in &mut cfg.blocks {
+        block.instr.retain(|instr| match instr {
+            Instr::Nop => false,
+            _ => removable(instr).map_or(true, |var_no| used.contains(&var_no)),
+        });
+    }
+}
+
+/// If the instruction only sets a variable, and it has no other effect, return the variable
+fn removable(instr: &Instr) -> Option<usize> {
+    match instr {
+        Instr::Set { res, expr, .. } if !has_side_effects(expr) => Some(*res),
+        _ => None,
+    }
+}
+
+/// Add the variables used in the instruction to the list
+fn used_variables(instr: &Instr, vars: &mut Vec<usize>) {
+    let variable = |expr: &Expression, vars: &mut Vec<usize>| {
+        if let Expression::Variable(_, _, var_no) = expr {
+            vars.push(*var_no);
+        }
+
+        true
+    };
+
+    instr.recurse_expressions(vars, variable);
+
+    // these are not visited by recurse_expressions()
+    match instr {
+        Instr::Call {
+            call: InternalCallTy::Dynamic(expr),
+            ..
+        }
+        | Instr::ExternalCall {
+            accounts: Some(expr),
+            ..
+        }
+        | Instr::WriteBuffer { buf: expr, .. } => expr.recurse(vars, variable),
+        _ => (),
+    }
+}
+
+/// Can evaluating the expression do anything other than produce a value, e.g. fail because of a
+/// division by zero or an overflow check
+fn has_side_effects(expr: &Expression) -> bool {
+    let mut side_effects = false;
+
+    expr.recurse(&mut side_effects, |expr, side_effects| {
+        if matches!(
+            expr,
+            Expression::Add(_, _, false, ..)
+                | Expression::Subtract(_, _, false, ..)
+                | Expression::Multiply(_, _, false, ..)
+                | Expression::Power(_, _, false, ..)
+                | Expression::UnsignedDivide(..)
+                | Expression::SignedDivide(..)
+                | Expression::UnsignedModulo(..)
+                | Expression::SignedModulo(..)
+                | Expression::Builtin(..)
+        ) {
+            *side_effects = true;
+        }
+
+        !*side_effects
+    });
+
+    side_effects
+}
@@ -230,7 +230,7 @@ fn instr_transfers(block_no: usize, block: &BasicBlock) -> Vec<Vec<Transfer>> {
                 v
             }
             Instr::AbiDecode { res, .. } => set_var(res),
-            Instr::LoadStorage { res, .. } => set_var(&[*res]),
+            Instr::LoadStorage { res, .. } | Instr::LoadImmutable { res, .. } => set_var(&[*res]),
             Instr::PushMemory { array, res, .. } => {
                 let mut v = set_var(&[*res]);
                 v.push(Transfer::Kill { var_no: *array });
@@ -490,6 +490,10 @@ fn block_edges(block: &BasicBlock) -> Vec<usize> {
                 out.push(*true_block);
                 out.push(*false_block);
             }
+            Instr::Switch { cases, default, .. } => {
+                out.extend(cases.iter().map(|(_, block)| *block));
+                out.push(*default);
+            }
             Instr::AbiDecode {
                 exception_block: Some(block),
                 ..
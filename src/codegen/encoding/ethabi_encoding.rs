@@ -0,0 +1,763 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::codegen::cfg::{ControlFlowGraph, Instr};
+use crate::codegen::encoding::{
+    finish_array_loop, load_struct_member, set_array_loop, AbiEncoding,
+};
+use crate::codegen::expression::load_storage;
+use crate::codegen::vartable::Vartable;
+use crate::codegen::{Builtin, Expression};
+use crate::sema::ast::{ArrayLength, Namespace, RetrieveType, StructType, Type};
+use num_bigint::BigInt;
+use num_traits::Zero;
+use solang_parser::pt::Loc;
+use std::collections::HashMap;
+use std::ops::MulAssign;
+
+/// This struct implements the trait AbiEncoding for the Ethereum ABI encoding. Every value
+/// occupies 32 bytes, and dynamic values are placed at the end of their enclosing tuple, with
+/// their offset written in its place.
+pub(super) struct EthAbiEncoding {
+    /// Items loaded from storage while calculating the size of the arguments. For more
+    /// information, check the comment at 'struct BorshEncoding' on borsh_encoding.rs
+    storage_cache: HashMap<usize, Expression>,
+}
+
+impl AbiEncoding for EthAbiEncoding {
+    fn abi_encode(
+        &mut self,
+        loc: &Loc,
+        args: &[Expression],
+        ns: &Namespace,
+        vartab: &mut Vartable,
+        cfg: &mut ControlFlowGraph,
+    ) -> Expression {
+        let mut size = Expression::NumberLiteral(Loc::Codegen, Type::Uint(32), BigInt::zero());
+        for (arg_no, arg) in args.iter().enumerate() {
+            let arg_size = self.encoded_size(arg, arg_no, ns, vartab, cfg);
+            size = add(size, head_and_tail(arg, arg_size, ns));
+        }
+
+        let encoded_bytes = vartab.temp_name("abi_encoded", &Type::DynamicBytes);
+        cfg.add(
+            vartab,
+            Instr::Set {
+                loc: *loc,
+                res: encoded_bytes,
+                expr: Expression::AllocDynamicArray(*loc, Type::DynamicBytes, Box::new(size), None),
+            },
+        );
+
+        let buffer = Expression::Variable(*loc, Type::DynamicBytes, encoded_bytes);
+        let base = Expression::NumberLiteral(Loc::Codegen, Type::Uint(32), BigInt::zero());
+
+        let heads_size = args
+            .iter()
+            .map(|arg| head_size(&arg.ty(), ns))
+            .sum::<BigInt>();
+        let tail = vartab.temp_anonymous(&Type::Uint(32));
+        cfg.add(
+            vartab,
+            Instr::Set {
+                loc: Loc::Codegen,
+                res: tail,
+                expr: Expression::NumberLiteral(Loc::Codegen, Type::Uint(32), heads_size),
+            },
+        );
+
+        let mut head = base.clone();
+        for (arg_no, arg) in args.iter().enumerate() {
+            let advance =
+                self.encode_member(arg, arg_no, &buffer, &base, &head, tail, ns, vartab, cfg);
+            head = add(head, advance);
+        }
+
+        buffer
+    }
+
+    fn cache_storage_loaded(&mut self, arg_no: usize, expr: Expression) {
+        self.storage_cache.insert(arg_no, expr);
+    }
+
+    fn storage_cache_remove(&mut self, arg_no: usize) -> Option<Expression> {
+        self.storage_cache.remove(&arg_no)
+    }
+
+    fn get_encoding_size(&self, expr: &Expression, ty: &Type, _ns: &Namespace) -> Expression {
+        match ty {
+            Type::String | Type::DynamicBytes | Type::Slice(_) => {
+                // The length is followed by the bytes, padded to a multiple of 32 bytes
+                let length = Expression::Builtin(
+                    Loc::Codegen,
+                    vec![Type::Uint(32)],
+                    Builtin::ArrayLength,
+                    vec![expr.clone()],
+                );
+
+                add(self.size_width(&length), padded_length(length))
+            }
+
+            _ => Expression::NumberLiteral(Loc::Codegen, Type::Uint(32), BigInt::from(32u8)),
+        }
+    }
+
+    fn size_width(&self, _size: &Expression) -> Expression {
+        // The length is encoded as a uint256
+        Expression::NumberLiteral(Loc::Codegen, Type::Uint(32), BigInt::from(32u8))
+    }
+
+    fn encode_size(
+        &mut self,
+        size: &Expression,
+        buffer: &Expression,
+        offset: &Expression,
+        vartab: &mut Vartable,
+        cfg: &mut ControlFlowGraph,
+    ) -> Expression {
+        // The buffer is zero initialized, so only the lower 32 bits of the uint256 need writing
+        cfg.add(
+            vartab,
+            Instr::WriteBuffer {
+                buf: buffer.clone(),
+                offset: add(offset.clone(), literal(28)),
+                value: byte_swap(Type::Uint(32), size.clone()),
+            },
+        );
+
+        literal(32)
+    }
+
+    fn encode_primitive(
+        &mut self,
+        expr: &Expression,
+        ty: &Type,
+        buffer: &Expression,
+        offset: &Expression,
+        ns: &Namespace,
+        vartab: &mut Vartable,
+        cfg: &mut ControlFlowGraph,
+    ) -> Expression {
+        // Values are big endian and right aligned in their 32 bytes, except for bytesN and
+        // function types which are left aligned. The buffer is zero initialized, so the
+        // padding of unsigned values needs no writing.
+        let (value, value_offset) = match ty {
+            Type::Bool => (
+                Expression::ZeroExt(Loc::Codegen, Type::Uint(8), Box::new(expr.clone())),
+                add(offset.clone(), literal(31)),
+            ),
+
+            Type::Uint(8) | Type::Ufixed(8, _) | Type::Enum(_) => {
+                (expr.clone(), add(offset.clone(), literal(31)))
+            }
+
+            Type::Uint(_) | Type::Ufixed(..) | Type::Value => {
+                let bytes = ty.bits(ns) as u64 / 8;
+                (
+                    byte_swap(ty.clone(), expr.clone()),
+                    add(offset.clone(), literal(32 - bytes)),
+                )
+            }
+
+            Type::Int(_) | Type::Fixed(..) => {
+                // Signed values are sign extended to all 32 bytes
+                let value = if ty.bits(ns) == 256 {
+                    expr.clone()
+                } else {
+                    Expression::SignExt(Loc::Codegen, Type::Int(256), Box::new(expr.clone()))
+                };
+
+                (byte_swap(Type::Int(256), value), offset.clone())
+            }
+
+            Type::Contract(_) | Type::Address(_) => (
+                expr.clone(),
+                add(offset.clone(), literal(32 - ns.address_length as u64)),
+            ),
+
+            Type::Bytes(1) => (expr.clone(), offset.clone()),
+
+            Type::Bytes(_) => (byte_swap(ty.clone(), expr.clone()), offset.clone()),
+
+            Type::ExternalFunction { .. } => {
+                // The address is followed by the selector
+                let address = expr.external_function_address();
+                let selector = expr.external_function_selector();
+
+                cfg.add(
+                    vartab,
+                    Instr::WriteBuffer {
+                        buf: buffer.clone(),
+                        offset: offset.clone(),
+                        value: address,
+                    },
+                );
+
+                (
+                    byte_swap(Type::Bytes(4), selector),
+                    add(offset.clone(), literal(ns.address_length as u64)),
+                )
+            }
+
+            _ => unreachable!("This type cannot be encoded as a primitive"),
+        };
+
+        cfg.add(
+            vartab,
+            Instr::WriteBuffer {
+                buf: buffer.clone(),
+                offset: value_offset,
+                value,
+            },
+        );
+
+        literal(32)
+    }
+
+    fn is_memcpy_compatible(&self, _ty: &Type, _ns: &Namespace) -> bool {
+        // Every value is padded to 32 bytes, so nothing is encoded as it is laid out in memory
+        false
+    }
+}
+
+impl EthAbiEncoding {
+    pub fn new() -> EthAbiEncoding {
+        EthAbiEncoding {
+            storage_cache: HashMap::new(),
+        }
+    }
+
+    /// Calculate the number of bytes 'encode' writes for an expression. For dynamic types, this
+    /// does not include the 32 bytes of the offset in the head of the enclosing tuple.
+    fn encoded_size(
+        &mut self,
+        expr: &Expression,
+        arg_no: usize,
+        ns: &Namespace,
+        vartab: &mut Vartable,
+        cfg: &mut ControlFlowGraph,
+    ) -> Expression {
+        let ty = expr.ty().unwrap_user_type(ns);
+
+        match &ty {
+            Type::StorageRef(_, r) => {
+                let var = load_storage(&Loc::Codegen, r, expr.clone(), cfg, vartab);
+                let size = self.encoded_size(&var, arg_no, ns, vartab, cfg);
+                self.cache_storage_loaded(arg_no, var);
+                size
+            }
+
+            _ if !ty.is_dynamic(ns) => {
+                Expression::NumberLiteral(Loc::Codegen, Type::Uint(32), static_size(&ty, ns))
+            }
+
+            Type::Ref(r) => match &**r {
+                Type::Struct(struct_ty) => {
+                    self.struct_size(expr, struct_ty, arg_no, ns, vartab, cfg)
+                }
+                Type::Array(_, dims) if is_fixed_length(dims) => {
+                    self.array_size(expr, r, dims, arg_no, ns, vartab, cfg)
+                }
+                _ => {
+                    let loaded = Expression::Load(Loc::Codegen, *r.clone(), Box::new(expr.clone()));
+                    self.encoded_size(&loaded, arg_no, ns, vartab, cfg)
+                }
+            },
+
+            Type::Struct(struct_ty) => self.struct_size(expr, struct_ty, arg_no, ns, vartab, cfg),
+
+            Type::Array(_, dims) => self.array_size(expr, &ty, dims, arg_no, ns, vartab, cfg),
+
+            _ => self.get_encoding_size(expr, &ty, ns),
+        }
+    }
+
+    /// Calculate the number of bytes 'encode_struct' writes
+    fn struct_size(
+        &mut self,
+        expr: &Expression,
+        struct_ty: &StructType,
+        arg_no: usize,
+        ns: &Namespace,
+        vartab: &mut Vartable,
+        cfg: &mut ControlFlowGraph,
+    ) -> Expression {
+        let mut size = literal(0);
+        for (field_no, field) in struct_ty.definition(ns).fields.iter().enumerate() {
+            let member = load_struct_member(field.ty.clone(), expr.clone(), field_no);
+            let member_size = self.encoded_size(&member, arg_no, ns, vartab, cfg);
+            size = add(size, head_and_tail(&member, member_size, ns));
+        }
+
+        size
+    }
+
+    /// Calculate the number of bytes 'encode_array' writes
+    fn array_size(
+        &mut self,
+        expr: &Expression,
+        array_ty: &Type,
+        dims: &[ArrayLength],
+        arg_no: usize,
+        ns: &Namespace,
+        vartab: &mut Vartable,
+        cfg: &mut ControlFlowGraph,
+    ) -> Expression {
+        let item_ty = array_ty.array_elem();
+        let length = array_length(expr, dims, vartab, cfg);
+
+        // A dynamic array starts with its length
+        let prefix = if dims.last() == Some(&ArrayLength::Dynamic) {
+            literal(32)
+        } else {
+            literal(0)
+        };
+
+        if !item_ty.is_dynamic(ns) {
+            let items = Expression::Multiply(
+                Loc::Codegen,
+                Type::Uint(32),
+                true,
+                Box::new(length),
+                Box::new(Expression::NumberLiteral(
+                    Loc::Codegen,
+                    Type::Uint(32),
+                    static_size(&item_ty, ns),
+                )),
+            );
+
+            return add(prefix, items);
+        }
+
+        // Each item has its offset in the head, followed by its encoding
+        let size_var = vartab.temp_name(
+            format!("array_bytes_size_{}", arg_no).as_str(),
+            &Type::Uint(32),
+        );
+        cfg.add(
+            vartab,
+            Instr::Set {
+                loc: Loc::Codegen,
+                res: size_var,
+                expr: add(
+                    prefix,
+                    Expression::Multiply(
+                        Loc::Codegen,
+                        Type::Uint(32),
+                        true,
+                        Box::new(length),
+                        Box::new(literal(32)),
+                    ),
+                ),
+            },
+        );
+
+        let mut indexes = Vec::new();
+        let for_loop = set_array_loop(expr, dims, dims.len() - 1, &mut indexes, vartab, cfg);
+        cfg.set_basic_block(for_loop.body_block);
+
+        let item = array_item(expr, array_ty, for_loop.index);
+        let item_size = self.encoded_size(&item, arg_no, ns, vartab, cfg);
+        cfg.add(
+            vartab,
+            Instr::Set {
+                loc: Loc::Codegen,
+                res: size_var,
+                expr: add(
+                    Expression::Variable(Loc::Codegen, Type::Uint(32), size_var),
+                    item_size,
+                ),
+            },
+        );
+
+        finish_array_loop(&for_loop, vartab, cfg);
+
+        Expression::Variable(Loc::Codegen, Type::Uint(32), size_var)
+    }
+
+    /// Encode a member of a tuple. Static values are encoded in the head, at 'head'. Dynamic
+    /// values are encoded at the end of the tuple, and their offset relative to 'base', the start
+    /// of the tuple, is written in the head. The variable 'tail' holds the current size of the
+    /// tuple. Returns the number of bytes the member occupies in the head.
+    fn encode_member(
+        &mut self,
+        expr: &Expression,
+        arg_no: usize,
+        buffer: &Expression,
+        base: &Expression,
+        head: &Expression,
+        tail: usize,
+        ns: &Namespace,
+        vartab: &mut Vartable,
+        cfg: &mut ControlFlowGraph,
+    ) -> Expression {
+        if !expr.ty().is_dynamic(ns) {
+            return self.encode(expr, arg_no, buffer, head, ns, vartab, cfg);
+        }
+
+        let tail_var = Expression::Variable(Loc::Codegen, Type::Uint(32), tail);
+        self.encode_size(&tail_var, buffer, head, vartab, cfg);
+
+        let offset = add(base.clone(), tail_var.clone());
+        let size = self.encode(expr, arg_no, buffer, &offset, ns, vartab, cfg);
+
+        cfg.add(
+            vartab,
+            Instr::Set {
+                loc: Loc::Codegen,
+                res: tail,
+                expr: add(tail_var, size),
+            },
+        );
+
+        literal(32)
+    }
+
+    /// Encode an expression at 'offset'. Returns the number of bytes written.
+    fn encode(
+        &mut self,
+        expr: &Expression,
+        arg_no: usize,
+        buffer: &Expression,
+        offset: &Expression,
+        ns: &Namespace,
+        vartab: &mut Vartable,
+        cfg: &mut ControlFlowGraph,
+    ) -> Expression {
+        let ty = expr.ty().unwrap_user_type(ns);
+
+        match &ty {
+            Type::String | Type::DynamicBytes | Type::Slice(_) => {
+                let length = vartab.temp_anonymous(&Type::Uint(32));
+                cfg.add(
+                    vartab,
+                    Instr::Set {
+                        loc: Loc::Codegen,
+                        res: length,
+                        expr: Expression::Builtin(
+                            Loc::Codegen,
+                            vec![Type::Uint(32)],
+                            Builtin::ArrayLength,
+                            vec![expr.clone()],
+                        ),
+                    },
+                );
+                let length = Expression::Variable(Loc::Codegen, Type::Uint(32), length);
+
+                let size_width = self.encode_size(&length, buffer, offset, vartab, cfg);
+
+                cfg.add(
+                    vartab,
+                    Instr::MemCopy {
+                        source: expr.clone(),
+                        destination: Expression::AdvancePointer {
+                            loc: Loc::Codegen,
+                            ty: Type::BufferPointer,
+                            pointer: Box::new(buffer.clone()),
+                            bytes_offset: Box::new(add(offset.clone(), size_width.clone())),
+                        },
+                        bytes: length.clone(),
+                    },
+                );
+
+                add(size_width, padded_length(length))
+            }
+
+            Type::Struct(struct_ty) => {
+                self.encode_struct(expr, struct_ty, arg_no, buffer, offset, ns, vartab, cfg)
+            }
+
+            Type::Array(_, dims) => {
+                self.encode_array(expr, &ty, dims, arg_no, buffer, offset, ns, vartab, cfg)
+            }
+
+            Type::Ref(r) => match &**r {
+                // Structs and fixed length arrays are accessed through their pointer
+                Type::Struct(struct_ty) => {
+                    self.encode_struct(expr, struct_ty, arg_no, buffer, offset, ns, vartab, cfg)
+                }
+                Type::Array(_, dims) if is_fixed_length(dims) => {
+                    self.encode_array(expr, r, dims, arg_no, buffer, offset, ns, vartab, cfg)
+                }
+                _ => {
+                    let loaded = Expression::Load(Loc::Codegen, *r.clone(), Box::new(expr.clone()));
+                    self.encode(&loaded, arg_no, buffer, offset, ns, vartab, cfg)
+                }
+            },
+
+            Type::StorageRef(..) => {
+                let loaded = self.storage_cache_remove(arg_no).unwrap();
+                self.encode(&loaded, arg_no, buffer, offset, ns, vartab, cfg)
+            }
+
+            Type::UserType(_) | Type::Unresolved | Type::Rational | Type::Unreachable => {
+                unreachable!("Type should not exist in codegen")
+            }
+
+            Type::InternalFunction { .. }
+            | Type::Void
+            | Type::BufferPointer
+            | Type::Mapping(..) => unreachable!("This type cannot be encoded"),
+
+            _ => self.encode_primitive(expr, &ty, buffer, offset, ns, vartab, cfg),
+        }
+    }
+
+    /// Encode a struct as a tuple of its fields. Returns the number of bytes written.
+    fn encode_struct(
+        &mut self,
+        expr: &Expression,
+        struct_ty: &StructType,
+        arg_no: usize,
+        buffer: &Expression,
+        offset: &Expression,
+        ns: &Namespace,
+        vartab: &mut Vartable,
+        cfg: &mut ControlFlowGraph,
+    ) -> Expression {
+        let fields = &struct_ty.definition(ns).fields;
+
+        let heads_size = fields
+            .iter()
+            .map(|field| head_size(&field.ty, ns))
+            .sum::<BigInt>();
+
+        let tail = vartab.temp_anonymous(&Type::Uint(32));
+        cfg.add(
+            vartab,
+            Instr::Set {
+                loc: Loc::Codegen,
+                res: tail,
+                expr: Expression::NumberLiteral(Loc::Codegen, Type::Uint(32), heads_size),
+            },
+        );
+
+        let mut head = offset.clone();
+        for (field_no, field) in fields.iter().enumerate() {
+            let member = load_struct_member(field.ty.clone(), expr.clone(), field_no);
+            let advance = self.encode_member(
+                &member, arg_no, buffer, offset, &head, tail, ns, vartab, cfg,
+            );
+            head = add(head, advance);
+        }
+
+        Expression::Variable(Loc::Codegen, Type::Uint(32), tail)
+    }
+
+    /// Encode an array as a tuple of its items, preceded by its length if it is dynamic. Returns
+    /// the number of bytes written.
+    fn encode_array(
+        &mut self,
+        expr: &Expression,
+        array_ty: &Type,
+        dims: &[ArrayLength],
+        arg_no: usize,
+        buffer: &Expression,
+        offset: &Expression,
+        ns: &Namespace,
+        vartab: &mut Vartable,
+        cfg: &mut ControlFlowGraph,
+    ) -> Expression {
+        let item_ty = array_ty.array_elem();
+        let length = array_length(expr, dims, vartab, cfg);
+
+        let (base, prefix) = if dims.last() == Some(&ArrayLength::Dynamic) {
+            let size_width = self.encode_size(&length, buffer, offset, vartab, cfg);
+            (add(offset.clone(), size_width.clone()), size_width)
+        } else {
+            (offset.clone(), literal(0))
+        };
+
+        let item_dynamic = item_ty.is_dynamic(ns);
+        let item_size = if item_dynamic {
+            BigInt::from(32u8)
+        } else {
+            static_size(&item_ty, ns)
+        };
+
+        let items_size = Expression::Multiply(
+            Loc::Codegen,
+            Type::Uint(32),
+            true,
+            Box::new(length),
+            Box::new(Expression::NumberLiteral(
+                Loc::Codegen,
+                Type::Uint(32),
+                item_size.clone(),
+            )),
+        );
+
+        // The tail of the tuple begins after the heads of all items
+        let tail = if item_dynamic {
+            let tail = vartab.temp_anonymous(&Type::Uint(32));
+            cfg.add(
+                vartab,
+                Instr::Set {
+                    loc: Loc::Codegen,
+                    res: tail,
+                    expr: items_size.clone(),
+                },
+            );
+            Some(tail)
+        } else {
+            None
+        };
+
+        let mut indexes = Vec::new();
+        let for_loop = set_array_loop(expr, dims, dims.len() - 1, &mut indexes, vartab, cfg);
+        cfg.set_basic_block(for_loop.body_block);
+
+        let item = array_item(expr, array_ty, for_loop.index);
+        let head = add(
+            base.clone(),
+            Expression::Multiply(
+                Loc::Codegen,
+                Type::Uint(32),
+                true,
+                Box::new(Expression::Variable(
+                    Loc::Codegen,
+                    Type::Uint(32),
+                    for_loop.index,
+                )),
+                Box::new(Expression::NumberLiteral(
+                    Loc::Codegen,
+                    Type::Uint(32),
+                    item_size,
+                )),
+            ),
+        );
+
+        if let Some(tail) = tail {
+            self.encode_member(&item, arg_no, buffer, &base, &head, tail, ns, vartab, cfg);
+        } else {
+            self.encode(&item, arg_no, buffer, &head, ns, vartab, cfg);
+        }
+
+        finish_array_loop(&for_loop, vartab, cfg);
+
+        match tail {
+            Some(tail) => add(
+                prefix,
+                Expression::Variable(Loc::Codegen, Type::Uint(32), tail),
+            ),
+            None => add(prefix, items_size),
+        }
+    }
+}
+
+/// Size in bytes of a value in the head of a tuple
+fn head_size(ty: &Type, ns: &Namespace) -> BigInt {
+    if ty.is_dynamic(ns) {
+        BigInt::from(32u8)
+    } else {
+        static_size(ty, ns)
+    }
+}
+
+/// Size in bytes of a value whose type is not dynamic
+fn static_size(ty: &Type, ns: &Namespace) -> BigInt {
+    match ty {
+        Type::Ref(r) | Type::StorageRef(_, r) => static_size(r, ns),
+        Type::UserType(_) => static_size(&ty.clone().unwrap_user_type(ns), ns),
+        Type::Struct(struct_ty) => struct_ty
+            .definition(ns)
+            .fields
+            .iter()
+            .map(|field| static_size(&field.ty, ns))
+            .sum(),
+        Type::Array(elem_ty, dims) => {
+            let mut size = static_size(elem_ty, ns);
+            for dim in dims {
+                size.mul_assign(dim.array_length().unwrap());
+            }
+            size
+        }
+        _ => BigInt::from(32u8),
+    }
+}
+
+/// Add the size of the offset in the head of the tuple to the size of a dynamic value
+fn head_and_tail(expr: &Expression, size: Expression, ns: &Namespace) -> Expression {
+    if expr.ty().is_dynamic(ns) {
+        add(literal(32), size)
+    } else {
+        size
+    }
+}
+
+/// Is the outer dimension of this array of fixed length?
+fn is_fixed_length(dims: &[ArrayLength]) -> bool {
+    matches!(dims.last(), Some(ArrayLength::Fixed(_)))
+}
+
+/// Retrieve the length of an array, saving it to a variable if it is not known at compile time
+fn array_length(
+    expr: &Expression,
+    dims: &[ArrayLength],
+    vartab: &mut Vartable,
+    cfg: &mut ControlFlowGraph,
+) -> Expression {
+    if let Some(ArrayLength::Fixed(length)) = dims.last() {
+        return Expression::NumberLiteral(Loc::Codegen, Type::Uint(32), length.clone());
+    }
+
+    let length = vartab.temp_anonymous(&Type::Uint(32));
+    cfg.add(
+        vartab,
+        Instr::Set {
+            loc: Loc::Codegen,
+            res: length,
+            expr: Expression::Builtin(
+                Loc::Codegen,
+                vec![Type::Uint(32)],
+                Builtin::ArrayLength,
+                vec![expr.clone()],
+            ),
+        },
+    );
+
+    Expression::Variable(Loc::Codegen, Type::Uint(32), length)
+}
+
+/// Retrieve an item of the outer dimension of an array
+fn array_item(array: &Expression, array_ty: &Type, index: usize) -> Expression {
+    Expression::Subscript(
+        Loc::Codegen,
+        Type::Ref(Box::new(array_ty.array_elem())),
+        array_ty.clone(),
+        Box::new(array.clone()),
+        Box::new(Expression::Variable(Loc::Codegen, Type::Uint(32), index)),
+    )
+}
+
+/// Round a length up to a multiple of 32 bytes
+fn padded_length(length: Expression) -> Expression {
+    Expression::BitwiseAnd(
+        Loc::Codegen,
+        Type::Uint(32),
+        Box::new(add(length, literal(31))),
+        Box::new(Expression::NumberLiteral(
+            Loc::Codegen,
+            Type::Uint(32),
+            BigInt::from(!31u32),
+        )),
+    )
+}
+
+/// Reverse the byte order of a value, so that it is written big endian
+fn byte_swap(ty: Type, value: Expression) -> Expression {
+    Expression::Builtin(Loc::Codegen, vec![ty], Builtin::ByteSwap, vec![value])
+}
+
+fn add(left: Expression, right: Expression) -> Expression {
+    Expression::Add(
+        Loc::Codegen,
+        Type::Uint(32),
+        true,
+        Box::new(left),
+        Box::new(right),
+    )
+}
+
+fn literal(value: u64) -> Expression {
+    Expression::NumberLiteral(Loc::Codegen, Type::Uint(32), BigInt::from(value))
+}
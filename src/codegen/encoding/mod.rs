@@ -1,17 +1,22 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod borsh_encoding;
+mod ethabi_encoding;
+mod scale_encoding;
 
 use crate::codegen::cfg::{ControlFlowGraph, Instr};
 use crate::codegen::encoding::borsh_encoding::BorshEncoding;
+use crate::codegen::encoding::ethabi_encoding::EthAbiEncoding;
+use crate::codegen::encoding::scale_encoding::ScaleEncoding;
 use crate::codegen::expression::load_storage;
 use crate::codegen::vartable::Vartable;
 use crate::codegen::{Builtin, Expression};
 use crate::sema::ast::{ArrayLength, Namespace, RetrieveType, StructType, Type};
 use crate::Target;
 use num_bigint::BigInt;
+use num_traits::Zero;
 use solang_parser::pt::Loc;
-use std::ops::AddAssign;
+use std::ops::{AddAssign, MulAssign};
 
 /// This trait should be implemented by all encoding methods (ethabi, Scale and Borsh), so that
 /// we have the same interface for creating encode and decode functions.
@@ -35,16 +40,544 @@ pub(super) trait AbiEncoding {
     /// 'struct BorshEncoding' on borsh_encoding.rs
     fn cache_storage_loaded(&mut self, arg_no: usize, expr: Expression);
 
+    /// Retrieve the item cached by 'cache_storage_loaded' for argument 'arg_no'.
+    fn storage_cache_remove(&mut self, arg_no: usize) -> Option<Expression>;
+
     /// Some types have sizes that are specific to each encoding scheme, so there is no way to generalize.
     fn get_encoding_size(&self, expr: &Expression, ty: &Type, ns: &Namespace) -> Expression;
+
+    /// Returns the number of bytes needed to encode 'size', the length of a dynamic array or string.
+    fn size_width(&self, size: &Expression) -> Expression;
+
+    /// Encode the length of a dynamic array or string into the buffer. Returns the number of bytes
+    /// written.
+    fn encode_size(
+        &mut self,
+        size: &Expression,
+        buffer: &Expression,
+        offset: &Expression,
+        vartab: &mut Vartable,
+        cfg: &mut ControlFlowGraph,
+    ) -> Expression;
+
+    /// Encode a value whose type is not a struct, an array or a string. Returns the number of
+    /// bytes written.
+    fn encode_primitive(
+        &mut self,
+        expr: &Expression,
+        ty: &Type,
+        buffer: &Expression,
+        offset: &Expression,
+        ns: &Namespace,
+        vartab: &mut Vartable,
+        cfg: &mut ControlFlowGraph,
+    ) -> Expression;
+
+    /// Is a value of this type encoded exactly as it is laid out in memory? If so, arrays of it
+    /// can be copied into the buffer with a single memcpy.
+    fn is_memcpy_compatible(&self, ty: &Type, ns: &Namespace) -> bool;
 }
 
 /// This function should return the correct encoder, given the target
-pub(super) fn create_encoder(ns: &Namespace) -> impl AbiEncoding {
+pub(super) fn create_encoder(ns: &Namespace) -> Box<dyn AbiEncoding> {
     match &ns.target {
-        Target::Solana => BorshEncoding::new(),
-        _ => unreachable!("Other types of encoding have not been implemented yet"),
+        Target::Solana => Box::new(BorshEncoding::new()),
+        Target::Substrate { .. } => Box::new(ScaleEncoding::new()),
+        Target::Ewasm => Box::new(EthAbiEncoding::new()),
+    }
+}
+
+/// Encode the arguments one after the other into a newly allocated buffer. Borsh and SCALE
+/// both lay out their arguments this way.
+fn encode_args<T: AbiEncoding>(
+    encoder: &mut T,
+    loc: &Loc,
+    args: &[Expression],
+    ns: &Namespace,
+    vartab: &mut Vartable,
+    cfg: &mut ControlFlowGraph,
+) -> Expression {
+    let size = calculate_size_args(encoder, args, ns, vartab, cfg);
+
+    let encoded_bytes = vartab.temp_name("abi_encoded", &Type::DynamicBytes);
+    cfg.add(
+        vartab,
+        Instr::Set {
+            loc: *loc,
+            res: encoded_bytes,
+            expr: Expression::AllocDynamicArray(*loc, Type::DynamicBytes, Box::new(size), None),
+        },
+    );
+
+    let mut offset = Expression::NumberLiteral(*loc, Type::Uint(32), BigInt::zero());
+    let buffer = Expression::Variable(*loc, Type::DynamicBytes, encoded_bytes);
+
+    for (arg_no, item) in args.iter().enumerate() {
+        let advance = encode(encoder, item, &buffer, &offset, arg_no, ns, vartab, cfg);
+        offset = Expression::Add(
+            Loc::Codegen,
+            Type::Uint(32),
+            true,
+            Box::new(offset),
+            Box::new(advance),
+        );
     }
+
+    buffer
+}
+
+/// Encode expression to buffer. Returns the size in bytes of the encoded item.
+fn encode<T: AbiEncoding>(
+    encoder: &mut T,
+    expr: &Expression,
+    buffer: &Expression,
+    offset: &Expression,
+    arg_no: usize,
+    ns: &Namespace,
+    vartab: &mut Vartable,
+    cfg: &mut ControlFlowGraph,
+) -> Expression {
+    let expr_ty = expr.ty().unwrap_user_type(ns);
+
+    match &expr_ty {
+        Type::String | Type::DynamicBytes | Type::Slice(_) => {
+            let get_size = Expression::Builtin(
+                Loc::Codegen,
+                vec![Type::Uint(32)],
+                Builtin::ArrayLength,
+                vec![expr.clone()],
+            );
+            let array_length = vartab.temp_anonymous(&Type::Uint(32));
+            cfg.add(
+                vartab,
+                Instr::Set {
+                    loc: Loc::Codegen,
+                    res: array_length,
+                    expr: get_size,
+                },
+            );
+
+            let var = Expression::Variable(Loc::Codegen, Type::Uint(32), array_length);
+            let size_width = encoder.encode_size(&var, buffer, offset, vartab, cfg);
+
+            // ptr + offset + size_width
+            let dest_address = Expression::AdvancePointer {
+                loc: Loc::Codegen,
+                ty: Type::BufferPointer,
+                pointer: Box::new(buffer.clone()),
+                bytes_offset: Box::new(Expression::Add(
+                    Loc::Codegen,
+                    Type::Uint(32),
+                    true,
+                    Box::new(offset.clone()),
+                    Box::new(size_width.clone()),
+                )),
+            };
+
+            cfg.add(
+                vartab,
+                Instr::MemCopy {
+                    source: expr.clone(),
+                    destination: dest_address,
+                    bytes: var.clone(),
+                },
+            );
+
+            Expression::Add(
+                Loc::Codegen,
+                Type::Uint(32),
+                true,
+                Box::new(var),
+                Box::new(size_width),
+            )
+        }
+
+        Type::Struct(struct_ty) => encode_struct(
+            encoder,
+            expr,
+            buffer,
+            offset.clone(),
+            &expr_ty,
+            struct_ty,
+            arg_no,
+            ns,
+            vartab,
+            cfg,
+        ),
+
+        Type::Array(ty, dims) => encode_array(
+            encoder, expr, &expr_ty, ty, dims, arg_no, buffer, offset, ns, vartab, cfg,
+        ),
+
+        Type::UserType(_) | Type::Unresolved | Type::Rational | Type::Unreachable => {
+            unreachable!("Type should not exist in codegen")
+        }
+
+        Type::InternalFunction { .. } | Type::Void | Type::BufferPointer | Type::Mapping(..) => {
+            unreachable!("This type cannot be encoded")
+        }
+
+        Type::Ref(r) => match &**r {
+            // Structs references should not be dereferenced
+            Type::Struct(struct_ty) => encode_struct(
+                encoder,
+                expr,
+                buffer,
+                offset.clone(),
+                &expr_ty,
+                struct_ty,
+                arg_no,
+                ns,
+                vartab,
+                cfg,
+            ),
+            // Neither should fixed length arrays
+            Type::Array(ty, dims) if r.is_fixed_reference_type() => encode_array(
+                encoder, expr, r, ty, dims, arg_no, buffer, offset, ns, vartab, cfg,
+            ),
+            _ => {
+                let loaded = Expression::Load(Loc::Codegen, *r.clone(), Box::new(expr.clone()));
+                encode(encoder, &loaded, buffer, offset, arg_no, ns, vartab, cfg)
+            }
+        },
+
+        Type::StorageRef(..) => {
+            let loaded = encoder.storage_cache_remove(arg_no).unwrap();
+            encode(encoder, &loaded, buffer, offset, arg_no, ns, vartab, cfg)
+        }
+
+        _ => encoder.encode_primitive(expr, &expr_ty, buffer, offset, ns, vartab, cfg),
+    }
+}
+
+/// Encode an array and return its size in bytes
+fn encode_array<T: AbiEncoding>(
+    encoder: &mut T,
+    array: &Expression,
+    array_ty: &Type,
+    elem_ty: &Type,
+    dims: &Vec<ArrayLength>,
+    arg_no: usize,
+    buffer: &Expression,
+    offset: &Expression,
+    ns: &Namespace,
+    vartab: &mut Vartable,
+    cfg: &mut ControlFlowGraph,
+) -> Expression {
+    // Check if we can MemCpy elements into the buffer
+    let direct_encoding = if array_ty.is_dynamic(ns) {
+        // If this is a dynamic array, we can only MemCpy if its elements are of
+        // any primitive type encoded as they are laid out in memory and we don't need to index it.
+        dims.len() == 1 && elem_ty.is_primitive() && encoder.is_memcpy_compatible(elem_ty, ns)
+    } else {
+        // If the array is not dynamic, we can MemCpy elements if their are primitive and
+        // encoded as they are laid out in memory.
+        elem_ty.is_primitive() && encoder.is_memcpy_compatible(elem_ty, ns)
+    };
+
+    if dims.is_empty() {
+        // Array has no dimension
+        let zero = Expression::NumberLiteral(Loc::Codegen, Type::Uint(32), BigInt::zero());
+        encoder.encode_size(&zero, buffer, offset, vartab, cfg)
+    } else if direct_encoding {
+        // Calculate number of elements
+        let (bytes_size, offset, size_width) =
+            if matches!(dims.last(), Some(&ArrayLength::Fixed(_))) {
+                let mut elem_no = BigInt::from(1u8);
+                for item in dims {
+                    assert!(matches!(item, &ArrayLength::Fixed(_)));
+                    elem_no.mul_assign(item.array_length().unwrap());
+                }
+
+                let bytes = elem_ty.memory_size_of(ns);
+                elem_no.mul_assign(&bytes);
+                (
+                    Expression::NumberLiteral(Loc::Codegen, Type::Uint(32), elem_no),
+                    offset.clone(),
+                    None,
+                )
+            } else {
+                let arr_size = Expression::Builtin(
+                    Loc::Codegen,
+                    vec![Type::Uint(32)],
+                    Builtin::ArrayLength,
+                    vec![array.clone()],
+                );
+
+                let size_temp = vartab.temp_anonymous(&Type::Uint(32));
+                cfg.add(
+                    vartab,
+                    Instr::Set {
+                        loc: Loc::Codegen,
+                        res: size_temp,
+                        expr: arr_size,
+                    },
+                );
+
+                let size_var = Expression::Variable(Loc::Codegen, Type::Uint(32), size_temp);
+                let size_width = encoder.encode_size(&size_var, buffer, offset, vartab, cfg);
+
+                let size = Expression::Multiply(
+                    Loc::Codegen,
+                    Type::Uint(32),
+                    true,
+                    Box::new(size_var),
+                    Box::new(Expression::NumberLiteral(
+                        Loc::Codegen,
+                        Type::Uint(32),
+                        elem_ty.memory_size_of(ns),
+                    )),
+                );
+
+                let offset = Expression::Add(
+                    Loc::Codegen,
+                    Type::Uint(32),
+                    true,
+                    Box::new(offset.clone()),
+                    Box::new(size_width.clone()),
+                );
+
+                (size, offset, Some(size_width))
+            };
+
+        let dest_address = Expression::AdvancePointer {
+            loc: Loc::Codegen,
+            pointer: Box::new(buffer.clone()),
+            ty: Type::BufferPointer,
+            bytes_offset: Box::new(offset),
+        };
+
+        cfg.add(
+            vartab,
+            Instr::MemCopy {
+                source: array.clone(),
+                destination: dest_address,
+                bytes: bytes_size.clone(),
+            },
+        );
+
+        // If the array is dynamic, we have written into the buffer its size and its elements
+        if let Some(size_width) = size_width {
+            Expression::Add(
+                Loc::Codegen,
+                Type::Uint(32),
+                true,
+                Box::new(bytes_size),
+                Box::new(size_width),
+            )
+        } else {
+            bytes_size
+        }
+    } else {
+        // In all other cases, we must loop through the array
+        let mut indexes: Vec<usize> = Vec::new();
+        let offset_var = vartab.temp_anonymous(&Type::Uint(32));
+        cfg.add(
+            vartab,
+            Instr::Set {
+                loc: Loc::Codegen,
+                res: offset_var,
+                expr: offset.clone(),
+            },
+        );
+        encode_complex_array(
+            encoder,
+            array,
+            arg_no,
+            dims,
+            buffer,
+            offset_var,
+            dims.len() - 1,
+            ns,
+            vartab,
+            cfg,
+            &mut indexes,
+        );
+
+        // Subtract the original offset from
+        // the offset variable to obtain the vector size in bytes
+        cfg.add(
+            vartab,
+            Instr::Set {
+                loc: Loc::Codegen,
+                res: offset_var,
+                expr: Expression::Subtract(
+                    Loc::Codegen,
+                    Type::Uint(32),
+                    true,
+                    Box::new(Expression::Variable(
+                        Loc::Codegen,
+                        Type::Uint(32),
+                        offset_var,
+                    )),
+                    Box::new(offset.clone()),
+                ),
+            },
+        );
+        Expression::Variable(Loc::Codegen, Type::Uint(32), offset_var)
+    }
+}
+
+/// Encode a complex array.
+/// This function indexes an array from its outer dimension to its inner one
+fn encode_complex_array<T: AbiEncoding>(
+    encoder: &mut T,
+    arr: &Expression,
+    arg_no: usize,
+    dims: &Vec<ArrayLength>,
+    buffer: &Expression,
+    offset_var: usize,
+    dimension: usize,
+    ns: &Namespace,
+    vartab: &mut Vartable,
+    cfg: &mut ControlFlowGraph,
+    indexes: &mut Vec<usize>,
+) {
+    // If this dimension is dynamic, we must save its length before all elements
+    if dims[dimension] == ArrayLength::Dynamic {
+        let size = get_array_length(arr, dims, indexes, dimension);
+
+        let offset_expr = Expression::Variable(Loc::Codegen, Type::Uint(32), offset_var);
+        let size_width = encoder.encode_size(&size, buffer, &offset_expr, vartab, cfg);
+        cfg.add(
+            vartab,
+            Instr::Set {
+                loc: Loc::Codegen,
+                res: offset_var,
+                expr: Expression::Add(
+                    Loc::Codegen,
+                    Type::Uint(32),
+                    true,
+                    Box::new(offset_expr),
+                    Box::new(size_width),
+                ),
+            },
+        );
+    }
+    let for_loop = set_array_loop(arr, dims, dimension, indexes, vartab, cfg);
+    cfg.set_basic_block(for_loop.body_block);
+    if 0 == dimension {
+        // If we are indexing the last dimension, we have an element, so we can encode it.
+        let deref = load_array_item(arr, dims, indexes);
+        let offset_expr = Expression::Variable(Loc::Codegen, Type::Uint(32), offset_var);
+        let elem_size = encode(
+            encoder,
+            &deref,
+            buffer,
+            &offset_expr,
+            arg_no,
+            ns,
+            vartab,
+            cfg,
+        );
+        cfg.add(
+            vartab,
+            Instr::Set {
+                loc: Loc::Codegen,
+                res: offset_var,
+                expr: Expression::Add(
+                    Loc::Codegen,
+                    Type::Uint(32),
+                    true,
+                    Box::new(elem_size),
+                    Box::new(offset_expr),
+                ),
+            },
+        );
+    } else {
+        encode_complex_array(
+            encoder,
+            arr,
+            arg_no,
+            dims,
+            buffer,
+            offset_var,
+            dimension - 1,
+            ns,
+            vartab,
+            cfg,
+            indexes,
+        )
+    };
+
+    finish_array_loop(&for_loop, vartab, cfg);
+}
+
+/// Encode a struct
+fn encode_struct<T: AbiEncoding>(
+    encoder: &mut T,
+    expr: &Expression,
+    buffer: &Expression,
+    mut offset: Expression,
+    expr_ty: &Type,
+    struct_ty: &StructType,
+    arg_no: usize,
+    ns: &Namespace,
+    vartab: &mut Vartable,
+    cfg: &mut ControlFlowGraph,
+) -> Expression {
+    let size = if let Some(no_padding_size) = calculate_struct_fixed_size(encoder, struct_ty, ns) {
+        let padded_size = expr_ty.solana_storage_size(ns);
+        // If the size without padding equals the size with padding, we
+        // can memcpy this struct directly.
+        if padded_size.eq(&no_padding_size) && encoder.is_memcpy_compatible(expr_ty, ns) {
+            let size = Expression::NumberLiteral(Loc::Codegen, Type::Uint(32), no_padding_size);
+            let dest_address = Expression::AdvancePointer {
+                loc: Loc::Codegen,
+                ty: Type::BufferPointer,
+                pointer: Box::new(buffer.clone()),
+                bytes_offset: Box::new(offset),
+            };
+            cfg.add(
+                vartab,
+                Instr::MemCopy {
+                    source: expr.clone(),
+                    destination: dest_address,
+                    bytes: size.clone(),
+                },
+            );
+            return size;
+        } else {
+            // This struct has a fixed size, but we cannot memcpy it due to
+            // its padding in memory
+            Some(Expression::NumberLiteral(
+                Loc::Codegen,
+                Type::Uint(32),
+                no_padding_size,
+            ))
+        }
+    } else {
+        None
+    };
+
+    let qty = struct_ty.definition(ns).fields.len();
+    let first_ty = struct_ty.definition(ns).fields[0].ty.clone();
+    let loaded = load_struct_member(first_ty, expr.clone(), 0);
+
+    let mut advance = encode(encoder, &loaded, buffer, &offset, arg_no, ns, vartab, cfg);
+    let mut runtime_size = advance.clone();
+    for i in 1..qty {
+        let ith_type = struct_ty.definition(ns).fields[i].ty.clone();
+        offset = Expression::Add(
+            Loc::Codegen,
+            Type::Uint(32),
+            true,
+            Box::new(offset.clone()),
+            Box::new(advance),
+        );
+        let loaded = load_struct_member(ith_type.clone(), expr.clone(), i);
+        // After fetching the struct member, we can encode it
+        advance = encode(encoder, &loaded, buffer, &offset, arg_no, ns, vartab, cfg);
+        runtime_size = Expression::Add(
+            Loc::Codegen,
+            Type::Uint(32),
+            true,
+            Box::new(runtime_size),
+            Box::new(advance.clone()),
+        );
+    }
+
+    size.unwrap_or(runtime_size)
 }
 
 /// Calculate the size of a set of arguments to encoding functions
@@ -55,12 +588,16 @@ fn calculate_size_args<T: AbiEncoding>(
     vartab: &mut Vartable,
     cfg: &mut ControlFlowGraph,
 ) -> Expression {
+    if args.is_empty() {
+        return Expression::NumberLiteral(Loc::Codegen, Type::Uint(32), BigInt::zero());
+    }
+
     let mut size = get_expr_size(encoder, 0, &args[0], ns, vartab, cfg);
     for (i, item) in args.iter().enumerate().skip(1) {
         size = Expression::Add(
             Loc::Codegen,
             Type::Uint(32),
-            false,
+            true,
             Box::new(size),
             Box::new(get_expr_size(encoder, i, item, ns, vartab, cfg)),
         );
@@ -80,10 +617,6 @@ fn get_expr_size<T: AbiEncoding>(
 ) -> Expression {
     let ty = expr.ty().unwrap_user_type(ns);
     match &ty {
-        Type::Value => {
-            Expression::NumberLiteral(Loc::Codegen, Type::Uint(32), BigInt::from(ns.value_length))
-        }
-
         Type::Struct(struct_ty) => {
             calculate_struct_size(encoder, arg_no, expr, struct_ty, ns, vartab, cfg)
         }
@@ -113,13 +646,18 @@ fn get_expr_size<T: AbiEncoding>(
         | Type::BufferPointer
         | Type::Mapping(..) => unreachable!("This type cannot be encoded"),
 
-        Type::Ref(r) => {
-            if let Type::Struct(struct_ty) = &**r {
-                return calculate_struct_size(encoder, arg_no, expr, struct_ty, ns, vartab, cfg);
+        Type::Ref(r) => match &**r {
+            Type::Struct(struct_ty) => {
+                calculate_struct_size(encoder, arg_no, expr, struct_ty, ns, vartab, cfg)
             }
-            let loaded = Expression::Load(Loc::Codegen, *r.clone(), Box::new(expr.clone()));
-            get_expr_size(encoder, arg_no, &loaded, ns, vartab, cfg)
-        }
+            Type::Array(ty, dims) if r.is_fixed_reference_type() => {
+                calculate_array_size(encoder, expr, ty, dims, arg_no, ns, vartab, cfg)
+            }
+            _ => {
+                let loaded = Expression::Load(Loc::Codegen, *r.clone(), Box::new(expr.clone()));
+                get_expr_size(encoder, arg_no, &loaded, ns, vartab, cfg)
+            }
+        },
 
         Type::StorageRef(_, r) => {
             let var = load_storage(&Loc::Codegen, r, expr.clone(), cfg, vartab);
@@ -154,10 +692,10 @@ fn calculate_array_size<T: AbiEncoding>(
 
     // Check if the array contains only fixed sized elements
     let primitive_size = if elem_ty.is_primitive() && direct_assessment {
-        Some(elem_ty.memory_size_of(ns))
+        Some(primitive_size(encoder, elem_ty, ns))
     } else if let Type::Struct(struct_ty) = elem_ty {
         if direct_assessment {
-            ns.calculate_struct_non_padded_size(struct_ty)
+            calculate_struct_fixed_size(encoder, struct_ty, ns)
         } else {
             None
         }
@@ -187,7 +725,7 @@ fn calculate_array_size<T: AbiEncoding>(
             size = Expression::Multiply(
                 Loc::Codegen,
                 Type::Uint(32),
-                false,
+                true,
                 Box::new(size),
                 Box::new(local_size),
             );
@@ -197,7 +735,7 @@ fn calculate_array_size<T: AbiEncoding>(
         let size = Expression::Multiply(
             Loc::Codegen,
             Type::Uint(32),
-            false,
+            true,
             Box::new(size),
             Box::new(type_size),
         );
@@ -241,10 +779,15 @@ fn calculate_array_size<T: AbiEncoding>(
         size_var
     };
 
-    // Each dynamic dimension size occupies 4 bytes in the buffer
-    let dyn_dims = dims.iter().filter(|d| **d == ArrayLength::Dynamic).count();
-
-    if dyn_dims > 0 {
+    // The length of the outer dimension is encoded before all elements. The lengths of inner
+    // dynamic dimensions have already been accounted for while looping through the array.
+    if dims.last() == Some(&ArrayLength::Dynamic) {
+        let length = Expression::Builtin(
+            Loc::Codegen,
+            vec![Type::Uint(32)],
+            Builtin::ArrayLength,
+            vec![array.clone()],
+        );
         cfg.add(
             vartab,
             Instr::Set {
@@ -253,13 +796,9 @@ fn calculate_array_size<T: AbiEncoding>(
                 expr: Expression::Add(
                     Loc::Codegen,
                     Type::Uint(32),
-                    false,
+                    true,
                     Box::new(Expression::Variable(Loc::Codegen, Type::Uint(32), size_var)),
-                    Box::new(Expression::NumberLiteral(
-                        Loc::Codegen,
-                        Type::Uint(32),
-                        BigInt::from(4 * dyn_dims),
-                    )),
+                    Box::new(encoder.size_width(&length)),
                 ),
             },
         );
@@ -296,7 +835,7 @@ fn calculate_complex_array_size<T: AbiEncoding>(
                 expr: Expression::Add(
                     Loc::Codegen,
                     Type::Uint(32),
-                    false,
+                    true,
                     Box::new(Expression::Variable(
                         Loc::Codegen,
                         Type::Uint(32),
@@ -307,6 +846,30 @@ fn calculate_complex_array_size<T: AbiEncoding>(
             },
         );
     } else {
+        // Each element of this dimension is itself an array. If it is dynamic, its length
+        // precedes its elements.
+        if dims[dimension - 1] == ArrayLength::Dynamic {
+            let length = get_array_length(arr, dims, indexes, dimension - 1);
+            cfg.add(
+                vartab,
+                Instr::Set {
+                    loc: Loc::Codegen,
+                    res: size_var_no,
+                    expr: Expression::Add(
+                        Loc::Codegen,
+                        Type::Uint(32),
+                        true,
+                        Box::new(Expression::Variable(
+                            Loc::Codegen,
+                            Type::Uint(32),
+                            size_var_no,
+                        )),
+                        Box::new(encoder.size_width(&length)),
+                    ),
+                },
+            );
+        }
+
         calculate_complex_array_size(
             encoder,
             arg_no,
@@ -324,7 +887,8 @@ fn calculate_complex_array_size<T: AbiEncoding>(
     finish_array_loop(&for_loop, vartab, cfg);
 }
 
-/// Get the array length at dimension 'index'
+/// Get the array length at dimension 'index'. The outer dimensions are indexed with the
+/// first elements of 'indexes'.
 fn get_array_length(
     arr: &Expression,
     dims: &[ArrayLength],
@@ -334,12 +898,8 @@ fn get_array_length(
     if let ArrayLength::Fixed(dim) = &dims[dimension] {
         Expression::NumberLiteral(Loc::Codegen, Type::Uint(32), dim.clone())
     } else {
-        let (sub_array, _) = load_sub_array(
-            arr.clone(),
-            &dims[(dimension + 1)..dims.len()],
-            indexes,
-            true,
-        );
+        let (sub_array, _) =
+            load_sub_array(arr.clone(), dims, &indexes[..(dims.len() - dimension - 1)]);
 
         Expression::Builtin(
             Loc::Codegen,
@@ -360,7 +920,7 @@ fn calculate_struct_size<T: AbiEncoding>(
     vartab: &mut Vartable,
     cfg: &mut ControlFlowGraph,
 ) -> Expression {
-    if let Some(struct_size) = ns.calculate_struct_non_padded_size(struct_ty) {
+    if let Some(struct_size) = calculate_struct_fixed_size(encoder, struct_ty, ns) {
         return Expression::NumberLiteral(Loc::Codegen, Type::Uint(32), struct_size);
     }
 
@@ -373,7 +933,7 @@ fn calculate_struct_size<T: AbiEncoding>(
         size = Expression::Add(
             Loc::Codegen,
             Type::Uint(32),
-            false,
+            true,
             Box::new(size.clone()),
             Box::new(get_expr_size(encoder, arg_no, &field, ns, vartab, cfg)),
         );
@@ -382,10 +942,38 @@ fn calculate_struct_size<T: AbiEncoding>(
     size
 }
 
+/// Calculate the encoded size of a struct, if all its fields have a size known at compile time
+fn calculate_struct_fixed_size<T: AbiEncoding>(
+    encoder: &T,
+    struct_ty: &StructType,
+    ns: &Namespace,
+) -> Option<BigInt> {
+    let mut size = BigInt::zero();
+    for field in &struct_ty.definition(ns).fields {
+        if field.ty.is_primitive() {
+            size.add_assign(primitive_size(encoder, &field.ty, ns));
+        } else if let Type::Struct(struct_ty) = &field.ty {
+            size.add_assign(calculate_struct_fixed_size(encoder, struct_ty, ns)?);
+        } else {
+            return None;
+        }
+    }
+
+    Some(size)
+}
+
+/// Retrieve the encoded size of a primitive type, which is always known at compile time
+fn primitive_size<T: AbiEncoding>(encoder: &T, ty: &Type, ns: &Namespace) -> BigInt {
+    match encoder.get_encoding_size(&Expression::Undefined(ty.clone()), ty, ns) {
+        Expression::NumberLiteral(_, _, size) => size,
+        _ => unreachable!("primitive types have a fixed size"),
+    }
+}
+
 /// Loads an item from an array
 fn load_array_item(arr: &Expression, dims: &[ArrayLength], indexes: &[usize]) -> Expression {
-    let elem_ty = arr.ty().elem_ty();
-    let (deref, ty) = load_sub_array(arr.clone(), dims, indexes, false);
+    let elem_ty = arr.ty().deref_memory().elem_ty();
+    let (deref, ty) = load_sub_array(arr.clone(), dims, &indexes[..(indexes.len() - 1)]);
     Expression::Subscript(
         Loc::Codegen,
         Type::Ref(Box::new(elem_ty)),
@@ -401,31 +989,33 @@ fn load_array_item(arr: &Expression, dims: &[ArrayLength], indexes: &[usize]) ->
 
 /// Dereferences a subarray. If we have 'int[3][][4] vec' and we need 'int[3][]',
 /// this function returns so.
-/// 'dims' should contain only the dimensions we want to index
-/// 'index' is the list of indexes to use
-/// 'index_first_dim' chooses whether to index the first dimension in dims
+/// 'dims' are all the dimensions of the array
+/// 'indexes' are the indexes for the outer dimensions, starting from the outermost one
 fn load_sub_array(
     mut arr: Expression,
     dims: &[ArrayLength],
     indexes: &[usize],
-    index_first_dim: bool,
 ) -> (Expression, Type) {
     let mut ty = arr.ty();
-    let elem_ty = ty.elem_ty();
-    let start = !index_first_dim as usize;
-    for i in (start..dims.len()).rev() {
-        let local_ty = Type::Array(Box::new(elem_ty.clone()), dims[0..i].to_vec());
+    let elem_ty = ty.deref_memory().elem_ty();
+    for (no, index) in indexes.iter().enumerate() {
+        let local_ty = Type::Array(
+            Box::new(elem_ty.clone()),
+            dims[..(dims.len() - no - 1)].to_vec(),
+        );
         arr = Expression::Subscript(
             Loc::Codegen,
             Type::Ref(Box::new(local_ty.clone())),
             ty,
             Box::new(arr),
-            Box::new(Expression::Variable(
-                Loc::Codegen,
-                Type::Uint(32),
-                indexes[indexes.len() - i - 1],
-            )),
+            Box::new(Expression::Variable(Loc::Codegen, Type::Uint(32), *index)),
         );
+
+        // Dynamic arrays are stored as pointers in their parent array
+        if !local_ty.is_fixed_reference_type() {
+            arr = Expression::Load(Loc::Codegen, local_ty.clone(), Box::new(arr));
+        }
+
         ty = local_ty;
     }
 
@@ -515,7 +1105,7 @@ fn finish_array_loop(for_loop: &ForLoop, vartab: &mut Vartable, cfg: &mut Contro
             expr: Expression::Add(
                 Loc::Codegen,
                 Type::Uint(32),
-                false,
+                true,
                 Box::new(Expression::Variable(
                     Loc::Codegen,
                     Type::Uint(32),
@@ -544,8 +1134,8 @@ fn finish_array_loop(for_loop: &ForLoop, vartab: &mut Vartable, cfg: &mut Contro
 
 /// Loads a struct member
 fn load_struct_member(ty: Type, expr: Expression, field: usize) -> Expression {
-    if matches!(ty, Type::Struct(_)) {
-        // We should not dereference a struct.
+    if ty.is_fixed_reference_type() {
+        // We should not dereference a struct or a fixed length array.
         return Expression::StructMember(
             Loc::Codegen,
             Type::Ref(Box::new(ty)),
@@ -572,7 +1162,7 @@ fn increment_four(expr: Expression) -> Expression {
     Expression::Add(
         Loc::Codegen,
         Type::Uint(32),
-        false,
+        true,
         Box::new(expr),
         Box::new(Expression::NumberLiteral(
             Loc::Codegen,
@@ -0,0 +1,497 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::codegen::cfg::{ControlFlowGraph, Instr};
+use crate::codegen::encoding::{encode_args, AbiEncoding};
+use crate::codegen::vartable::Vartable;
+use crate::codegen::{Builtin, Expression};
+use crate::sema::ast::{Namespace, Type};
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+use solang_parser::pt::Loc;
+use std::collections::HashMap;
+
+/// This struct implements the trait AbiEncoding for the SCALE encoding used on Substrate
+pub(super) struct ScaleEncoding {
+    /// Items loaded from storage while calculating the size of the arguments. For more
+    /// information, check the comment at 'struct BorshEncoding' on borsh_encoding.rs
+    storage_cache: HashMap<usize, Expression>,
+}
+
+impl AbiEncoding for ScaleEncoding {
+    fn abi_encode(
+        &mut self,
+        loc: &Loc,
+        args: &[Expression],
+        ns: &Namespace,
+        vartab: &mut Vartable,
+        cfg: &mut ControlFlowGraph,
+    ) -> Expression {
+        encode_args(self, loc, args, ns, vartab, cfg)
+    }
+
+    fn cache_storage_loaded(&mut self, arg_no: usize, expr: Expression) {
+        self.storage_cache.insert(arg_no, expr);
+    }
+
+    fn storage_cache_remove(&mut self, arg_no: usize) -> Option<Expression> {
+        self.storage_cache.remove(&arg_no)
+    }
+
+    fn get_encoding_size(&self, expr: &Expression, ty: &Type, ns: &Namespace) -> Expression {
+        match ty {
+            Type::Enum(_) | Type::Contract(_) | Type::Bool | Type::Address(_) | Type::Bytes(_) => {
+                let size = ty.memory_size_of(ns);
+                Expression::NumberLiteral(Loc::Codegen, Type::Uint(32), size)
+            }
+
+            Type::Uint(_) | Type::Int(_) | Type::Fixed(..) | Type::Ufixed(..) | Type::Value => {
+                let size = ty.bits(ns).next_power_of_two() / 8;
+                Expression::NumberLiteral(Loc::Codegen, Type::Uint(32), BigInt::from(size))
+            }
+
+            Type::String | Type::DynamicBytes | Type::Slice(_) => {
+                // When encoding a variable length array, the total size is "compact length" + elements
+                let length = Expression::Builtin(
+                    Loc::Codegen,
+                    vec![Type::Uint(32)],
+                    Builtin::ArrayLength,
+                    vec![expr.clone()],
+                );
+
+                Expression::Add(
+                    Loc::Codegen,
+                    Type::Uint(32),
+                    true,
+                    Box::new(self.size_width(&length)),
+                    Box::new(length),
+                )
+            }
+
+            _ => unreachable!("Type should have the same size for all encoding schemes"),
+        }
+    }
+
+    fn size_width(&self, size: &Expression) -> Expression {
+        if let Expression::NumberLiteral(_, _, size) = size {
+            let width = compact_width(size.to_u32().unwrap());
+
+            return Expression::NumberLiteral(Loc::Codegen, Type::Uint(32), BigInt::from(width));
+        }
+
+        // The compact encoding takes 1 byte for lengths up to 63, 2 bytes for lengths up
+        // to 2^14-1, 4 bytes for lengths up to 2^30-1 and 5 bytes otherwise, so the width is
+        // 1 + (size > 63) + 2 * (size > 2^14-1) + (size > 2^30-1)
+        let more_than = |limit: u32| {
+            Expression::ZeroExt(
+                Loc::Codegen,
+                Type::Uint(32),
+                Box::new(Expression::UnsignedMore(
+                    Loc::Codegen,
+                    Box::new(size.clone()),
+                    Box::new(Expression::NumberLiteral(
+                        Loc::Codegen,
+                        Type::Uint(32),
+                        BigInt::from(limit),
+                    )),
+                )),
+            )
+        };
+
+        let add = |left: Expression, right: Expression| {
+            Expression::Add(
+                Loc::Codegen,
+                Type::Uint(32),
+                true,
+                Box::new(left),
+                Box::new(right),
+            )
+        };
+
+        let two_bytes = add(
+            Expression::NumberLiteral(Loc::Codegen, Type::Uint(32), BigInt::from(1u8)),
+            more_than(0x3f),
+        );
+
+        let four_bytes = add(
+            two_bytes,
+            Expression::ShiftLeft(
+                Loc::Codegen,
+                Type::Uint(32),
+                Box::new(more_than(0x3fff)),
+                Box::new(Expression::NumberLiteral(
+                    Loc::Codegen,
+                    Type::Uint(32),
+                    BigInt::from(1u8),
+                )),
+            ),
+        );
+
+        add(four_bytes, more_than(0x3fff_ffff))
+    }
+
+    fn encode_size(
+        &mut self,
+        size: &Expression,
+        buffer: &Expression,
+        offset: &Expression,
+        vartab: &mut Vartable,
+        cfg: &mut ControlFlowGraph,
+    ) -> Expression {
+        if let Expression::NumberLiteral(_, _, size) = size {
+            return encode_compact_literal(size.to_u32().unwrap(), buffer, offset, vartab, cfg);
+        }
+
+        // The length is used several times, so it should only be evaluated once
+        let length = if let Expression::Variable(..) = size {
+            size.clone()
+        } else {
+            let length = vartab.temp_anonymous(&Type::Uint(32));
+            cfg.add(
+                vartab,
+                Instr::Set {
+                    loc: Loc::Codegen,
+                    res: length,
+                    expr: size.clone(),
+                },
+            );
+            Expression::Variable(Loc::Codegen, Type::Uint(32), length)
+        };
+
+        let small = cfg.new_basic_block("compact_small".to_string());
+        let not_small = cfg.new_basic_block("compact_not_small".to_string());
+        let medium = cfg.new_basic_block("compact_medium".to_string());
+        let not_medium = cfg.new_basic_block("compact_not_medium".to_string());
+        let large = cfg.new_basic_block("compact_large".to_string());
+        let big = cfg.new_basic_block("compact_big".to_string());
+        let done = cfg.new_basic_block("compact_done".to_string());
+
+        // Single byte mode: the length shifted left by two
+        less_than(&length, 0x40, small, not_small, vartab, cfg);
+        cfg.set_basic_block(small);
+        write_compact_mode(&length, 0, 8, buffer, offset, vartab, cfg);
+        cfg.add(vartab, Instr::Branch { block: done });
+
+        // Two byte mode: shifted length with the lower two bits set to 0b01
+        cfg.set_basic_block(not_small);
+        less_than(&length, 0x4000, medium, not_medium, vartab, cfg);
+        cfg.set_basic_block(medium);
+        write_compact_mode(&length, 1, 16, buffer, offset, vartab, cfg);
+        cfg.add(vartab, Instr::Branch { block: done });
+
+        // Four byte mode: shifted length with the lower two bits set to 0b10
+        cfg.set_basic_block(not_medium);
+        less_than(&length, 0x4000_0000, large, big, vartab, cfg);
+        cfg.set_basic_block(large);
+        write_compact_mode(&length, 2, 32, buffer, offset, vartab, cfg);
+        cfg.add(vartab, Instr::Branch { block: done });
+
+        // Big integer mode: a byte saying that four bytes of length follow
+        cfg.set_basic_block(big);
+        write_compact_big(length.clone(), buffer, offset, vartab, cfg);
+        cfg.add(vartab, Instr::Branch { block: done });
+
+        cfg.set_basic_block(done);
+
+        // The width is computed from the length rather than set in each branch, so the offsets
+        // which follow do not depend on which mode was taken
+        self.size_width(&length)
+    }
+
+    fn encode_primitive(
+        &mut self,
+        expr: &Expression,
+        ty: &Type,
+        buffer: &Expression,
+        offset: &Expression,
+        ns: &Namespace,
+        vartab: &mut Vartable,
+        cfg: &mut ControlFlowGraph,
+    ) -> Expression {
+        let (value, size) = match ty {
+            Type::Contract(_) | Type::Address(_) => (expr.clone(), ns.address_length),
+
+            Type::Bool => (
+                Expression::ZeroExt(Loc::Codegen, Type::Uint(8), Box::new(expr.clone())),
+                1,
+            ),
+
+            Type::Uint(_) | Type::Int(_) | Type::Fixed(..) | Type::Ufixed(..) | Type::Value => {
+                let bits = ty.bits(ns);
+                let power_of_two = bits.next_power_of_two();
+
+                // SCALE only supports power-of-two integer types; upcast to the correct type
+                let value = if bits == power_of_two {
+                    expr.clone()
+                } else if ty.is_signed_numeric() {
+                    Expression::SignExt(
+                        Loc::Codegen,
+                        Type::Int(power_of_two),
+                        Box::new(expr.clone()),
+                    )
+                } else {
+                    Expression::ZeroExt(
+                        Loc::Codegen,
+                        Type::Uint(power_of_two),
+                        Box::new(expr.clone()),
+                    )
+                };
+
+                (value, power_of_two as usize / 8)
+            }
+
+            Type::Bytes(1) | Type::Enum(_) => (expr.clone(), 1),
+
+            Type::Bytes(length) => {
+                // byte order needs to be reversed. e.g. hex"11223344" should be 0x11 0x22 0x33 0x44
+                let value = Expression::Builtin(
+                    Loc::Codegen,
+                    vec![ty.clone()],
+                    Builtin::ByteSwap,
+                    vec![expr.clone()],
+                );
+
+                (value, *length as usize)
+            }
+
+            Type::ExternalFunction { .. } => {
+                let address = expr.external_function_address();
+                let selector = expr.external_function_selector();
+
+                cfg.add(
+                    vartab,
+                    Instr::WriteBuffer {
+                        buf: buffer.clone(),
+                        offset: offset.clone(),
+                        value: address,
+                    },
+                );
+
+                let selector_offset = Expression::Add(
+                    Loc::Codegen,
+                    Type::Uint(32),
+                    true,
+                    Box::new(offset.clone()),
+                    Box::new(Expression::NumberLiteral(
+                        Loc::Codegen,
+                        Type::Uint(32),
+                        BigInt::from(ns.address_length),
+                    )),
+                );
+
+                cfg.add(
+                    vartab,
+                    Instr::WriteBuffer {
+                        buf: buffer.clone(),
+                        offset: selector_offset,
+                        value: selector,
+                    },
+                );
+
+                return Expression::NumberLiteral(
+                    Loc::Codegen,
+                    Type::Uint(32),
+                    BigInt::from(ns.address_length + 4),
+                );
+            }
+
+            _ => unreachable!("This type cannot be encoded as a primitive"),
+        };
+
+        cfg.add(
+            vartab,
+            Instr::WriteBuffer {
+                buf: buffer.clone(),
+                offset: offset.clone(),
+                value,
+            },
+        );
+
+        Expression::NumberLiteral(Loc::Codegen, Type::Uint(32), BigInt::from(size))
+    }
+
+    fn is_memcpy_compatible(&self, ty: &Type, ns: &Namespace) -> bool {
+        match ty {
+            Type::Bool | Type::Address(_) | Type::Contract(_) | Type::Bytes(1) => true,
+            Type::Uint(_) | Type::Int(_) | Type::Fixed(..) | Type::Ufixed(..) | Type::Value => {
+                ty.bits(ns).is_power_of_two()
+            }
+            _ => false,
+        }
+    }
+}
+
+impl ScaleEncoding {
+    pub fn new() -> ScaleEncoding {
+        ScaleEncoding {
+            storage_cache: HashMap::new(),
+        }
+    }
+}
+
+/// Number of bytes the compact encoding of 'length' occupies
+fn compact_width(length: u32) -> u32 {
+    if length < 0x40 {
+        1
+    } else if length < 0x4000 {
+        2
+    } else if length < 0x4000_0000 {
+        4
+    } else {
+        5
+    }
+}
+
+/// Encode a length known at compile time in the compact format
+fn encode_compact_literal(
+    length: u32,
+    buffer: &Expression,
+    offset: &Expression,
+    vartab: &mut Vartable,
+    cfg: &mut ControlFlowGraph,
+) -> Expression {
+    let width = compact_width(length);
+
+    let value = match width {
+        1 => Expression::NumberLiteral(Loc::Codegen, Type::Uint(8), BigInt::from(length << 2)),
+        2 => Expression::NumberLiteral(
+            Loc::Codegen,
+            Type::Uint(16),
+            BigInt::from((length << 2) | 1),
+        ),
+        4 => Expression::NumberLiteral(
+            Loc::Codegen,
+            Type::Uint(32),
+            BigInt::from((length << 2) | 2),
+        ),
+        _ => {
+            let length =
+                Expression::NumberLiteral(Loc::Codegen, Type::Uint(32), BigInt::from(length));
+            write_compact_big(length.clone(), buffer, offset, vartab, cfg);
+
+            return Expression::NumberLiteral(Loc::Codegen, Type::Uint(32), BigInt::from(width));
+        }
+    };
+
+    cfg.add(
+        vartab,
+        Instr::WriteBuffer {
+            buf: buffer.clone(),
+            offset: offset.clone(),
+            value,
+        },
+    );
+
+    Expression::NumberLiteral(Loc::Codegen, Type::Uint(32), BigInt::from(width))
+}
+
+/// Branch to 'true_block' if 'length' is less than 'limit'
+fn less_than(
+    length: &Expression,
+    limit: u32,
+    true_block: usize,
+    false_block: usize,
+    vartab: &mut Vartable,
+    cfg: &mut ControlFlowGraph,
+) {
+    cfg.add(
+        vartab,
+        Instr::BranchCond {
+            cond: Expression::UnsignedLess(
+                Loc::Codegen,
+                Box::new(length.clone()),
+                Box::new(Expression::NumberLiteral(
+                    Loc::Codegen,
+                    Type::Uint(32),
+                    BigInt::from(limit),
+                )),
+            ),
+            true_block,
+            false_block,
+        },
+    );
+}
+
+/// Write the length shifted left by two with 'mode' in the lower two bits, as an integer of 'bits'
+fn write_compact_mode(
+    length: &Expression,
+    mode: u8,
+    bits: u16,
+    buffer: &Expression,
+    offset: &Expression,
+    vartab: &mut Vartable,
+    cfg: &mut ControlFlowGraph,
+) {
+    let mut value = Expression::ShiftLeft(
+        Loc::Codegen,
+        Type::Uint(32),
+        Box::new(length.clone()),
+        Box::new(Expression::NumberLiteral(
+            Loc::Codegen,
+            Type::Uint(32),
+            BigInt::from(2u8),
+        )),
+    );
+
+    if mode != 0 {
+        value = Expression::BitwiseOr(
+            Loc::Codegen,
+            Type::Uint(32),
+            Box::new(value),
+            Box::new(Expression::NumberLiteral(
+                Loc::Codegen,
+                Type::Uint(32),
+                BigInt::from(mode),
+            )),
+        );
+    }
+
+    if bits < 32 {
+        value = Expression::Trunc(Loc::Codegen, Type::Uint(bits), Box::new(value));
+    }
+
+    cfg.add(
+        vartab,
+        Instr::WriteBuffer {
+            buf: buffer.clone(),
+            offset: offset.clone(),
+            value,
+        },
+    );
+}
+
+/// Write the big integer mode: the byte 0b11 (four bytes follow) and then the length itself
+fn write_compact_big(
+    length: Expression,
+    buffer: &Expression,
+    offset: &Expression,
+    vartab: &mut Vartable,
+    cfg: &mut ControlFlowGraph,
+) {
+    cfg.add(
+        vartab,
+        Instr::WriteBuffer {
+            buf: buffer.clone(),
+            offset: offset.clone(),
+            value: Expression::NumberLiteral(Loc::Codegen, Type::Uint(8), BigInt::from(3u8)),
+        },
+    );
+
+    cfg.add(
+        vartab,
+        Instr::WriteBuffer {
+            buf: buffer.clone(),
+            offset: Expression::Add(
+                Loc::Codegen,
+                Type::Uint(32),
+                true,
+                Box::new(offset.clone()),
+                Box::new(Expression::NumberLiteral(
+                    Loc::Codegen,
+                    Type::Uint(32),
+                    BigInt::from(1u8),
+                )),
+            ),
+            value: length,
+        },
+    );
+}
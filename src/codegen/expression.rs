@@ -10,7 +10,6 @@ use super::{
 };
 use crate::codegen::array_boundary::handle_array_assign;
 use crate::codegen::encoding::create_encoder;
-use crate::codegen::encoding::AbiEncoding;
 use crate::codegen::unused_variable::should_remove_assignment;
 use crate::codegen::{Builtin, Expression};
 use crate::sema::{
@@ -25,10 +24,10 @@ use crate::sema::{
 };
 use crate::Target;
 use num_bigint::BigInt;
-use num_traits::{FromPrimitive, One, ToPrimitive, Zero};
+use num_traits::{FromPrimitive, Pow, ToPrimitive, Zero};
 use solang_parser::pt;
 use solang_parser::pt::{CodeLocation, Loc};
-use std::ops::Mul;
+use std::{cmp::Ordering, ops::Mul};
 
 pub fn expression(
     expr: &ast::Expression,
@@ -45,6 +44,10 @@ pub fn expression(
             ns.contracts[contract_no].get_storage_slot(*var_contract_no, *var_no, ns, None)
         }
         ast::Expression::StorageLoad(loc, ty, expr) => {
+            if let Some(offset) = immutable_offset(expr, contract_no, ns) {
+                return load_immutable(loc, ty, offset, cfg, vartab);
+            }
+
             let storage = expression(expr, cfg, contract_no, func, ns, vartab, opt);
 
             load_storage(loc, ty, storage, cfg, vartab)
@@ -52,7 +55,7 @@ pub fn expression(
         ast::Expression::Add(loc, ty, unchecked, left, right) => add(
             loc,
             ty,
-            unchecked,
+            &unchecked_arithmetic(loc, *unchecked, ns, opt),
             left,
             cfg,
             contract_no,
@@ -65,7 +68,7 @@ pub fn expression(
         ast::Expression::Subtract(loc, ty, unchecked, left, right) => substract(
             loc,
             ty,
-            unchecked,
+            &unchecked_arithmetic(loc, *unchecked, ns, opt),
             left,
             cfg,
             contract_no,
@@ -80,20 +83,45 @@ pub fn expression(
                 let (_, r) = eval_const_rational(expr, ns).unwrap();
 
                 Expression::NumberLiteral(*loc, ty.clone(), r.to_integer())
+            } else if ty.is_fixed() {
+                let l = expression(left, cfg, contract_no, func, ns, vartab, opt);
+                let r = expression(right, cfg, contract_no, func, ns, vartab, opt);
+
+                fixed_multiply(
+                    loc,
+                    ty,
+                    unchecked_arithmetic(loc, *unchecked, ns, opt),
+                    l,
+                    r,
+                    cfg,
+                    vartab,
+                    ns,
+                )
             } else {
                 Expression::Multiply(
                     *loc,
                     ty.clone(),
-                    *unchecked,
+                    unchecked_arithmetic(loc, *unchecked, ns, opt),
                     Box::new(expression(left, cfg, contract_no, func, ns, vartab, opt)),
                     Box::new(expression(right, cfg, contract_no, func, ns, vartab, opt)),
                 )
             }
         }
-        ast::Expression::Divide(loc, ty, left, right) => {
+        ast::Expression::Divide(loc, ty, unchecked, left, right) => {
             let l = expression(left, cfg, contract_no, func, ns, vartab, opt);
             let r = expression(right, cfg, contract_no, func, ns, vartab, opt);
-            if ty.is_signed_int() {
+            if ty.is_fixed() {
+                fixed_divide(
+                    loc,
+                    ty,
+                    unchecked_arithmetic(loc, *unchecked, ns, opt),
+                    l,
+                    r,
+                    cfg,
+                    vartab,
+                    ns,
+                )
+            } else if ty.is_signed_int() {
                 Expression::SignedDivide(*loc, ty.clone(), Box::new(l), Box::new(r))
             } else {
                 Expression::UnsignedDivide(*loc, ty.clone(), Box::new(l), Box::new(r))
@@ -102,7 +130,7 @@ pub fn expression(
         ast::Expression::Modulo(loc, ty, left, right) => {
             let l = expression(left, cfg, contract_no, func, ns, vartab, opt);
             let r = expression(right, cfg, contract_no, func, ns, vartab, opt);
-            if ty.is_signed_int() {
+            if ty.is_signed_numeric() {
                 Expression::SignedModulo(*loc, ty.clone(), Box::new(l), Box::new(r))
             } else {
                 Expression::UnsignedModulo(*loc, ty.clone(), Box::new(l), Box::new(r))
@@ -111,7 +139,7 @@ pub fn expression(
         ast::Expression::Power(loc, ty, unchecked, left, right) => Expression::Power(
             *loc,
             ty.clone(),
-            *unchecked,
+            unchecked_arithmetic(loc, *unchecked, ns, opt),
             Box::new(expression(left, cfg, contract_no, func, ns, vartab, opt)),
             Box::new(expression(right, cfg, contract_no, func, ns, vartab, opt)),
         ),
@@ -159,7 +187,7 @@ pub fn expression(
         ast::Expression::More(loc, left, right) => {
             let l = expression(left, cfg, contract_no, func, ns, vartab, opt);
             let r = expression(right, cfg, contract_no, func, ns, vartab, opt);
-            if l.ty().is_signed_int() {
+            if l.ty().is_signed_numeric() {
                 Expression::SignedMore(*loc, Box::new(l), Box::new(r))
             } else {
                 Expression::UnsignedMore(*loc, Box::new(l), Box::new(r))
@@ -173,7 +201,7 @@ pub fn expression(
         ast::Expression::Less(loc, left, right) => {
             let l = expression(left, cfg, contract_no, func, ns, vartab, opt);
             let r = expression(right, cfg, contract_no, func, ns, vartab, opt);
-            if l.ty().is_signed_int() {
+            if l.ty().is_signed_numeric() {
                 Expression::SignedLess(*loc, Box::new(l), Box::new(r))
             } else {
                 Expression::UnsignedLess(*loc, Box::new(l), Box::new(r))
@@ -275,7 +303,7 @@ pub fn expression(
             ns,
             loc,
             expr,
-            unchecked,
+            &unchecked_arithmetic(loc, *unchecked, ns, opt),
             opt,
         ),
         ast::Expression::PostDecrement(loc, ty, unchecked, var)
@@ -289,7 +317,7 @@ pub fn expression(
             ns,
             loc,
             expr,
-            unchecked,
+            &unchecked_arithmetic(loc, *unchecked, ns, opt),
             opt,
         ),
         ast::Expression::Constructor {
@@ -473,6 +501,25 @@ pub fn expression(
             vartab,
             opt,
         ),
+        ast::Expression::ArraySlice {
+            loc,
+            ty,
+            array,
+            start,
+            end,
+        } => array_slice(
+            loc,
+            ty,
+            array,
+            start,
+            end,
+            cfg,
+            contract_no,
+            func,
+            ns,
+            vartab,
+            opt,
+        ),
         ast::Expression::StructMember(loc, ty, var, field_no) if ty.is_contract_storage() => {
             if let Type::Struct(struct_ty) = var.ty().deref_any() {
                 let offset = if ns.target == Target::Solana {
@@ -553,13 +600,18 @@ pub fn expression(
             let codegen_expr = expression(e, cfg, contract_no, func, ns, vartab, opt);
             array_literal_to_memory_array(loc, &codegen_expr, ty, cfg, vartab)
         }
+        ast::Expression::Cast(loc, ty, e) if ty.is_fixed() || e.ty().is_fixed() => {
+            let expr = expression(e, cfg, contract_no, func, ns, vartab, opt);
+
+            fixed_cast(loc, ty, expr, ns)
+        }
         ast::Expression::Cast(loc, ty, e) => {
             if e.ty() == Type::Rational {
                 let (_, n) = eval_const_rational(e, ns).unwrap();
 
                 Expression::NumberLiteral(*loc, ty.clone(), n.to_integer())
             } else if matches!(ty, Type::String | Type::DynamicBytes)
-                && matches!(expr.ty(), Type::String | Type::DynamicBytes)
+                && matches!(e.ty(), Type::String | Type::DynamicBytes)
             {
                 expression(e, cfg, contract_no, func, ns, vartab, opt)
             } else {
@@ -814,11 +866,15 @@ fn post_incdec(
     opt: &Options,
 ) -> Expression {
     let res = vartab.temp_anonymous(ty);
-    let v = expression(var, cfg, contract_no, func, ns, vartab, opt);
-    let v = match var.ty() {
-        Type::Ref(ty) => Expression::Load(var.loc(), ty.as_ref().clone(), Box::new(v)),
-        Type::StorageRef(_, ty) => load_storage(&var.loc(), ty.as_ref(), v, cfg, vartab),
-        _ => v,
+    let v = if let Some(offset) = immutable_offset(var, contract_no, ns) {
+        load_immutable(&var.loc(), ty, offset, cfg, vartab)
+    } else {
+        let v = expression(var, cfg, contract_no, func, ns, vartab, opt);
+        match var.ty() {
+            Type::Ref(ty) => Expression::Load(var.loc(), ty.as_ref().clone(), Box::new(v)),
+            Type::StorageRef(_, ty) => load_storage(&var.loc(), ty.as_ref(), v, cfg, vartab),
+            _ => v,
+        }
     };
     cfg.add(
         vartab,
@@ -828,7 +884,12 @@ fn post_incdec(
             expr: v,
         },
     );
-    let one = Box::new(Expression::NumberLiteral(*loc, ty.clone(), BigInt::one()));
+    // for fixed point types, one is scaled like any other value
+    let one = Box::new(Expression::NumberLiteral(
+        *loc,
+        ty.clone(),
+        BigInt::from(10).pow(ty.fixed_decimals()),
+    ));
     let expr = match expr {
         ast::Expression::PostDecrement(..) => Expression::Subtract(
             *loc,
@@ -858,38 +919,59 @@ fn post_incdec(
             );
         }
         _ => {
-            let dest = expression(var, cfg, contract_no, func, ns, vartab, opt);
-            let res = vartab.temp_anonymous(ty);
-            cfg.add(
-                vartab,
-                Instr::Set {
-                    loc: pt::Loc::Codegen,
-                    res,
-                    expr,
-                },
-            );
+            if let Some(offset) = immutable_offset(var, contract_no, ns) {
+                let res = vartab.temp_anonymous(ty);
+                cfg.add(
+                    vartab,
+                    Instr::Set {
+                        loc: pt::Loc::Codegen,
+                        res,
+                        expr,
+                    },
+                );
 
-            match var.ty() {
-                Type::StorageRef(..) => {
-                    cfg.add(
-                        vartab,
-                        Instr::SetStorage {
-                            value: Expression::Variable(*loc, ty.clone(), res),
-                            ty: ty.clone(),
-                            storage: dest,
-                        },
-                    );
-                }
-                Type::Ref(_) => {
-                    cfg.add(
-                        vartab,
-                        Instr::Store {
-                            dest,
-                            data: Expression::Variable(Loc::Codegen, ty.clone(), res),
-                        },
-                    );
+                cfg.add(
+                    vartab,
+                    Instr::SetImmutable {
+                        value: Expression::Variable(*loc, ty.clone(), res),
+                        ty: ty.clone(),
+                        offset,
+                    },
+                );
+            } else {
+                let dest = expression(var, cfg, contract_no, func, ns, vartab, opt);
+                let res = vartab.temp_anonymous(ty);
+                cfg.add(
+                    vartab,
+                    Instr::Set {
+                        loc: pt::Loc::Codegen,
+                        res,
+                        expr,
+                    },
+                );
+
+                match var.ty() {
+                    Type::StorageRef(..) => {
+                        cfg.add(
+                            vartab,
+                            Instr::SetStorage {
+                                value: Expression::Variable(*loc, ty.clone(), res),
+                                ty: ty.clone(),
+                                storage: dest,
+                            },
+                        );
+                    }
+                    Type::Ref(_) => {
+                        cfg.add(
+                            vartab,
+                            Instr::Store {
+                                dest,
+                                data: Expression::Variable(Loc::Codegen, ty.clone(), res),
+                            },
+                        );
+                    }
+                    _ => unreachable!(),
                 }
-                _ => unreachable!(),
             }
         }
     }
@@ -910,13 +992,22 @@ fn pre_incdec(
     opt: &Options,
 ) -> Expression {
     let res = vartab.temp_anonymous(ty);
-    let v = expression(var, cfg, contract_no, func, ns, vartab, opt);
-    let v = match var.ty() {
-        Type::Ref(ty) => Expression::Load(var.loc(), ty.as_ref().clone(), Box::new(v)),
-        Type::StorageRef(_, ty) => load_storage(&var.loc(), ty.as_ref(), v, cfg, vartab),
-        _ => v,
+    let v = if let Some(offset) = immutable_offset(var, contract_no, ns) {
+        load_immutable(&var.loc(), ty, offset, cfg, vartab)
+    } else {
+        let v = expression(var, cfg, contract_no, func, ns, vartab, opt);
+        match var.ty() {
+            Type::Ref(ty) => Expression::Load(var.loc(), ty.as_ref().clone(), Box::new(v)),
+            Type::StorageRef(_, ty) => load_storage(&var.loc(), ty.as_ref(), v, cfg, vartab),
+            _ => v,
+        }
     };
-    let one = Box::new(Expression::NumberLiteral(*loc, ty.clone(), BigInt::one()));
+    // for fixed point types, one is scaled like any other value
+    let one = Box::new(Expression::NumberLiteral(
+        *loc,
+        ty.clone(),
+        BigInt::from(10).pow(ty.fixed_decimals()),
+    ));
     let expr = match expr {
         ast::Expression::PreDecrement(..) => {
             Expression::Subtract(*loc, ty.clone(), *unchecked, Box::new(v), one)
@@ -946,29 +1037,40 @@ fn pre_incdec(
             );
         }
         _ => {
-            let dest = expression(var, cfg, contract_no, func, ns, vartab, opt);
+            if let Some(offset) = immutable_offset(var, contract_no, ns) {
+                cfg.add(
+                    vartab,
+                    Instr::SetImmutable {
+                        value: Expression::Variable(*loc, ty.clone(), res),
+                        ty: ty.clone(),
+                        offset,
+                    },
+                );
+            } else {
+                let dest = expression(var, cfg, contract_no, func, ns, vartab, opt);
 
-            match var.ty() {
-                Type::StorageRef(..) => {
-                    cfg.add(
-                        vartab,
-                        Instr::SetStorage {
-                            value: Expression::Variable(*loc, ty.clone(), res),
-                            ty: ty.clone(),
-                            storage: dest,
-                        },
-                    );
-                }
-                Type::Ref(_) => {
-                    cfg.add(
-                        vartab,
-                        Instr::Store {
-                            dest,
-                            data: Expression::Variable(Loc::Codegen, ty.clone(), res),
-                        },
-                    );
+                match var.ty() {
+                    Type::StorageRef(..) => {
+                        cfg.add(
+                            vartab,
+                            Instr::SetStorage {
+                                value: Expression::Variable(*loc, ty.clone(), res),
+                                ty: ty.clone(),
+                                storage: dest,
+                            },
+                        );
+                    }
+                    Type::Ref(_) => {
+                        cfg.add(
+                            vartab,
+                            Instr::Store {
+                                dest,
+                                data: Expression::Variable(Loc::Codegen, ty.clone(), res),
+                            },
+                        );
+                    }
+                    _ => unreachable!(),
                 }
-                _ => unreachable!(),
             }
         }
     }
@@ -1141,7 +1243,19 @@ fn require(
             }
             cfg.add(vartab, Instr::AssertFailure { expr: None });
         }
-        _ => cfg.add(vartab, Instr::AssertFailure { expr }),
+        _ => {
+            let expr = expr.map(|expr| {
+                revert_data(
+                    &args[0].loc(),
+                    ERROR_SELECTOR,
+                    vec![expr],
+                    vec![Type::String],
+                    cfg,
+                    vartab,
+                )
+            });
+            cfg.add(vartab, Instr::AssertFailure { expr });
+        }
     }
     cfg.set_basic_block(true_);
     Expression::Poison
@@ -1156,13 +1270,66 @@ fn revert(
     vartab: &mut Vartable,
     opt: &Options,
 ) -> Expression {
-    let expr = args
-        .get(0)
-        .map(|s| expression(s, cfg, contract_no, func, ns, vartab, opt));
+    let expr = args.get(0).map(|s| {
+        let expr = expression(s, cfg, contract_no, func, ns, vartab, opt);
+
+        revert_data(
+            &s.loc(),
+            ERROR_SELECTOR,
+            vec![expr],
+            vec![Type::String],
+            cfg,
+            vartab,
+        )
+    });
     cfg.add(vartab, Instr::AssertFailure { expr });
     Expression::Poison
 }
 
+/// Selector of the builtin `Error(string)`, i.e. the first four bytes of keccak256("Error(string)")
+const ERROR_SELECTOR: u32 = 0x08c3_79a0;
+
+/// ABI encode the return data for a revert: the 4-byte error selector followed by the
+/// arguments. The encoded data is stored in a temporary which is returned.
+pub fn revert_data(
+    loc: &pt::Loc,
+    selector: u32,
+    args: Vec<Expression>,
+    mut tys: Vec<Type>,
+    cfg: &mut ControlFlowGraph,
+    vartab: &mut Vartable,
+) -> Expression {
+    let res = vartab.temp(
+        &pt::Identifier {
+            loc: *loc,
+            name: "encoded".to_owned(),
+        },
+        &Type::DynamicBytes,
+    );
+
+    tys.insert(0, Type::Bytes(4));
+
+    cfg.add(
+        vartab,
+        Instr::Set {
+            loc: *loc,
+            res,
+            expr: Expression::AbiEncode {
+                loc: *loc,
+                tys,
+                packed: vec![Expression::NumberLiteral(
+                    *loc,
+                    Type::Bytes(4),
+                    BigInt::from(selector),
+                )],
+                args,
+            },
+        },
+    );
+
+    Expression::Variable(*loc, Type::DynamicBytes, res)
+}
+
 fn self_destruct(
     args: &[ast::Expression],
     cfg: &mut ControlFlowGraph,
@@ -1177,6 +1344,60 @@ fn self_destruct(
     Expression::Poison
 }
 
+/// On Solana, value is sent with a call by transferring the lamports to the account of the callee
+/// before the call. The callee calculates `msg.value` from the increase of its lamports balance.
+fn solana_value_transfer(
+    loc: &pt::Loc,
+    address: &Expression,
+    value: &Expression,
+    cfg: &mut ControlFlowGraph,
+    contract_no: usize,
+    ns: &Namespace,
+    vartab: &mut Vartable,
+) {
+    cfg.add(
+        vartab,
+        Instr::ValueTransfer {
+            success: None,
+            address: address.clone(),
+            value: value.clone(),
+        },
+    );
+
+    record_lamports(loc, cfg, contract_no, ns, vartab);
+}
+
+/// After lamports are transferred out of the account on Solana, record the new balance so that
+/// it is not seen as value received by the next call
+fn record_lamports(
+    loc: &pt::Loc,
+    cfg: &mut ControlFlowGraph,
+    contract_no: usize,
+    ns: &Namespace,
+    vartab: &mut Vartable,
+) {
+    if let Some(offset) = &ns.contracts[contract_no].lamports_offset {
+        cfg.add(
+            vartab,
+            Instr::SetStorage {
+                ty: Type::Value,
+                value: Expression::Builtin(
+                    *loc,
+                    vec![Type::Value],
+                    Builtin::Balance,
+                    vec![Expression::Builtin(
+                        *loc,
+                        vec![Type::Address(false)],
+                        Builtin::GetAddress,
+                        Vec::new(),
+                    )],
+                ),
+                storage: Expression::NumberLiteral(*loc, ns.storage_type(), offset.clone()),
+            },
+        );
+    }
+}
+
 fn payable_send(
     args: &[ast::Expression],
     cfg: &mut ControlFlowGraph,
@@ -1205,6 +1426,8 @@ fn payable_send(
                 value,
             },
         );
+
+        record_lamports(loc, cfg, contract_no, ns, vartab);
     } else {
         // Ethereum can only transfer via external call
         cfg.add(
@@ -1253,6 +1476,8 @@ fn payable_transfer(
                 value,
             },
         );
+
+        record_lamports(loc, cfg, contract_no, ns, vartab);
     } else {
         // Ethereum can only transfer via external call
         cfg.add(
@@ -1290,38 +1515,13 @@ fn abi_encode(
     loc: &pt::Loc,
     opt: &Options,
 ) -> Expression {
-    let tys = args.iter().map(|a| a.ty()).collect();
     let args = args
         .iter()
         .map(|v| expression(v, cfg, contract_no, func, ns, vartab, opt))
         .collect::<Vec<Expression>>();
 
-    if ns.target == Target::Solana {
-        let mut encoder = create_encoder(ns);
-        return encoder.abi_encode(loc, &args, ns, vartab, cfg);
-    }
-
-    let res = vartab.temp(
-        &pt::Identifier {
-            loc: *loc,
-            name: "encoded".to_owned(),
-        },
-        &Type::DynamicBytes,
-    );
-    cfg.add(
-        vartab,
-        Instr::Set {
-            loc: *loc,
-            res,
-            expr: Expression::AbiEncode {
-                loc: *loc,
-                tys,
-                packed: vec![],
-                args,
-            },
-        },
-    );
-    Expression::Variable(*loc, Type::DynamicBytes, res)
+    let mut encoder = create_encoder(ns);
+    encoder.abi_encode(loc, &args, ns, vartab, cfg)
 }
 
 fn abi_encode_packed(
@@ -1560,7 +1760,7 @@ fn expr_builtin(
                 Box::new(Expression::Add(
                     *loc,
                     Type::Uint(32),
-                    false,
+                    true,
                     Box::new(offset.clone()),
                     Box::new(Expression::NumberLiteral(
                         *loc,
@@ -1619,7 +1819,7 @@ fn expr_builtin(
                 Box::new(Expression::Add(
                     *loc,
                     Type::Uint(32),
-                    false,
+                    true,
                     Box::new(offset.clone()),
                     Box::new(Expression::NumberLiteral(
                         *loc,
@@ -1804,6 +2004,245 @@ fn checking_trunc(
     )
 }
 
+/// Change the width of an integer or fixed point value. No scaling is done.
+fn resize_scaled(loc: &pt::Loc, expr: Expression, to: &Type, ns: &Namespace) -> Expression {
+    let from = expr.ty();
+
+    match from.bits(ns).cmp(&to.bits(ns)) {
+        Ordering::Less if from.is_signed_numeric() => {
+            Expression::SignExt(*loc, to.clone(), Box::new(expr))
+        }
+        Ordering::Less => Expression::ZeroExt(*loc, to.clone(), Box::new(expr)),
+        Ordering::Greater => Expression::Trunc(*loc, to.clone(), Box::new(expr)),
+        Ordering::Equal => Expression::Cast(*loc, to.clone(), Box::new(expr)),
+    }
+}
+
+/// The integer type which can hold the intermediate result of multiplying or dividing two fixed
+/// point values. Sema has checked that this fits into 256 bits.
+fn fixed_intermediate_type(ty: &Type, multiply: bool, ns: &Namespace) -> Type {
+    let bits = ty.fixed_intermediate_bits(multiply, ns);
+
+    if ty.is_signed_numeric() {
+        Type::Int(bits)
+    } else {
+        Type::Uint(bits)
+    }
+}
+
+/// Fixed point values are stored as integers multiplied by 10^decimals, so after multiplying
+/// the scale has to be divided out once.
+fn fixed_multiply(
+    loc: &pt::Loc,
+    ty: &Type,
+    unchecked: bool,
+    left: Expression,
+    right: Expression,
+    cfg: &mut ControlFlowGraph,
+    vartab: &mut Vartable,
+    ns: &Namespace,
+) -> Expression {
+    let wide_ty = fixed_intermediate_type(ty, true, ns);
+    let scale = Expression::NumberLiteral(
+        *loc,
+        wide_ty.clone(),
+        BigInt::from(10).pow(ty.fixed_decimals()),
+    );
+
+    // the intermediate type is wide enough to hold any product
+    let product = Expression::Multiply(
+        *loc,
+        wide_ty.clone(),
+        true,
+        Box::new(resize_scaled(loc, left, &wide_ty, ns)),
+        Box::new(resize_scaled(loc, right, &wide_ty, ns)),
+    );
+
+    let result = if ty.is_signed_numeric() {
+        Expression::SignedDivide(*loc, wide_ty, Box::new(product), Box::new(scale))
+    } else {
+        Expression::UnsignedDivide(*loc, wide_ty, Box::new(product), Box::new(scale))
+    };
+
+    fixed_truncate(loc, ty, unchecked, result, cfg, vartab, ns)
+}
+
+/// When dividing fixed point values, the dividend is scaled up first so that the quotient keeps
+/// its decimals.
+fn fixed_divide(
+    loc: &pt::Loc,
+    ty: &Type,
+    unchecked: bool,
+    left: Expression,
+    right: Expression,
+    cfg: &mut ControlFlowGraph,
+    vartab: &mut Vartable,
+    ns: &Namespace,
+) -> Expression {
+    let wide_ty = fixed_intermediate_type(ty, false, ns);
+    let scale = Expression::NumberLiteral(
+        *loc,
+        wide_ty.clone(),
+        BigInt::from(10).pow(ty.fixed_decimals()),
+    );
+
+    // the intermediate type is wide enough to hold any scaled dividend
+    let dividend = Expression::Multiply(
+        *loc,
+        wide_ty.clone(),
+        true,
+        Box::new(resize_scaled(loc, left, &wide_ty, ns)),
+        Box::new(scale),
+    );
+    let divisor = resize_scaled(loc, right, &wide_ty, ns);
+
+    let result = if ty.is_signed_numeric() {
+        Expression::SignedDivide(*loc, wide_ty, Box::new(dividend), Box::new(divisor))
+    } else {
+        Expression::UnsignedDivide(*loc, wide_ty, Box::new(dividend), Box::new(divisor))
+    };
+
+    fixed_truncate(loc, ty, unchecked, result, cfg, vartab, ns)
+}
+
+/// Truncate the wide result of a fixed point multiply or divide to the fixed point type. If the
+/// arithmetic is checked, revert if the result does not fit.
+fn fixed_truncate(
+    loc: &pt::Loc,
+    ty: &Type,
+    unchecked: bool,
+    result: Expression,
+    cfg: &mut ControlFlowGraph,
+    vartab: &mut Vartable,
+    ns: &Namespace,
+) -> Expression {
+    if unchecked {
+        return resize_scaled(loc, result, ty, ns);
+    }
+
+    let wide_ty = result.ty();
+    let bits = ty.bits(ns) as u32;
+
+    let pos = vartab.temp_name("fixed_result", &wide_ty);
+
+    cfg.add(
+        vartab,
+        Instr::Set {
+            loc: pt::Loc::Codegen,
+            res: pos,
+            expr: result,
+        },
+    );
+
+    let value = Expression::Variable(*loc, wide_ty.clone(), pos);
+
+    let out_of_range = cfg.new_basic_block("fixed_out_of_range".to_string());
+    let in_range = cfg.new_basic_block("fixed_in_range".to_string());
+
+    if ty.is_signed_numeric() {
+        let min = -BigInt::from(2u32).pow(bits - 1);
+        let max = BigInt::from(2u32).pow(bits - 1) - 1;
+
+        let check_max = cfg.new_basic_block("fixed_check_max".to_string());
+
+        cfg.add(
+            vartab,
+            Instr::BranchCond {
+                cond: Expression::SignedLess(
+                    *loc,
+                    Box::new(value.clone()),
+                    Box::new(Expression::NumberLiteral(*loc, wide_ty.clone(), min)),
+                ),
+                true_block: out_of_range,
+                false_block: check_max,
+            },
+        );
+
+        cfg.set_basic_block(check_max);
+
+        cfg.add(
+            vartab,
+            Instr::BranchCond {
+                cond: Expression::SignedMore(
+                    *loc,
+                    Box::new(value.clone()),
+                    Box::new(Expression::NumberLiteral(*loc, wide_ty, max)),
+                ),
+                true_block: out_of_range,
+                false_block: in_range,
+            },
+        );
+    } else {
+        let max = BigInt::from(2u32).pow(bits) - 1;
+
+        cfg.add(
+            vartab,
+            Instr::BranchCond {
+                cond: Expression::UnsignedMore(
+                    *loc,
+                    Box::new(value.clone()),
+                    Box::new(Expression::NumberLiteral(*loc, wide_ty, max)),
+                ),
+                true_block: out_of_range,
+                false_block: in_range,
+            },
+        );
+    }
+
+    cfg.set_basic_block(out_of_range);
+    cfg.add(vartab, Instr::AssertFailure { expr: None });
+
+    cfg.set_basic_block(in_range);
+
+    resize_scaled(loc, value, ty, ns)
+}
+
+/// Convert between fixed point types, or between integer and fixed point types. The value
+/// is rescaled in 256 bits, and then truncated or extended to the target type.
+fn fixed_cast(loc: &pt::Loc, to: &Type, expr: Expression, ns: &Namespace) -> Expression {
+    let from = expr.ty();
+    let from_decimals = from.fixed_decimals();
+    let to_decimals = to.fixed_decimals();
+
+    let wide_ty = if from.is_signed_numeric() {
+        Type::Int(256)
+    } else {
+        Type::Uint(256)
+    };
+
+    let value = resize_scaled(loc, expr, &wide_ty, ns);
+
+    let value = match from_decimals.cmp(&to_decimals) {
+        Ordering::Less => Expression::Multiply(
+            *loc,
+            wide_ty.clone(),
+            true,
+            Box::new(value),
+            Box::new(Expression::NumberLiteral(
+                *loc,
+                wide_ty.clone(),
+                BigInt::from(10).pow(to_decimals - from_decimals),
+            )),
+        ),
+        Ordering::Greater => {
+            let scale = Box::new(Expression::NumberLiteral(
+                *loc,
+                wide_ty.clone(),
+                BigInt::from(10).pow(from_decimals - to_decimals),
+            ));
+
+            if from.is_signed_numeric() {
+                Expression::SignedDivide(*loc, wide_ty.clone(), Box::new(value), scale)
+            } else {
+                Expression::UnsignedDivide(*loc, wide_ty.clone(), Box::new(value), scale)
+            }
+        }
+        Ordering::Equal => value,
+    };
+
+    resize_scaled(loc, value, to, ns)
+}
+
 fn format_string(
     args: &[(FormatArg, ast::Expression)],
     cfg: &mut ControlFlowGraph,
@@ -1940,6 +2379,31 @@ pub fn assign_single(
             let left_ty = left.ty();
             let ty = left_ty.deref_memory();
 
+            if let Some(offset) = immutable_offset(left, contract_no, ns) {
+                let ty = ty.deref_any();
+                let pos = vartab.temp_anonymous(ty);
+
+                cfg.add(
+                    vartab,
+                    Instr::Set {
+                        loc: pt::Loc::Codegen,
+                        res: pos,
+                        expr: cfg_right,
+                    },
+                );
+
+                cfg.add(
+                    vartab,
+                    Instr::SetImmutable {
+                        value: Expression::Variable(left.loc(), ty.clone(), pos),
+                        ty: ty.clone(),
+                        offset,
+                    },
+                );
+
+                return Expression::Variable(left.loc(), ty.clone(), pos);
+            }
+
             let pos = vartab.temp_anonymous(ty);
 
             // Set a subscript in storage bytes needs special handling
@@ -2042,7 +2506,7 @@ pub fn emit_function_call(
 
                 let ftype = &ns.functions[function_no];
 
-                let call = if ns.functions[function_no].loc == pt::Loc::Builtin {
+                let call = if matches!(ns.functions[function_no].loc, pt::Loc::Builtin) {
                     InternalCallTy::Builtin {
                         ast_func_no: function_no,
                     }
@@ -2172,6 +2636,10 @@ pub fn emit_function_call(
 
             let success = vartab.temp_name("success", &Type::Bool);
 
+            if ns.target == Target::Solana && call_args.value.is_some() {
+                solana_value_transfer(loc, &address, &value, cfg, callee_contract_no, ns, vartab);
+            }
+
             let (payload, address) = if ns.target == Target::Solana && call_args.accounts.is_none()
             {
                 (
@@ -2261,6 +2729,18 @@ pub fn emit_function_call(
                 tys.insert(0, Type::Bytes(4));
 
                 let (payload, address) = if ns.target == Target::Solana {
+                    if call_args.value.is_some() {
+                        solana_value_transfer(
+                            loc,
+                            &address,
+                            &value,
+                            cfg,
+                            callee_contract_no,
+                            ns,
+                            vartab,
+                        );
+                    }
+
                     tys.insert(0, Type::Address(false));
                     tys.insert(1, Type::Address(false));
                     tys.insert(2, Type::Uint(64));
@@ -2377,6 +2857,18 @@ pub fn emit_function_call(
                 let address = function.external_function_address();
 
                 let (payload, address) = if ns.target == Target::Solana {
+                    if call_args.value.is_some() {
+                        solana_value_transfer(
+                            loc,
+                            &address,
+                            &value,
+                            cfg,
+                            callee_contract_no,
+                            ns,
+                            vartab,
+                        );
+                    }
+
                     tys.insert(0, Type::Address(false));
                     tys.insert(1, Type::Address(false));
                     tys.insert(2, Type::Uint(64));
@@ -2636,7 +3128,7 @@ fn array_subscript(
                 expression(&ast_big_int, cfg, contract_no, func, ns, vartab, opt)
             }
         },
-        Type::DynamicBytes => Expression::Builtin(
+        Type::DynamicBytes | Type::Slice(_) => Expression::Builtin(
             *loc,
             vec![Type::Uint(32)],
             Builtin::ArrayLength,
@@ -2831,7 +3323,7 @@ fn array_subscript(
                     )),
                 )
             }
-            Type::DynamicBytes | Type::Array(..) => Expression::Subscript(
+            Type::DynamicBytes | Type::Array(..) | Type::Slice(_) => Expression::Subscript(
                 *loc,
                 elem_ty.clone(),
                 array_ty.clone(),
@@ -2846,6 +3338,163 @@ fn array_subscript(
     }
 }
 
+/// Arithmetic is checked for overflow if the `--math-overflow` option is given, or if the source
+/// file requires Solidity 0.8 or later. In either case, `unchecked { }` blocks are not checked.
+fn unchecked_arithmetic(loc: &pt::Loc, unchecked: bool, ns: &Namespace, opt: &Options) -> bool {
+    let checked = opt.math_overflow_check
+        || matches!(loc.try_file_no(), Some(file_no) if ns.files[file_no].checked_arithmetic);
+
+    unchecked || !checked
+}
+
+/// Codegen for an array slice. The bounds are checked against the length of the array, and
+/// the resulting slice points into the original array, so no data is copied.
+#[allow(clippy::too_many_arguments)]
+fn array_slice(
+    loc: &pt::Loc,
+    ty: &Type,
+    array: &ast::Expression,
+    start: &Option<Box<ast::Expression>>,
+    end: &Option<Box<ast::Expression>>,
+    cfg: &mut ControlFlowGraph,
+    contract_no: usize,
+    func: Option<&Function>,
+    ns: &Namespace,
+    vartab: &mut Vartable,
+    opt: &Options,
+) -> Expression {
+    let array_ty = array.ty();
+    let array = expression(array, cfg, contract_no, func, ns, vartab, opt);
+
+    // the array is used for both the length and the data pointer, so evaluate it only once
+    let array = if let Expression::Variable(..) = array {
+        array
+    } else {
+        let array_pos = vartab.temp_anonymous(&array_ty);
+
+        cfg.add(
+            vartab,
+            Instr::Set {
+                loc: pt::Loc::Codegen,
+                res: array_pos,
+                expr: array,
+            },
+        );
+
+        Expression::Variable(*loc, array_ty, array_pos)
+    };
+
+    let start = start
+        .as_ref()
+        .map(|start| expression(start, cfg, contract_no, func, ns, vartab, opt));
+    let end = end
+        .as_ref()
+        .map(|end| expression(end, cfg, contract_no, func, ns, vartab, opt));
+
+    // the bounds may be wider than 32 bits, so do the comparisons in the widest type
+    let width = start
+        .iter()
+        .chain(end.iter())
+        .map(|bound| bound.ty().bits(ns))
+        .fold(32, std::cmp::max);
+    let bound_ty = Type::Uint(width);
+
+    let array_length = Expression::Builtin(
+        *loc,
+        vec![Type::Uint(32)],
+        Builtin::ArrayLength,
+        vec![array.clone()],
+    )
+    .cast(&bound_ty, ns);
+
+    // the slice is out of bounds if end > length or start > end; an omitted bound cannot
+    // cause either, so only explicit bounds are checked
+    let mut checks = Vec::new();
+
+    let end = match end {
+        Some(end) => {
+            let end_pos = vartab.temp_name("slice_end", &bound_ty);
+
+            cfg.add(
+                vartab,
+                Instr::Set {
+                    loc: pt::Loc::Codegen,
+                    res: end_pos,
+                    expr: end.cast(&bound_ty, ns),
+                },
+            );
+
+            let end = Expression::Variable(*loc, bound_ty.clone(), end_pos);
+
+            checks.push((
+                "slice_end_in_bounds",
+                Expression::UnsignedMore(*loc, Box::new(end.clone()), Box::new(array_length)),
+            ));
+
+            end
+        }
+        None => array_length,
+    };
+
+    let start = match start {
+        Some(start) => {
+            let start_pos = vartab.temp_name("slice_start", &bound_ty);
+
+            cfg.add(
+                vartab,
+                Instr::Set {
+                    loc: pt::Loc::Codegen,
+                    res: start_pos,
+                    expr: start.cast(&bound_ty, ns),
+                },
+            );
+
+            let start = Expression::Variable(*loc, bound_ty.clone(), start_pos);
+
+            checks.push((
+                "slice_in_bounds",
+                Expression::UnsignedMore(*loc, Box::new(start.clone()), Box::new(end.clone())),
+            ));
+
+            start
+        }
+        None => Expression::NumberLiteral(*loc, bound_ty.clone(), BigInt::zero()),
+    };
+
+    if !checks.is_empty() {
+        let out_of_bounds = cfg.new_basic_block("slice_out_of_bounds".to_string());
+        let mut in_bounds = out_of_bounds;
+
+        for (name, cond) in checks {
+            in_bounds = cfg.new_basic_block(name.to_string());
+
+            cfg.add(
+                vartab,
+                Instr::BranchCond {
+                    cond,
+                    true_block: out_of_bounds,
+                    false_block: in_bounds,
+                },
+            );
+
+            cfg.set_basic_block(in_bounds);
+        }
+
+        cfg.set_basic_block(out_of_bounds);
+        cfg.add(vartab, Instr::AssertFailure { expr: None });
+
+        cfg.set_basic_block(in_bounds);
+    }
+
+    Expression::ArraySlice {
+        loc: *loc,
+        ty: ty.clone(),
+        array: Box::new(array),
+        start: Box::new(start.cast(&Type::Uint(32), ns)),
+        end: Box::new(end.cast(&Type::Uint(32), ns)),
+    }
+}
+
 fn string_location(
     loc: &StringLocation<ast::Expression>,
     cfg: &mut ControlFlowGraph,
@@ -2870,6 +3519,38 @@ fn string_location(
 }
 
 // Generate a load from storage instruction
+/// Immutable variables are not in contract storage. If the expression refers to an immutable
+/// variable, return its offset in the immutables area.
+fn immutable_offset(expr: &ast::Expression, contract_no: usize, ns: &Namespace) -> Option<usize> {
+    if let ast::Expression::StorageVariable(_, _, var_contract_no, var_no) = expr {
+        if ns.contracts[*var_contract_no].variables[*var_no].immutable {
+            return Some(ns.contracts[contract_no].get_immutable_offset(*var_contract_no, *var_no));
+        }
+    }
+
+    None
+}
+
+fn load_immutable(
+    loc: &pt::Loc,
+    ty: &Type,
+    offset: usize,
+    cfg: &mut ControlFlowGraph,
+    vartab: &mut Vartable,
+) -> Expression {
+    let res = vartab.temp_anonymous(ty);
+    cfg.add(
+        vartab,
+        Instr::LoadImmutable {
+            res,
+            ty: ty.clone(),
+            offset,
+        },
+    );
+
+    Expression::Variable(*loc, ty.clone(), res)
+}
+
 pub fn load_storage(
     loc: &pt::Loc,
     ty: &Type,
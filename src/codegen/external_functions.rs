@@ -45,7 +45,7 @@ pub fn add_external_functions(contract_no: usize, ns: &mut Namespace) {
 
         // add functions to contract functions list
         for function_no in &call_list.solidity {
-            if ns.functions[*function_no].loc != pt::Loc::Builtin {
+            if !matches!(ns.functions[*function_no].loc, pt::Loc::Builtin) {
                 ns.contracts[contract_no]
                     .all_functions
                     .insert(*function_no, usize::MAX);
@@ -90,6 +90,21 @@ pub fn add_external_functions(contract_no: usize, ns: &mut Namespace) {
     }
 
     ns.contracts[contract_no].sends_events = send_events;
+
+    // and the list of errors this contract can revert with
+    let mut reverts_errors = Vec::new();
+
+    for function_no in ns.contracts[contract_no].all_functions.keys() {
+        let func = &ns.functions[*function_no];
+
+        for error_no in &func.reverts_errors {
+            if !reverts_errors.contains(error_no) {
+                reverts_errors.push(*error_no);
+            }
+        }
+    }
+
+    ns.contracts[contract_no].reverts_errors = reverts_errors;
 }
 
 fn check_expression(expr: &Expression, call_list: &mut CallList) -> bool {
@@ -142,7 +157,7 @@ fn check_statement(stmt: &Statement, call_list: &mut CallList) -> bool {
         Statement::TryCatch(_, _, try_catch) => {
             try_catch.expr.recurse(call_list, check_expression);
         }
-        Statement::Emit { args, .. } => {
+        Statement::Emit { args, .. } | Statement::Revert { args, .. } => {
             for e in args {
                 e.recurse(call_list, check_expression);
             }
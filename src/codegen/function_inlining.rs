@@ -0,0 +1,370 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::cfg::{BasicBlock, ControlFlowGraph, Instr, InternalCallTy};
+use super::reaching_definitions;
+use super::vartable::{Storage, Variable, Vartable};
+use super::{Expression, OptimizationLevel, Options};
+use crate::sema::ast::Namespace;
+use crate::sema::Recurse;
+use solang_parser::pt;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/*
+  Every internal function call is a call instruction, even when the function is a one-line getter,
+  or the body of a function which has a modifier. On Solana each call frame costs compute units
+  and stack space, and the optimization passes cannot see what happens inside the called function.
+
+  This pass copies the cfg of small functions into their callers. Functions which are only called
+  from one place are inlined whatever their size, since the function body is not duplicated. The
+  call instruction is replaced by:
+  - the instructions before the call, followed by setting the arguments and a branch to the inlined
+    entry block
+  - the blocks of the called function, with all its variables renumbered so they do not clash
+    with the caller's variables (a function may be inlined more than once into the same caller).
+    Each return sets the variables which received the return values and branches to
+  - a new block with the instructions after the call
+
+  This pass runs on the cfgs as they were generated, before any optimization passes, so the inlined
+  code is optimized together with the rest of the caller. Only the calls in the caller as it was
+  generated are inlined, so recursive functions are inlined only once.
+*/
+
+/// Inline small and single-call-site private functions into their callers
+pub(crate) fn function_inlining(cfgs: &mut [ControlFlowGraph], ns: &mut Namespace, opt: &Options) {
+    let threshold = match opt.opt_level {
+        OptimizationLevel::None => return,
+        OptimizationLevel::Less => 10,
+        OptimizationLevel::Default => 25,
+        OptimizationLevel::Aggressive => 50,
+    };
+
+    let callees: HashMap<usize, ControlFlowGraph> = inline_candidates(cfgs, threshold)
+        .into_iter()
+        .map(|cfg_no| (cfg_no, cfgs[cfg_no].clone()))
+        .collect();
+
+    if callees.is_empty() {
+        return;
+    }
+
+    for (cfg_no, cfg) in cfgs.iter_mut().enumerate() {
+        // inline from the last call to the first, so the positions of the calls before it
+        // do not change
+        let mut calls = Vec::new();
+
+        for (block_no, block) in cfg.blocks.iter().enumerate() {
+            for (instr_no, instr) in block.instr.iter().enumerate() {
+                if let Instr::Call {
+                    call: InternalCallTy::Static { cfg_no: callee_no },
+                    ..
+                } = instr
+                {
+                    if *callee_no != cfg_no && callees.contains_key(callee_no) {
+                        calls.push((block_no, instr_no, *callee_no));
+                    }
+                }
+            }
+        }
+
+        if calls.is_empty() {
+            continue;
+        }
+
+        for (block_no, instr_no, callee_no) in calls.into_iter().rev() {
+            inline_call(cfg, block_no, instr_no, &callees[&callee_no], ns);
+        }
+
+        // reaching definitions are merged into the existing ones, so clear the stale ones
+        for block in &mut cfg.blocks {
+            block.defs.clear();
+            block.loop_reaching_variables.clear();
+        }
+
+        reaching_definitions::find(cfg);
+    }
+}
+
+/// The private functions which are small enough to be inlined, or have a single call site and
+/// are never referenced through a function pointer
+fn inline_candidates(cfgs: &[ControlFlowGraph], threshold: usize) -> HashSet<usize> {
+    let mut call_sites: HashMap<usize, usize> = HashMap::new();
+    let mut referenced = HashSet::new();
+
+    for instr in cfgs
+        .iter()
+        .flat_map(|cfg| cfg.blocks.iter())
+        .flat_map(|block| block.instr.iter())
+    {
+        match instr {
+            Instr::Call {
+                call: InternalCallTy::Static { cfg_no },
+                ..
+            } => {
+                *call_sites.entry(*cfg_no).or_default() += 1;
+            }
+            Instr::Call {
+                call: InternalCallTy::Dynamic(expr),
+                ..
+            } => {
+                expr.recurse(&mut referenced, function_references);
+            }
+            _ => (),
+        }
+
+        instr.recurse_expressions(&mut referenced, function_references);
+    }
+
+    call_sites
+        .into_iter()
+        .filter(|(cfg_no, calls)| {
+            let cfg = &cfgs[*cfg_no];
+
+            if cfg.public || cfg.is_placeholder() || cfg.ty != pt::FunctionTy::Function {
+                return false;
+            }
+
+            let size: usize = cfg.blocks.iter().map(|block| block.instr.len()).sum();
+
+            size <= threshold || (*calls == 1 && !referenced.contains(cfg_no))
+        })
+        .map(|(cfg_no, _)| cfg_no)
+        .collect()
+}
+
+fn function_references(expr: &Expression, referenced: &mut HashSet<usize>) -> bool {
+    if let Expression::InternalFunctionCfg(cfg_no) = expr {
+        referenced.insert(*cfg_no);
+    }
+
+    true
+}
+
+/// Replace the call instruction at block_no, instr_no with the blocks of the callee
+fn inline_call(
+    cfg: &mut ControlFlowGraph,
+    block_no: usize,
+    instr_no: usize,
+    callee: &ControlFlowGraph,
+    ns: &mut Namespace,
+) {
+    let mut tail = cfg.blocks[block_no].instr.split_off(instr_no);
+
+    let (res, args) = match tail.remove(0) {
+        Instr::Call { res, args, .. } => (res, args),
+        _ => unreachable!(),
+    };
+
+    // the callee blocks go straight after the block with the call, followed by the block
+    // with the instructions after the call
+    let entry = block_no + 1;
+    let next = entry + callee.blocks.len();
+
+    let caller_block = |target: usize| {
+        if target > block_no {
+            target + callee.blocks.len() + 1
+        } else {
+            target
+        }
+    };
+
+    for instr in cfg
+        .blocks
+        .iter_mut()
+        .flat_map(|block| block.instr.iter_mut())
+        .chain(tail.iter_mut())
+    {
+        instr.update_blocks(caller_block);
+    }
+
+    // give the variables of the callee new numbers
+    let mut vars = HashMap::new();
+
+    for (var_no, var) in &callee.vars {
+        let new_var_no = ns.next_id;
+        ns.next_id += 1;
+
+        let id = Vartable::make_unique(&cfg.vars, &var.id, new_var_no);
+
+        cfg.vars.insert(new_var_no, Variable { id, ..var.clone() });
+
+        vars.insert(*var_no, new_var_no);
+    }
+
+    // the arguments are evaluated once and stored in variables
+    let mut arguments = Vec::new();
+
+    for (arg_no, (param, arg)) in callee.params.iter().zip(args).enumerate() {
+        let var_no = ns.next_id;
+        ns.next_id += 1;
+
+        let id = pt::Identifier {
+            name: match &param.id {
+                Some(id) => format!("{}.arg", id.name),
+                None => format!("arg{}", arg_no),
+            },
+            loc: pt::Loc::Codegen,
+        };
+
+        cfg.vars.insert(
+            var_no,
+            Variable {
+                id: Vartable::make_unique(&cfg.vars, &id, var_no),
+                ty: param.ty.clone(),
+                storage: Storage::Local,
+            },
+        );
+
+        cfg.blocks[block_no].instr.push(Instr::Set {
+            loc: pt::Loc::Codegen,
+            res: var_no,
+            expr: arg,
+        });
+
+        arguments.push(var_no);
+    }
+
+    cfg.blocks[block_no]
+        .instr
+        .push(Instr::Branch { block: entry });
+
+    let name = callee.name.rsplit("::").next().unwrap_or_default();
+    let mut returns = 0;
+
+    let mut blocks: Vec<BasicBlock> = callee
+        .blocks
+        .iter()
+        .map(|block| {
+            let mut instrs = Vec::new();
+
+            for instr in &block.instr {
+                let mut instr = instr.clone();
+
+                instr.update_blocks(|target| target + entry);
+                update_variables(&mut instr, &vars);
+                instr.update_expressions(|expr| {
+                    *expr = rename_expression(expr, &mut (&vars, &arguments[..]));
+                });
+
+                if let Instr::Return { value } = instr {
+                    for (res, expr) in res.iter().zip(value) {
+                        instrs.push(Instr::Set {
+                            loc: pt::Loc::Codegen,
+                            res: *res,
+                            expr,
+                        });
+                    }
+
+                    instrs.push(Instr::Branch { block: next });
+
+                    returns += 1;
+                } else {
+                    instrs.push(instr);
+                }
+            }
+
+            BasicBlock {
+                phis: block
+                    .phis
+                    .as_ref()
+                    .map(|phis| phis.iter().map(|var_no| vars[var_no]).collect()),
+                name: format!("{}.{}", name, block.name),
+                instr: instrs,
+                defs: Default::default(),
+                loop_reaching_variables: HashSet::new(),
+                transfers: Vec::new(),
+                inlined: true,
+            }
+        })
+        .collect();
+
+    // the return values differ depending on which return was taken
+    let phis: BTreeSet<usize> = res.iter().copied().collect();
+
+    blocks.push(BasicBlock {
+        phis: if returns > 1 && !phis.is_empty() {
+            Some(phis)
+        } else {
+            None
+        },
+        name: format!("{}.return", name),
+        instr: tail,
+        defs: Default::default(),
+        loop_reaching_variables: HashSet::new(),
+        transfers: Vec::new(),
+        inlined: cfg.blocks[block_no].inlined,
+    });
+
+    cfg.blocks.splice(entry..entry, blocks);
+
+    for (array, length) in &callee.array_lengths_temps {
+        cfg.array_lengths_temps.insert(vars[array], vars[length]);
+    }
+}
+
+/// Change the variables which an instruction assigns to
+fn update_variables(instr: &mut Instr, vars: &HashMap<usize, usize>) {
+    match instr {
+        Instr::Set { res, .. }
+        | Instr::LoadStorage { res, .. }
+        | Instr::LoadImmutable { res, .. }
+        | Instr::PushStorage { res, .. }
+        | Instr::PopStorage { res: Some(res), .. } => *res = vars[res],
+        Instr::PushMemory { res, array, .. } | Instr::PopMemory { res, array, .. } => {
+            *res = vars[res];
+            *array = vars[array];
+        }
+        Instr::Call { res, .. } | Instr::AbiDecode { res, .. } => {
+            for res in res {
+                *res = vars[res];
+            }
+        }
+        Instr::Constructor { success, res, .. } => {
+            *res = vars[res];
+
+            if let Some(success) = success {
+                *success = vars[success];
+            }
+        }
+        Instr::ExternalCall {
+            success: Some(success),
+            ..
+        }
+        | Instr::ValueTransfer {
+            success: Some(success),
+            ..
+        } => *success = vars[success],
+        _ => (),
+    }
+}
+
+/// Replace the variables with their new numbers, and the function arguments with the variables
+/// holding the arguments
+fn rename_expression(expr: &Expression, cx: &mut (&HashMap<usize, usize>, &[usize])) -> Expression {
+    match expr {
+        Expression::Variable(loc, ty, var_no) if cx.0.contains_key(var_no) => {
+            Expression::Variable(*loc, ty.clone(), cx.0[var_no])
+        }
+        Expression::FunctionArg(loc, ty, arg_no) => {
+            Expression::Variable(*loc, ty.clone(), cx.1[*arg_no])
+        }
+        _ if needs_renaming(expr, cx.0) => expr.copy_filter(cx, rename_expression),
+        _ => expr.clone(),
+    }
+}
+
+/// Does the expression use any function arguments or variables of the callee
+fn needs_renaming(expr: &Expression, vars: &HashMap<usize, usize>) -> bool {
+    let mut cx = (vars, false);
+
+    expr.recurse(&mut cx, |expr, cx| {
+        match expr {
+            Expression::FunctionArg(..) => cx.1 = true,
+            Expression::Variable(_, _, var_no) if cx.0.contains_key(var_no) => cx.1 = true,
+            _ => (),
+        }
+
+        !cx.1
+    });
+
+    cx.1
+}
@@ -0,0 +1,691 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::cfg::{BasicBlock, ControlFlowGraph, Instr};
+use super::reaching_definitions::{apply_transfers, block_edges, find, Transfer, VarDefs};
+use super::vartable::{Storage, Variable, Vartable};
+use super::Expression;
+use crate::sema::ast::{ArrayLength, Namespace, RetrieveType, Type};
+use crate::sema::Recurse;
+use num_bigint::BigInt;
+use num_traits::One;
+use solang_parser::pt;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+/*
+  Loop invariant code motion moves calculations which give the same result on every iteration of
+  a loop to just before the loop, so they are done only once. For example, in:
+
+    for (uint i = 0; i < arr.length; i++) { ... }
+
+  the length of the storage array arr is loaded from storage on every iteration, even if the loop
+  never modifies arr.
+
+  A loop is found by an edge to a block which dominates the block the edge comes from (the loop
+  header); all the blocks which can reach that edge without going through the header are part of
+  the loop. An expression is invariant if it has no side effects, and the reaching definitions of
+  all the variables it uses are outside the loop. A storage load is invariant if its slot is
+  constant, or derived from a keccak256 hash, and no instruction in the loop may write to it.
+
+  The invariant expression is assigned to a new temporary in the preheader, the block which
+  branches to the loop header from outside the loop, and the expression in the loop is replaced
+  by the temporary. If there is no such block, one is created. Expressions which can fail (e.g.
+  divide by zero or overflow checking) are only moved if they are evaluated before the loop can
+  be left. Constant folding replaces variables with their values, so the value may already be in
+  a variable set before the loop; if it only depends on the function arguments, that variable is
+  used instead of a new temporary.
+
+  Moving an expression changes the cfg, so all the analysis is done again until nothing else can
+  be moved. An expression moved out of an inner loop may then be moved out of the outer loop.
+*/
+
+/// Move loop invariant expressions and storage loads out of loops
+pub fn loop_invariant_code_motion(cfg: &mut ControlFlowGraph, ns: &mut Namespace) {
+    loop {
+        // reaching definitions are merged into the existing ones, so clear them first
+        for block in &mut cfg.blocks {
+            block.defs.clear();
+            block.loop_reaching_variables.clear();
+        }
+
+        find(cfg);
+
+        let preds = predecessors(cfg);
+        let dominators = dominators(cfg, &preds);
+        let loops = find_loops(cfg, &preds, &dominators);
+
+        if !loops
+            .iter()
+            .any(|(header, body)| hoist(cfg, *header, body, &preds, &dominators, ns))
+        {
+            break;
+        }
+    }
+}
+
+/// The blocks which branch to each block
+fn predecessors(cfg: &ControlFlowGraph) -> Vec<Vec<usize>> {
+    let mut preds = vec![Vec::new(); cfg.blocks.len()];
+
+    for (block_no, block) in cfg.blocks.iter().enumerate() {
+        for edge in block_edges(block) {
+            if !preds[edge].contains(&block_no) {
+                preds[edge].push(block_no);
+            }
+        }
+    }
+
+    preds
+}
+
+/// For each block, the blocks which are on every path from the entry block to it. Unreachable
+/// blocks have no dominators.
+fn dominators(cfg: &ControlFlowGraph, preds: &[Vec<usize>]) -> Vec<HashSet<usize>> {
+    let mut reachable = HashSet::from([0]);
+    let mut todo = vec![0];
+
+    while let Some(block_no) = todo.pop() {
+        for edge in block_edges(&cfg.blocks[block_no]) {
+            if reachable.insert(edge) {
+                todo.push(edge);
+            }
+        }
+    }
+
+    let mut dominators: Vec<HashSet<usize>> = (0..cfg.blocks.len())
+        .map(|block_no| {
+            if block_no == 0 {
+                HashSet::from([0])
+            } else if reachable.contains(&block_no) {
+                reachable.clone()
+            } else {
+                HashSet::new()
+            }
+        })
+        .collect();
+
+    let mut changes = true;
+
+    while changes {
+        changes = false;
+
+        for block_no in 1..cfg.blocks.len() {
+            if !reachable.contains(&block_no) {
+                continue;
+            }
+
+            let mut dom = preds[block_no]
+                .iter()
+                .filter(|pred| reachable.contains(pred))
+                .map(|pred| dominators[*pred].clone())
+                .reduce(|a, b| a.intersection(&b).copied().collect())
+                .unwrap_or_default();
+
+            dom.insert(block_no);
+
+            if dom != dominators[block_no] {
+                dominators[block_no] = dom;
+                changes = true;
+            }
+        }
+    }
+
+    dominators
+}
+
+/// Find the loops in the cfg, by their header block. Inner loops come before the loops which
+/// contain them.
+fn find_loops(
+    cfg: &ControlFlowGraph,
+    preds: &[Vec<usize>],
+    dominators: &[HashSet<usize>],
+) -> Vec<(usize, BTreeSet<usize>)> {
+    let mut loops: BTreeMap<usize, BTreeSet<usize>> = BTreeMap::new();
+
+    for (block_no, block) in cfg.blocks.iter().enumerate() {
+        for header in block_edges(block) {
+            if !dominators[block_no].contains(&header) {
+                continue;
+            }
+
+            let body = loops
+                .entry(header)
+                .or_insert_with(|| BTreeSet::from([header]));
+
+            let mut todo = vec![block_no];
+
+            while let Some(block_no) = todo.pop() {
+                if body.insert(block_no) {
+                    todo.extend(
+                        preds[block_no]
+                            .iter()
+                            .filter(|pred| !dominators[**pred].is_empty()),
+                    );
+                }
+            }
+        }
+    }
+
+    let mut loops: Vec<(usize, BTreeSet<usize>)> = loops.into_iter().collect();
+
+    loops.sort_by_key(|(_, body)| body.len());
+
+    loops
+}
+
+/// Move the invariant expressions and storage loads of a loop to its preheader. Returns true if
+/// anything was moved.
+fn hoist(
+    cfg: &mut ControlFlowGraph,
+    header: usize,
+    body: &BTreeSet<usize>,
+    preds: &[Vec<usize>],
+    dominators: &[HashSet<usize>],
+    ns: &mut Namespace,
+) -> bool {
+    let writes = storage_writes(cfg, body, ns);
+
+    // a variable which is copied from an invariant variable has the same reaching definitions,
+    // but its value is not set before the loop
+    let assigned: HashSet<usize> = body
+        .iter()
+        .flat_map(|block_no| cfg.blocks[*block_no].transfers.iter().flatten())
+        .filter_map(|transfer| match transfer {
+            Transfer::Kill { var_no } => Some(*var_no),
+            _ => None,
+        })
+        .collect();
+
+    // the variables which are set before the loop to a value which only depends on the function
+    // arguments, so the value can be used rather than calculating it again
+    let available: Vec<(usize, Expression)> = cfg.blocks[header]
+        .defs
+        .iter()
+        .filter(|(var_no, defs)| !assigned.contains(var_no) && defs.len() == 1)
+        .filter_map(|(var_no, defs)| {
+            let (def, modified) = defs.iter().next()?;
+
+            if *modified || body.contains(&def.block_no) {
+                return None;
+            }
+
+            match &cfg.blocks[def.block_no].instr[def.instr_no] {
+                Instr::Set { expr, .. } if arguments_only(expr) => Some((*var_no, expr.clone())),
+                _ => None,
+            }
+        })
+        .collect();
+
+    // the blocks from which the loop can be left, including those which leave the function,
+    // like a return or an assert failure. An instruction is always executed if its block
+    // dominates all of them. If there are none, the loop never finishes, and a block which
+    // is only executed conditionally would dominate all of them
+    let exits: Vec<usize> = body
+        .iter()
+        .filter(|block_no| {
+            let edges = block_edges(&cfg.blocks[**block_no]);
+
+            edges.is_empty() || edges.iter().any(|edge| !body.contains(edge))
+        })
+        .copied()
+        .collect();
+
+    let mut preheader = Vec::new();
+    let mut next_id = ns.next_id;
+
+    for block_no in body {
+        let mut defs = cfg.blocks[*block_no].defs.clone();
+
+        for instr_no in 0..cfg.blocks[*block_no].instr.len() {
+            let mut cx = Hoist {
+                body,
+                assigned: &assigned,
+                defs: &defs,
+                available: &available,
+                writes: &writes,
+                always: !exits.is_empty()
+                    && exits
+                        .iter()
+                        .all(|exit| dominators[*exit].contains(block_no)),
+                ns,
+                next_id: &mut next_id,
+                hoisted: &mut preheader,
+            };
+
+            let instr = match cfg.blocks[*block_no].instr[instr_no].clone() {
+                Instr::LoadStorage { res, ty, storage }
+                    if cx.load_invariant(&storage, Some(&ty)) =>
+                {
+                    let var_no = cx.new_var();
+
+                    cx.hoisted.push((
+                        var_no,
+                        ty.clone(),
+                        Instr::LoadStorage {
+                            res: var_no,
+                            ty: ty.clone(),
+                            storage,
+                        },
+                    ));
+
+                    Instr::Set {
+                        loc: pt::Loc::Codegen,
+                        res,
+                        expr: Expression::Variable(pt::Loc::Codegen, ty, var_no),
+                    }
+                }
+                mut instr => {
+                    instr.update_expressions(|expr| *expr = hoist_expression(expr, &mut cx));
+
+                    instr
+                }
+            };
+
+            cfg.blocks[*block_no].instr[instr_no] = instr;
+
+            apply_transfers(&cfg.blocks[*block_no].transfers[instr_no], &mut defs);
+        }
+    }
+
+    ns.next_id = next_id;
+
+    if preheader.is_empty() {
+        return false;
+    }
+
+    let mut instrs = Vec::new();
+
+    for (var_no, ty, instr) in preheader {
+        let id = pt::Identifier {
+            loc: pt::Loc::Codegen,
+            name: String::new(),
+        };
+
+        cfg.vars.insert(
+            var_no,
+            Variable {
+                id: Vartable::make_unique(&cfg.vars, &id, var_no),
+                ty,
+                storage: Storage::Local,
+            },
+        );
+
+        instrs.push(instr);
+    }
+
+    let outside: Vec<usize> = preds[header]
+        .iter()
+        .filter(|pred| !body.contains(pred) && !dominators[**pred].is_empty())
+        .copied()
+        .collect();
+
+    match outside.as_slice() {
+        [pred] if block_edges(&cfg.blocks[*pred]) == [header] => {
+            // the block before the loop only branches to the loop; add the instructions before
+            // the branch
+            let block = &mut cfg.blocks[*pred];
+            let pos = block.instr.len() - 1;
+
+            block.instr.splice(pos..pos, instrs);
+        }
+        _ => {
+            // create a block between the loop and the blocks before it
+            for instr in cfg
+                .blocks
+                .iter_mut()
+                .flat_map(|block| block.instr.iter_mut())
+            {
+                instr.update_blocks(|block| if block >= header { block + 1 } else { block });
+            }
+
+            for pred in &outside {
+                let pred = if *pred >= header { pred + 1 } else { *pred };
+
+                for instr in &mut cfg.blocks[pred].instr {
+                    instr.update_blocks(|block| if block == header + 1 { header } else { block });
+                }
+            }
+
+            instrs.push(Instr::Branch { block: header + 1 });
+
+            let phis = if outside.len() > 1 {
+                cfg.blocks[header].phis.clone()
+            } else {
+                None
+            };
+
+            cfg.blocks.insert(
+                header,
+                BasicBlock {
+                    phis,
+                    name: String::from("preheader"),
+                    instr: instrs,
+                    defs: Default::default(),
+                    loop_reaching_variables: HashSet::new(),
+                    transfers: Vec::new(),
+                    inlined: false,
+                },
+            );
+        }
+    }
+
+    true
+}
+
+/// What is known about a loop while looking for invariant expressions in an instruction
+struct Hoist<'a> {
+    body: &'a BTreeSet<usize>,
+    /// The variables which are set in the loop
+    assigned: &'a HashSet<usize>,
+    /// The reaching definitions at the instruction
+    defs: &'a VarDefs,
+    /// The variables which hold a value before the loop
+    available: &'a [(usize, Expression)],
+    writes: &'a [StorageWrite],
+    /// Is the instruction always executed when the loop is entered
+    always: bool,
+    ns: &'a Namespace,
+    next_id: &'a mut usize,
+    /// The new variables and the instructions which set them before the loop
+    hoisted: &'a mut Vec<(usize, Type, Instr)>,
+}
+
+impl<'a> Hoist<'a> {
+    fn new_var(&mut self) -> usize {
+        let var_no = *self.next_id;
+
+        *self.next_id += 1;
+
+        var_no
+    }
+
+    /// Can this expression be calculated before the loop
+    fn hoistable(&self, expr: &Expression) -> bool {
+        worth_hoisting(expr) && self.invariant(expr) && (self.always || !may_fail(expr))
+    }
+
+    /// Does the expression have any subexpressions which can be calculated before the loop
+    fn contains_hoistable(&self, expr: &Expression) -> bool {
+        let mut cx = (self, false);
+
+        expr.recurse(&mut cx, |expr, cx| {
+            if cx.0.hoistable(expr) {
+                cx.1 = true;
+            }
+
+            !cx.1
+        });
+
+        cx.1
+    }
+
+    /// Does the expression have the same value on every iteration of the loop
+    fn invariant(&self, expr: &Expression) -> bool {
+        match expr {
+            Expression::NumberLiteral(..)
+            | Expression::BoolLiteral(..)
+            | Expression::BytesLiteral(..)
+            | Expression::FunctionArg(..) => true,
+            Expression::Variable(_, ty, var_no) => {
+                !ty.is_reference_type(self.ns)
+                    && !self.assigned.contains(var_no)
+                    && self.defs.get(var_no).map_or(true, |defs| {
+                        defs.iter()
+                            .all(|(def, modified)| !modified && !self.body.contains(&def.block_no))
+                    })
+            }
+            Expression::StorageArrayLength { array, .. } => self.load_invariant(array, None),
+            Expression::Add(_, _, _, left, right)
+            | Expression::Subtract(_, _, _, left, right)
+            | Expression::Multiply(_, _, _, left, right)
+            | Expression::Power(_, _, _, left, right)
+            | Expression::UnsignedDivide(_, _, left, right)
+            | Expression::SignedDivide(_, _, left, right)
+            | Expression::UnsignedModulo(_, _, left, right)
+            | Expression::SignedModulo(_, _, left, right)
+            | Expression::BitwiseAnd(_, _, left, right)
+            | Expression::BitwiseOr(_, _, left, right)
+            | Expression::BitwiseXor(_, _, left, right)
+            | Expression::ShiftLeft(_, _, left, right)
+            | Expression::ShiftRight(_, _, left, right, _)
+            | Expression::Equal(_, left, right)
+            | Expression::NotEqual(_, left, right)
+            | Expression::SignedLess(_, left, right)
+            | Expression::UnsignedLess(_, left, right)
+            | Expression::LessEqual(_, left, right)
+            | Expression::SignedMore(_, left, right)
+            | Expression::UnsignedMore(_, left, right)
+            | Expression::MoreEqual(_, left, right) => {
+                self.invariant(left) && self.invariant(right)
+            }
+            Expression::Not(_, expr)
+            | Expression::Complement(_, _, expr)
+            | Expression::UnaryMinus(_, _, expr)
+            | Expression::ZeroExt(_, _, expr)
+            | Expression::SignExt(_, _, expr)
+            | Expression::Trunc(_, _, expr)
+            | Expression::Cast(_, _, expr) => self.invariant(expr),
+            Expression::Keccak256(_, _, exprs) => exprs.iter().all(|expr| self.invariant(expr)),
+            _ => false,
+        }
+    }
+
+    /// Does loading from this storage slot give the same value on every iteration of the loop
+    fn load_invariant(&self, storage: &Expression, ty: Option<&Type>) -> bool {
+        let load = storage_location(storage, ty, self.ns);
+
+        match load {
+            StorageWrite::Slot(..) => (),
+            // the slot may not exist when the loop is not entered
+            StorageWrite::Derived if self.always && self.invariant(storage) => (),
+            _ => return false,
+        }
+
+        !self.writes.iter().any(|write| write.overlaps(&load))
+    }
+}
+
+/// Replace the invariant subexpressions with variables, which are set before the loop
+fn hoist_expression(expr: &Expression, cx: &mut Hoist) -> Expression {
+    if cx.hoistable(expr) {
+        let ty = expr.ty();
+
+        // the same expression may be used more than once in the loop
+        if let Some((var_no, ..)) = cx.hoisted.iter().find(
+            |(_, _, instr)| matches!(instr, Instr::Set { expr: hoisted, .. } if hoisted == expr),
+        ) {
+            return Expression::Variable(pt::Loc::Codegen, ty, *var_no);
+        }
+
+        if let Some((var_no, _)) = cx.available.iter().find(|(_, value)| value == expr) {
+            return Expression::Variable(pt::Loc::Codegen, ty, *var_no);
+        }
+
+        let var_no = cx.new_var();
+
+        cx.hoisted.push((
+            var_no,
+            ty.clone(),
+            Instr::Set {
+                loc: pt::Loc::Codegen,
+                res: var_no,
+                expr: expr.clone(),
+            },
+        ));
+
+        Expression::Variable(pt::Loc::Codegen, ty, var_no)
+    } else if cx.contains_hoistable(expr) {
+        expr.copy_filter(cx, hoist_expression)
+    } else {
+        expr.clone()
+    }
+}
+
+/// Is there anything to be gained from calculating the expression once
+fn worth_hoisting(expr: &Expression) -> bool {
+    match expr {
+        Expression::NumberLiteral(..)
+        | Expression::BoolLiteral(..)
+        | Expression::BytesLiteral(..)
+        | Expression::FunctionArg(..)
+        | Expression::Variable(..) => false,
+        Expression::ZeroExt(_, _, expr)
+        | Expression::SignExt(_, _, expr)
+        | Expression::Trunc(_, _, expr)
+        | Expression::Cast(_, _, expr) => worth_hoisting(expr),
+        _ => {
+            // an expression of literals is folded by llvm anyway
+            let mut literals = true;
+
+            expr.recurse(&mut literals, |expr, literals| {
+                if matches!(
+                    expr,
+                    Expression::FunctionArg(..)
+                        | Expression::Variable(..)
+                        | Expression::StorageArrayLength { .. }
+                ) {
+                    *literals = false;
+                }
+
+                *literals
+            });
+
+            !literals
+        }
+    }
+}
+
+/// Does the expression only depend on the function arguments, so it has the same value
+/// everywhere in the function
+fn arguments_only(expr: &Expression) -> bool {
+    let mut arguments_only = true;
+
+    expr.recurse(&mut arguments_only, |expr, arguments_only| {
+        if matches!(
+            expr,
+            Expression::Variable(..) | Expression::StorageArrayLength { .. }
+        ) {
+            *arguments_only = false;
+        }
+
+        *arguments_only
+    });
+
+    arguments_only
+}
+
+/// Can evaluating the expression fail, e.g. because of a division by zero or an overflow check
+fn may_fail(expr: &Expression) -> bool {
+    let mut fail = false;
+
+    expr.recurse(&mut fail, |expr, fail| {
+        if matches!(
+            expr,
+            Expression::Add(_, _, false, ..)
+                | Expression::Subtract(_, _, false, ..)
+                | Expression::Multiply(_, _, false, ..)
+                | Expression::Power(_, _, false, ..)
+                | Expression::UnsignedDivide(..)
+                | Expression::SignedDivide(..)
+                | Expression::UnsignedModulo(..)
+                | Expression::SignedModulo(..)
+        ) {
+            *fail = true;
+        }
+
+        !*fail
+    });
+
+    fail
+}
+
+/// The storage which an instruction may write to
+enum StorageWrite {
+    /// A constant slot, and the number of slots
+    Slot(BigInt, BigInt),
+    /// A slot derived from a keccak256 hash, or an element of a dynamic array or mapping. These
+    /// never overlap with the constant slots of the contract variables.
+    Derived,
+    /// Anything
+    Unknown,
+}
+
+impl StorageWrite {
+    fn overlaps(&self, other: &StorageWrite) -> bool {
+        match (self, other) {
+            (StorageWrite::Slot(left, left_len), StorageWrite::Slot(right, right_len)) => {
+                left < &(right + right_len) && right < &(left + left_len)
+            }
+            (StorageWrite::Slot(..), StorageWrite::Derived)
+            | (StorageWrite::Derived, StorageWrite::Slot(..)) => false,
+            _ => true,
+        }
+    }
+}
+
+/// Classify a storage slot expression. If the type is not known, only the first slot is
+/// included.
+fn storage_location(storage: &Expression, ty: Option<&Type>, ns: &Namespace) -> StorageWrite {
+    match storage {
+        Expression::NumberLiteral(_, _, slot) => StorageWrite::Slot(
+            slot.clone(),
+            ty.map_or_else(BigInt::one, |ty| ty.storage_slots(ns)),
+        ),
+        Expression::Keccak256(..) => StorageWrite::Derived,
+        Expression::Add(_, _, _, left, right) => {
+            match (
+                storage_location(left, None, ns),
+                storage_location(right, None, ns),
+            ) {
+                (StorageWrite::Derived, _) | (_, StorageWrite::Derived) => StorageWrite::Derived,
+                _ => StorageWrite::Unknown,
+            }
+        }
+        Expression::Subscript(_, _, array_ty, ..) => match array_ty.deref_any() {
+            Type::Mapping(..) => StorageWrite::Derived,
+            Type::Array(_, dims) if dims.last() == Some(&ArrayLength::Dynamic) => {
+                StorageWrite::Derived
+            }
+            _ => StorageWrite::Unknown,
+        },
+        Expression::BytesLiteral(..) => StorageWrite::Derived,
+        _ => StorageWrite::Unknown,
+    }
+}
+
+/// The storage which the instructions in the loop may write to
+fn storage_writes(
+    cfg: &ControlFlowGraph,
+    body: &BTreeSet<usize>,
+    ns: &Namespace,
+) -> Vec<StorageWrite> {
+    let mut writes = Vec::new();
+
+    for instr in body
+        .iter()
+        .flat_map(|block_no| cfg.blocks[*block_no].instr.iter())
+    {
+        match instr {
+            Instr::SetStorage { ty, storage, .. } | Instr::ClearStorage { ty, storage } => {
+                writes.push(storage_location(storage, Some(ty), ns));
+            }
+            Instr::SetStorageBytes { storage, .. }
+            | Instr::PushStorage { storage, .. }
+            | Instr::PopStorage { storage, .. } => {
+                // this changes the array at the slot, and its elements
+                writes.push(storage_location(storage, None, ns));
+                writes.push(StorageWrite::Derived);
+            }
+            Instr::Call { .. }
+            | Instr::ExternalCall { .. }
+            | Instr::Constructor { .. }
+            | Instr::ValueTransfer { .. } => {
+                // We don't know what the function does to storage, and external calls can
+                // call us back
+                writes.push(StorageWrite::Unknown);
+            }
+            _ => (),
+        }
+    }
+
+    writes
+}
@@ -3,10 +3,13 @@
 mod array_boundary;
 pub mod cfg;
 mod constant_folding;
+mod dead_code_elimination;
 mod dead_storage;
 mod encoding;
 mod expression;
 mod external_functions;
+mod function_inlining;
+mod loop_invariant_code_motion;
 mod reaching_definitions;
 mod statements;
 mod storage;
@@ -20,7 +23,7 @@ mod vector_to_slice;
 mod yul;
 
 use self::{
-    cfg::{optimize_and_check_cfg, ControlFlowGraph, Instr},
+    cfg::{check_cfg, optimize_cfg, ControlFlowGraph, Instr},
     expression::expression,
     vartable::Vartable,
 };
@@ -84,6 +87,9 @@ pub struct Options {
     pub vector_to_slice: bool,
     pub math_overflow_check: bool,
     pub common_subexpression_elimination: bool,
+    pub function_inlining: bool,
+    pub loop_invariant_code_motion: bool,
+    pub dead_code_elimination: bool,
     pub opt_level: OptimizationLevel,
 }
 
@@ -96,6 +102,9 @@ impl Default for Options {
             vector_to_slice: true,
             math_overflow_check: false,
             common_subexpression_elimination: true,
+            function_inlining: true,
+            loop_invariant_code_motion: true,
+            dead_code_elimination: true,
             opt_level: OptimizationLevel::Default,
         }
     }
@@ -142,7 +151,7 @@ pub fn codegen(ns: &mut Namespace, opt: &Options) {
             // Solana creates a single bundle
             if ns.target != Target::Solana {
                 #[cfg(not(feature = "llvm"))]
-                panic!("LLVM feature is not enabled");
+                {}
                 #[cfg(feature = "llvm")]
                 {
                     let context = inkwell::context::Context::create();
@@ -154,7 +163,6 @@ pub fn codegen(ns: &mut Namespace, opt: &Options) {
                         &context,
                         &filename,
                         opt.opt_level.into(),
-                        opt.math_overflow_check,
                     );
 
                     let code = binary.code(Generate::Linked).expect("llvm build");
@@ -193,6 +201,9 @@ fn contract(contract_no: usize, ns: &mut Namespace, opt: &Options) {
 
         all_cfg.resize(cfg_no, ControlFlowGraph::placeholder());
 
+        // the cfgs which are checked and optimized once they have all been generated
+        let mut generated = Vec::new();
+
         // clone all_functions so we can pass a mutable reference to generate_cfg
         for (function_no, cfg_no) in ns.contracts[contract_no]
             .all_functions
@@ -208,12 +219,19 @@ fn contract(contract_no: usize, ns: &mut Namespace, opt: &Options) {
                 &mut all_cfg,
                 ns,
                 opt,
-            )
+            );
+
+            generated.push((cfg_no, ASTFunction::SolidityFunction(function_no)));
         }
 
         // generate the cfg for yul functions
         for yul_func_no in ns.contracts[contract_no].yul_functions.clone() {
             generate_yul_function_cfg(contract_no, yul_func_no, &mut all_cfg, ns, opt);
+
+            generated.push((
+                ns.yul_functions[yul_func_no].cfg_no,
+                ASTFunction::YulFunction(yul_func_no),
+            ));
         }
 
         // Generate cfg for storage initializers
@@ -221,6 +239,7 @@ fn contract(contract_no: usize, ns: &mut Namespace, opt: &Options) {
         let pos = all_cfg.len();
         all_cfg.push(cfg);
         ns.contracts[contract_no].initializer = Some(pos);
+        generated.push((pos, ASTFunction::None));
 
         if !ns.contracts[contract_no].have_constructor(ns) {
             // generate the default constructor
@@ -231,6 +250,27 @@ fn contract(contract_no: usize, ns: &mut Namespace, opt: &Options) {
             cfg::generate_cfg(contract_no, None, cfg_no, &mut all_cfg, ns, opt);
 
             ns.contracts[contract_no].default_constructor = Some((func, cfg_no));
+            generated.push((cfg_no, ASTFunction::None));
+        }
+
+        // undefined variables must be found before inlining, so they are reported in the function
+        // which uses them. The cfgs of virtual functions are left as placeholders.
+        generated.retain(|(cfg_no, func_no)| {
+            let cfg = &mut all_cfg[*cfg_no];
+
+            !cfg.is_placeholder() && check_cfg(cfg, ns, *func_no)
+        });
+
+        if !ns.diagnostics.any_errors() && opt.function_inlining {
+            function_inlining::function_inlining(&mut all_cfg, ns, opt);
+        }
+
+        for (cfg_no, func_no) in generated {
+            optimize_cfg(&mut all_cfg[cfg_no], ns, func_no, opt);
+        }
+
+        if !ns.diagnostics.any_errors() && (opt.constant_folding || opt.strength_reduce) {
+            strength_reduce::propagate_call_summaries(&mut all_cfg, ns, opt);
         }
 
         ns.contracts[contract_no].cfg = all_cfg;
@@ -246,27 +286,46 @@ fn storage_initializer(contract_no: usize, ns: &mut Namespace, opt: &Options) ->
     );
     let mut vartab = Vartable::new(ns.next_id);
 
-    for layout in &ns.contracts[contract_no].layout {
-        let var = &ns.contracts[layout.contract_no].variables[layout.var_no];
+    // initializers are run in declaration order, so immutables and storage variables are interleaved
+    for base_contract_no in ns.contract_bases(contract_no) {
+        for (var_no, var) in ns.contracts[base_contract_no].variables.iter().enumerate() {
+            if var.constant {
+                continue;
+            }
 
-        if let Some(init) = &var.initializer {
-            let storage = ns.contracts[contract_no].get_storage_slot(
-                layout.contract_no,
-                layout.var_no,
-                ns,
-                None,
-            );
+            if let Some(init) = &var.initializer {
+                let value = expression(init, &mut cfg, contract_no, None, ns, &mut vartab, opt);
 
-            let value = expression(init, &mut cfg, contract_no, None, ns, &mut vartab, opt);
+                if var.immutable {
+                    let offset =
+                        ns.contracts[contract_no].get_immutable_offset(base_contract_no, var_no);
 
-            cfg.add(
-                &mut vartab,
-                Instr::SetStorage {
-                    value,
-                    ty: var.ty.clone(),
-                    storage,
-                },
-            );
+                    cfg.add(
+                        &mut vartab,
+                        Instr::SetImmutable {
+                            value,
+                            ty: var.ty.clone(),
+                            offset,
+                        },
+                    );
+                } else {
+                    let storage = ns.contracts[contract_no].get_storage_slot(
+                        base_contract_no,
+                        var_no,
+                        ns,
+                        None,
+                    );
+
+                    cfg.add(
+                        &mut vartab,
+                        Instr::SetStorage {
+                            value,
+                            ty: var.ty.clone(),
+                            storage,
+                        },
+                    );
+                }
+            }
         }
     }
 
@@ -276,8 +335,6 @@ fn storage_initializer(contract_no: usize, ns: &mut Namespace, opt: &Options) ->
     cfg.vars = vars;
     ns.next_id = next_id;
 
-    optimize_and_check_cfg(&mut cfg, ns, ASTFunction::None, opt);
-
     cfg
 }
 
@@ -288,11 +345,47 @@ fn layout(contract_no: usize, ns: &mut Namespace) {
     } else {
         BigInt::zero()
     };
+    let mut immutable_offset = BigInt::zero();
+
+    if ns.target == Target::Solana
+        && ns
+            .contract_bases(contract_no)
+            .iter()
+            .any(|base_no| ns.files[ns.contracts[*base_no].loc.file_no()].value_transfer)
+    {
+        // record the lamports balance of the account, so the value received can be calculated
+        ns.contracts[contract_no].lamports_offset = Some(slot.clone());
+
+        slot += 8;
+    }
 
     for base_contract_no in ns.contract_bases(contract_no) {
         for var_no in 0..ns.contracts[base_contract_no].variables.len() {
             if !ns.contracts[base_contract_no].variables[var_no].constant {
                 let ty = ns.contracts[base_contract_no].variables[var_no].ty.clone();
+                let immutable = ns.contracts[base_contract_no].variables[var_no].immutable;
+
+                if immutable && ns.target != Target::Solana {
+                    // immutables are not in contract storage, they are embedded in the code
+                    let alignment = ty.align_of(ns);
+
+                    let offset = immutable_offset.clone() % alignment;
+
+                    if offset > BigInt::zero() {
+                        immutable_offset += alignment - offset;
+                    }
+
+                    ns.contracts[contract_no].immutable_layout.push(Layout {
+                        slot: immutable_offset.clone(),
+                        contract_no: base_contract_no,
+                        var_no,
+                        ty: ty.clone(),
+                    });
+
+                    immutable_offset += ty.memory_size_of(ns);
+
+                    continue;
+                }
 
                 if ns.target == Target::Solana {
                     // elements need to be aligned on solana
@@ -305,12 +398,20 @@ fn layout(contract_no: usize, ns: &mut Namespace) {
                     }
                 }
 
-                ns.contracts[contract_no].layout.push(Layout {
+                let layout = Layout {
                     slot: slot.clone(),
                     contract_no: base_contract_no,
                     var_no,
                     ty: ty.clone(),
-                });
+                };
+
+                if immutable {
+                    // Solana programs cannot have writable data, so immutables live in the account
+                    // data. Only the constructor writes them.
+                    ns.contracts[contract_no].immutable_layout.push(layout);
+                } else {
+                    ns.contracts[contract_no].layout.push(layout);
+                }
 
                 slot += ty.storage_slots(ns);
             }
@@ -318,6 +419,7 @@ fn layout(contract_no: usize, ns: &mut Namespace) {
     }
 
     ns.contracts[contract_no].fixed_layout_size = slot;
+    ns.contracts[contract_no].immutable_layout_size = immutable_offset;
 }
 
 trait LLVMName {
@@ -356,6 +458,13 @@ pub enum Expression {
     Add(pt::Loc, Type, bool, Box<Expression>, Box<Expression>),
     AllocDynamicArray(pt::Loc, Type, Box<Expression>, Option<Vec<u8>>),
     ArrayLiteral(pt::Loc, Type, Vec<u32>, Vec<Expression>),
+    ArraySlice {
+        loc: pt::Loc,
+        ty: Type,
+        array: Box<Expression>,
+        start: Box<Expression>,
+        end: Box<Expression>,
+    },
     BitwiseAnd(pt::Loc, Type, Box<Expression>, Box<Expression>),
     BitwiseOr(pt::Loc, Type, Box<Expression>, Box<Expression>),
     BitwiseXor(pt::Loc, Type, Box<Expression>, Box<Expression>),
@@ -486,6 +595,7 @@ impl CodeLocation for Expression {
             | Expression::SignedMore(loc, ..)
             | Expression::UnsignedMore(loc, ..)
             | Expression::ZeroExt(loc, ..)
+            | Expression::ArraySlice { loc, .. }
             | Expression::AdvancePointer { loc, .. } => *loc,
 
             Expression::InternalFunctionCfg(_) | Expression::Poison | Expression::Undefined(_) => {
@@ -516,6 +626,8 @@ impl Recurse for Expression {
             | Expression::BitwiseOr(_, _, left, right)
             | Expression::UnsignedDivide(_, _, left, right)
             | Expression::SignedDivide(_, _, left, right)
+            | Expression::UnsignedModulo(_, _, left, right)
+            | Expression::SignedModulo(_, _, left, right)
             | Expression::Equal(_, left, right)
             | Expression::UnsignedLess(_, left, right)
             | Expression::SignedLess(_, left, right)
@@ -541,6 +653,14 @@ impl Recurse for Expression {
                 right.recurse(cx, f);
             }
 
+            Expression::ArraySlice {
+                array, start, end, ..
+            } => {
+                array.recurse(cx, f);
+                start.recurse(cx, f);
+                end.recurse(cx, f);
+            }
+
             Expression::BytesCast(_, _, _, exp)
             | Expression::Cast(_, _, exp)
             | Expression::GetRef(_, _, exp)
@@ -561,6 +681,7 @@ impl Recurse for Expression {
             | Expression::ConstArrayLiteral(_, _, _, vec)
             | Expression::Keccak256(_, _, vec)
             | Expression::StructLiteral(_, _, vec)
+            | Expression::List(_, vec)
             | Expression::ArrayLiteral(_, _, _, vec) => {
                 for item in vec {
                     item.recurse(cx, f);
@@ -636,6 +757,7 @@ impl RetrieveType for Expression {
             | Expression::BytesCast(_, ty, ..)
             | Expression::RationalNumberLiteral(_, ty, ..)
             | Expression::Subscript(_, ty, ..)
+            | Expression::ArraySlice { ty, .. }
             | Expression::AdvancePointer { ty, .. } => ty.clone(),
 
             Expression::BoolLiteral(..)
@@ -996,6 +1118,18 @@ impl Expression {
                     Box::new(filter(left, ctx)),
                     Box::new(filter(right, ctx)),
                 ),
+                Expression::UnsignedModulo(loc, ty, left, right) => Expression::UnsignedModulo(
+                    *loc,
+                    ty.clone(),
+                    Box::new(filter(left, ctx)),
+                    Box::new(filter(right, ctx)),
+                ),
+                Expression::SignedModulo(loc, ty, left, right) => Expression::SignedModulo(
+                    *loc,
+                    ty.clone(),
+                    Box::new(filter(left, ctx)),
+                    Box::new(filter(right, ctx)),
+                ),
                 Expression::Power(loc, ty, unchecked, left, right) => Expression::Power(
                     *loc,
                     ty.clone(),
@@ -1124,6 +1258,19 @@ impl Expression {
                         Box::new(filter(right, ctx)),
                     )
                 }
+                Expression::ArraySlice {
+                    loc,
+                    ty,
+                    array,
+                    start,
+                    end,
+                } => Expression::ArraySlice {
+                    loc: *loc,
+                    ty: ty.clone(),
+                    array: Box::new(filter(array, ctx)),
+                    start: Box::new(filter(start, ctx)),
+                    end: Box::new(filter(end, ctx)),
+                },
                 Expression::StructMember(loc, ty, expr, field) => {
                     Expression::StructMember(*loc, ty.clone(), Box::new(filter(expr, ctx)), *field)
                 }
@@ -1187,6 +1334,30 @@ impl Expression {
 
                     Expression::Builtin(*loc, tys.clone(), *builtin, args)
                 }
+                Expression::Keccak256(loc, ty, args) => {
+                    let args = args.iter().map(|e| filter(e, ctx)).collect();
+
+                    Expression::Keccak256(*loc, ty.clone(), args)
+                }
+                Expression::List(loc, args) => {
+                    let args = args.iter().map(|e| filter(e, ctx)).collect();
+
+                    Expression::List(*loc, args)
+                }
+                Expression::GetRef(loc, ty, expr) => {
+                    Expression::GetRef(*loc, ty.clone(), Box::new(filter(expr, ctx)))
+                }
+                Expression::AbiEncode {
+                    loc,
+                    tys,
+                    packed,
+                    args,
+                } => Expression::AbiEncode {
+                    loc: *loc,
+                    tys: tys.clone(),
+                    packed: packed.iter().map(|e| filter(e, ctx)).collect(),
+                    args: args.iter().map(|e| filter(e, ctx)).collect(),
+                },
                 _ => self.clone(),
             },
             ctx,
@@ -1236,7 +1407,9 @@ pub enum Builtin {
     BlockDifficulty,
     BlockHash,
     BlockNumber,
+    ByteSwap,
     Calldata,
+    ECRecover,
     Gasleft,
     GasLimit,
     Gasprice,
@@ -1314,6 +1487,7 @@ impl From<&ast::Builtin> for Builtin {
             ast::Builtin::Sha256 => Builtin::Sha256,
             ast::Builtin::Signature => Builtin::Signature,
             ast::Builtin::SignatureVerify => Builtin::SignatureVerify,
+            ast::Builtin::ECRecover => Builtin::ECRecover,
             ast::Builtin::Timestamp => Builtin::Timestamp,
             ast::Builtin::TombstoneDeposit => Builtin::TombstoneDeposit,
             ast::Builtin::Value => Builtin::Value,
@@ -135,9 +135,9 @@ fn instr_transfers(block_no: usize, block: &BasicBlock) -> Vec<Vec<Transfer>> {
             Instr::Set { res, .. } => set_var(&[*res]),
             Instr::Call { res, .. } => set_var(res),
             Instr::AbiDecode { res, .. } => set_var(res),
-            Instr::LoadStorage { res, .. } | Instr::PopStorage { res: Some(res), .. } => {
-                set_var(&[*res])
-            }
+            Instr::LoadStorage { res, .. }
+            | Instr::LoadImmutable { res, .. }
+            | Instr::PopStorage { res: Some(res), .. } => set_var(&[*res]),
             Instr::PushMemory { array, res, .. } => {
                 let mut v = set_var(&[*res]);
                 v.push(Transfer::Mod { var_no: *array });
@@ -241,6 +241,10 @@ pub fn block_edges(block: &BasicBlock) -> Vec<usize> {
                 out.push(*true_block);
                 out.push(*false_block);
             }
+            Instr::Switch { cases, default, .. } => {
+                out.extend(cases.iter().map(|(_, block)| *block));
+                out.push(*default);
+            }
             Instr::AbiDecode {
                 exception_block: Some(block),
                 ..
@@ -3,7 +3,7 @@
 use num_bigint::BigInt;
 use std::collections::LinkedList;
 
-use super::expression::{assign_single, default_gas, emit_function_call, expression};
+use super::expression::{assign_single, default_gas, emit_function_call, expression, revert_data};
 use super::Options;
 use super::{
     cfg::{ControlFlowGraph, Instr},
@@ -668,6 +668,29 @@ pub(crate) fn statement(
                 },
             );
         }
+        Statement::Revert {
+            loc,
+            error_no,
+            args,
+        } => {
+            let error = &ns.errors[*error_no];
+
+            let args = args
+                .iter()
+                .map(|a| expression(a, cfg, contract_no, Some(func), ns, vartab, opt))
+                .collect();
+
+            let tys = error.fields.iter().map(|field| field.ty.clone()).collect();
+
+            let encoded_args = revert_data(loc, error.selector(), args, tys, cfg, vartab);
+
+            cfg.add(
+                vartab,
+                Instr::AssertFailure {
+                    expr: Some(encoded_args),
+                },
+            );
+        }
         Statement::Underscore(_) => {
             // ensure we get phi nodes for the return values
             if let Some(instr @ Instr::Call { res, .. }) = placeholder {
@@ -1295,28 +1318,36 @@ fn try_catch(
 
     cfg.set_basic_block(catch_block);
 
-    for (error_param_pos, error_param, error_stmt) in &try_stmt.errors {
+    for clause in &try_stmt.errors {
         let no_reason_block = cfg.new_basic_block("no_reason".to_string());
 
-        let error_var = match error_param_pos {
-            Some(pos) => *pos,
-            _ => vartab.temp_anonymous(&Type::String),
-        };
+        let res = clause
+            .params
+            .iter()
+            .map(|(pos, param)| match pos {
+                Some(pos) => *pos,
+                None => vartab.temp_anonymous(&param.ty),
+            })
+            .collect();
 
         cfg.add(
             vartab,
             Instr::AbiDecode {
-                selector: Some(0x08c3_79a0),
+                selector: Some(clause.selector),
                 exception_block: Some(no_reason_block),
-                res: vec![error_var],
-                tys: vec![error_param.clone()],
+                res,
+                tys: clause
+                    .params
+                    .iter()
+                    .map(|(_, param)| param.clone())
+                    .collect(),
                 data: Expression::ReturnData(pt::Loc::Codegen),
             },
         );
 
         let mut reachable = true;
 
-        for stmt in error_stmt {
+        for stmt in &clause.stmt {
             statement(
                 stmt,
                 func,
@@ -1387,9 +1418,11 @@ fn try_catch(
     if let Some(pos) = &try_stmt.catch_param_pos {
         set.remove(pos);
     }
-    for (pos, _, _) in &try_stmt.errors {
-        if let Some(pos) = pos {
-            set.remove(pos);
+    for clause in &try_stmt.errors {
+        for (pos, _) in &clause.params {
+            if let Some(pos) = pos {
+                set.remove(pos);
+            }
         }
     }
     cfg.set_phis(finally_block, set);
@@ -1434,7 +1467,11 @@ impl Type {
     /// for example a reference to a variable in storage.
     pub fn default(&self, ns: &Namespace) -> Option<Expression> {
         match self {
-            Type::Address(_) | Type::Uint(_) | Type::Int(_) => Some(Expression::NumberLiteral(
+            Type::Address(_)
+            | Type::Uint(_)
+            | Type::Int(_)
+            | Type::Fixed(..)
+            | Type::Ufixed(..) => Some(Expression::NumberLiteral(
                 pt::Loc::Codegen,
                 self.clone(),
                 BigInt::from(0),
@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::value::Value;
-use super::{bigint_to_bitarr, highest_set_bit, track, Variables};
+use super::{argument_key, bigint_to_bitarr, highest_set_bit, track, Variables};
 use crate::codegen::Expression;
 use crate::sema::ast::RetrieveType;
 use crate::sema::ast::{Namespace, Type};
@@ -44,6 +44,9 @@ pub(super) fn expression_values(
         Expression::Not(_, expr) => not_values(expr, vars, ns),
         Expression::Complement(_, _, expr) => complement_values(expr, vars, ns),
         Expression::Variable(_, _, var_no) => variable_values(var_no, vars),
+        Expression::FunctionArg(_, _, arg_no) if vars.contains_key(&argument_key(*arg_no)) => {
+            variable_values(&argument_key(*arg_no), vars)
+        }
         Expression::InternalFunctionCfg(_) => {
             // reference to a function; ignore
             HashSet::new()
@@ -365,7 +368,7 @@ fn multiply_values(
         .map(|(l, r)| {
             let mut known_bits = BitArray::new([0u8; 32]);
 
-            if ty.is_signed_int() {
+            if ty.is_signed_numeric() {
                 match (l.sign(), r.sign()) {
                     ((true, left_sign), (true, right_sign)) => {
                         let left = if left_sign {
@@ -469,12 +472,12 @@ fn more_values(
             let mut known_bits = BitArray::new([0u8; 32]);
             let mut value = BitArray::new([0u8; 32]);
 
-            let is_true = if ty.is_signed_int() {
-                BigInt::from_signed_bytes_le(l.get_signed_max_value().as_buffer())
-                    > BigInt::from_signed_bytes_le(r.get_signed_min_value().as_buffer())
+            let is_true = if ty.is_signed_numeric() {
+                BigInt::from_signed_bytes_le(l.get_signed_min_value().as_buffer())
+                    > BigInt::from_signed_bytes_le(r.get_signed_max_value().as_buffer())
             } else {
-                BigInt::from_bytes_le(Sign::Plus, l.get_unsigned_max_value().as_buffer())
-                    > BigInt::from_bytes_le(Sign::Plus, r.get_unsigned_min_value().as_buffer())
+                BigInt::from_bytes_le(Sign::Plus, l.get_unsigned_min_value().as_buffer())
+                    > BigInt::from_bytes_le(Sign::Plus, r.get_unsigned_max_value().as_buffer())
             };
 
             if is_true {
@@ -483,12 +486,12 @@ fn more_values(
                 value.set(0, true);
             } else {
                 // maybe the comparison is always false
-                let is_false = if ty.is_signed_int() {
-                    BigInt::from_signed_bytes_le(l.get_signed_min_value().as_buffer())
-                        <= BigInt::from_signed_bytes_le(r.get_signed_max_value().as_buffer())
+                let is_false = if ty.is_signed_numeric() {
+                    BigInt::from_signed_bytes_le(l.get_signed_max_value().as_buffer())
+                        <= BigInt::from_signed_bytes_le(r.get_signed_min_value().as_buffer())
                 } else {
-                    BigInt::from_bytes_le(Sign::Plus, l.get_unsigned_min_value().as_buffer())
-                        <= BigInt::from_bytes_le(Sign::Plus, r.get_unsigned_max_value().as_buffer())
+                    BigInt::from_bytes_le(Sign::Plus, l.get_unsigned_max_value().as_buffer())
+                        <= BigInt::from_bytes_le(Sign::Plus, r.get_unsigned_min_value().as_buffer())
                 };
 
                 if is_false {
@@ -524,12 +527,12 @@ fn more_equal_values(
             let mut known_bits = BitArray::new([0u8; 32]);
             let mut value = BitArray::new([0u8; 32]);
 
-            let is_true = if ty.is_signed_int() {
-                BigInt::from_signed_bytes_le(l.get_signed_max_value().as_buffer())
-                    >= BigInt::from_signed_bytes_le(r.get_signed_min_value().as_buffer())
+            let is_true = if ty.is_signed_numeric() {
+                BigInt::from_signed_bytes_le(l.get_signed_min_value().as_buffer())
+                    >= BigInt::from_signed_bytes_le(r.get_signed_max_value().as_buffer())
             } else {
-                BigInt::from_bytes_le(Sign::Plus, l.get_unsigned_max_value().as_buffer())
-                    >= BigInt::from_bytes_le(Sign::Plus, r.get_unsigned_min_value().as_buffer())
+                BigInt::from_bytes_le(Sign::Plus, l.get_unsigned_min_value().as_buffer())
+                    >= BigInt::from_bytes_le(Sign::Plus, r.get_unsigned_max_value().as_buffer())
             };
 
             if is_true {
@@ -538,12 +541,12 @@ fn more_equal_values(
                 value.set(0, true);
             } else {
                 // maybe the comparison is always false
-                let is_false = if ty.is_signed_int() {
-                    BigInt::from_signed_bytes_le(l.get_signed_min_value().as_buffer())
-                        < BigInt::from_signed_bytes_le(r.get_signed_max_value().as_buffer())
+                let is_false = if ty.is_signed_numeric() {
+                    BigInt::from_signed_bytes_le(l.get_signed_max_value().as_buffer())
+                        < BigInt::from_signed_bytes_le(r.get_signed_min_value().as_buffer())
                 } else {
-                    BigInt::from_bytes_le(Sign::Plus, l.get_unsigned_min_value().as_buffer())
-                        < BigInt::from_bytes_le(Sign::Plus, r.get_unsigned_max_value().as_buffer())
+                    BigInt::from_bytes_le(Sign::Plus, l.get_unsigned_max_value().as_buffer())
+                        < BigInt::from_bytes_le(Sign::Plus, r.get_unsigned_min_value().as_buffer())
                 };
 
                 if is_false {
@@ -579,7 +582,7 @@ fn less_values(
             let mut known_bits = BitArray::new([0u8; 32]);
             let mut value = BitArray::new([0u8; 32]);
 
-            let is_true = if ty.is_signed_int() {
+            let is_true = if ty.is_signed_numeric() {
                 BigInt::from_signed_bytes_le(l.get_signed_max_value().as_buffer())
                     < BigInt::from_signed_bytes_le(r.get_signed_min_value().as_buffer())
             } else {
@@ -593,7 +596,7 @@ fn less_values(
                 value.set(0, true);
             } else {
                 // maybe the comparison is always false
-                let is_false = if ty.is_signed_int() {
+                let is_false = if ty.is_signed_numeric() {
                     BigInt::from_signed_bytes_le(l.get_signed_min_value().as_buffer())
                         >= BigInt::from_signed_bytes_le(r.get_signed_max_value().as_buffer())
                 } else {
@@ -634,7 +637,7 @@ fn less_equal_values(
             let mut known_bits = BitArray::new([0u8; 32]);
             let mut value = BitArray::new([0u8; 32]);
 
-            let is_true = if ty.is_signed_int() {
+            let is_true = if ty.is_signed_numeric() {
                 BigInt::from_signed_bytes_le(l.get_signed_max_value().as_buffer())
                     <= BigInt::from_signed_bytes_le(r.get_signed_min_value().as_buffer())
             } else {
@@ -648,7 +651,7 @@ fn less_equal_values(
                 value.set(0, true);
             } else {
                 // maybe the comparison is always false
-                let is_false = if ty.is_signed_int() {
+                let is_false = if ty.is_signed_numeric() {
                     BigInt::from_signed_bytes_le(l.get_signed_min_value().as_buffer())
                         > BigInt::from_signed_bytes_le(r.get_signed_max_value().as_buffer())
                 } else {
@@ -686,17 +689,10 @@ fn equal_values(
             let mut known_bits = BitArray::new([0u8; 32]);
             let mut value = BitArray::new([0u8; 32]);
 
-            let could_be_equal = if left_expr.ty().is_signed_int() {
-                BigInt::from_signed_bytes_le(l.get_signed_min_value().as_buffer())
-                    >= BigInt::from_signed_bytes_le(r.get_signed_max_value().as_buffer())
-                    && BigInt::from_signed_bytes_le(l.get_signed_min_value().as_buffer())
-                        <= BigInt::from_signed_bytes_le(r.get_signed_max_value().as_buffer())
-            } else {
-                BigInt::from_signed_bytes_le(l.get_unsigned_min_value().as_buffer())
-                    >= BigInt::from_signed_bytes_le(r.get_unsigned_max_value().as_buffer())
-                    && BigInt::from_signed_bytes_le(l.get_unsigned_min_value().as_buffer())
-                        <= BigInt::from_signed_bytes_le(r.get_unsigned_max_value().as_buffer())
-            };
+            // the values can only be equal if none of the bits known on both sides differ
+            let bits = std::cmp::min(l.bits, r.bits);
+            let could_be_equal =
+                ((l.value ^ r.value) & l.known_bits & r.known_bits)[0..bits].not_any();
 
             if !could_be_equal || l.all_known() && r.all_known() {
                 known_bits.set(0, true);
@@ -727,17 +723,10 @@ fn not_equal_values(
             let mut known_bits = BitArray::new([0u8; 32]);
             let mut value = BitArray::new([0u8; 32]);
 
-            let could_be_equal = if left_expr.ty().is_signed_int() {
-                BigInt::from_signed_bytes_le(l.get_signed_min_value().as_buffer())
-                    >= BigInt::from_signed_bytes_le(r.get_signed_max_value().as_buffer())
-                    && BigInt::from_signed_bytes_le(l.get_signed_min_value().as_buffer())
-                        <= BigInt::from_signed_bytes_le(r.get_signed_max_value().as_buffer())
-            } else {
-                BigInt::from_signed_bytes_le(l.get_unsigned_min_value().as_buffer())
-                    >= BigInt::from_signed_bytes_le(r.get_unsigned_max_value().as_buffer())
-                    && BigInt::from_signed_bytes_le(l.get_unsigned_min_value().as_buffer())
-                        <= BigInt::from_signed_bytes_le(r.get_unsigned_max_value().as_buffer())
-            };
+            // the values can only be equal if none of the bits known on both sides differ
+            let bits = std::cmp::min(l.bits, r.bits);
+            let could_be_equal =
+                ((l.value ^ r.value) & l.known_bits & r.known_bits)[0..bits].not_any();
 
             if !could_be_equal || l.all_known() && r.all_known() {
                 known_bits.set(0, true);
@@ -2,19 +2,25 @@
 
 mod expression_values;
 mod reaching_values;
+mod summary;
 mod tests;
 mod value;
 
 use super::cfg::{ControlFlowGraph, Instr};
+use super::vartable::Storage;
 use crate::codegen::Expression;
-use crate::sema::ast::{Namespace, Type};
+use crate::sema::ast::{Namespace, RetrieveType, Type};
+use crate::sema::Recurse;
 use bitvec::prelude::*;
 use expression_values::expression_values;
 use num_bigint::{BigInt, Sign};
 use num_traits::{One, ToPrimitive};
 use reaching_values::{reaching_values, transfer};
+use solang_parser::pt;
+use solang_parser::pt::CodeLocation;
 use std::collections::{HashMap, HashSet};
 use std::convert::TryInto;
+pub(super) use summary::propagate_call_summaries;
 use value::{is_single_constant, set_max_signed, set_max_unsigned, Value};
 
 /**
@@ -22,6 +28,8 @@ use value::{is_single_constant, set_max_signed, set_max_unsigned, Value};
 
   Currently implemented:
   - Replace 256/128 bit multiply/divide/modulo with smaller width operations
+  - Use branch conditions like "if (i < 100) { ... }" to restrict the values of i on each edge
+  - Replace local variables like loop counters with 64 bit variables, if every assignment fits
 
 */
 
@@ -66,10 +74,9 @@ use value::{is_single_constant, set_max_signed, set_max_unsigned, Value};
   bit operations again.
 
   TODO/ideas to explore
-  - In the first example above, the variable i can be replaced with a 64 bit. Check each assignment to i
-    and check if the result fits into 64 bit
-  - Conditions like "if (i < 100) { ... }" are not used to know the bounds of i
-  - The pass does not work across function calls
+  - Values are tracked as known bits, so "i < n" only tells us which high bits of i are zero
+  - Only private functions which are never called through a function pointer get the values of their
+    arguments from the call sites (see summary.rs)
   - Can we replace Expression::Power() with a cheaper one
   - Can we replace Expression::BitwiseAnd() with a cheaper one if either side fits into u64
 */
@@ -80,17 +87,486 @@ const MAX_VALUES: usize = 100;
 
 /// some information when hovering over a variable.
 pub fn strength_reduce(cfg: &mut ControlFlowGraph, ns: &mut Namespace) {
-    // reaching definitions for integer calculations
+    let facts = Facts::new(cfg, HashMap::new(), HashMap::new());
+
+    reduce(cfg, &facts, ns);
+}
+
+/// What is known about a cfg which does not follow from its own instructions
+struct Facts {
+    /// The variable which holds each function argument, see `argument_variables`
+    aliases: HashMap<usize, usize>,
+    /// The values passed for the function arguments at all the call sites
+    arguments: HashMap<usize, HashSet<Value>>,
+    /// The values which the functions called may return, by cfg number. An empty set means
+    /// nothing is known about that return value.
+    returns: HashMap<usize, Vec<HashSet<Value>>>,
+}
+
+impl Facts {
+    fn new(
+        cfg: &ControlFlowGraph,
+        arguments: HashMap<usize, HashSet<Value>>,
+        returns: HashMap<usize, Vec<HashSet<Value>>>,
+    ) -> Self {
+        Facts {
+            aliases: argument_variables(cfg),
+            arguments,
+            returns,
+        }
+    }
+}
+
+/// The values of function arguments are tracked along with the variables. Variable numbers are
+/// allocated counting up from zero, so the arguments are keyed from the top of the range.
+fn argument_key(arg_no: usize) -> usize {
+    usize::MAX - arg_no
+}
+
+/// Calculate the reaching values at the top of each block which is reachable
+fn block_values(
+    cfg: &ControlFlowGraph,
+    facts: &Facts,
+    ns: &Namespace,
+) -> HashMap<usize, Variables> {
     let mut block_vars = HashMap::new();
-    let mut vars = HashMap::new();
+    let mut vars = facts
+        .arguments
+        .iter()
+        .map(|(arg_no, set)| (argument_key(*arg_no), set.clone()))
+        .collect();
 
-    reaching_values(0, cfg, &mut vars, &mut block_vars, ns);
+    reaching_values(0, cfg, &mut vars, &mut block_vars, facts, ns);
+
+    block_vars
+}
+
+fn reduce(cfg: &mut ControlFlowGraph, facts: &Facts, ns: &mut Namespace) {
+    // reaching definitions for integer calculations
+    let block_vars = block_values(cfg, facts, ns);
+
+    // find the variables which can be narrowed before we start modifying the expressions
+    let narrow = narrow_candidates(cfg, &block_vars, facts, ns);
 
     // now we have all the reaching values for the top of each block
     // we can now step through each block and do any strength reduction where possible
     for (block_no, vars) in block_vars.into_iter() {
-        block_reduce(block_no, cfg, vars, ns);
+        block_reduce(block_no, cfg, vars, facts, ns);
+    }
+
+    if !narrow.is_empty() {
+        narrow_variables(cfg, &narrow, ns);
+    }
+}
+
+/// Find the variables which are assigned a function argument, and never assigned anything
+/// else. Branch conditions on the argument then tell us something about the variable.
+fn argument_variables(cfg: &ControlFlowGraph) -> HashMap<usize, usize> {
+    let mut assigned: HashMap<usize, Option<usize>> = HashMap::new();
+
+    for instr in cfg.blocks.iter().flat_map(|block| block.instr.iter()) {
+        let arg_no = match instr {
+            Instr::Set {
+                expr: Expression::FunctionArg(_, _, arg_no),
+                ..
+            } => Some(*arg_no),
+            _ => None,
+        };
+
+        for var_no in assigned_variables(instr) {
+            assigned
+                .entry(var_no)
+                .and_modify(|e| *e = None)
+                .or_insert(arg_no);
+        }
+    }
+
+    assigned
+        .into_iter()
+        .filter_map(|(var_no, arg_no)| arg_no.map(|arg_no| (arg_no, var_no)))
+        .collect()
+}
+
+/// The variables which are assigned by an instruction
+fn assigned_variables(instr: &Instr) -> Vec<usize> {
+    match instr {
+        Instr::Set { res, .. } => vec![*res],
+        Instr::Call { res, .. } | Instr::AbiDecode { res, .. } => res.clone(),
+        Instr::LoadStorage { res, .. }
+        | Instr::LoadImmutable { res, .. }
+        | Instr::PushStorage { res, .. }
+        | Instr::PopStorage { res: Some(res), .. }
+        | Instr::PushMemory { res, .. }
+        | Instr::PopMemory { res, .. }
+        | Instr::Constructor {
+            success: None, res, ..
+        }
+        | Instr::ExternalCall {
+            success: Some(res), ..
+        }
+        | Instr::ValueTransfer {
+            success: Some(res), ..
+        } => vec![*res],
+        Instr::Constructor {
+            success: Some(success),
+            res,
+            ..
+        } => vec![*res, *success],
+        _ => Vec::new(),
+    }
+}
+
+/// Find the integer variables wider than 64 bits, for which every assignment fits into
+/// 64 bits. Returns the narrower type for each of them.
+///
+/// Narrowing a variable which is simply assigned and then used gains nothing, since the
+/// extension would move to its uses. So, only variables which are assigned from themselves
+/// (e.g. loop induction variables) are narrowed, along with any copies of them.
+fn narrow_candidates(
+    cfg: &ControlFlowGraph,
+    block_vars: &HashMap<usize, Variables>,
+    facts: &Facts,
+    ns: &Namespace,
+) -> HashMap<usize, Type> {
+    let mut candidates: HashMap<usize, Type> = cfg
+        .vars
+        .iter()
+        .filter_map(|(var_no, var)| match (&var.ty, &var.storage) {
+            (Type::Uint(bits), Storage::Local) if *bits > 64 => Some((*var_no, Type::Uint(64))),
+            (Type::Int(bits), Storage::Local) if *bits > 64 => Some((*var_no, Type::Int(64))),
+            _ => None,
+        })
+        .collect();
+
+    // for each assignment, the variable it copies from (if any)
+    let mut assignments: HashMap<usize, Vec<Option<usize>>> = HashMap::new();
+    // all the variables used in the assignments to a variable
+    let mut uses: HashMap<usize, HashSet<usize>> = HashMap::new();
+
+    for (block_no, block) in cfg.blocks.iter().enumerate() {
+        // blocks which are never reached have no reaching values
+        let mut vars = block_vars.get(&block_no).cloned();
+
+        for instr in &block.instr {
+            match instr {
+                Instr::Set { res, expr, .. } => {
+                    if let Some(ty) = candidates.get(res) {
+                        let fits = vars.as_ref().map_or(false, |vars| {
+                            values_fit(&expression_values(expr, vars, ns), ty)
+                        });
+
+                        if !fits {
+                            candidates.remove(res);
+                        }
+
+                        let copy_from = match expr {
+                            Expression::Variable(_, _, var_no) => Some(*var_no),
+                            _ => None,
+                        };
+
+                        assignments.entry(*res).or_default().push(copy_from);
+
+                        expr.recurse(uses.entry(*res).or_default(), |expr, uses| {
+                            if let Expression::Variable(_, _, var_no) = expr {
+                                uses.insert(*var_no);
+                            }
+                            true
+                        });
+                    }
+                }
+                _ => {
+                    for var_no in assigned_variables(instr) {
+                        candidates.remove(&var_no);
+                    }
+                }
+            }
+
+            if let Some(vars) = &mut vars {
+                transfer(instr, vars, facts, ns);
+            }
+        }
+    }
+
+    // is the variable a copy of the other one
+    let is_copy_of = |var_no: &usize, src: usize| {
+        assignments
+            .get(var_no)
+            .map_or(false, |a| a.iter().all(|copy_from| *copy_from == Some(src)))
+    };
+
+    let mut narrow: HashMap<usize, Type> = candidates
+        .iter()
+        .filter(|(var_no, _)| {
+            uses.get(var_no).map_or(false, |uses| {
+                uses.iter()
+                    .any(|used| used == *var_no || is_copy_of(used, **var_no))
+            })
+        })
+        .map(|(var_no, ty)| (*var_no, ty.clone()))
+        .collect();
+
+    loop {
+        let copies: Vec<usize> = candidates
+            .keys()
+            .filter(|var_no| {
+                !narrow.contains_key(var_no)
+                    && assignments.get(var_no).map_or(false, |a| {
+                        a.iter().all(|src| {
+                            src.map_or(false, |src| narrow.get(&src) == candidates.get(var_no))
+                        })
+                    })
+            })
+            .cloned()
+            .collect();
+
+        if copies.is_empty() {
+            break narrow;
+        }
+
+        for var_no in copies {
+            narrow.insert(var_no, candidates[&var_no].clone());
+        }
+    }
+}
+
+/// Do all the values in the set fit into the given 64 bit type
+fn values_fit(values: &HashSet<Value>, ty: &Type) -> bool {
+    if values.is_empty() {
+        return false;
+    }
+
+    if ty.is_signed_numeric() {
+        values.iter().all(|v| {
+            v.sign().0
+                && BigInt::from_signed_bytes_le(v.get_signed_min_value().as_buffer())
+                    .to_i64()
+                    .is_some()
+                && BigInt::from_signed_bytes_le(v.get_signed_max_value().as_buffer())
+                    .to_i64()
+                    .is_some()
+        })
+    } else {
+        set_max_unsigned(values).to_u64().is_some()
+    }
+}
+
+/// Change the type of variables which always fit into 64 bits. Every assignment is truncated
+/// and every use is extended again, so the types of the expressions do not change.
+fn narrow_variables(cfg: &mut ControlFlowGraph, narrow: &HashMap<usize, Type>, ns: &mut Namespace) {
+    for (var_no, ty) in narrow {
+        let var = &mut cfg.vars[var_no];
+
+        if let pt::Loc::File(..) = var.id.loc {
+            ns.hover_overrides.insert(
+                var.id.loc,
+                format!(
+                    "{} variable narrowed to {}",
+                    var.ty.to_string(ns),
+                    ty.to_string(ns)
+                ),
+            );
+        }
+
+        var.ty = ty.clone();
+    }
+
+    for block in &mut cfg.blocks {
+        for instr in &mut block.instr {
+            narrow_instr(instr, narrow);
+        }
+    }
+}
+
+/// Update all the expressions in an instruction for the narrowed variables
+fn narrow_instr(instr: &mut Instr, narrow: &HashMap<usize, Type>) {
+    instr.update_expressions(|expr| *expr = narrow_expression(expr, &mut &*narrow));
+
+    if let Instr::Set { res, expr, .. } = instr {
+        if let Some(ty) = narrow.get(res) {
+            *expr = narrow_value(expr, ty);
+        }
+    }
+}
+
+/// Replace the uses of narrowed variables with an extension of the narrowed variable, and
+/// do comparisons with narrowed variables in the narrower type where possible
+fn narrow_expression(expr: &Expression, narrow: &mut &HashMap<usize, Type>) -> Expression {
+    match expr {
+        Expression::Variable(loc, ty, var_no) => match narrow.get(var_no) {
+            Some(narrow_ty) if ty != narrow_ty => {
+                let var = Box::new(Expression::Variable(*loc, narrow_ty.clone(), *var_no));
+
+                if narrow_ty.is_signed_numeric() {
+                    Expression::SignExt(*loc, ty.clone(), var)
+                } else {
+                    Expression::ZeroExt(*loc, ty.clone(), var)
+                }
+            }
+            _ => expr.clone(),
+        },
+        Expression::Trunc(loc, ty, inner) if narrowed_variable(inner, narrow).is_some() => {
+            let (var_no, narrow_ty) = narrowed_variable(inner, narrow).unwrap();
+            let var = Expression::Variable(*loc, narrow_ty.clone(), var_no);
+
+            match ty {
+                _ if ty == narrow_ty => var,
+                Type::Uint(bits) | Type::Int(bits) if *bits < 64 => {
+                    Expression::Trunc(*loc, ty.clone(), Box::new(var))
+                }
+                _ => expr.copy_filter(narrow, narrow_expression),
+            }
+        }
+        _ if uses_narrowed(expr, narrow) => {
+            let expr = expr.copy_filter(narrow, narrow_expression);
+
+            narrow_comparison(&expr).unwrap_or(expr)
+        }
+        _ => expr.clone(),
+    }
+}
+
+/// If the expression is a variable which is narrowed, but still has the wider type, return the
+/// variable number and the narrower type
+fn narrowed_variable<'a>(
+    expr: &Expression,
+    narrow: &'a HashMap<usize, Type>,
+) -> Option<(usize, &'a Type)> {
+    match expr {
+        Expression::Variable(_, ty, var_no) => match narrow.get(var_no) {
+            Some(narrow_ty) if ty != narrow_ty => Some((*var_no, narrow_ty)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Does the expression use any narrowed variable with its wider type
+fn uses_narrowed(expr: &Expression, narrow: &HashMap<usize, Type>) -> bool {
+    let mut cx = (narrow, false);
+
+    expr.recurse(&mut cx, |expr, cx| {
+        if narrowed_variable(expr, cx.0).is_some() {
+            cx.1 = true;
+        }
+
+        !cx.1
+    });
+
+    cx.1
+}
+
+/// If both sides of a comparison fit into 64 bits, do the comparison with 64 bits
+fn narrow_comparison(expr: &Expression) -> Option<Expression> {
+    let (left, right) = match expr {
+        Expression::UnsignedLess(_, left, right)
+        | Expression::SignedLess(_, left, right)
+        | Expression::UnsignedMore(_, left, right)
+        | Expression::SignedMore(_, left, right)
+        | Expression::LessEqual(_, left, right)
+        | Expression::MoreEqual(_, left, right)
+        | Expression::Equal(_, left, right)
+        | Expression::NotEqual(_, left, right) => (left, right),
+        _ => return None,
+    };
+
+    let ty = match left.ty() {
+        Type::Uint(bits) if bits > 64 => Type::Uint(64),
+        Type::Int(bits) if bits > 64 => Type::Int(64),
+        _ => return None,
+    };
+
+    // nothing to gain if neither side is extended
+    if !matches!(
+        left.as_ref(),
+        Expression::ZeroExt(..) | Expression::SignExt(..)
+    ) && !matches!(
+        right.as_ref(),
+        Expression::ZeroExt(..) | Expression::SignExt(..)
+    ) {
+        return None;
     }
+
+    let left = Box::new(narrow_operand(left, &ty)?);
+    let right = Box::new(narrow_operand(right, &ty)?);
+
+    Some(match expr {
+        Expression::UnsignedLess(loc, ..) => Expression::UnsignedLess(*loc, left, right),
+        Expression::SignedLess(loc, ..) => Expression::SignedLess(*loc, left, right),
+        Expression::UnsignedMore(loc, ..) => Expression::UnsignedMore(*loc, left, right),
+        Expression::SignedMore(loc, ..) => Expression::SignedMore(*loc, left, right),
+        Expression::LessEqual(loc, ..) => Expression::LessEqual(*loc, left, right),
+        Expression::MoreEqual(loc, ..) => Expression::MoreEqual(*loc, left, right),
+        Expression::Equal(loc, ..) => Expression::Equal(*loc, left, right),
+        Expression::NotEqual(loc, ..) => Expression::NotEqual(*loc, left, right),
+        _ => unreachable!(),
+    })
+}
+
+/// If the expression is an extended 64 bit value or a literal which fits, return it with
+/// the 64 bit type
+fn narrow_operand(expr: &Expression, ty: &Type) -> Option<Expression> {
+    match expr {
+        Expression::ZeroExt(_, _, inner) if !ty.is_signed_numeric() && inner.ty() == *ty => {
+            Some(inner.as_ref().clone())
+        }
+        Expression::SignExt(_, _, inner) if ty.is_signed_numeric() && inner.ty() == *ty => {
+            Some(inner.as_ref().clone())
+        }
+        Expression::ZeroExt(loc, _, inner) if !ty.is_signed_numeric() => match inner.ty() {
+            Type::Uint(bits) if bits < 64 => {
+                Some(Expression::ZeroExt(*loc, ty.clone(), inner.clone()))
+            }
+            _ => None,
+        },
+        Expression::SignExt(loc, _, inner) if ty.is_signed_numeric() => match inner.ty() {
+            Type::Int(bits) if bits < 64 => {
+                Some(Expression::SignExt(*loc, ty.clone(), inner.clone()))
+            }
+            _ => None,
+        },
+        Expression::NumberLiteral(loc, _, n)
+            if (ty.is_signed_numeric() && n.to_i64().is_some())
+                || (!ty.is_signed_numeric() && n.to_u64().is_some()) =>
+        {
+            Some(Expression::NumberLiteral(*loc, ty.clone(), n.clone()))
+        }
+        Expression::Undefined(_) => Some(Expression::Undefined(ty.clone())),
+        _ => None,
+    }
+}
+
+/// Convert the value assigned to a narrowed variable to the narrower type. We know that the
+/// result fits, so if the operands fit as well the arithmetic can be done in the narrower type
+/// without changing the overflow behaviour.
+fn narrow_value(expr: &Expression, ty: &Type) -> Expression {
+    if let Some(expr) = narrow_operand(expr, ty) {
+        return expr;
+    }
+
+    match expr {
+        Expression::Add(loc, _, unchecked, left, right)
+        | Expression::Subtract(loc, _, unchecked, left, right)
+        | Expression::Multiply(loc, _, unchecked, left, right) => {
+            if let (Some(left), Some(right)) = (narrow_operand(left, ty), narrow_operand(right, ty))
+            {
+                let left = Box::new(left);
+                let right = Box::new(right);
+
+                return match expr {
+                    Expression::Add(..) => {
+                        Expression::Add(*loc, ty.clone(), *unchecked, left, right)
+                    }
+                    Expression::Subtract(..) => {
+                        Expression::Subtract(*loc, ty.clone(), *unchecked, left, right)
+                    }
+                    _ => Expression::Multiply(*loc, ty.clone(), *unchecked, left, right),
+                };
+            }
+        }
+        _ => (),
+    }
+
+    Expression::Trunc(expr.loc(), ty.clone(), Box::new(expr.clone()))
 }
 
 /// Walk through all the expressions in a block, and find any expressions which can be
@@ -99,6 +575,7 @@ fn block_reduce(
     block_no: usize,
     cfg: &mut ControlFlowGraph,
     mut vars: Variables,
+    facts: &Facts,
     ns: &mut Namespace,
 ) {
     for instr in &mut cfg.blocks[block_no].instr {
@@ -135,6 +612,9 @@ fn block_reduce(
                 *value = expression_reduce(value, &vars, ns);
                 *storage = expression_reduce(storage, &vars, ns);
             }
+            Instr::SetImmutable { value, .. } => {
+                *value = expression_reduce(value, &vars, ns);
+            }
             Instr::SetStorageBytes {
                 storage,
                 value,
@@ -211,7 +691,7 @@ fn block_reduce(
             _ => (),
         }
 
-        transfer(instr, &mut vars, ns);
+        transfer(instr, &mut vars, facts, ns);
     }
 }
 
@@ -255,7 +735,7 @@ fn expression_reduce(expr: &Expression, vars: &Variables, ns: &mut Namespace) ->
                         }
                     }
 
-                    if ty.is_signed_int() {
+                    if ty.is_signed_numeric() {
                         if let (Some(left_max), Some(right_max)) =
                             (set_max_signed(&left_values), set_max_signed(&right_values))
                         {
@@ -343,7 +823,7 @@ fn expression_reduce(expr: &Expression, vars: &Variables, ns: &mut Namespace) ->
                                     ty.clone(),
                                     left.clone(),
                                     Box::new(Expression::NumberLiteral(*loc, ty.clone(), shift)),
-                                    ty.is_signed_int(),
+                                    ty.is_signed_numeric(),
                                 );
                             }
 
@@ -352,7 +832,7 @@ fn expression_reduce(expr: &Expression, vars: &Variables, ns: &mut Namespace) ->
                         }
                     }
 
-                    if ty.is_signed_int() {
+                    if ty.is_signed_numeric() {
                         if let (Some(left_max), Some(right_max)) =
                             (set_max_signed(&left_values), set_max_signed(&right_values))
                         {
@@ -441,7 +921,7 @@ fn expression_reduce(expr: &Expression, vars: &Variables, ns: &mut Namespace) ->
                         }
                     }
 
-                    if ty.is_signed_int() {
+                    if ty.is_signed_numeric() {
                         if let (Some(left_max), Some(right_max)) =
                             (set_max_signed(&left_values), set_max_signed(&right_values))
                         {
@@ -500,11 +980,20 @@ fn expression_reduce(expr: &Expression, vars: &Variables, ns: &mut Namespace) ->
     expr.copy_filter(ns, filter)
 }
 
-/// This optimization pass only tracks bools and integers variables.
+/// This optimization pass only tracks bools and integers variables. Fixed point
+/// values are integers in codegen, so they are tracked too.
 /// Other types (e.g. bytes) is not relevant for strength reduce. Bools are only
 /// tracked so we can following branching after integer compare.
 fn track(ty: &Type) -> bool {
-    matches!(ty, Type::Uint(_) | Type::Int(_) | Type::Bool | Type::Value)
+    matches!(
+        ty,
+        Type::Uint(_)
+            | Type::Int(_)
+            | Type::Fixed(..)
+            | Type::Ufixed(..)
+            | Type::Bool
+            | Type::Value
+    )
 }
 
 // A variable can
@@ -1,10 +1,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::expression_values::expression_values;
-use super::value::Value;
-use super::{track, Variables, MAX_VALUES};
-use crate::codegen::cfg::{ControlFlowGraph, Instr};
-use crate::sema::ast::Namespace;
+use super::value::{is_single_constant, set_join, set_max_unsigned, set_min_unsigned, Value};
+use super::{track, Facts, Variables, MAX_VALUES};
+use crate::codegen::cfg::{ControlFlowGraph, Instr, InternalCallTy};
+use crate::codegen::Expression;
+use crate::sema::ast::{Namespace, RetrieveType};
+use bitvec::prelude::BitArray;
+use num_traits::Zero;
 use std::collections::{HashMap, HashSet};
 
 /// Step through a block, and calculate the reaching values for all the variables
@@ -13,6 +16,7 @@ pub(super) fn reaching_values(
     cfg: &ControlFlowGraph,
     vars: &mut Variables,
     block_vars: &mut HashMap<usize, Variables>,
+    facts: &Facts,
     ns: &Namespace,
 ) {
     // We should merge the incoming set of variables with the existing ones. If there
@@ -24,7 +28,7 @@ pub(super) fn reaching_values(
     if let Some(map) = block_vars.get_mut(&block_no) {
         let mut changes = false;
 
-        for (var_no, set) in vars.iter() {
+        for (var_no, set) in vars.iter_mut() {
             changes |= update_map(var_no, set, map);
         }
 
@@ -37,12 +41,12 @@ pub(super) fn reaching_values(
     }
 
     for instr in &cfg.blocks[block_no].instr {
-        transfer(instr, vars, ns);
+        transfer(instr, vars, facts, ns);
 
         match instr {
             Instr::Branch { block } => {
                 // must be last in the block
-                reaching_values(*block, cfg, vars, block_vars, ns);
+                reaching_values(*block, cfg, vars, block_vars, facts, ns);
             }
             Instr::BranchCond {
                 cond,
@@ -66,6 +70,7 @@ pub(super) fn reaching_values(
                             cfg,
                             vars,
                             block_vars,
+                            facts,
                             ns,
                         );
 
@@ -73,12 +78,27 @@ pub(super) fn reaching_values(
                     }
                 }
 
-                // we don't know the value of the condition. Follow both paths
+                // we don't know the value of the condition. Follow both paths, and use the
+                // condition to refine the values on each path
                 let mut vars_copy = vars.clone();
 
-                reaching_values(*true_block, cfg, &mut vars_copy, block_vars, ns);
+                refine_condition(cond, true, &mut vars_copy, &facts.aliases, ns);
 
-                reaching_values(*false_block, cfg, vars, block_vars, ns);
+                reaching_values(*true_block, cfg, &mut vars_copy, block_vars, facts, ns);
+
+                refine_condition(cond, false, vars, &facts.aliases, ns);
+
+                reaching_values(*false_block, cfg, vars, block_vars, facts, ns);
+            }
+            Instr::Switch { cases, default, .. } => {
+                // must be last in the block
+                for (_, block) in cases {
+                    let mut vars_copy = vars.clone();
+
+                    reaching_values(*block, cfg, &mut vars_copy, block_vars, facts, ns);
+                }
+
+                reaching_values(*default, cfg, vars, block_vars, facts, ns);
             }
             Instr::AbiDecode {
                 exception_block: Some(block),
@@ -86,7 +106,7 @@ pub(super) fn reaching_values(
             } => {
                 let mut vars = vars.clone();
 
-                reaching_values(*block, cfg, &mut vars, block_vars, ns);
+                reaching_values(*block, cfg, &mut vars, block_vars, facts, ns);
             }
 
             _ => (),
@@ -95,63 +115,382 @@ pub(super) fn reaching_values(
 }
 
 /// Update the Variable's map based on the incoming set of values. Returns true if there was any
-/// changes in the set.
+/// changes in the set. If the set in the map had to be widened, the incoming set is replaced with
+/// the widened set, so that the widened values are propagated further.
 /// There is a discussion to improve this function: https://github.com/hyperledger-labs/solang/issues/934
-fn update_map(var_no: &usize, set: &HashSet<Value>, map: &mut Variables) -> bool {
+fn update_map(var_no: &usize, set: &mut HashSet<Value>, map: &mut Variables) -> bool {
     return if let Some(existing) = map.get_mut(var_no) {
         if existing.iter().next().map_or(false, |v| v.all_unknown()) {
             // If we already think it is unknown, nothing can improve on that
+            *set = existing.clone();
             false
         } else if let Some(v) = set.iter().find(|v| v.all_unknown()) {
             // If we are merging an unknown value, set the entire value set to unknown
-            let mut set = HashSet::new();
+            let mut unknown = HashSet::new();
 
-            set.insert(v.clone());
+            unknown.insert(v.clone());
 
-            map.insert(*var_no, set);
+            *set = unknown.clone();
+            map.insert(*var_no, unknown);
             true
         } else {
             let mut changes = false;
-            for v in set {
-                if !existing.contains(v) {
-                    existing.insert(v.clone());
-                    changes = true;
+            // Only the values which are not fully known can cover other values; there are
+            // few of these, so avoid comparing every pair of values
+            let mut partial: Option<Vec<Value>> = None;
+
+            for v in set.iter() {
+                if existing.contains(v) {
+                    continue;
+                }
+
+                let partial = partial.get_or_insert_with(|| {
+                    existing
+                        .iter()
+                        .filter(|e| !e.all_known())
+                        .cloned()
+                        .collect()
+                });
+
+                if partial.iter().any(|e| e.covers(v)) {
+                    continue;
                 }
+
+                if v.bits > 64
+                    && v.get_unsigned_max_value()[64..v.bits].any()
+                    && partial.iter().any(|e| v.covers(e))
+                {
+                    // The set was widened before and is still growing, e.g. a loop counter
+                    // without a bound. Nothing can be reduced once the values do not fit
+                    // into 64 bits, and it would take a step for every bit to converge
+                    let mut unknown = HashSet::new();
+
+                    unknown.insert(Value::unknown(v.bits));
+
+                    *set = unknown.clone();
+                    map.insert(*var_no, unknown);
+                    return true;
+                }
+
+                existing.insert(v.clone());
+                changes = true;
             }
 
             if existing.len() > MAX_VALUES {
-                let bits = existing.iter().next().unwrap().bits;
+                // Too many values; keep only the bits which all the values agree on
+                let mut widened = HashSet::new();
 
-                let mut set = HashSet::new();
-
-                set.insert(Value::unknown(bits));
+                widened.insert(set_join(existing));
 
                 changes = true;
-                map.insert(*var_no, set);
+                *set = widened.clone();
+                map.insert(*var_no, widened);
             }
             changes
         }
     } else {
         // We have no existing set. Create one but folding unknown
 
-        if set.len() > MAX_VALUES || set.iter().any(|v| v.all_unknown()) {
-            let bits = set.iter().next().unwrap().bits;
+        if let Some(v) = set.iter().find(|v| v.all_unknown()) {
+            let mut unknown = HashSet::new();
 
-            let mut set = HashSet::new();
+            unknown.insert(v.clone());
 
-            set.insert(Value::unknown(bits));
+            *set = unknown;
+        } else if set.len() > MAX_VALUES {
+            let mut widened = HashSet::new();
 
-            map.insert(*var_no, set);
-        } else {
-            map.insert(*var_no, set.clone());
+            widened.insert(set_join(set));
+
+            *set = widened;
         }
 
+        map.insert(*var_no, set.clone());
+
         true
     };
 }
 
+/// Comparison of a variable against some other value. For example, the edge where
+/// "i < 100" is true is the edge where "100 > i" is true, and "i >= 100" is false.
+#[derive(Clone, Copy)]
+enum Compare {
+    Less,
+    LessEqual,
+    More,
+    MoreEqual,
+}
+
+impl Compare {
+    /// The comparison with the operands swapped
+    fn swap(self) -> Compare {
+        match self {
+            Compare::Less => Compare::More,
+            Compare::LessEqual => Compare::MoreEqual,
+            Compare::More => Compare::Less,
+            Compare::MoreEqual => Compare::LessEqual,
+        }
+    }
+
+    /// The comparison which is true when this one is false
+    fn negate(self) -> Compare {
+        match self {
+            Compare::Less => Compare::MoreEqual,
+            Compare::LessEqual => Compare::More,
+            Compare::More => Compare::LessEqual,
+            Compare::MoreEqual => Compare::Less,
+        }
+    }
+}
+
+/// Refine the reaching values of the variables in a branch condition, for the edge where
+/// the condition evaluates to `taken`. If the refined set would be empty, the edge is not
+/// possible with the values we have; in that case the set is left as is.
+///
+/// Constant folding replaces function parameters with the function argument, so
+/// `aliases` maps the argument number to the variable which holds it.
+pub(super) fn refine_condition(
+    cond: &Expression,
+    taken: bool,
+    vars: &mut Variables,
+    aliases: &HashMap<usize, usize>,
+    ns: &Namespace,
+) {
+    let (left, right, cmp, signed) = match cond {
+        Expression::Not(_, expr) => {
+            refine_condition(expr, !taken, vars, aliases, ns);
+            return;
+        }
+        Expression::Equal(_, left, right) => {
+            refine_equal(left, right, taken, vars, aliases, ns);
+            return;
+        }
+        Expression::NotEqual(_, left, right) => {
+            refine_equal(left, right, !taken, vars, aliases, ns);
+            return;
+        }
+        Expression::UnsignedLess(_, left, right) => (left, right, Compare::Less, false),
+        Expression::SignedLess(_, left, right) => (left, right, Compare::Less, true),
+        Expression::UnsignedMore(_, left, right) => (left, right, Compare::More, false),
+        Expression::SignedMore(_, left, right) => (left, right, Compare::More, true),
+        Expression::LessEqual(_, left, right) => (
+            left,
+            right,
+            Compare::LessEqual,
+            left.ty().is_signed_numeric(),
+        ),
+        Expression::MoreEqual(_, left, right) => (
+            left,
+            right,
+            Compare::MoreEqual,
+            left.ty().is_signed_numeric(),
+        ),
+        _ => return,
+    };
+
+    let cmp = if taken { cmp } else { cmp.negate() };
+
+    let left_values = expression_values(left, vars, ns);
+    let right_values = expression_values(right, vars, ns);
+
+    if let Some(var_no) = refined_variable(left, aliases) {
+        refine_compare(var_no, cmp, &right_values, signed, vars);
+    }
+
+    if let Some(var_no) = refined_variable(right, aliases) {
+        refine_compare(var_no, cmp.swap(), &left_values, signed, vars);
+    }
+}
+
+/// The variable whose values can be refined if the expression is compared
+fn refined_variable(expr: &Expression, aliases: &HashMap<usize, usize>) -> Option<usize> {
+    match expr {
+        Expression::Variable(_, _, var_no) => Some(*var_no),
+        Expression::FunctionArg(_, _, arg_no) => aliases.get(arg_no).cloned(),
+        _ => None,
+    }
+}
+
+/// Restrict the values of a variable given that "var <cmp> other" holds
+fn refine_compare(
+    var_no: usize,
+    cmp: Compare,
+    other: &HashSet<Value>,
+    signed: bool,
+    vars: &mut Variables,
+) {
+    if other.is_empty() {
+        return;
+    }
+
+    let set = match vars.get(&var_no) {
+        Some(set) => set,
+        None => return,
+    };
+
+    let refined: HashSet<Value> = if signed {
+        // only bother with signed constants
+        let bound = match is_single_constant(other) {
+            Some(bound) => bound,
+            None => return,
+        };
+
+        match cmp {
+            Compare::Less => set
+                .iter()
+                .filter_map(|v| v.signed_upper_bound(&(bound.clone() - 1)))
+                .collect(),
+            Compare::LessEqual => set
+                .iter()
+                .filter_map(|v| v.signed_upper_bound(&bound))
+                .collect(),
+            Compare::More => set
+                .iter()
+                .filter_map(|v| v.signed_lower_bound(&(bound.clone() + 1)))
+                .collect(),
+            Compare::MoreEqual => set
+                .iter()
+                .filter_map(|v| v.signed_lower_bound(&bound))
+                .collect(),
+        }
+    } else {
+        match cmp {
+            Compare::Less => {
+                let bound = set_max_unsigned(other);
+
+                if bound.is_zero() {
+                    return;
+                }
+
+                set.iter()
+                    .filter_map(|v| v.unsigned_upper_bound(&(bound.clone() - 1)))
+                    .collect()
+            }
+            Compare::LessEqual => {
+                let bound = set_max_unsigned(other);
+
+                set.iter()
+                    .filter_map(|v| v.unsigned_upper_bound(&bound))
+                    .collect()
+            }
+            Compare::More => {
+                let bound = set_min_unsigned(other) + 1;
+
+                set.iter()
+                    .filter_map(|v| v.unsigned_lower_bound(&bound))
+                    .collect()
+            }
+            Compare::MoreEqual => {
+                let bound = set_min_unsigned(other);
+
+                set.iter()
+                    .filter_map(|v| v.unsigned_lower_bound(&bound))
+                    .collect()
+            }
+        }
+    };
+
+    if !refined.is_empty() {
+        vars.insert(var_no, refined);
+    }
+}
+
+/// Restrict the values of a variable given that "var == constant" or "(var & mask) == constant"
+/// is `equal`.
+fn refine_equal(
+    left: &Expression,
+    right: &Expression,
+    equal: bool,
+    vars: &mut Variables,
+    aliases: &HashMap<usize, usize>,
+    ns: &Namespace,
+) {
+    let (expr, constant) = match (
+        single_known_value(left, vars, ns),
+        single_known_value(right, vars, ns),
+    ) {
+        (None, Some(constant)) => (left, constant),
+        (Some(constant), None) => (right, constant),
+        _ => return,
+    };
+
+    let bits = constant.bits;
+
+    let (var_no, mask) = match expr {
+        Expression::BitwiseAnd(_, _, left, right) if equal => {
+            match (
+                refined_variable(left, aliases),
+                single_known_value(right, vars, ns),
+            ) {
+                (Some(var_no), Some(mask)) => (var_no, mask.value),
+                _ => match (
+                    refined_variable(right, aliases),
+                    single_known_value(left, vars, ns),
+                ) {
+                    (Some(var_no), Some(mask)) => (var_no, mask.value),
+                    _ => return,
+                },
+            }
+        }
+        _ => match refined_variable(expr, aliases) {
+            Some(var_no) => {
+                let mut mask = BitArray::new([0u8; 32]);
+                mask[0..bits].set_all(true);
+
+                (var_no, mask)
+            }
+            None => return,
+        },
+    };
+
+    let set = match vars.get(&var_no) {
+        Some(set) if set.iter().all(|v| v.bits == bits) => set,
+        _ => return,
+    };
+
+    let refined: HashSet<Value> = if equal {
+        // the masked bits of the variable are known now
+        set.iter()
+            .filter(|v| ((v.value ^ constant.value) & v.known_bits & mask)[0..bits].not_any())
+            .map(|v| {
+                let mut v = v.clone();
+
+                v.known_bits |= mask;
+                v.value = (v.value & !mask) | (constant.value & mask);
+
+                v
+            })
+            .collect()
+    } else {
+        // the variable can not be this constant
+        set.iter()
+            .filter(|v| !(v.all_known() && v.value[0..bits] == constant.value[0..bits]))
+            .cloned()
+            .collect()
+    };
+
+    if !refined.is_empty() {
+        vars.insert(var_no, refined);
+    }
+}
+
+/// If the expression evaluates to a single constant, return its value
+fn single_known_value(expr: &Expression, vars: &Variables, ns: &Namespace) -> Option<Value> {
+    let set = expression_values(expr, vars, ns);
+
+    if set.len() == 1 {
+        let v = set.into_iter().next().unwrap();
+
+        if v.all_known() {
+            return Some(v);
+        }
+    }
+
+    None
+}
+
 /// For a given instruction, calculate the new reaching values
-pub(super) fn transfer(instr: &Instr, vars: &mut Variables, ns: &Namespace) {
+pub(super) fn transfer(instr: &Instr, vars: &mut Variables, facts: &Facts, ns: &Namespace) {
     match instr {
         Instr::Set { res, expr, .. } => {
             let v = expression_values(expr, vars, ns);
@@ -174,17 +513,31 @@ pub(super) fn transfer(instr: &Instr, vars: &mut Variables, ns: &Namespace) {
             }
         }
         Instr::Call {
-            res, return_tys, ..
+            res,
+            return_tys,
+            call,
+            ..
         } => {
-            for (i, var_no) in res.iter().enumerate() {
-                let mut set = HashSet::new();
+            // do we know anything about the values the function returns
+            let returns = match call {
+                InternalCallTy::Static { cfg_no } => facts.returns.get(cfg_no),
+                _ => None,
+            };
 
+            for (i, var_no) in res.iter().enumerate() {
                 let ty = &return_tys[i];
 
                 if track(ty) {
-                    let bits = ty.bits(ns) as usize;
+                    let set = match returns {
+                        Some(returns) if !returns[i].is_empty() => returns[i].clone(),
+                        _ => {
+                            let mut set = HashSet::new();
 
-                    set.insert(Value::unknown(bits));
+                            set.insert(Value::unknown(ty.bits(ns) as usize));
+
+                            set
+                        }
+                    };
 
                     vars.insert(*var_no, set);
                 }
@@ -0,0 +1,356 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::expression_values::expression_values;
+use super::reaching_values::transfer;
+use super::value::{is_single_constant, set_join, Value};
+use super::{block_values, reduce, Facts, MAX_VALUES};
+use crate::codegen::cfg::{ControlFlowGraph, Instr, InternalCallTy};
+use crate::codegen::{constant_folding, reaching_definitions, Expression, Options};
+use crate::sema::ast::{Namespace, Type};
+use crate::sema::Recurse;
+use num_bigint::{BigInt, Sign};
+use num_traits::{One, Zero};
+use solang_parser::pt;
+use std::collections::{HashMap, HashSet};
+
+/*
+  Constant folding and strength reduction stop at function calls: nothing is known about the
+  arguments of a function, or about the values returned by the functions it calls. However, a
+  private function can only be called from within the contract, so if we look at all the cfgs
+  of the contract we know all of its call sites.
+
+  For every private function which is only called directly (i.e. never through a function
+  pointer), collect the values of the arguments at all of its call sites, and the values it can
+  return. Then:
+  - If an argument has the same constant value at all the call sites, replace the argument with
+    the constant in the function and run constant folding again. Any errors were already reported
+    when the function was folded the first time; the new ones may be on paths which are never
+    taken, so they are not reported.
+  - Use the values of the arguments and the values returned by the functions called for strength
+    reduction of the function
+
+  This is done once; the values at the call sites are the values before any of the functions
+  are optimized again.
+*/
+
+/// For each argument or return value, the values from all the call sites or returns. None means
+/// nothing is known.
+type Summary = Vec<Option<HashSet<Value>>>;
+
+/// Propagate the values of arguments and return values of private functions over all the cfgs
+/// of a contract
+pub(crate) fn propagate_call_summaries(
+    cfgs: &mut [ControlFlowGraph],
+    ns: &mut Namespace,
+    opt: &Options,
+) {
+    let callees = direct_callees(cfgs);
+
+    if callees.is_empty() {
+        return;
+    }
+
+    let mut arguments: HashMap<usize, Summary> = HashMap::new();
+
+    for cfg in cfgs.iter() {
+        call_site_values(cfg, &callees, &mut arguments, ns);
+    }
+
+    let arguments: HashMap<usize, HashMap<usize, HashSet<Value>>> = arguments
+        .into_iter()
+        .map(|(cfg_no, summary)| (cfg_no, known_values(summary)))
+        .collect();
+
+    let returns: HashMap<usize, Vec<HashSet<Value>>> = callees
+        .iter()
+        .filter_map(|cfg_no| {
+            let facts = Facts::new(
+                &cfgs[*cfg_no],
+                arguments.get(cfg_no).cloned().unwrap_or_default(),
+                HashMap::new(),
+            );
+
+            let summary = return_values(&cfgs[*cfg_no], &facts, ns)?;
+
+            if summary.iter().all(|set| set.is_none()) {
+                return None;
+            }
+
+            let returns = summary
+                .into_iter()
+                .map(|set| set.unwrap_or_default())
+                .collect();
+
+            Some((*cfg_no, returns))
+        })
+        .collect();
+
+    for (cfg_no, cfg) in cfgs.iter_mut().enumerate() {
+        let arguments = arguments.get(&cfg_no).cloned().unwrap_or_default();
+
+        let returns: HashMap<usize, Vec<HashSet<Value>>> = called(cfg)
+            .into_iter()
+            .filter_map(|callee| returns.get(&callee).map(|values| (callee, values.clone())))
+            .collect();
+
+        if arguments.is_empty() && returns.is_empty() {
+            continue;
+        }
+
+        if opt.constant_folding {
+            let constants: HashMap<usize, Expression> = arguments
+                .iter()
+                .filter_map(|(arg_no, set)| {
+                    constant_argument(set, &cfg.params[*arg_no].ty)
+                        .map(|constant| (*arg_no, constant))
+                })
+                .collect();
+
+            if !constants.is_empty() {
+                for instr in cfg.blocks.iter_mut().flat_map(|b| b.instr.iter_mut()) {
+                    instr.update_expressions(|expr| {
+                        *expr = replace_arguments(expr, &mut &constants);
+                    });
+                }
+
+                reaching_definitions::find(cfg);
+
+                // the constants may reach paths which are never taken with those values,
+                // e.g. a division guarded by a check for zero, so do not report anything
+                let diagnostics = std::mem::take(&mut ns.diagnostics);
+                constant_folding::constant_folding(cfg, ns);
+                ns.diagnostics = diagnostics;
+            }
+        }
+
+        if opt.strength_reduce {
+            let facts = Facts::new(cfg, arguments, returns);
+
+            reduce(cfg, &facts, ns);
+        }
+    }
+}
+
+/// The private functions which are only ever called directly, so that all their call sites
+/// are known
+fn direct_callees(cfgs: &[ControlFlowGraph]) -> HashSet<usize> {
+    let mut called = HashSet::new();
+    let mut referenced = HashSet::new();
+
+    for instr in cfgs
+        .iter()
+        .flat_map(|cfg| cfg.blocks.iter())
+        .flat_map(|block| block.instr.iter())
+    {
+        match instr {
+            Instr::Call {
+                call: InternalCallTy::Static { cfg_no },
+                ..
+            } => {
+                called.insert(*cfg_no);
+            }
+            Instr::Call {
+                call: InternalCallTy::Dynamic(expr),
+                ..
+            } => {
+                expr.recurse(&mut referenced, function_references);
+            }
+            _ => (),
+        }
+
+        instr.recurse_expressions(&mut referenced, function_references);
+    }
+
+    called
+        .into_iter()
+        .filter(|cfg_no| {
+            let cfg = &cfgs[*cfg_no];
+
+            !referenced.contains(cfg_no)
+                && !cfg.public
+                && !cfg.is_placeholder()
+                && cfg.ty == pt::FunctionTy::Function
+        })
+        .collect()
+}
+
+fn function_references(expr: &Expression, referenced: &mut HashSet<usize>) -> bool {
+    if let Expression::InternalFunctionCfg(cfg_no) = expr {
+        referenced.insert(*cfg_no);
+    }
+
+    true
+}
+
+/// The functions which are called directly from a cfg
+fn called(cfg: &ControlFlowGraph) -> HashSet<usize> {
+    cfg.blocks
+        .iter()
+        .flat_map(|block| block.instr.iter())
+        .filter_map(|instr| match instr {
+            Instr::Call {
+                call: InternalCallTy::Static { cfg_no },
+                ..
+            } => Some(*cfg_no),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Merge the values of the arguments at the call sites in this cfg into the summaries
+fn call_site_values(
+    cfg: &ControlFlowGraph,
+    callees: &HashSet<usize>,
+    summaries: &mut HashMap<usize, Summary>,
+    ns: &Namespace,
+) {
+    if cfg.is_placeholder() || !called(cfg).iter().any(|c| callees.contains(c)) {
+        return;
+    }
+
+    let facts = Facts::new(cfg, HashMap::new(), HashMap::new());
+
+    for (block_no, mut vars) in block_values(cfg, &facts, ns) {
+        for instr in &cfg.blocks[block_no].instr {
+            if let Instr::Call {
+                call: InternalCallTy::Static { cfg_no },
+                args,
+                ..
+            } = instr
+            {
+                if callees.contains(cfg_no) {
+                    let values = args
+                        .iter()
+                        .map(|arg| expression_values(arg, &vars, ns))
+                        .collect();
+
+                    merge_summary(summaries, *cfg_no, values);
+                }
+            }
+
+            transfer(instr, &mut vars, &facts, ns);
+        }
+    }
+}
+
+/// The values a function can return, if it returns at all
+fn return_values(cfg: &ControlFlowGraph, facts: &Facts, ns: &Namespace) -> Option<Summary> {
+    let mut summaries = HashMap::new();
+
+    for (block_no, mut vars) in block_values(cfg, facts, ns) {
+        for instr in &cfg.blocks[block_no].instr {
+            if let Instr::Return { value } = instr {
+                let values = value
+                    .iter()
+                    .map(|expr| expression_values(expr, &vars, ns))
+                    .collect();
+
+                merge_summary(&mut summaries, 0, values);
+            }
+
+            transfer(instr, &mut vars, facts, ns);
+        }
+    }
+
+    summaries.remove(&0)
+}
+
+/// Merge the values of a call site or return into the summary. If there are too many values,
+/// keep the bits which all the values agree on.
+fn merge_summary(summaries: &mut HashMap<usize, Summary>, key: usize, values: Vec<HashSet<Value>>) {
+    let values = values.into_iter().map(|set| {
+        if set.is_empty() || set.iter().any(|v| v.all_unknown()) {
+            None
+        } else {
+            Some(set)
+        }
+    });
+
+    if let Some(summary) = summaries.get_mut(&key) {
+        for (existing, set) in summary.iter_mut().zip(values) {
+            *existing = match (existing.take(), set) {
+                (Some(mut existing), Some(set)) => {
+                    existing.extend(set);
+
+                    if existing.len() > MAX_VALUES {
+                        let mut widened = HashSet::new();
+
+                        widened.insert(set_join(&existing));
+
+                        Some(widened)
+                    } else {
+                        Some(existing)
+                    }
+                }
+                _ => None,
+            };
+        }
+    } else {
+        summaries.insert(key, values.collect());
+    }
+}
+
+/// The arguments which something is known about
+fn known_values(summary: Summary) -> HashMap<usize, HashSet<Value>> {
+    summary
+        .into_iter()
+        .enumerate()
+        .filter_map(|(arg_no, set)| set.map(|set| (arg_no, set)))
+        .collect()
+}
+
+/// If the argument has the same value at all the call sites, the constant to replace it with
+fn constant_argument(set: &HashSet<Value>, ty: &Type) -> Option<Expression> {
+    let value = is_single_constant(set)?;
+
+    match ty {
+        Type::Bool => Some(Expression::BoolLiteral(pt::Loc::Codegen, !value.is_zero())),
+        Type::Int(_) => Some(Expression::NumberLiteral(
+            pt::Loc::Codegen,
+            ty.clone(),
+            value,
+        )),
+        Type::Uint(bits) => {
+            let value = if value.sign() == Sign::Minus {
+                value + (BigInt::one() << *bits)
+            } else {
+                value
+            };
+
+            Some(Expression::NumberLiteral(
+                pt::Loc::Codegen,
+                ty.clone(),
+                value,
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Replace the function arguments with their constant values
+fn replace_arguments(expr: &Expression, constants: &mut &HashMap<usize, Expression>) -> Expression {
+    match expr {
+        Expression::FunctionArg(_, _, arg_no) if constants.contains_key(arg_no) => {
+            constants[arg_no].clone()
+        }
+        _ if uses_arguments(expr, constants) => expr.copy_filter(constants, replace_arguments),
+        _ => expr.clone(),
+    }
+}
+
+/// Does the expression use any of the function arguments
+fn uses_arguments(expr: &Expression, constants: &HashMap<usize, Expression>) -> bool {
+    let mut cx = (constants, false);
+
+    expr.recurse(&mut cx, |expr, cx| {
+        if let Expression::FunctionArg(_, _, arg_no) = expr {
+            if cx.0.contains_key(arg_no) {
+                cx.1 = true;
+            }
+        }
+
+        !cx.1
+    });
+
+    cx.1
+}
@@ -2,8 +2,9 @@
 
 #![cfg(test)]
 use super::expression_values::expression_values;
+use super::reaching_values::refine_condition;
 use super::{highest_set_bit, Variables};
-use crate::codegen::strength_reduce::value::Value;
+use crate::codegen::strength_reduce::value::{set_max_unsigned, Value};
 use crate::codegen::Expression;
 use crate::sema::ast::{Namespace, Type};
 use bitvec::prelude::BitArray;
@@ -604,10 +605,19 @@ fn expresson_known_bits() {
     let mut vars = HashMap::new();
 
     let mut var1 = expression_values(
-        &Expression::ZeroExt(
+        &Expression::BitwiseOr(
             loc,
             Type::Int(64),
-            Box::new(Expression::FunctionArg(loc, Type::Uint(16), 0)),
+            Box::new(Expression::ZeroExt(
+                loc,
+                Type::Int(64),
+                Box::new(Expression::FunctionArg(loc, Type::Uint(16), 0)),
+            )),
+            Box::new(Expression::NumberLiteral(
+                loc,
+                Type::Int(64),
+                BigInt::from(1024),
+            )),
         ),
         &vars,
         &ns,
@@ -650,6 +660,36 @@ fn expresson_known_bits() {
     assert!(v.known_bits[0]);
     assert!(v.value[0]);
 
+    // the function argument could be zero, so we do not know
+    let mut vars = HashMap::new();
+
+    let var1 = expression_values(
+        &Expression::ZeroExt(
+            loc,
+            Type::Int(64),
+            Box::new(Expression::FunctionArg(loc, Type::Uint(16), 0)),
+        ),
+        &vars,
+        &ns,
+    );
+
+    vars.insert(0, var1);
+
+    let var2 = expression_values(
+        &Expression::NumberLiteral(loc, Type::Int(64), BigInt::from(3)),
+        &vars,
+        &ns,
+    );
+
+    vars.insert(1, var2);
+
+    let res = expression_values(&expr, &vars, &ns);
+
+    assert_eq!(res.len(), 1);
+    let v = res.iter().next().unwrap();
+
+    assert!(!v.known_bits[0]);
+
     /////////////
     // test: less
     /////////////
@@ -884,3 +924,94 @@ fn expresson_known_bits() {
     assert!(v.known_bits[0]);
     assert!(v.value[0]);
 }
+
+#[test]
+fn branch_condition_values() {
+    use crate::Target;
+    use solang_parser::pt::Loc;
+
+    let ns = Namespace::new(Target::default_substrate());
+    let loc = Loc::Codegen;
+    let arguments = HashMap::new();
+
+    let mut vars: Variables = HashMap::new();
+
+    vars.insert(0, HashSet::from([Value::unknown(256)]));
+
+    let var = Expression::Variable(loc, Type::Uint(256), 0);
+    let limit = Expression::NumberLiteral(loc, Type::Uint(256), BigInt::from(1000));
+
+    // i < 1000
+    let cond = Expression::UnsignedLess(loc, Box::new(var.clone()), Box::new(limit.clone()));
+
+    let mut taken = vars.clone();
+    refine_condition(&cond, true, &mut taken, &arguments, &ns);
+    assert_eq!(set_max_unsigned(&taken[&0]), BigInt::from(1023));
+
+    // nothing is known about i if the condition is false
+    let mut not_taken = vars.clone();
+    refine_condition(&cond, false, &mut not_taken, &arguments, &ns);
+    assert!(not_taken[&0].iter().all(|v| v.all_unknown()));
+
+    // 1000 >= i, negated
+    let cond = Expression::Not(
+        loc,
+        Box::new(Expression::MoreEqual(
+            loc,
+            Box::new(limit),
+            Box::new(var.clone()),
+        )),
+    );
+
+    let mut not_taken = vars.clone();
+    refine_condition(&cond, false, &mut not_taken, &arguments, &ns);
+    assert_eq!(set_max_unsigned(&not_taken[&0]), BigInt::from(1023));
+
+    // (i & 0xffff0000) == 0
+    let cond = Expression::Equal(
+        loc,
+        Box::new(Expression::BitwiseAnd(
+            loc,
+            Type::Uint(256),
+            Box::new(var),
+            Box::new(Expression::NumberLiteral(
+                loc,
+                Type::Uint(256),
+                BigInt::from(0xffff0000u32),
+            )),
+        )),
+        Box::new(Expression::NumberLiteral(
+            loc,
+            Type::Uint(256),
+            BigInt::from(0),
+        )),
+    );
+
+    let mut taken = vars.clone();
+    refine_condition(&cond, true, &mut taken, &arguments, &ns);
+    let v = taken[&0].iter().next().unwrap();
+    assert!(v.known_bits[16..32].all());
+    assert!(v.value[16..32].not_any());
+    assert!(v.known_bits[0..16].not_any());
+
+    // the condition on a function argument refines the variable holding it
+    let arguments = HashMap::from([(0, 0)]);
+
+    let cond = Expression::SignedLess(
+        loc,
+        Box::new(Expression::FunctionArg(loc, Type::Int(256), 0)),
+        Box::new(Expression::NumberLiteral(
+            loc,
+            Type::Int(256),
+            BigInt::from(0),
+        )),
+    );
+
+    let mut taken = vars.clone();
+    refine_condition(&cond, true, &mut taken, &arguments, &ns);
+    assert_eq!(taken[&0].iter().next().unwrap().sign(), (true, true));
+
+    let mut not_taken = vars;
+    refine_condition(&cond, false, &mut not_taken, &arguments, &ns);
+    assert_eq!(not_taken[&0].iter().next().unwrap().sign(), (true, false));
+}
@@ -103,6 +103,29 @@ pub(super) fn set_max_unsigned(set: &HashSet<Value>) -> BigInt {
     m
 }
 
+/// Get the minimum unsigned value in a set
+pub(super) fn set_min_unsigned(set: &HashSet<Value>) -> BigInt {
+    set.iter()
+        .map(|v| BigInt::from_bytes_le(Sign::Plus, v.get_unsigned_min_value().as_buffer()))
+        .min()
+        .unwrap_or_else(BigInt::zero)
+}
+
+/// Merge all the values in a set into a single value. Only the bits which are known and
+/// identical in all the values remain known.
+pub(super) fn set_join(set: &HashSet<Value>) -> Value {
+    let mut iter = set.iter();
+    let mut res = iter.next().unwrap().clone();
+
+    for v in iter {
+        res.known_bits &= v.known_bits & !(res.value ^ v.value);
+    }
+
+    res.value &= res.known_bits;
+
+    res
+}
+
 impl Value {
     /// Calculate the unsigned min value. Higher bits than the type are 0
     pub(super) fn get_unsigned_min_value(&self) -> Bits {
@@ -123,39 +146,118 @@ impl Value {
 
     /// Calculate the signed max value
     pub(super) fn get_signed_max_value(&self) -> Bits {
-        let negative = match self.sign() {
-            (true, sign) => sign,
-            (false, _) => false,
-        };
-
-        if !negative {
-            // we know the value is positive; same as unsigned
-            self.get_unsigned_max_value()
-        } else {
-            // the value might be negative. So, we want to know which bits are zero
-            let mut v = self.get_unsigned_min_value();
-            v[self.bits - 1..].set_all(true);
-            v
+        match self.sign() {
+            (true, true) => {
+                // we know the value is negative; sign extend the unsigned max
+                let mut v = self.get_unsigned_max_value();
+                v[self.bits - 1..].set_all(true);
+                v
+            }
+            _ => {
+                // the value might be positive, so the sign bit is not set for the max
+                let mut v = self.get_unsigned_max_value();
+                v[self.bits - 1..].set_all(false);
+                v
+            }
         }
     }
 
+    /// Calculate the signed min value
     pub(super) fn get_signed_min_value(&self) -> Bits {
-        let negative = match self.sign() {
-            (true, sign) => sign,
-            (false, _) => true,
-        };
+        match self.sign() {
+            (true, false) => {
+                // we know the value is positive; same as unsigned
+                self.get_unsigned_min_value()
+            }
+            _ => {
+                // the value might be negative, so the sign bit is set for the min
+                let mut v = self.get_unsigned_min_value();
+                v[self.bits - 1..].set_all(true);
+                v
+            }
+        }
+    }
+
+    /// Does this value include all the possible values of other
+    pub(super) fn covers(&self, other: &Value) -> bool {
+        let bits = self.bits;
+
+        self.bits == other.bits
+            && (self.known_bits & !other.known_bits)[0..bits].not_any()
+            && ((self.value ^ other.value) & self.known_bits)[0..bits].not_any()
+    }
+
+    /// Restrict the value to be less than or equal to bound. Returns None if this value
+    /// is always more than the bound.
+    pub(super) fn unsigned_upper_bound(&self, bound: &BigInt) -> Option<Value> {
+        let min = BigInt::from_bytes_le(Sign::Plus, self.get_unsigned_min_value().as_buffer());
+
+        if min > *bound {
+            return None;
+        }
 
-        if !negative {
-            // we know the value is positive; same as unsigned
-            self.get_unsigned_min_value()
+        let mut v = self.clone();
+
+        // any bits higher than the highest set bit of the bound must be zero
+        let top = bound.bits() as usize;
+
+        if top < v.bits {
+            v.known_bits[top..v.bits].set_all(true);
+            v.value[top..v.bits].set_all(false);
+        }
+
+        Some(v)
+    }
+
+    /// Restrict the value to be more than or equal to bound. Returns None if this value
+    /// is always less than the bound.
+    pub(super) fn unsigned_lower_bound(&self, bound: &BigInt) -> Option<Value> {
+        let max = BigInt::from_bytes_le(Sign::Plus, self.get_unsigned_max_value().as_buffer());
+
+        if max < *bound {
+            None
         } else {
-            // the value might be negative. So, we want to know which bits are zero
-            let mut v = self.get_unsigned_max_value();
-            v[self.bits - 1..].set_all(true);
-            v
+            Some(self.clone())
         }
     }
 
+    /// Restrict the signed value to be less than or equal to bound. We only track the sign
+    /// bit here, unless the value is known to be positive.
+    pub(super) fn signed_upper_bound(&self, bound: &BigInt) -> Option<Value> {
+        let sign_bit = self.bits - 1;
+
+        match self.sign() {
+            (true, false) if bound.sign() == Sign::Minus => None,
+            (true, false) => self.unsigned_upper_bound(bound),
+            _ if bound.sign() == Sign::Minus => {
+                let mut v = self.clone();
+                v.known_bits.set(sign_bit, true);
+                v.value.set(sign_bit, true);
+                Some(v)
+            }
+            _ => Some(self.clone()),
+        }
+    }
+
+    /// Restrict the signed value to be more than or equal to bound. We only track the sign
+    /// bit here, unless the value is known to be positive.
+    pub(super) fn signed_lower_bound(&self, bound: &BigInt) -> Option<Value> {
+        if bound.sign() == Sign::Minus {
+            return Some(self.clone());
+        }
+
+        if self.sign() == (true, true) {
+            return None;
+        }
+
+        let sign_bit = self.bits - 1;
+        let mut v = self.clone();
+        v.known_bits.set(sign_bit, true);
+        v.value.set(sign_bit, false);
+
+        v.unsigned_lower_bound(bound)
+    }
+
     pub(super) fn all_known(&self) -> bool {
         self.known_bits[0..self.bits].all()
     }
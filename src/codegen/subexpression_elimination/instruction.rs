@@ -16,12 +16,14 @@ impl AvailableExpressionSet {
     ) {
         match instr {
             Instr::BranchCond { cond: expr, .. }
+            | Instr::Switch { cond: expr, .. }
             | Instr::LoadStorage { storage: expr, .. }
             | Instr::ClearStorage { storage: expr, .. }
             | Instr::Print { expr }
             | Instr::AssertFailure { expr: Some(expr) }
             | Instr::PopStorage { storage: expr, .. }
             | Instr::AbiDecode { data: expr, .. }
+            | Instr::SetImmutable { value: expr, .. }
             | Instr::SelfDestruct { recipient: expr } => {
                 let _ = self.gen_expression(expr, ave, cst);
             }
@@ -159,6 +161,7 @@ impl AvailableExpressionSet {
             | Instr::Unreachable
             | Instr::Nop
             | Instr::Branch { .. }
+            | Instr::LoadImmutable { .. }
             | Instr::PopMemory { .. } => {}
         }
     }
@@ -213,6 +216,16 @@ impl AvailableExpressionSet {
                 false_block: *false_block,
             },
 
+            Instr::Switch {
+                cond,
+                cases,
+                default,
+            } => Instr::Switch {
+                cond: self.regenerate_expression(cond, ave, cst).1,
+                cases: cases.clone(),
+                default: *default,
+            },
+
             Instr::Store { dest, data } => Instr::Store {
                 dest: self.regenerate_expression(dest, ave, cst).1,
                 data: self.regenerate_expression(data, ave, cst).1,
@@ -243,6 +256,12 @@ impl AvailableExpressionSet {
                 storage: self.regenerate_expression(storage, ave, cst).1,
             },
 
+            Instr::SetImmutable { ty, value, offset } => Instr::SetImmutable {
+                ty: ty.clone(),
+                value: self.regenerate_expression(value, ave, cst).1,
+                offset: *offset,
+            },
+
             Instr::SetStorageBytes {
                 value,
                 storage,
@@ -17,6 +17,7 @@ fn test_builtin_conversion() {
         ast::Builtin::BlockHash,
         ast::Builtin::BlockNumber,
         ast::Builtin::Calldata,
+        ast::Builtin::ECRecover,
         ast::Builtin::Gasleft,
         ast::Builtin::GasLimit,
         ast::Builtin::Gasprice,
@@ -73,6 +74,7 @@ fn test_builtin_conversion() {
         codegen::Builtin::BlockHash,
         codegen::Builtin::BlockNumber,
         codegen::Builtin::Calldata,
+        codegen::Builtin::ECRecover,
         codegen::Builtin::Gasleft,
         codegen::Builtin::GasLimit,
         codegen::Builtin::Gasprice,
@@ -5,6 +5,7 @@ use crate::codegen::reaching_definitions::{apply_transfers, VarDefs};
 use crate::codegen::{Builtin, Expression};
 use crate::sema::ast::{Diagnostic, ErrorType, Level, Namespace, Note, Type};
 use crate::sema::symtable;
+use crate::sema::Recurse;
 use solang_parser::pt::CodeLocation;
 use solang_parser::pt::{Loc, StorageLocation};
 use std::collections::HashMap;
@@ -58,10 +59,6 @@ pub fn check_variables_in_expression(
     cfg: &ControlFlowGraph,
     diagnostics: &mut HashMap<usize, Diagnostic>,
 ) {
-    if matches!(instr, Instr::Store { .. }) {
-        return;
-    }
-
     let mut params = FindUndefinedVariablesParams {
         func_no,
         defs,
@@ -69,7 +66,13 @@ pub fn check_variables_in_expression(
         cfg,
         diagnostics,
     };
-    instr.recurse_expressions(&mut params, find_undefined_variables_in_expression);
+
+    // The destination of a store is a pointer, so only the value being stored is read
+    if let Instr::Store { data, .. } = instr {
+        data.recurse(&mut params, find_undefined_variables_in_expression);
+    } else {
+        instr.recurse_expressions(&mut params, find_undefined_variables_in_expression);
+    }
 }
 
 /// Auxiliar function for expression.recurse. It checks if a variable is read before being defined
@@ -150,5 +153,3 @@ fn add_diagnostic(
         message: "Variable read before being defined".to_string(),
     });
 }
-
-// TODO: undefined variables are not yet compatible with Yul
@@ -2,7 +2,7 @@
 
 use super::Options;
 use crate::codegen::{cfg::ControlFlowGraph, vartable::Vartable, OptimizationLevel};
-use crate::sema::ast::{Expression, Function, Namespace};
+use crate::sema::ast::{Expression, Function, FunctionAttributes, Namespace};
 use crate::sema::symtable::VariableUsage;
 
 /// This struct saves the parameters to call 'check_side_effects_expressions'
@@ -51,12 +51,12 @@ pub fn should_remove_assignment(
 }
 
 /// Checks if we should remove a variable
-pub fn should_remove_variable(pos: &usize, func: &Function, opt: &Options) -> bool {
+pub fn should_remove_variable<T: FunctionAttributes>(pos: &usize, func: &T, opt: &Options) -> bool {
     if opt.opt_level == OptimizationLevel::None {
         return false;
     }
 
-    let var = &func.symtable.vars[pos];
+    let var = &func.get_symbol_table().vars[pos];
 
     //If the variable has never been read nor assigned, we can remove it right away.
     if !var.read && !var.assigned {
@@ -69,7 +69,9 @@ pub fn should_remove_variable(pos: &usize, func: &Function, opt: &Options) -> bo
         && var.assigned
         && matches!(
             var.usage_type,
-            VariableUsage::DestructureVariable | VariableUsage::LocalVariable
+            VariableUsage::DestructureVariable
+                | VariableUsage::LocalVariable
+                | VariableUsage::YulLocalVariable
         )
     {
         // Variables that are reference to other cannot be removed
@@ -78,5 +80,3 @@ pub fn should_remove_variable(pos: &usize, func: &Function, opt: &Options) -> bo
 
     false
 }
-
-// TODO: unused variables should remove Yul assignments!
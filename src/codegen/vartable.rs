@@ -73,7 +73,7 @@ impl Vartable {
         }
     }
 
-    fn make_unique(vars: &Vars, id: &pt::Identifier, no: usize) -> pt::Identifier {
+    pub(crate) fn make_unique(vars: &Vars, id: &pt::Identifier, no: usize) -> pt::Identifier {
         let mut id = id.clone();
 
         if id.name.is_empty() {
@@ -37,6 +37,26 @@ fn find_writable_vectors(
     writable: &mut HashSet<Def>,
 ) {
     for instr_no in 0..block.instr.len() {
+        // inline assembly can write to a vector via its memory address
+        let mut addressed = Vec::new();
+
+        block.instr[instr_no].recurse_expressions(&mut addressed, |expr, addressed| {
+            if let Expression::Cast(_, Type::Uint(_), expr) = expr {
+                if let Expression::Variable(_, ty, var_no) = expr.as_ref() {
+                    if ty.is_dynamic_memory() {
+                        addressed.push(*var_no);
+                    }
+                }
+            }
+            true
+        });
+
+        for var_no in addressed {
+            if let Some(entry) = vars.get(&var_no) {
+                writable.extend(entry.keys());
+            }
+        }
+
         match &block.instr[instr_no] {
             Instr::Set {
                 res,
@@ -101,9 +121,12 @@ fn find_writable_vectors(
             | Instr::Nop
             | Instr::Branch { .. }
             | Instr::BranchCond { .. }
+            | Instr::Switch { .. }
             | Instr::PopMemory { .. }
             | Instr::LoadStorage { .. }
             | Instr::SetStorage { .. }
+            | Instr::LoadImmutable { .. }
+            | Instr::SetImmutable { .. }
             | Instr::ClearStorage { .. }
             | Instr::SetStorageBytes { .. }
             | Instr::PushStorage { .. }
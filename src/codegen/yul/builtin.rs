@@ -78,93 +78,287 @@ pub(crate) fn process_builtin(
             process_binary_arithmetic(loc, builtin_ty, args, contract_no, ns, vartab, cfg, opt)
         }
 
-        YulBuiltInFunction::Byte => {
-            byte_builtin(loc, args, contract_no, ns, cfg, vartab, opt)
-        }
+        YulBuiltInFunction::Byte => byte_builtin(loc, args, contract_no, ns, cfg, vartab, opt),
 
-        YulBuiltInFunction::AddMod
-        | YulBuiltInFunction::MulMod => {
+        YulBuiltInFunction::AddMod | YulBuiltInFunction::MulMod => {
             let left = expression(&args[0], contract_no, ns, vartab, cfg, opt);
             let right = expression(&args[1], contract_no, ns, vartab, cfg, opt);
             let (left, right) = equalize_types(left, right, ns);
 
             let main_expr = if matches!(builtin_ty, YulBuiltInFunction::AddMod) {
-                Expression::Add(*loc, left.ty(), false, Box::new(left), Box::new(right))
+                Expression::Add(*loc, left.ty(), true, Box::new(left), Box::new(right))
             } else {
-                Expression::Multiply(*loc, left.ty(), false, Box::new(left), Box::new(right))
+                Expression::Multiply(*loc, left.ty(), true, Box::new(left), Box::new(right))
             };
 
             let mod_arg = expression(&args[2], contract_no, ns, vartab, cfg, opt);
             let (mod_left, mod_right) = equalize_types(main_expr, mod_arg, ns);
-            let codegen_expr = Expression::UnsignedModulo(*loc, mod_left.ty(), Box::new(mod_left), Box::new(mod_right.clone()));
+            let codegen_expr = Expression::UnsignedModulo(
+                *loc,
+                mod_left.ty(),
+                Box::new(mod_left),
+                Box::new(mod_right.clone()),
+            );
             branch_if_zero(mod_right, codegen_expr, cfg, vartab)
         }
 
+        // Not implemented yet:
+        // - msize: there is no notion of the largest accessed memory address
+        // - codesize, codecopy, extcodecopy and extcodehash manage code memory
+        // - create, create2, call, callcode, delegatecall and staticcall manage contracts
+        // - return, stop (which is the same as return(0, 0)) and revert
+        // - the log functions
+        // - origin, which is the same as tx.origin
         YulBuiltInFunction::SignExtend
-        | YulBuiltInFunction::Keccak256
         | YulBuiltInFunction::Pop
         | YulBuiltInFunction::Pc
         | YulBuiltInFunction::ChainId
         | YulBuiltInFunction::BaseFee
-        // Memory functions: need to convert between number to pointer type
-        | YulBuiltInFunction::MLoad
-        | YulBuiltInFunction::MStore
-        | YulBuiltInFunction::MStore8
         | YulBuiltInFunction::MSize
-        // Storage function: need to think about how to deal with pointer size and the size of chunk to load
-        | YulBuiltInFunction::SStore
-        | YulBuiltInFunction::SLoad
-        // Calldata functions: the same problems with other memory functions
-        | YulBuiltInFunction::CallDataLoad
-        | YulBuiltInFunction::CallDataSize
-        | YulBuiltInFunction::CallDataCopy
-        // Functions that manage code memory
         | YulBuiltInFunction::CodeSize
         | YulBuiltInFunction::CodeCopy
         | YulBuiltInFunction::ExtCodeCopy
         | YulBuiltInFunction::ExtCodeHash
-        // Functions that manage return data
-        | YulBuiltInFunction::ReturnDataSize
-        | YulBuiltInFunction::ReturnDataCopy
-        // Functions that manage contracts
         | YulBuiltInFunction::Create
         | YulBuiltInFunction::Create2
         | YulBuiltInFunction::Call
         | YulBuiltInFunction::CallCode
         | YulBuiltInFunction::DelegateCall
         | YulBuiltInFunction::StaticCall
-        // Return and revert also load from memory, so we first need to solve mload and mstore builtins
         | YulBuiltInFunction::Return
-        | YulBuiltInFunction::Stop // Stop is the same as return(0, 0)
+        | YulBuiltInFunction::Stop
         | YulBuiltInFunction::Revert
-        // Log functions
         | YulBuiltInFunction::Log0
         | YulBuiltInFunction::Log1
         | YulBuiltInFunction::Log2
         | YulBuiltInFunction::Log3
         | YulBuiltInFunction::Log4
-        // origin is the same as tx.origin and is not implemented
-        | YulBuiltInFunction::Origin
-        => {
+        | YulBuiltInFunction::Origin => {
             let function_ty = builtin_ty.get_prototype_info();
             unreachable!("{} yul builtin not implemented", function_ty.name);
         }
 
+        YulBuiltInFunction::SLoad => {
+            let storage = expression(&args[0], contract_no, ns, vartab, cfg, opt)
+                .cast(&ns.storage_type(), ns);
+            let res = vartab.temp_anonymous(&Type::Uint(256));
+            cfg.add(
+                vartab,
+                Instr::LoadStorage {
+                    res,
+                    ty: Type::Uint(256),
+                    storage,
+                },
+            );
+            Expression::Variable(*loc, Type::Uint(256), res)
+        }
+
+        YulBuiltInFunction::SStore => {
+            let storage = expression(&args[0], contract_no, ns, vartab, cfg, opt)
+                .cast(&ns.storage_type(), ns);
+            let value =
+                expression(&args[1], contract_no, ns, vartab, cfg, opt).cast(&Type::Uint(256), ns);
+            cfg.add(
+                vartab,
+                Instr::SetStorage {
+                    ty: Type::Uint(256),
+                    value,
+                    storage,
+                },
+            );
+            Expression::Poison
+        }
+
+        YulBuiltInFunction::LoadImmutable => {
+            if let ast::YulExpression::StorageVariable(_, ty, var_contract_no, var_no) = &args[0] {
+                let offset =
+                    ns.contracts[contract_no].get_immutable_offset(*var_contract_no, *var_no);
+                let ty = immutable_type(ty, ns);
+                let res = vartab.temp_anonymous(&ty);
+                cfg.add(
+                    vartab,
+                    Instr::LoadImmutable {
+                        res,
+                        ty: ty.clone(),
+                        offset,
+                    },
+                );
+                Expression::Variable(*loc, ty, res).cast(&Type::Uint(256), ns)
+            } else {
+                unreachable!("sema replaces the name of the immutable with the variable");
+            }
+        }
+
+        YulBuiltInFunction::SetImmutable => {
+            if let ast::YulExpression::StorageVariable(_, ty, var_contract_no, var_no) = &args[1] {
+                // the offset is for the runtime code in memory in EVM; there is no such thing here
+                let _ = expression(&args[0], contract_no, ns, vartab, cfg, opt);
+                let ty = immutable_type(ty, ns);
+                let value = expression(&args[2], contract_no, ns, vartab, cfg, opt).cast(&ty, ns);
+                let offset =
+                    ns.contracts[contract_no].get_immutable_offset(*var_contract_no, *var_no);
+                cfg.add(vartab, Instr::SetImmutable { ty, value, offset });
+                Expression::Poison
+            } else {
+                unreachable!("sema replaces the name of the immutable with the variable");
+            }
+        }
+
+        YulBuiltInFunction::MLoad => {
+            let address = expression(&args[0], contract_no, ns, vartab, cfg, opt);
+            let res = vartab.temp_anonymous(&Type::Uint(256));
+            cfg.add(
+                vartab,
+                Instr::Set {
+                    loc: *loc,
+                    res,
+                    expr: Expression::Load(
+                        *loc,
+                        Type::Uint(256),
+                        Box::new(memory_address(loc, address, Type::Uint(256), ns)),
+                    ),
+                },
+            );
+            Expression::Variable(*loc, Type::Uint(256), res)
+        }
+
+        YulBuiltInFunction::MStore | YulBuiltInFunction::MStore8 => {
+            let address = expression(&args[0], contract_no, ns, vartab, cfg, opt);
+            let data =
+                expression(&args[1], contract_no, ns, vartab, cfg, opt).cast(&Type::Uint(256), ns);
+            // mstore8 only stores the least significant byte
+            let (ty, data) = if matches!(builtin_ty, YulBuiltInFunction::MStore) {
+                (Type::Uint(256), data)
+            } else {
+                (
+                    Type::Uint(8),
+                    Expression::Trunc(*loc, Type::Uint(8), Box::new(data)),
+                )
+            };
+            cfg.add(
+                vartab,
+                Instr::Store {
+                    dest: memory_address(loc, address, ty, ns),
+                    data,
+                },
+            );
+            Expression::Poison
+        }
+
+        YulBuiltInFunction::Keccak256 => {
+            let address = expression(&args[0], contract_no, ns, vartab, cfg, opt);
+            let length =
+                expression(&args[1], contract_no, ns, vartab, cfg, opt).cast(&Type::Uint(32), ns);
+            let buf = vartab.temp_anonymous(&Type::DynamicBytes);
+            cfg.add(
+                vartab,
+                Instr::Set {
+                    loc: *loc,
+                    res: buf,
+                    expr: Expression::AllocDynamicArray(
+                        *loc,
+                        Type::DynamicBytes,
+                        Box::new(length.clone()),
+                        None,
+                    ),
+                },
+            );
+            let buf = Expression::Variable(*loc, Type::DynamicBytes, buf);
+            cfg.add(
+                vartab,
+                Instr::MemCopy {
+                    source: memory_address(loc, address, Type::Uint(8), ns),
+                    destination: buffer_pointer(
+                        loc,
+                        buf.clone(),
+                        Expression::NumberLiteral(*loc, Type::Uint(32), BigInt::zero()),
+                    ),
+                    bytes: length,
+                },
+            );
+            Expression::Builtin(*loc, vec![Type::Bytes(32)], Builtin::Keccak256, vec![buf])
+                .cast(&Type::Uint(256), ns)
+        }
+
+        YulBuiltInFunction::CallDataSize => {
+            let calldata =
+                Expression::Builtin(*loc, vec![Type::DynamicBytes], Builtin::Calldata, vec![]);
+            Expression::Builtin(
+                *loc,
+                vec![Type::Uint(32)],
+                Builtin::ArrayLength,
+                vec![calldata],
+            )
+        }
+
+        YulBuiltInFunction::ReturnDataSize => Expression::Builtin(
+            *loc,
+            vec![Type::Uint(32)],
+            Builtin::ArrayLength,
+            vec![Expression::ReturnData(*loc)],
+        ),
+
+        YulBuiltInFunction::CallDataLoad => {
+            let offset = expression(&args[0], contract_no, ns, vartab, cfg, opt);
+            let calldata =
+                Expression::Builtin(*loc, vec![Type::DynamicBytes], Builtin::Calldata, vec![]);
+            let calldata = buffer_temp(loc, calldata, cfg, vartab);
+            let word = Expression::NumberLiteral(*loc, Type::Uint(256), BigInt::from(32));
+            let (offset, _) = buffer_bounds_check(loc, &calldata, offset, word, ns, cfg, vartab);
+            Expression::Builtin(
+                *loc,
+                vec![Type::Uint(256)],
+                Builtin::ReadFromBuffer,
+                vec![calldata, offset],
+            )
+        }
+
+        YulBuiltInFunction::CallDataCopy | YulBuiltInFunction::ReturnDataCopy => {
+            let address = expression(&args[0], contract_no, ns, vartab, cfg, opt);
+            let offset = expression(&args[1], contract_no, ns, vartab, cfg, opt);
+            let length = expression(&args[2], contract_no, ns, vartab, cfg, opt);
+            let data = if matches!(builtin_ty, YulBuiltInFunction::CallDataCopy) {
+                Expression::Builtin(*loc, vec![Type::DynamicBytes], Builtin::Calldata, vec![])
+            } else {
+                Expression::ReturnData(*loc)
+            };
+            let data = buffer_temp(loc, data, cfg, vartab);
+            let (offset, length) = buffer_bounds_check(loc, &data, offset, length, ns, cfg, vartab);
+            cfg.add(
+                vartab,
+                Instr::MemCopy {
+                    source: buffer_pointer(loc, data, offset),
+                    destination: memory_address(loc, address, Type::Uint(8), ns),
+                    bytes: length,
+                },
+            );
+            Expression::Poison
+        }
+
         YulBuiltInFunction::Gas => {
             Expression::Builtin(*loc, vec![Type::Uint(64)], Builtin::Gasleft, vec![])
         }
 
-        YulBuiltInFunction::Address => {
-            Expression::Builtin(*loc, vec![Type::Address(false)], Builtin::GetAddress, vec![])
-        }
+        YulBuiltInFunction::Address => Expression::Builtin(
+            *loc,
+            vec![Type::Address(false)],
+            Builtin::GetAddress,
+            vec![],
+        ),
 
         YulBuiltInFunction::Balance => {
-            let addr = expression(&args[0], contract_no, ns, vartab, cfg, opt).cast(&Type::Address(false), ns);
-            Expression::Builtin(*loc, vec![Type::Value], Builtin::Balance,vec![addr])
+            let addr = expression(&args[0], contract_no, ns, vartab, cfg, opt)
+                .cast(&Type::Address(false), ns);
+            Expression::Builtin(*loc, vec![Type::Value], Builtin::Balance, vec![addr])
         }
 
         YulBuiltInFunction::SelfBalance => {
-            let addr = Expression::Builtin(*loc, vec![Type::Contract(contract_no)], Builtin::GetAddress,vec![]);
+            let addr = Expression::Builtin(
+                *loc,
+                vec![Type::Contract(contract_no)],
+                Builtin::GetAddress,
+                vec![],
+            );
             Expression::Builtin(*loc, vec![Type::Value], Builtin::Balance, vec![addr])
         }
 
@@ -177,7 +371,8 @@ pub(crate) fn process_builtin(
         }
 
         YulBuiltInFunction::SelfDestruct => {
-            let recipient = expression(&args[0], contract_no, ns, vartab, cfg, opt).cast(&Type::Address(true), ns);
+            let recipient = expression(&args[0], contract_no, ns, vartab, cfg, opt)
+                .cast(&Type::Address(true), ns);
             cfg.add(vartab, Instr::SelfDestruct { recipient });
             Expression::Poison
         }
@@ -192,18 +387,28 @@ pub(crate) fn process_builtin(
         }
 
         YulBuiltInFunction::ExtCodeSize => {
-            let address = expression(&args[0], contract_no, ns, vartab, cfg, opt).cast(&Type::Address(false), ns);
-            Expression::Builtin(*loc, vec![Type::Uint(32)], Builtin::ExtCodeSize, vec![address])
+            let address = expression(&args[0], contract_no, ns, vartab, cfg, opt)
+                .cast(&Type::Address(false), ns);
+            Expression::Builtin(
+                *loc,
+                vec![Type::Uint(32)],
+                Builtin::ExtCodeSize,
+                vec![address],
+            )
         }
 
         YulBuiltInFunction::BlockHash => {
-            let arg = expression(&args[0], contract_no, ns, vartab, cfg, opt).cast(&Type::Uint(64), ns);
+            let arg =
+                expression(&args[0], contract_no, ns, vartab, cfg, opt).cast(&Type::Uint(64), ns);
             Expression::Builtin(*loc, vec![Type::Uint(256)], Builtin::BlockHash, vec![arg])
         }
 
-        YulBuiltInFunction::CoinBase => {
-            Expression::Builtin(*loc, vec![Type::Address(false)], Builtin::BlockCoinbase, vec![])
-        }
+        YulBuiltInFunction::CoinBase => Expression::Builtin(
+            *loc,
+            vec![Type::Address(false)],
+            Builtin::BlockCoinbase,
+            vec![],
+        ),
 
         YulBuiltInFunction::Timestamp => {
             Expression::Builtin(*loc, vec![Type::Uint(64)], Builtin::Timestamp, vec![])
@@ -213,9 +418,12 @@ pub(crate) fn process_builtin(
             Expression::Builtin(*loc, vec![Type::Uint(64)], Builtin::BlockNumber, vec![])
         }
 
-        YulBuiltInFunction::Difficulty => {
-            Expression::Builtin(*loc, vec![Type::Uint(256)], Builtin::BlockDifficulty, vec![])
-        }
+        YulBuiltInFunction::Difficulty => Expression::Builtin(
+            *loc,
+            vec![Type::Uint(256)],
+            Builtin::BlockDifficulty,
+            vec![],
+        ),
 
         YulBuiltInFunction::GasLimit => {
             Expression::Builtin(*loc, vec![Type::Uint(64)], Builtin::GasLimit, vec![])
@@ -223,6 +431,18 @@ pub(crate) fn process_builtin(
     }
 }
 
+/// Yul accesses immutables as plain values. This returns a type with the same representation as
+/// the immutable, which can be converted to and from uint256.
+fn immutable_type(ty: &Type, ns: &Namespace) -> Type {
+    match ty {
+        Type::Fixed(n, _) => Type::Int(*n),
+        Type::Ufixed(n, _) => Type::Uint(*n),
+        Type::Contract(_) => Type::Address(false),
+        Type::UserType(no) => immutable_type(&ns.user_types[*no].ty, ns),
+        _ => ty.clone(),
+    }
+}
+
 /// Process arithmetic operations with two arguments
 fn process_binary_arithmetic(
     loc: &pt::Loc,
@@ -464,7 +684,7 @@ fn byte_builtin(
     let op_31_sub_arg0 = Expression::Subtract(
         *loc,
         Type::Uint(256),
-        false,
+        true,
         Box::new(Expression::NumberLiteral(
             *loc,
             Type::Uint(256),
@@ -517,3 +737,122 @@ fn byte_builtin(
 
     Expression::Variable(pt::Loc::Codegen, Type::Uint(256), temp)
 }
+
+/// Yul memory addresses are pointers into the linear memory of the contract. Convert the
+/// address into a pointer to a value of the given type.
+fn memory_address(loc: &pt::Loc, address: Expression, ty: Type, ns: &Namespace) -> Expression {
+    let address = address.cast(&Type::Uint(ns.target.ptr_size()), ns);
+
+    Expression::Cast(*loc, Type::Ref(Box::new(ty)), Box::new(address))
+}
+
+/// Pointer to the byte at offset in a buffer
+fn buffer_pointer(loc: &pt::Loc, buf: Expression, offset: Expression) -> Expression {
+    Expression::AdvancePointer {
+        loc: *loc,
+        ty: Type::BufferPointer,
+        pointer: Box::new(buf),
+        bytes_offset: Box::new(offset),
+    }
+}
+
+/// Calldata and return data are retrieved each time they are used, so keep them in a temporary
+fn buffer_temp(
+    loc: &pt::Loc,
+    buf: Expression,
+    cfg: &mut ControlFlowGraph,
+    vartab: &mut Vartable,
+) -> Expression {
+    let res = vartab.temp_anonymous(&Type::DynamicBytes);
+
+    cfg.add(
+        vartab,
+        Instr::Set {
+            loc: *loc,
+            res,
+            expr: buf,
+        },
+    );
+
+    Expression::Variable(*loc, Type::DynamicBytes, res)
+}
+
+/// Abort execution if the range offset..offset+length is not within the buffer. The offset and
+/// length are uint256 values from Yul; they are compared before truncating them, so that values
+/// which do not fit into 32 bits are out of bounds rather than wrapped. Returns the offset and
+/// length as uint32.
+fn buffer_bounds_check(
+    loc: &pt::Loc,
+    buf: &Expression,
+    offset: Expression,
+    length: Expression,
+    ns: &Namespace,
+    cfg: &mut ControlFlowGraph,
+    vartab: &mut Vartable,
+) -> (Expression, Expression) {
+    let offset = offset.cast(&Type::Uint(256), ns);
+    let length = length.cast(&Type::Uint(256), ns);
+    let buf_length = Expression::ZeroExt(
+        *loc,
+        Type::Uint(256),
+        Box::new(Expression::Builtin(
+            *loc,
+            vec![Type::Uint(32)],
+            Builtin::ArrayLength,
+            vec![buf.clone()],
+        )),
+    );
+
+    let out_of_bounds = cfg.new_basic_block("out_of_bounds".to_string());
+    let offset_in_bounds = cfg.new_basic_block("offset_in_bounds".to_string());
+    let in_bounds = cfg.new_basic_block("in_bounds".to_string());
+
+    // offset <= buffer length && length <= buffer length - offset, which cannot overflow
+    cfg.add(
+        vartab,
+        Instr::BranchCond {
+            cond: Expression::LessEqual(
+                *loc,
+                Box::new(offset.clone()),
+                Box::new(buf_length.clone()),
+            ),
+            true_block: offset_in_bounds,
+            false_block: out_of_bounds,
+        },
+    );
+
+    cfg.set_basic_block(offset_in_bounds);
+    cfg.add(
+        vartab,
+        Instr::BranchCond {
+            cond: Expression::LessEqual(
+                *loc,
+                Box::new(length.clone()),
+                Box::new(Expression::Subtract(
+                    *loc,
+                    Type::Uint(256),
+                    true,
+                    Box::new(buf_length),
+                    Box::new(offset.clone()),
+                )),
+            ),
+            true_block: in_bounds,
+            false_block: out_of_bounds,
+        },
+    );
+
+    cfg.set_basic_block(out_of_bounds);
+    cfg.add(vartab, Instr::AssertFailure { expr: None });
+
+    cfg.set_basic_block(in_bounds);
+
+    // cast() does not truncate literals; those which do not fit are always out of bounds
+    let truncate = |expr: Expression| match expr {
+        Expression::NumberLiteral(loc, _, value) => {
+            Expression::NumberLiteral(loc, Type::Uint(32), value & BigInt::from(u32::MAX))
+        }
+        _ => expr.cast(&Type::Uint(32), ns),
+    };
+
+    (truncate(offset), truncate(length))
+}
@@ -73,7 +73,14 @@ pub(crate) fn expression(
             panic!("Storage variables cannot be accessed without suffixed in yul");
         }
         ast::YulExpression::SolidityLocalVariable(loc, ty, _, var_no) => {
-            Expression::Variable(*loc, ty.clone(), *var_no)
+            let var = Expression::Variable(*loc, ty.clone(), *var_no);
+
+            // Reference types are memory addresses in yul
+            if ty.is_reference_type(ns) {
+                Expression::Cast(*loc, Type::Uint(256), Box::new(var))
+            } else {
+                var
+            }
         }
         ast::YulExpression::SuffixAccess(loc, expr, suffix) => {
             process_suffix_access(loc, expr, suffix, contract_no, vartab, cfg, ns, opt)
@@ -155,9 +162,9 @@ fn process_suffix_access(
         YulSuffix::Length => {
             if let ast::YulExpression::SolidityLocalVariable(
                 _,
-                Type::Array(_, ref dims),
-                Some(StorageLocation::Calldata(_)),
-                _,
+                ty @ Type::Array(_, ref dims),
+                Some(StorageLocation::Calldata(_) | StorageLocation::Memory(_)),
+                var_no,
             ) = expr
             {
                 if dims.last() == Some(&ArrayLength::Dynamic) {
@@ -165,7 +172,7 @@ fn process_suffix_access(
                         *loc,
                         vec![Type::Uint(32)],
                         Builtin::ArrayLength,
-                        vec![expression(expr, contract_no, ns, vartab, cfg, opt)],
+                        vec![Expression::Variable(*loc, ty.clone(), *var_no)],
                     );
                 }
             }
@@ -1,8 +1,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::codegen::cfg::{
-    optimize_and_check_cfg, populate_arguments, populate_named_returns, ASTFunction,
-    ControlFlowGraph, Instr,
+    populate_arguments, populate_named_returns, ASTFunction, ControlFlowGraph, Instr,
 };
 use crate::codegen::statements::LoopScopes;
 use crate::codegen::vartable::Vartable;
@@ -41,9 +40,8 @@ pub(crate) fn generate_yul_function_cfg(
     ns: &mut Namespace,
     opt: &Options,
 ) {
-    let mut cfg = yul_function_cfg(contract_no, function_no, ns, opt);
+    let cfg = yul_function_cfg(contract_no, function_no, ns, opt);
 
-    optimize_and_check_cfg(&mut cfg, ns, ASTFunction::YulFunction(function_no), opt);
     all_cfgs[ns.yul_functions[function_no].cfg_no] = cfg;
 }
 
@@ -1,16 +1,19 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::codegen::cfg::{ControlFlowGraph, Instr};
+use crate::codegen::cfg::{ASTFunction, ControlFlowGraph, Instr};
 use crate::codegen::statements::LoopScopes;
+use crate::codegen::unused_variable;
+use crate::codegen::unused_variable::SideEffectsCheckParameters;
 use crate::codegen::vartable::Vartable;
 use crate::codegen::yul::builtin::process_builtin;
 use crate::codegen::yul::expression::{expression, process_function_call};
-use crate::codegen::{Expression, Options};
+use crate::codegen::{Builtin, Expression, Options};
 use crate::sema::ast::{Namespace, RetrieveType, Type};
 use crate::sema::yul::ast;
 use crate::sema::yul::ast::{YulStatement, YulSuffix};
+use crate::sema::Recurse;
 use num_bigint::BigInt;
-use num_traits::FromPrimitive;
+use num_traits::{FromPrimitive, One};
 use solang_parser::pt;
 use solang_parser::pt::StorageLocation;
 
@@ -67,10 +70,25 @@ pub(crate) fn statement(
             opt,
         ),
 
-        YulStatement::Switch { .. } => {
-            // Switch statements should use LLVM switch instruction, which requires changes in emit.
-            unreachable!("Switch statements for yul are not implemented yet");
-        }
+        YulStatement::Switch {
+            loc,
+            condition,
+            cases,
+            default,
+            ..
+        } => process_switch(
+            loc,
+            condition,
+            cases,
+            default,
+            contract_no,
+            loops,
+            ns,
+            cfg,
+            vartab,
+            early_return,
+            opt,
+        ),
 
         YulStatement::For {
             loc,
@@ -133,12 +151,27 @@ fn process_variable_declaration(
     vartab: &mut Vartable,
     opt: &Options,
 ) {
-    let initializer = if let Some(expr) = init {
-        if let ast::YulExpression::FunctionCall(_, func_no, args, _) = expr {
-            process_function_call(*func_no, args, contract_no, vartab, cfg, ns, opt)
-        } else {
-            vec![expression(expr, contract_no, ns, vartab, cfg, opt)]
+    if vars
+        .iter()
+        .all(|(var_no, _)| should_remove_variable(var_no, cfg, ns, opt))
+    {
+        // If we remove the declaration, we must keep expressions that have side effects
+        if let Some(expr) = init {
+            let mut params = SideEffectsCheckParameters {
+                cfg,
+                contract_no,
+                func: None,
+                ns,
+                vartab,
+                opt,
+            };
+            expr.recurse(&mut params, process_side_effects_expressions);
         }
+        return;
+    }
+
+    let initializer = if let Some(expr) = init {
+        process_returns(expr, contract_no, ns, cfg, vartab, opt)
     } else {
         let mut inits: Vec<Expression> = Vec::with_capacity(vars.len());
         for item in vars {
@@ -149,6 +182,10 @@ fn process_variable_declaration(
     };
 
     for (var_index, item) in vars.iter().enumerate() {
+        if should_remove_variable(&item.0, cfg, ns, opt) {
+            continue;
+        }
+
         cfg.add(
             vartab,
             Instr::Set {
@@ -171,22 +208,140 @@ fn process_assignment(
     vartab: &mut Vartable,
     opt: &Options,
 ) {
-    if lhs.len() > 1 {
-        // builtins with multiple returns are not implemented (yet)
-        let returns = if let ast::YulExpression::FunctionCall(_, func_no, args, _) = rhs {
-            process_function_call(*func_no, args, contract_no, vartab, cfg, ns, opt)
-        } else {
-            unreachable!("only function call return multiple values");
+    if lhs
+        .iter()
+        .all(|item| should_remove_assignment(item, cfg, ns, opt))
+    {
+        // If we remove the assignment, we must keep expressions that have side effects
+        let mut params = SideEffectsCheckParameters {
+            cfg,
+            contract_no,
+            func: None,
+            ns,
+            vartab,
+            opt,
         };
+        rhs.recurse(&mut params, process_side_effects_expressions);
+        return;
+    }
+
+    let returns = process_returns(rhs, contract_no, ns, cfg, vartab, opt);
+    assert_eq!(returns.len(), lhs.len());
 
-        for (lhs_no, lhs_item) in lhs.iter().enumerate() {
-            cfg_single_assigment(loc, lhs_item, returns[lhs_no].clone(), ns, cfg, vartab);
+    for (lhs_item, rhs_item) in lhs.iter().zip(returns) {
+        if should_remove_assignment(lhs_item, cfg, ns, opt) {
+            continue;
         }
-        return;
+
+        cfg_single_assigment(loc, lhs_item, rhs_item, ns, cfg, vartab);
     }
+}
 
-    let codegen_rhs = expression(rhs, contract_no, ns, vartab, cfg, opt);
-    cfg_single_assigment(loc, &lhs[0], codegen_rhs, ns, cfg, vartab);
+/// Checks if we should remove an assignment to a yul expression
+fn should_remove_assignment(
+    lhs: &ast::YulExpression,
+    cfg: &ControlFlowGraph,
+    ns: &Namespace,
+    opt: &Options,
+) -> bool {
+    match lhs {
+        ast::YulExpression::YulLocalVariable(_, _, var_no)
+        | ast::YulExpression::SolidityLocalVariable(_, _, None, var_no)
+        | ast::YulExpression::SolidityLocalVariable(
+            _,
+            _,
+            Some(StorageLocation::Memory(_)),
+            var_no,
+        ) => should_remove_variable(var_no, cfg, ns, opt),
+
+        _ => false,
+    }
+}
+
+/// Checks if we should remove a variable, using the symbol table of the function being generated
+fn should_remove_variable(
+    var_no: &usize,
+    cfg: &ControlFlowGraph,
+    ns: &Namespace,
+    opt: &Options,
+) -> bool {
+    match cfg.function_no {
+        ASTFunction::SolidityFunction(function_no) => {
+            unused_variable::should_remove_variable(var_no, &ns.functions[function_no], opt)
+        }
+        ASTFunction::YulFunction(function_no) => {
+            let func = &ns.yul_functions[function_no];
+            // The return variables of a yul function are read when the function returns
+            !func.symtable.returns.contains(var_no)
+                && unused_variable::should_remove_variable(var_no, func, opt)
+        }
+        ASTFunction::None => false,
+    }
+}
+
+/// Generate the code for the expressions that have side effects, when the assignment they belong
+/// to has been removed
+fn process_side_effects_expressions(
+    exp: &ast::YulExpression,
+    ctx: &mut SideEffectsCheckParameters,
+) -> bool {
+    match exp {
+        ast::YulExpression::FunctionCall(_, func_no, args, _) => {
+            let _ = process_function_call(
+                *func_no,
+                args,
+                ctx.contract_no,
+                ctx.vartab,
+                ctx.cfg,
+                ctx.ns,
+                ctx.opt,
+            );
+            false
+        }
+
+        ast::YulExpression::BuiltInCall(loc, builtin_ty, args) if builtin_ty.modify_state() => {
+            let _ = process_builtin(
+                loc,
+                builtin_ty,
+                args,
+                ctx.contract_no,
+                ctx.ns,
+                ctx.vartab,
+                ctx.cfg,
+                ctx.opt,
+            );
+            false
+        }
+
+        _ => true,
+    }
+}
+
+/// Generate the code for the right hand side of an assignment or declaration, which may
+/// return multiple values.
+fn process_returns(
+    rhs: &ast::YulExpression,
+    contract_no: usize,
+    ns: &Namespace,
+    cfg: &mut ControlFlowGraph,
+    vartab: &mut Vartable,
+    opt: &Options,
+) -> Vec<Expression> {
+    match rhs {
+        ast::YulExpression::FunctionCall(_, func_no, args, _) => {
+            process_function_call(*func_no, args, contract_no, vartab, cfg, ns, opt)
+        }
+
+        ast::YulExpression::BuiltInCall(loc, builtin_ty, args) => {
+            // Builtins with multiple returns give a list of values
+            match process_builtin(loc, builtin_ty, args, contract_no, ns, vartab, cfg, opt) {
+                Expression::List(_, returns) => returns,
+                ret => vec![ret],
+            }
+        }
+
+        _ => vec![expression(rhs, contract_no, ns, vartab, cfg, opt)],
+    }
 }
 
 /// As YUL assignments may contain multiple variables, this function treats one assignment at a time.
@@ -199,10 +354,19 @@ fn cfg_single_assigment(
     vartab: &mut Vartable,
 ) {
     match lhs {
-        ast::YulExpression::YulLocalVariable(_, ty, var_no)
-        | ast::YulExpression::SolidityLocalVariable(_, ty, None, var_no) => {
-            // Ensure both types are compatible
-            let rhs = rhs.cast(ty, ns);
+        ast::YulExpression::SolidityLocalVariable(_, ty, None, var_no)
+        | ast::YulExpression::SolidityLocalVariable(
+            _,
+            ty,
+            Some(StorageLocation::Memory(_)),
+            var_no,
+        ) if ty.is_reference_type(ns) => {
+            // This is an assignment to a pointer, so the rhs is a memory address
+            let rhs = Expression::Cast(
+                *loc,
+                ty.clone(),
+                Box::new(rhs.cast(&Type::Uint(ns.target.ptr_size()), ns)),
+            );
             cfg.add(
                 vartab,
                 Instr::Set {
@@ -213,13 +377,9 @@ fn cfg_single_assigment(
             );
         }
 
-        ast::YulExpression::SolidityLocalVariable(
-            _,
-            ty,
-            Some(StorageLocation::Memory(_)),
-            var_no,
-        ) => {
-            // This is an assignment to a pointer, so we make sure the rhs has a compatible size
+        ast::YulExpression::YulLocalVariable(_, ty, var_no)
+        | ast::YulExpression::SolidityLocalVariable(_, ty, None, var_no) => {
+            // Ensure both types are compatible
             let rhs = rhs.cast(ty, ns);
             cfg.add(
                 vartab,
@@ -228,19 +388,24 @@ fn cfg_single_assigment(
                     res: *var_no,
                     expr: rhs,
                 },
-            )
+            );
         }
 
         ast::YulExpression::SuffixAccess(_, member, suffix) => {
             match &**member {
                 ast::YulExpression::SolidityLocalVariable(
                     _,
-                    _,
+                    ty,
                     Some(StorageLocation::Calldata(_)),
                     var_no,
                 ) => match suffix {
                     YulSuffix::Offset => {
-                        let rhs = rhs.cast(&lhs.ty(), ns);
+                        // Calldata arrays live in memory, so the offset is a memory address
+                        let rhs = Expression::Cast(
+                            *loc,
+                            ty.clone(),
+                            Box::new(rhs.cast(&Type::Uint(ns.target.ptr_size()), ns)),
+                        );
                         cfg.add(
                             vartab,
                             Instr::Set {
@@ -251,11 +416,20 @@ fn cfg_single_assigment(
                         );
                     }
                     YulSuffix::Length => {
-                        unimplemented!("Assignment to calldata array suffix is not implemented");
+                        array_length_assignment(loc, ty, *var_no, rhs, ns, cfg, vartab);
                     }
 
                     _ => unreachable!(),
                 },
+                ast::YulExpression::SolidityLocalVariable(
+                    _,
+                    ty,
+                    Some(StorageLocation::Memory(_)),
+                    var_no,
+                ) => {
+                    // sema only allows the '.length' suffix on dynamic memory arrays
+                    array_length_assignment(loc, ty, *var_no, rhs, ns, cfg, vartab);
+                }
                 ast::YulExpression::SolidityLocalVariable(
                     _,
                     ty @ Type::ExternalFunction { .. },
@@ -379,6 +553,137 @@ fn process_if_block(
     cfg.set_basic_block(endif);
 }
 
+/// Switch statements with fewer cases than this are lowered to a chain of conditional branches.
+/// Larger switches use a switch instruction, which LLVM lowers to a jump table or binary search.
+const SWITCH_BRANCH_CHAIN_LIMIT: usize = 3;
+
+/// Add a switch statement to the CFG
+fn process_switch(
+    loc: &pt::Loc,
+    condition: &ast::YulExpression,
+    cases: &[ast::CaseBlock],
+    default: &Option<ast::YulBlock>,
+    contract_no: usize,
+    loops: &mut LoopScopes,
+    ns: &Namespace,
+    cfg: &mut ControlFlowGraph,
+    vartab: &mut Vartable,
+    early_return: &Option<Instr>,
+    opt: &Options,
+) {
+    let cond = expression(condition, contract_no, ns, vartab, cfg, opt);
+    let ty = cond.ty();
+    let bits = ty.bits(ns) as u64;
+
+    let mut case_blocks: Vec<(Expression, usize, &ast::YulBlock)> = Vec::new();
+
+    for case in cases {
+        let value = match expression(&case.condition, contract_no, ns, vartab, cfg, opt) {
+            Expression::NumberLiteral(_, _, value) => value,
+            Expression::BoolLiteral(_, value) => BigInt::from(value as u8),
+            _ => unreachable!("case condition must be a literal"),
+        };
+
+        // a case with a value that does not fit into the condition type can never match
+        if value.bits() > bits {
+            continue;
+        }
+
+        let value = if ty == Type::Bool {
+            Expression::BoolLiteral(case.loc, value.is_one())
+        } else {
+            Expression::NumberLiteral(case.loc, ty.clone(), value)
+        };
+
+        let block = cfg.new_basic_block("case".to_string());
+
+        case_blocks.push((value, block, &case.block));
+    }
+
+    let default_block = default
+        .as_ref()
+        .map(|block| (cfg.new_basic_block("default".to_string()), block));
+    let end_switch = cfg.new_basic_block("end_switch".to_string());
+    let no_match = default_block.map_or(end_switch, |(block_no, _)| block_no);
+
+    if case_blocks.len() < SWITCH_BRANCH_CHAIN_LIMIT {
+        let cond = if case_blocks.len() > 1 {
+            // the condition is compared more than once, so evaluate it only once
+            let pos = vartab.temp_name("switch_condition", &ty);
+
+            cfg.add(
+                vartab,
+                Instr::Set {
+                    loc: *loc,
+                    res: pos,
+                    expr: cond,
+                },
+            );
+
+            Expression::Variable(*loc, ty, pos)
+        } else {
+            cond
+        };
+
+        for (case_no, (value, block, _)) in case_blocks.iter().enumerate() {
+            let next = if case_no == case_blocks.len() - 1 {
+                no_match
+            } else {
+                cfg.new_basic_block("case_cond".to_string())
+            };
+
+            cfg.add(
+                vartab,
+                Instr::BranchCond {
+                    cond: Expression::Equal(*loc, Box::new(cond.clone()), Box::new(value.clone())),
+                    true_block: *block,
+                    false_block: next,
+                },
+            );
+
+            cfg.set_basic_block(next);
+        }
+
+        if case_blocks.is_empty() {
+            cfg.add(vartab, Instr::Branch { block: no_match });
+        }
+    } else {
+        cfg.add(
+            vartab,
+            Instr::Switch {
+                cond,
+                cases: case_blocks
+                    .iter()
+                    .map(|(value, block, _)| (value.clone(), *block))
+                    .collect(),
+                default: no_match,
+            },
+        );
+    }
+
+    vartab.new_dirty_tracker();
+
+    for (block_no, block) in case_blocks
+        .iter()
+        .map(|(_, block_no, block)| (*block_no, *block))
+        .chain(default_block)
+    {
+        cfg.set_basic_block(block_no);
+
+        for stmt in &block.body {
+            statement(stmt, contract_no, loops, ns, cfg, vartab, early_return, opt);
+        }
+
+        if block.is_next_reachable() {
+            cfg.add(vartab, Instr::Branch { block: end_switch });
+        }
+    }
+
+    cfg.set_phis(end_switch, vartab.pop_dirty_tracker());
+
+    cfg.set_basic_block(end_switch);
+}
+
 /// Add the for statement to the CFG
 fn process_for_block(
     loc: &pt::Loc,
@@ -465,3 +770,72 @@ fn process_for_block(
     cfg.set_phis(end_block, set.clone());
     cfg.set_phis(cond_block, set);
 }
+
+/// Assigning to the length of a calldata or memory array shrinks the array in place. The array
+/// cannot grow, as there is no memory allocated beyond its current length. Only the length field
+/// of the vector is changed; its size field and elements are left as they are.
+fn array_length_assignment(
+    loc: &pt::Loc,
+    ty: &Type,
+    var_no: usize,
+    rhs: Expression,
+    ns: &Namespace,
+    cfg: &mut ControlFlowGraph,
+    vartab: &mut Vartable,
+) {
+    let array = Expression::Variable(*loc, ty.clone(), var_no);
+    let new_length = rhs.cast(&Type::Uint(256), ns);
+    let length = Expression::ZeroExt(
+        *loc,
+        Type::Uint(256),
+        Box::new(Expression::Builtin(
+            *loc,
+            vec![Type::Uint(32)],
+            Builtin::ArrayLength,
+            vec![array.clone()],
+        )),
+    );
+
+    let cond = Expression::LessEqual(*loc, Box::new(new_length.clone()), Box::new(length));
+
+    let out_of_bounds = cfg.new_basic_block("out_of_bounds".to_string());
+    let in_bounds = cfg.new_basic_block("in_bounds".to_string());
+
+    cfg.add(
+        vartab,
+        Instr::BranchCond {
+            cond,
+            true_block: in_bounds,
+            false_block: out_of_bounds,
+        },
+    );
+
+    cfg.set_basic_block(out_of_bounds);
+    cfg.add(vartab, Instr::AssertFailure { expr: None });
+
+    cfg.set_basic_block(in_bounds);
+    cfg.add(
+        vartab,
+        Instr::Store {
+            dest: Expression::StructMember(
+                *loc,
+                Type::Ref(Box::new(Type::Uint(32))),
+                Box::new(array),
+                0,
+            ),
+            data: new_length.clone().cast(&Type::Uint(32), ns),
+        },
+    );
+
+    // codegen may keep the length of a memory array in a variable, which must follow
+    if let Some(length_var) = cfg.array_lengths_temps.get(&var_no).copied() {
+        cfg.add(
+            vartab,
+            Instr::Set {
+                loc: pt::Loc::Codegen,
+                res: length_var,
+                expr: new_length.cast(&Type::Uint(32), ns),
+            },
+        );
+    }
+}
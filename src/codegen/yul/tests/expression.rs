@@ -124,6 +124,9 @@ fn contract_constant_variable() {
         using: vec![],
         layout: vec![],
         fixed_layout_size: Default::default(),
+        immutable_layout: vec![],
+        immutable_layout_size: Default::default(),
+        lamports_offset: None,
         functions: vec![],
         all_functions: Default::default(),
         virtual_functions: Default::default(),
@@ -131,6 +134,7 @@ fn contract_constant_variable() {
         variables: vec![var],
         creates: vec![],
         sends_events: vec![],
+        reverts_errors: vec![],
         initializer: None,
         default_constructor: None,
         cfg: vec![],
@@ -242,6 +246,9 @@ fn slot_suffix() {
         using: vec![],
         layout: vec![layout],
         fixed_layout_size: Default::default(),
+        immutable_layout: vec![],
+        immutable_layout_size: Default::default(),
+        lamports_offset: None,
         functions: vec![],
         all_functions: Default::default(),
         virtual_functions: Default::default(),
@@ -249,6 +256,7 @@ fn slot_suffix() {
         variables: vec![],
         creates: vec![],
         sends_events: vec![],
+        reverts_errors: vec![],
         initializer: None,
         default_constructor: None,
         cfg: vec![],
@@ -38,7 +38,6 @@ pub struct Binary<'a> {
     target: Target,
     pub(crate) function_abort_value_transfers: bool,
     pub(crate) constructor_abort_value_transfers: bool,
-    pub(crate) math_overflow_check: bool,
     pub builder: Builder<'a>,
     pub(crate) context: &'a Context,
     pub(crate) functions: HashMap<usize, FunctionValue<'a>>,
@@ -62,37 +61,18 @@ impl<'a> Binary<'a> {
         ns: &'a Namespace,
         filename: &'a str,
         opt: OptimizationLevel,
-        math_overflow_check: bool,
     ) -> Self {
         let std_lib = load_stdlib(context, &ns.target);
         match ns.target {
-            Target::Substrate { .. } => substrate::SubstrateTarget::build(
-                context,
-                &std_lib,
-                contract,
-                ns,
-                filename,
-                opt,
-                math_overflow_check,
-            ),
-            Target::Ewasm => ewasm::EwasmTarget::build(
-                context,
-                &std_lib,
-                contract,
-                ns,
-                filename,
-                opt,
-                math_overflow_check,
-            ),
-            Target::Solana => solana::SolanaTarget::build(
-                context,
-                &std_lib,
-                contract,
-                ns,
-                filename,
-                opt,
-                math_overflow_check,
-            ),
+            Target::Substrate { .. } => {
+                substrate::SubstrateTarget::build(context, &std_lib, contract, ns, filename, opt)
+            }
+            Target::Ewasm => {
+                ewasm::EwasmTarget::build(context, &std_lib, contract, ns, filename, opt)
+            }
+            Target::Solana => {
+                solana::SolanaTarget::build(context, &std_lib, contract, ns, filename, opt)
+            }
         }
     }
 
@@ -102,19 +82,11 @@ impl<'a> Binary<'a> {
         namespaces: &'a [&Namespace],
         filename: &str,
         opt: OptimizationLevel,
-        math_overflow_check: bool,
     ) -> Self {
         assert!(namespaces.iter().all(|ns| ns.target == Target::Solana));
 
         let std_lib = load_stdlib(context, &Target::Solana);
-        solana::SolanaTarget::build_bundle(
-            context,
-            &std_lib,
-            namespaces,
-            filename,
-            opt,
-            math_overflow_check,
-        )
+        solana::SolanaTarget::build_bundle(context, &std_lib, namespaces, filename, opt)
     }
 
     /// Compile the bin and return the code as bytes. The result is
@@ -246,7 +218,6 @@ impl<'a> Binary<'a> {
         name: &str,
         filename: &str,
         opt: OptimizationLevel,
-        math_overflow_check: bool,
         std_lib: &Module<'a>,
         runtime: Option<Box<Binary<'a>>>,
     ) -> Self {
@@ -307,7 +278,6 @@ impl<'a> Binary<'a> {
             runtime,
             function_abort_value_transfers: false,
             constructor_abort_value_transfers: false,
-            math_overflow_check,
             builder: context.create_builder(),
             context,
             target,
@@ -381,6 +351,53 @@ impl<'a> Binary<'a> {
         )
     }
 
+    /// Creates the buffer which holds the immutable variables of the contract, if it has any
+    pub(crate) fn emit_immutables(&self, contract: &Contract) {
+        let size = contract.immutable_layout_size.to_u32().unwrap();
+
+        if size > 0 {
+            let ty = self.context.i8_type().array_type(size);
+
+            let gv = self
+                .module
+                .add_global(ty, Some(AddressSpace::Generic), "immutables");
+
+            gv.set_linkage(Linkage::Internal);
+            gv.set_initializer(&ty.const_zero());
+        }
+    }
+
+    /// Returns a pointer to the immutable variable at the given offset in the immutables buffer
+    pub(crate) fn immutable_pointer(
+        &self,
+        ty: &Type,
+        offset: usize,
+        ns: &Namespace,
+    ) -> PointerValue<'a> {
+        let immutables = self
+            .module
+            .get_global("immutables")
+            .unwrap()
+            .as_pointer_value();
+
+        let ptr = unsafe {
+            self.builder.build_gep(
+                immutables,
+                &[
+                    self.context.i32_type().const_zero(),
+                    self.context.i32_type().const_int(offset as u64, false),
+                ],
+                "immutable",
+            )
+        };
+
+        self.builder.build_pointer_cast(
+            ptr,
+            self.llvm_type(ty, ns).ptr_type(AddressSpace::Generic),
+            "immutable",
+        )
+    }
+
     /// Wrapper for alloca. Ensures that the alloca is done on the first basic block.
     /// If alloca is not on the first basic block, llvm will get to llvm_unreachable
     /// for the BPF target.
@@ -785,7 +802,7 @@ impl<'a> Binary<'a> {
         } else {
             match ty {
                 Type::Bool => BasicTypeEnum::IntType(self.context.bool_type()),
-                Type::Int(n) | Type::Uint(n) => {
+                Type::Int(n) | Type::Uint(n) | Type::Fixed(n, _) | Type::Ufixed(n, _) => {
                     BasicTypeEnum::IntType(self.context.custom_width_int_type(*n as u32))
                 }
                 Type::Value => BasicTypeEnum::IntType(
@@ -869,7 +886,7 @@ impl<'a> Binary<'a> {
                 Type::Slice(ty) => BasicTypeEnum::StructType(
                     self.context.struct_type(
                         &[
-                            self.llvm_type(ty, ns)
+                            self.llvm_field_ty(ty, ns)
                                 .ptr_type(AddressSpace::Generic)
                                 .into(),
                             self.context
@@ -1035,10 +1052,17 @@ impl<'a> Binary<'a> {
             // slice
             let slice = vector.into_struct_value();
 
-            self.builder
+            let data = self
+                .builder
                 .build_extract_value(slice, 0, "slice_data")
                 .unwrap()
-                .into_pointer_value()
+                .into_pointer_value();
+
+            self.builder.build_pointer_cast(
+                data,
+                self.context.i8_type().ptr_type(AddressSpace::Generic),
+                "data",
+            )
         } else {
             let data = unsafe {
                 self.builder.build_gep(
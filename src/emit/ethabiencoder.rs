@@ -186,7 +186,8 @@ impl<'a, 'b> EncoderBuilder<'a, 'b> {
 
                 sum.as_basic_value().into_int_value()
             }
-            ast::Type::Array(elem_ty, dims) if elem_ty.is_dynamic(ns) => {
+            ast::Type::Array(_, dims) if ty.array_elem().is_dynamic(ns) => {
+                let elem_ty = &ty.array_elem();
                 let arg = if load {
                     binary.builder.build_load(arg.into_pointer_value(), "")
                 } else {
@@ -281,7 +282,8 @@ impl<'a, 'b> EncoderBuilder<'a, 'b> {
 
                 encoded_length.as_basic_value().into_int_value()
             }
-            ast::Type::Array(elem_ty, dims) => {
+            ast::Type::Array(_, dims) => {
+                let elem_ty = &ty.array_elem();
                 let arg = if load {
                     binary.builder.build_load(arg.into_pointer_value(), "")
                 } else {
@@ -315,7 +317,10 @@ impl<'a, 'b> EncoderBuilder<'a, 'b> {
 
                 binary.vector_len(arg)
             }
-            ast::Type::Uint(n) | ast::Type::Int(n) => {
+            ast::Type::Uint(n)
+            | ast::Type::Int(n)
+            | ast::Type::Fixed(n, _)
+            | ast::Type::Ufixed(n, _) => {
                 binary.context.i32_type().const_int((*n as u64) / 8, false)
             }
             ast::Type::Bytes(n) => binary.context.i32_type().const_int(*n as u64, false),
@@ -443,7 +448,8 @@ impl<'a, 'b> EncoderBuilder<'a, 'b> {
 
                 sum.as_basic_value().into_int_value()
             }
-            ast::Type::Array(elem_ty, dims) if ty.is_dynamic(ns) => {
+            ast::Type::Array(_, dims) if ty.is_dynamic(ns) => {
+                let elem_ty = &ty.array_elem();
                 let arg = if load {
                     binary.builder.build_load(arg.into_pointer_value(), "")
                 } else {
@@ -598,6 +604,8 @@ impl<'a, 'b> EncoderBuilder<'a, 'b> {
             | ast::Type::Address(_)
             | ast::Type::Int(_)
             | ast::Type::Uint(_)
+            | ast::Type::Fixed(..)
+            | ast::Type::Ufixed(..)
             | ast::Type::Bytes(_)
             | ast::Type::ExternalFunction { .. } => 32,
             // String and Dynamic bytes use 32 bytes for the offset into dynamic encoded
@@ -712,6 +720,8 @@ impl<'a, 'b> EncoderBuilder<'a, 'b> {
             | ast::Type::Contract(_)
             | ast::Type::Int(_)
             | ast::Type::Uint(_)
+            | ast::Type::Fixed(..)
+            | ast::Type::Ufixed(..)
             | ast::Type::Bytes(_) => {
                 self.encode_primitive(binary, load, function, ty, *fixed, arg, ns);
 
@@ -747,7 +757,8 @@ impl<'a, 'b> EncoderBuilder<'a, 'b> {
                     )
                 };
             }
-            ast::Type::Array(elem_ty, dim) if ty.is_dynamic(ns) => {
+            ast::Type::Array(_, dim) if ty.is_dynamic(ns) => {
+                let elem_ty = &ty.array_elem();
                 let arg = if load {
                     binary.builder.build_load(arg.into_pointer_value(), "")
                 } else {
@@ -990,7 +1001,8 @@ impl<'a, 'b> EncoderBuilder<'a, 'b> {
                     "new_offset",
                 );
             }
-            ast::Type::Array(elem_ty, dim) => {
+            ast::Type::Array(_, dim) => {
+                let elem_ty = &ty.array_elem();
                 let arg = if load {
                     binary.builder.build_load(arg.into_pointer_value(), "")
                 } else {
@@ -1598,7 +1610,11 @@ impl<'a, 'b> EncoderBuilder<'a, 'b> {
                     )
                 };
             }
-            ast::Type::Bytes(1) | ast::Type::Int(8) | ast::Type::Uint(8) => {
+            ast::Type::Bytes(1)
+            | ast::Type::Int(8)
+            | ast::Type::Uint(8)
+            | ast::Type::Fixed(8, _)
+            | ast::Type::Ufixed(8, _) => {
                 let arg = if load {
                     binary.builder.build_load(arg.into_pointer_value(), "")
                 } else {
@@ -1615,7 +1631,10 @@ impl<'a, 'b> EncoderBuilder<'a, 'b> {
                     )
                 };
             }
-            ast::Type::Uint(n) | ast::Type::Int(n)
+            ast::Type::Uint(n)
+            | ast::Type::Int(n)
+            | ast::Type::Fixed(n, _)
+            | ast::Type::Ufixed(n, _)
                 if self.bswap && (*n == 16 || *n == 32 || *n == 64) =>
             {
                 let arg = if load {
@@ -1652,7 +1671,12 @@ impl<'a, 'b> EncoderBuilder<'a, 'b> {
                     )
                 };
             }
-            ast::Type::Uint(bits) | ast::Type::Int(bits) if load => {
+            ast::Type::Uint(bits)
+            | ast::Type::Int(bits)
+            | ast::Type::Fixed(bits, _)
+            | ast::Type::Ufixed(bits, _)
+                if load =>
+            {
                 let arg8 = binary.builder.build_pointer_cast(
                     arg.into_pointer_value(),
                     binary.context.i8_type().ptr_type(AddressSpace::Generic),
@@ -1712,7 +1736,12 @@ impl<'a, 'b> EncoderBuilder<'a, 'b> {
 
                 *output = unsafe { binary.builder.build_gep(*output, &[len], "") };
             }
-            ast::Type::Uint(bits) | ast::Type::Int(bits) if !load => {
+            ast::Type::Uint(bits)
+            | ast::Type::Int(bits)
+            | ast::Type::Fixed(bits, _)
+            | ast::Type::Ufixed(bits, _)
+                if !load =>
+            {
                 let temp = binary.build_alloca(
                     function,
                     arg.into_int_value().get_type(),
@@ -1778,7 +1807,8 @@ impl<'a, 'b> EncoderBuilder<'a, 'b> {
 
                 *output = unsafe { binary.builder.build_gep(*output, &[len], "") };
             }
-            ast::Type::Array(elem_ty, dim) => {
+            ast::Type::Array(_, dim) => {
+                let elem_ty = &ty.array_elem();
                 let arg = if load {
                     binary.builder.build_load(arg.into_pointer_value(), "")
                 } else {
@@ -2069,7 +2099,10 @@ impl<'a, 'b> EncoderBuilder<'a, 'b> {
 
                 binary.builder.build_store(dest, value);
             }
-            ast::Type::Int(8) | ast::Type::Uint(8) => {
+            ast::Type::Int(8)
+            | ast::Type::Uint(8)
+            | ast::Type::Fixed(8, _)
+            | ast::Type::Ufixed(8, _) => {
                 let arg = if load {
                     binary.builder.build_load(arg.into_pointer_value(), "")
                 } else {
@@ -2082,7 +2115,7 @@ impl<'a, 'b> EncoderBuilder<'a, 'b> {
                     "destvoid",
                 );
 
-                if let ast::Type::Int(_) = ty {
+                if ty.is_signed_numeric() {
                     let negative = binary.builder.build_int_compare(
                         IntPredicate::SLT,
                         arg.into_int_value(),
@@ -2121,7 +2154,10 @@ impl<'a, 'b> EncoderBuilder<'a, 'b> {
 
                 binary.builder.build_store(dest, arg);
             }
-            ast::Type::Uint(n) | ast::Type::Int(n)
+            ast::Type::Uint(n)
+            | ast::Type::Int(n)
+            | ast::Type::Fixed(n, _)
+            | ast::Type::Ufixed(n, _)
                 if self.bswap && (*n == 16 || *n == 32 || *n == 64) =>
             {
                 let arg = if load {
@@ -2136,7 +2172,7 @@ impl<'a, 'b> EncoderBuilder<'a, 'b> {
                     "dest8",
                 );
 
-                if let ast::Type::Int(_) = ty {
+                if ty.is_signed_numeric() {
                     let negative = binary.builder.build_int_compare(
                         IntPredicate::SLT,
                         arg.into_int_value(),
@@ -2234,7 +2270,12 @@ impl<'a, 'b> EncoderBuilder<'a, 'b> {
 
                 binary.builder.build_store(address, arg);
             }
-            ast::Type::Uint(bits) | ast::Type::Int(bits) if load => {
+            ast::Type::Uint(bits)
+            | ast::Type::Int(bits)
+            | ast::Type::Fixed(bits, _)
+            | ast::Type::Ufixed(bits, _)
+                if load =>
+            {
                 let dest8 = binary.builder.build_pointer_cast(
                     dest,
                     binary.context.i8_type().ptr_type(AddressSpace::Generic),
@@ -2248,49 +2289,47 @@ impl<'a, 'b> EncoderBuilder<'a, 'b> {
                 );
 
                 // first clear/set the upper bits
-                if *bits < 256 {
-                    if let ast::Type::Int(_) = ty {
-                        let signdest = unsafe {
-                            binary.builder.build_gep(
-                                arg8,
-                                &[binary
-                                    .context
-                                    .i32_type()
-                                    .const_int((*bits as u64 / 8) - 1, false)],
-                                "signbyte",
-                            )
-                        };
-
-                        let negative = binary.builder.build_int_compare(
-                            IntPredicate::SLT,
-                            binary
-                                .builder
-                                .build_load(signdest, "signbyte")
-                                .into_int_value(),
-                            binary.context.i8_type().const_zero(),
-                            "neg",
-                        );
+                if *bits < 256 && ty.is_signed_numeric() {
+                    let signdest = unsafe {
+                        binary.builder.build_gep(
+                            arg8,
+                            &[binary
+                                .context
+                                .i32_type()
+                                .const_int((*bits as u64 / 8) - 1, false)],
+                            "signbyte",
+                        )
+                    };
 
-                        let signval = binary
+                    let negative = binary.builder.build_int_compare(
+                        IntPredicate::SLT,
+                        binary
                             .builder
-                            .build_select(
-                                negative,
-                                binary.context.i64_type().const_int(std::u64::MAX, true),
-                                binary.context.i64_type().const_zero(),
-                                "val",
-                            )
-                            .into_int_value();
+                            .build_load(signdest, "signbyte")
+                            .into_int_value(),
+                        binary.context.i8_type().const_zero(),
+                        "neg",
+                    );
 
-                        binary.builder.build_call(
-                            binary.module.get_function("__memset8").unwrap(),
-                            &[
-                                dest8.into(),
-                                signval.into(),
-                                binary.context.i32_type().const_int(4, false).into(),
-                            ],
-                            "",
-                        );
-                    }
+                    let signval = binary
+                        .builder
+                        .build_select(
+                            negative,
+                            binary.context.i64_type().const_int(std::u64::MAX, true),
+                            binary.context.i64_type().const_zero(),
+                            "val",
+                        )
+                        .into_int_value();
+
+                    binary.builder.build_call(
+                        binary.module.get_function("__memset8").unwrap(),
+                        &[
+                            dest8.into(),
+                            signval.into(),
+                            binary.context.i32_type().const_int(4, false).into(),
+                        ],
+                        "",
+                    );
                 }
 
                 binary.builder.build_call(
@@ -2307,7 +2346,12 @@ impl<'a, 'b> EncoderBuilder<'a, 'b> {
                     "",
                 );
             }
-            ast::Type::Uint(bits) | ast::Type::Int(bits) if !load => {
+            ast::Type::Uint(bits)
+            | ast::Type::Int(bits)
+            | ast::Type::Fixed(bits, _)
+            | ast::Type::Ufixed(bits, _)
+                if !load =>
+            {
                 let dest8 = binary.builder.build_pointer_cast(
                     dest,
                     binary.context.i8_type().ptr_type(AddressSpace::Generic),
@@ -2315,35 +2359,33 @@ impl<'a, 'b> EncoderBuilder<'a, 'b> {
                 );
 
                 // first clear/set the upper bits
-                if *bits < 256 {
-                    if let ast::Type::Int(_) = ty {
-                        let negative = binary.builder.build_int_compare(
-                            IntPredicate::SLT,
-                            arg.into_int_value(),
-                            arg.get_type().into_int_type().const_zero(),
-                            "neg",
-                        );
+                if *bits < 256 && ty.is_signed_numeric() {
+                    let negative = binary.builder.build_int_compare(
+                        IntPredicate::SLT,
+                        arg.into_int_value(),
+                        arg.get_type().into_int_type().const_zero(),
+                        "neg",
+                    );
 
-                        let signval = binary
-                            .builder
-                            .build_select(
-                                negative,
-                                binary.context.i64_type().const_int(std::u64::MAX, true),
-                                binary.context.i64_type().const_zero(),
-                                "val",
-                            )
-                            .into_int_value();
+                    let signval = binary
+                        .builder
+                        .build_select(
+                            negative,
+                            binary.context.i64_type().const_int(std::u64::MAX, true),
+                            binary.context.i64_type().const_zero(),
+                            "val",
+                        )
+                        .into_int_value();
 
-                        binary.builder.build_call(
-                            binary.module.get_function("__memset8").unwrap(),
-                            &[
-                                dest8.into(),
-                                signval.into(),
-                                binary.context.i32_type().const_int(4, false).into(),
-                            ],
-                            "",
-                        );
-                    }
+                    binary.builder.build_call(
+                        binary.module.get_function("__memset8").unwrap(),
+                        &[
+                            dest8.into(),
+                            signval.into(),
+                            binary.context.i32_type().const_int(4, false).into(),
+                        ],
+                        "",
+                    );
                 }
 
                 let temp = binary.build_alloca(
@@ -2503,7 +2545,10 @@ impl EthAbiDecoder {
                 }
                 val.into()
             }
-            ast::Type::Uint(8) | ast::Type::Int(8) => {
+            ast::Type::Uint(8)
+            | ast::Type::Int(8)
+            | ast::Type::Fixed(8, _)
+            | ast::Type::Ufixed(8, _) => {
                 let int8_ptr = unsafe {
                     binary.builder.build_gep(
                         data,
@@ -2548,7 +2593,12 @@ impl EthAbiDecoder {
                     address.into()
                 }
             }
-            ast::Type::Uint(n) | ast::Type::Int(n) if self.bswap && *n <= 64 => {
+            ast::Type::Uint(n)
+            | ast::Type::Int(n)
+            | ast::Type::Fixed(n, _)
+            | ast::Type::Ufixed(n, _)
+                if self.bswap && *n <= 64 =>
+            {
                 let bits = if n.is_power_of_two() {
                     *n
                 } else {
@@ -2605,7 +2655,10 @@ impl EthAbiDecoder {
 
                 val.into()
             }
-            ast::Type::Uint(n) | ast::Type::Int(n) => {
+            ast::Type::Uint(n)
+            | ast::Type::Int(n)
+            | ast::Type::Fixed(n, _)
+            | ast::Type::Ufixed(n, _) => {
                 let int_type = binary.context.custom_width_int_type(*n as u32);
                 let type_size = int_type.size_of();
 
@@ -2691,7 +2744,10 @@ impl EthAbiDecoder {
         ns: &ast::Namespace,
     ) -> BasicValueEnum<'b> {
         match &ty {
-            ast::Type::Array(elem_ty, dim) => {
+            ast::Type::Array(_, dim) => {
+                // the elements of a multidimensional array are arrays themselves
+                let elem_ty = &ty.array_elem();
+
                 let llvm_ty = binary.llvm_type(ty.deref_any(), ns);
 
                 let size = llvm_ty
@@ -18,6 +18,8 @@ use inkwell::values::{
 use inkwell::AddressSpace;
 use inkwell::IntPredicate;
 use inkwell::OptimizationLevel;
+use num_traits::ToPrimitive;
+use parity_wasm::elements::{Serialize, VarUint32};
 
 use crate::emit::ethabiencoder;
 use crate::emit::Generate;
@@ -35,7 +37,6 @@ impl EwasmTarget {
         ns: &'a ast::Namespace,
         filename: &'a str,
         opt: OptimizationLevel,
-        math_overflow_check: bool,
     ) -> Binary<'a> {
         // first emit runtime code
         let mut b = EwasmTarget {
@@ -47,12 +48,12 @@ impl EwasmTarget {
             &contract.name,
             filename,
             opt,
-            math_overflow_check,
             std_lib,
             None,
         );
 
         runtime_code.set_early_value_aborts(contract, ns);
+        runtime_code.emit_immutables(contract);
 
         // externals
         b.declare_externals(&mut runtime_code);
@@ -77,12 +78,12 @@ impl EwasmTarget {
             &contract.name,
             filename,
             opt,
-            math_overflow_check,
             std_lib,
             Some(Box::new(runtime_code)),
         );
 
         deploy_code.set_early_value_aborts(contract, ns);
+        deploy_code.emit_immutables(contract);
 
         // externals
         b.declare_externals(&mut deploy_code);
@@ -671,20 +672,93 @@ impl EwasmTarget {
         }
 
         // the deploy code should return the runtime wasm code
-        let runtime_code = binary.emit_global_string("runtime_code", runtime, true);
+        if let Some(immutables) = binary.module.get_global("immutables") {
+            let size = contract.immutable_layout_size.to_u32().unwrap();
+
+            // the immutables are appended to the runtime code in a custom section, so that
+            // the runtime code can read them using codeCopy
+            let name = b"immutables";
+            let mut header = Vec::new();
+            VarUint32::from(name.len() as u32)
+                .serialize(&mut header)
+                .unwrap();
+            header.extend(name);
+
+            let mut code = runtime.to_vec();
+            code.push(0);
+            VarUint32::from(header.len() as u32 + size)
+                .serialize(&mut code)
+                .unwrap();
+            code.extend(header);
+
+            let code_len = binary
+                .context
+                .i32_type()
+                .const_int(code.len() as u64, false);
+            let size = binary.context.i32_type().const_int(size as u64, false);
 
-        binary.builder.build_call(
-            binary.module.get_function("finish").unwrap(),
-            &[
-                runtime_code.into(),
-                binary
-                    .context
-                    .i32_type()
-                    .const_int(runtime.len() as u64, false)
-                    .into(),
-            ],
-            "",
-        );
+            let runtime_code = binary.emit_global_string("runtime_code", &code, true);
+
+            let len = binary.builder.build_int_add(code_len, size, "len");
+
+            let dest = binary
+                .builder
+                .build_call(
+                    binary.module.get_function("__malloc").unwrap(),
+                    &[len.into()],
+                    "",
+                )
+                .try_as_basic_value()
+                .left()
+                .unwrap()
+                .into_pointer_value();
+
+            binary.builder.build_call(
+                binary.module.get_function("__memcpy").unwrap(),
+                &[dest.into(), runtime_code.into(), code_len.into()],
+                "",
+            );
+
+            let tail = unsafe { binary.builder.build_gep(dest, &[code_len], "immutables") };
+
+            binary.builder.build_call(
+                binary.module.get_function("__memcpy").unwrap(),
+                &[
+                    tail.into(),
+                    binary
+                        .builder
+                        .build_pointer_cast(
+                            immutables.as_pointer_value(),
+                            binary.context.i8_type().ptr_type(AddressSpace::Generic),
+                            "",
+                        )
+                        .into(),
+                    size.into(),
+                ],
+                "",
+            );
+
+            binary.builder.build_call(
+                binary.module.get_function("finish").unwrap(),
+                &[dest.into(), len.into()],
+                "",
+            );
+        } else {
+            let runtime_code = binary.emit_global_string("runtime_code", runtime, true);
+
+            binary.builder.build_call(
+                binary.module.get_function("finish").unwrap(),
+                &[
+                    runtime_code.into(),
+                    binary
+                        .context
+                        .i32_type()
+                        .const_int(runtime.len() as u64, false)
+                        .into(),
+                ],
+                "",
+            );
+        }
 
         // since finish is marked noreturn, this should be optimized away
         // however it is needed to create valid LLVM IR
@@ -704,6 +778,46 @@ impl EwasmTarget {
 
         let (argsdata, argslen) = self.runtime_prelude(binary, function, ns);
 
+        // the immutables are at the end of the code, see deployer_dispatch()
+        if let Some(immutables) = binary.module.get_global("immutables") {
+            let size = binary
+                .context
+                .i32_type()
+                .const_int(contract.immutable_layout_size.to_u64().unwrap(), false);
+
+            let code_size = binary
+                .builder
+                .build_call(
+                    binary.module.get_function("getCodeSize").unwrap(),
+                    &[],
+                    "codesize",
+                )
+                .try_as_basic_value()
+                .left()
+                .unwrap()
+                .into_int_value();
+
+            binary.builder.build_call(
+                binary.module.get_function("codeCopy").unwrap(),
+                &[
+                    binary
+                        .builder
+                        .build_pointer_cast(
+                            immutables.as_pointer_value(),
+                            binary.context.i8_type().ptr_type(AddressSpace::Generic),
+                            "",
+                        )
+                        .into(),
+                    binary
+                        .builder
+                        .build_int_sub(code_size, size, "offset")
+                        .into(),
+                    size.into(),
+                ],
+                "",
+            );
+        }
+
         self.emit_function_dispatch(
             binary,
             contract,
@@ -851,24 +965,141 @@ impl<'a> TargetRuntime<'a> for EwasmTarget {
     ) -> PointerValue<'a> {
         unimplemented!();
     }
+
     fn set_storage_extfunc(
         &self,
-        _binary: &Binary,
+        binary: &Binary,
         _function: FunctionValue,
-        _slot: PointerValue,
-        _dest: PointerValue,
+        slot: PointerValue,
+        dest: PointerValue,
     ) {
-        unimplemented!();
+        // address and selector fit into a single 32 byte storage slot
+        let value = binary
+            .builder
+            .build_alloca(binary.context.custom_width_int_type(256), "value");
+
+        let value8 = binary.builder.build_pointer_cast(
+            value,
+            binary.context.i8_type().ptr_type(AddressSpace::Generic),
+            "value8",
+        );
+
+        binary.builder.build_call(
+            binary.module.get_function("__bzero8").unwrap(),
+            &[
+                value8.into(),
+                binary.context.i32_type().const_int(4, false).into(),
+            ],
+            "",
+        );
+
+        binary.builder.build_call(
+            binary.module.get_function("__memcpy").unwrap(),
+            &[
+                value8.into(),
+                binary
+                    .builder
+                    .build_pointer_cast(
+                        dest,
+                        binary.context.i8_type().ptr_type(AddressSpace::Generic),
+                        "",
+                    )
+                    .into(),
+                dest.get_type()
+                    .get_element_type()
+                    .size_of()
+                    .unwrap()
+                    .const_cast(binary.context.i32_type(), false)
+                    .into(),
+            ],
+            "",
+        );
+
+        binary.builder.build_call(
+            binary.module.get_function("storageStore").unwrap(),
+            &[
+                binary
+                    .builder
+                    .build_pointer_cast(
+                        slot,
+                        binary.context.i8_type().ptr_type(AddressSpace::Generic),
+                        "",
+                    )
+                    .into(),
+                value8.into(),
+            ],
+            "",
+        );
     }
+
     fn get_storage_extfunc(
         &self,
-        _binary: &Binary<'a>,
+        binary: &Binary<'a>,
         _function: FunctionValue,
-        _slot: PointerValue<'a>,
-        _ns: &ast::Namespace,
+        slot: PointerValue<'a>,
+        ns: &ast::Namespace,
     ) -> PointerValue<'a> {
-        unimplemented!();
+        let ty = binary.llvm_type(
+            &ast::Type::ExternalFunction {
+                params: Vec::new(),
+                mutability: ast::Mutability::Nonpayable(pt::Loc::Codegen),
+                returns: Vec::new(),
+            },
+            ns,
+        );
+
+        let len = ty
+            .into_pointer_type()
+            .get_element_type()
+            .size_of()
+            .unwrap()
+            .const_cast(binary.context.i32_type(), false);
+
+        let buf = binary.builder.build_array_alloca(
+            binary.context.i8_type(),
+            binary.context.i32_type().const_int(32, false),
+            "buf",
+        );
+
+        binary.builder.build_call(
+            binary.module.get_function("storageLoad").unwrap(),
+            &[
+                binary
+                    .builder
+                    .build_pointer_cast(
+                        slot,
+                        binary.context.i8_type().ptr_type(AddressSpace::Generic),
+                        "",
+                    )
+                    .into(),
+                buf.into(),
+            ],
+            "",
+        );
+
+        let ef = binary
+            .builder
+            .build_call(
+                binary.module.get_function("__malloc").unwrap(),
+                &[len.into()],
+                "",
+            )
+            .try_as_basic_value()
+            .left()
+            .unwrap()
+            .into_pointer_value();
+
+        binary.builder.build_call(
+            binary.module.get_function("__memcpy").unwrap(),
+            &[ef.into(), buf.into(), len.into()],
+            "",
+        );
+
+        binary
+            .builder
+            .build_pointer_cast(ef, ty.into_pointer_type(), "function_type")
     }
+
     fn get_storage_bytes_subscript(
         &self,
         _binary: &Binary<'a>,
@@ -1302,14 +1533,7 @@ impl<'a> TargetRuntime<'a> for EwasmTarget {
     ) {
         let resolver_binary = &ns.contracts[contract_no];
 
-        let target_binary = Binary::build(
-            binary.context,
-            resolver_binary,
-            ns,
-            "",
-            binary.opt,
-            binary.math_overflow_check,
-        );
+        let target_binary = Binary::build(binary.context, resolver_binary, ns, "", binary.opt);
 
         // wasm
         let wasm = target_binary
@@ -2094,6 +2318,157 @@ impl<'a> TargetRuntime<'a> for EwasmTarget {
                     .left()
                     .unwrap()
             }
+            codegen::Expression::Builtin(_, _, codegen::Builtin::ECRecover, args) => {
+                assert_eq!(args.len(), 4);
+
+                // the ecrecover precompile takes hash, v, r, s as big endian 32 byte words
+                let input = binary.builder.build_array_alloca(
+                    binary.context.i8_type(),
+                    binary.context.i32_type().const_int(128, false),
+                    "input",
+                );
+
+                for (i, arg) in args.iter().enumerate() {
+                    let value = binary.build_alloca(
+                        function,
+                        binary.context.custom_width_int_type(256),
+                        "value",
+                    );
+
+                    let arg = self
+                        .expression(binary, arg, vartab, function, ns)
+                        .into_int_value();
+
+                    binary.builder.build_store(
+                        value,
+                        binary.builder.build_int_z_extend_or_bit_cast(
+                            arg,
+                            binary.context.custom_width_int_type(256),
+                            "",
+                        ),
+                    );
+
+                    binary.builder.build_call(
+                        binary.module.get_function("__leNtobeN").unwrap(),
+                        &[
+                            binary
+                                .builder
+                                .build_pointer_cast(
+                                    value,
+                                    binary.context.i8_type().ptr_type(AddressSpace::Generic),
+                                    "",
+                                )
+                                .into(),
+                            unsafe {
+                                binary.builder.build_gep(
+                                    input,
+                                    &[binary.context.i32_type().const_int(i as u64 * 32, false)],
+                                    "",
+                                )
+                            }
+                            .into(),
+                            binary.context.i32_type().const_int(32, false).into(),
+                        ],
+                        "",
+                    );
+                }
+
+                let balance = binary
+                    .builder
+                    .build_alloca(binary.value_type(ns), "balance");
+
+                binary
+                    .builder
+                    .build_store(balance, binary.value_type(ns).const_zero());
+
+                let address = binary.emit_global_string(
+                    "precompile",
+                    &[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1],
+                    true,
+                );
+
+                binary.builder.build_call(
+                    binary.module.get_function("call").unwrap(),
+                    &[
+                        binary
+                            .context
+                            .i64_type()
+                            .const_int(i64::MAX as u64, false)
+                            .into(),
+                        binary
+                            .builder
+                            .build_pointer_cast(
+                                address,
+                                binary.context.i8_type().ptr_type(AddressSpace::Generic),
+                                "address",
+                            )
+                            .into(),
+                        binary
+                            .builder
+                            .build_pointer_cast(
+                                balance,
+                                binary.context.i8_type().ptr_type(AddressSpace::Generic),
+                                "balance",
+                            )
+                            .into(),
+                        input.into(),
+                        binary.context.i32_type().const_int(128, false).into(),
+                    ],
+                    "",
+                );
+
+                // on failure, the precompile returns no data, and the result is the zero address
+                let res = binary
+                    .builder
+                    .build_alloca(binary.context.custom_width_int_type(256), "res");
+
+                binary
+                    .builder
+                    .build_store(res, binary.context.custom_width_int_type(256).const_zero());
+
+                let res = binary.builder.build_pointer_cast(
+                    res,
+                    binary.context.i8_type().ptr_type(AddressSpace::Generic),
+                    "res",
+                );
+
+                let length = binary
+                    .builder
+                    .build_call(
+                        binary.module.get_function("getReturnDataSize").unwrap(),
+                        &[],
+                        "returndatasize",
+                    )
+                    .try_as_basic_value()
+                    .left()
+                    .unwrap();
+
+                binary.builder.build_call(
+                    binary.module.get_function("returnDataCopy").unwrap(),
+                    &[
+                        res.into(),
+                        binary.context.i32_type().const_zero().into(),
+                        length.into(),
+                    ],
+                    "",
+                );
+
+                // the address is in the last 20 bytes of the word
+                binary.builder.build_load(
+                    binary.builder.build_pointer_cast(
+                        unsafe {
+                            binary.builder.build_gep(
+                                res,
+                                &[binary.context.i32_type().const_int(12, false)],
+                                "",
+                            )
+                        },
+                        binary.address_type(ns).ptr_type(AddressSpace::Generic),
+                        "",
+                    ),
+                    "signer",
+                )
+            }
             _ => unimplemented!("{:?}", expr),
         }
     }
@@ -22,7 +22,7 @@ use inkwell::targets::TargetTriple;
 use inkwell::types::{BasicType, IntType, StringRadix};
 use inkwell::values::{
     ArrayValue, BasicMetadataValueEnum, BasicValueEnum, CallableValue, FunctionValue, IntValue,
-    PhiValue, PointerValue,
+    PhiValue, PointerValue, StructValue,
 };
 use inkwell::AddressSpace;
 use inkwell::IntPredicate;
@@ -35,7 +35,7 @@ pub mod solana;
 pub mod substrate;
 
 use crate::codegen::{
-    cfg::{ControlFlowGraph, HashTy, Instr, InternalCallTy},
+    cfg::{ASTFunction, ControlFlowGraph, HashTy, Instr, InternalCallTy},
     vartable::Storage,
 };
 use crate::emit::binary::Binary;
@@ -352,43 +352,41 @@ pub trait TargetRuntime<'a> {
 
     /// If we receive a value transfer, and we are "payable", abort with revert
     fn abort_if_value_transfer(&self, binary: &Binary, function: FunctionValue, ns: &Namespace) {
-        if ns.target != Target::Solana {
-            let value = self.value_transferred(binary, ns);
-
-            let got_value = binary.builder.build_int_compare(
-                IntPredicate::NE,
-                value,
-                binary.value_type(ns).const_zero(),
-                "is_value_transfer",
-            );
+        let value = self.value_transferred(binary, ns);
 
-            let not_value_transfer = binary
-                .context
-                .append_basic_block(function, "not_value_transfer");
-            let abort_value_transfer = binary
-                .context
-                .append_basic_block(function, "abort_value_transfer");
+        let got_value = binary.builder.build_int_compare(
+            IntPredicate::NE,
+            value,
+            binary.value_type(ns).const_zero(),
+            "is_value_transfer",
+        );
 
-            binary.builder.build_conditional_branch(
-                got_value,
-                abort_value_transfer,
-                not_value_transfer,
-            );
+        let not_value_transfer = binary
+            .context
+            .append_basic_block(function, "not_value_transfer");
+        let abort_value_transfer = binary
+            .context
+            .append_basic_block(function, "abort_value_transfer");
+
+        binary.builder.build_conditional_branch(
+            got_value,
+            abort_value_transfer,
+            not_value_transfer,
+        );
 
-            binary.builder.position_at_end(abort_value_transfer);
+        binary.builder.position_at_end(abort_value_transfer);
 
-            self.assert_failure(
-                binary,
-                binary
-                    .context
-                    .i8_type()
-                    .ptr_type(AddressSpace::Generic)
-                    .const_null(),
-                binary.context.i32_type().const_zero(),
-            );
+        self.assert_failure(
+            binary,
+            binary
+                .context
+                .i8_type()
+                .ptr_type(AddressSpace::Generic)
+                .const_null(),
+            binary.context.i32_type().const_zero(),
+        );
 
-            binary.builder.position_at_end(not_value_transfer);
-        }
+        binary.builder.position_at_end(not_value_transfer);
     }
 
     /// Recursively load a type from bin storage
@@ -407,6 +405,39 @@ pub trait TargetRuntime<'a> {
         self.storage_load_slot(binary, ty, slot, slot_ptr, function, ns)
     }
 
+    /// Load an immutable variable. By default, the immutables are kept in a buffer which is
+    /// embedded in the code of the contract
+    fn immutable_load(
+        &self,
+        bin: &Binary<'a>,
+        ty: &Type,
+        offset: usize,
+        _function: FunctionValue,
+        ns: &Namespace,
+    ) -> BasicValueEnum<'a> {
+        let ptr = bin.immutable_pointer(ty, offset, ns);
+
+        bin.builder.build_load(ptr, "immutable")
+    }
+
+    /// Store an immutable variable; this is only done in the constructor
+    fn immutable_store(
+        &self,
+        bin: &Binary<'a>,
+        ty: &Type,
+        offset: usize,
+        value: BasicValueEnum<'a>,
+        _function: FunctionValue<'a>,
+        ns: &Namespace,
+    ) {
+        let ptr = bin.immutable_pointer(ty, offset, ns);
+
+        bin.builder.build_store(ptr, value);
+    }
+
+    /// Persist the immutables once the constructor has completed successfully
+    fn store_immutables(&self, _bin: &Binary<'a>) {}
+
     /// Recursively load a type from bin storage for slot based bin storage
     fn storage_load_slot(
         &self,
@@ -628,6 +659,12 @@ pub trait TargetRuntime<'a> {
 
                 let ret = self.get_storage_int(bin, function, slot_ptr, ptr_ty);
 
+                *slot = bin.builder.build_int_add(
+                    *slot,
+                    bin.number_literal(256, &BigInt::one(), ns),
+                    "function",
+                );
+
                 bin.builder
                     .build_int_to_ptr(
                         ret,
@@ -727,8 +764,9 @@ pub trait TargetRuntime<'a> {
                                 )
                             };
 
-                            if elem_ty.is_reference_type(ns)
-                                && !elem_ty.deref_memory().is_fixed_reference_type()
+                            if (elem_ty.is_reference_type(ns)
+                                && !elem_ty.deref_memory().is_fixed_reference_type())
+                                || elem_ty.is_function_pointer()
                             {
                                 elem = bin.builder.build_load(elem, "").into_pointer_value();
                             }
@@ -832,8 +870,9 @@ pub trait TargetRuntime<'a> {
                                 "entry",
                             );
 
-                            if elem_ty.is_reference_type(ns)
-                                && !elem_ty.deref_memory().is_fixed_reference_type()
+                            if (elem_ty.is_reference_type(ns)
+                                && !elem_ty.deref_memory().is_fixed_reference_type())
+                                || elem_ty.is_function_pointer()
                             {
                                 elem = bin.builder.build_load(elem, "").into_pointer_value();
                             }
@@ -892,7 +931,9 @@ pub trait TargetRuntime<'a> {
                         )
                     };
 
-                    if field.ty.is_reference_type(ns) && !field.ty.is_fixed_reference_type() {
+                    if (field.ty.is_reference_type(ns) && !field.ty.is_fixed_reference_type())
+                        || field.ty.is_function_pointer()
+                    {
                         elem = bin
                             .builder
                             .build_load(elem, field.name_as_str())
@@ -1225,14 +1266,7 @@ pub trait TargetRuntime<'a> {
             Expression::CodeLiteral(_, bin_no, runtime) => {
                 let codegen_bin = &ns.contracts[*bin_no];
 
-                let target_bin = Binary::build(
-                    bin.context,
-                    codegen_bin,
-                    ns,
-                    "",
-                    bin.opt,
-                    bin.math_overflow_check,
-                );
+                let target_bin = Binary::build(bin.context, codegen_bin, ns, "", bin.opt);
 
                 let code = if *runtime && target_bin.runtime.is_some() {
                     target_bin
@@ -1278,8 +1312,8 @@ pub trait TargetRuntime<'a> {
                     .expression(bin, r, vartab, function, ns)
                     .into_int_value();
 
-                if bin.math_overflow_check && !*unchecked {
-                    let signed = l.ty().is_signed_int();
+                if !*unchecked {
+                    let signed = l.ty().is_signed_numeric();
                     self.build_binary_op_with_overflow_check(
                         bin,
                         function,
@@ -1301,8 +1335,8 @@ pub trait TargetRuntime<'a> {
                     .expression(bin, r, vartab, function, ns)
                     .into_int_value();
 
-                if bin.math_overflow_check && !*unchecked {
-                    let signed = l.ty().is_signed_int();
+                if !*unchecked {
+                    let signed = l.ty().is_signed_numeric();
                     self.build_binary_op_with_overflow_check(
                         bin,
                         function,
@@ -1330,7 +1364,7 @@ pub trait TargetRuntime<'a> {
                     *unchecked,
                     left,
                     right,
-                    res_ty.is_signed_int(),
+                    res_ty.is_signed_numeric(),
                 )
                 .into()
             }
@@ -1824,11 +1858,28 @@ pub trait TargetRuntime<'a> {
 
                 let f = self.power(bin, *unchecked, bits, res_ty.is_signed_int());
 
-                bin.builder
+                let res = bin
+                    .builder
                     .build_call(f, &[left.into(), right.into()], "power")
                     .try_as_basic_value()
                     .left()
-                    .unwrap()
+                    .unwrap();
+
+                if !*unchecked {
+                    let res = res.into_struct_value();
+
+                    let overflow = bin
+                        .builder
+                        .build_extract_value(res, 1, "overflow")
+                        .unwrap()
+                        .into_int_value();
+
+                    self.overflow_failure(bin, function, overflow);
+
+                    bin.builder.build_extract_value(res, 0, "power").unwrap()
+                } else {
+                    res
+                }
             }
             Expression::Equal(_, l, r) => {
                 if l.ty().is_address() {
@@ -1927,7 +1978,7 @@ pub trait TargetRuntime<'a> {
 
                     bin.builder
                         .build_int_compare(
-                            if l.ty().is_signed_int() {
+                            if l.ty().is_signed_numeric() {
                                 IntPredicate::SGE
                             } else {
                                 IntPredicate::UGE
@@ -1979,7 +2030,7 @@ pub trait TargetRuntime<'a> {
 
                     bin.builder
                         .build_int_compare(
-                            if l.ty().is_signed_int() {
+                            if l.ty().is_signed_numeric() {
                                 IntPredicate::SLE
                             } else {
                                 IntPredicate::ULE
@@ -2160,7 +2211,7 @@ pub trait TargetRuntime<'a> {
                     .left()
                     .unwrap()
             }
-            Expression::BytesCast(_, Type::DynamicBytes, Type::Bytes(n), e) => {
+            Expression::BytesCast(_, Type::DynamicBytes | Type::Slice(_), Type::Bytes(n), e) => {
                 let array = self.expression(bin, e, vartab, function, ns);
 
                 let len = bin.vector_len(array);
@@ -2365,6 +2416,52 @@ pub trait TargetRuntime<'a> {
                     }
                 }
             }
+            Expression::ArraySlice {
+                ty,
+                array,
+                start,
+                end,
+                ..
+            } => {
+                let array = self.expression(bin, array, vartab, function, ns);
+                let start = self
+                    .expression(bin, start, vartab, function, ns)
+                    .into_int_value();
+                let end = self
+                    .expression(bin, end, vartab, function, ns)
+                    .into_int_value();
+
+                let slice_ty = bin.llvm_type(ty, ns).into_struct_type();
+
+                // the slice points into the array, so nothing is copied
+                let data = bin.builder.build_pointer_cast(
+                    bin.vector_bytes(array),
+                    slice_ty
+                        .get_field_type_at_index(0)
+                        .unwrap()
+                        .into_pointer_type(),
+                    "data",
+                );
+
+                let data = unsafe { bin.builder.build_gep(data, &[start], "slice_data") };
+
+                let len = bin.builder.build_int_z_extend_or_bit_cast(
+                    bin.builder.build_int_sub(end, start, "slice_len"),
+                    slice_ty.get_field_type_at_index(1).unwrap().into_int_type(),
+                    "slice_len",
+                );
+
+                let slice = bin
+                    .builder
+                    .build_insert_value(slice_ty.get_undef(), data, 0, "slice")
+                    .unwrap();
+
+                bin.builder
+                    .build_insert_value(slice, len, 1, "slice")
+                    .unwrap()
+                    .into_struct_value()
+                    .into()
+            }
             Expression::StructMember(_, _, a, _)
                 if a.ty().is_builtin_struct() == Some(StructType::AccountInfo) =>
             {
@@ -2539,6 +2636,46 @@ pub trait TargetRuntime<'a> {
 
                 bin.builder.build_load(start, "value")
             }
+            Expression::Builtin(_, _, Builtin::ByteSwap, args) => {
+                let value = self
+                    .expression(bin, &args[0], vartab, function, ns)
+                    .into_int_value();
+                let ty = value.get_type();
+                let bits = ty.get_bit_width();
+
+                if bits == 8 {
+                    value.into()
+                } else if bits % 16 == 0 {
+                    bin.builder
+                        .build_call(bin.llvm_bswap(bits), &[value.into()], "bswap")
+                        .try_as_basic_value()
+                        .left()
+                        .unwrap()
+                } else {
+                    // llvm.bswap only works on an even number of bytes, so widen the value by one
+                    // byte, swap it and shift the extra byte out again
+                    let wide_ty = bin.context.custom_width_int_type(bits + 8);
+
+                    let wide = bin.builder.build_int_z_extend(value, wide_ty, "wide");
+
+                    let swapped = bin
+                        .builder
+                        .build_call(bin.llvm_bswap(bits + 8), &[wide.into()], "bswap")
+                        .try_as_basic_value()
+                        .left()
+                        .unwrap()
+                        .into_int_value();
+
+                    let shifted = bin.builder.build_right_shift(
+                        swapped,
+                        wide_ty.const_int(8, false),
+                        false,
+                        "",
+                    );
+
+                    bin.builder.build_int_truncate(shifted, ty, "").into()
+                }
+            }
             Expression::Keccak256(_, _, exprs) => {
                 let mut length = bin.context.i32_type().const_zero();
                 let mut values: Vec<(BasicValueEnum, IntValue, Type)> = Vec::new();
@@ -3149,6 +3286,47 @@ pub trait TargetRuntime<'a> {
                     "ptr_to_int",
                 )
                 .into()
+        } else if matches!(from, Type::Uint(_)) && matches!(to, Type::Ref(_)) {
+            bin.builder
+                .build_int_to_ptr(
+                    val.into_int_value(),
+                    bin.llvm_type(to, ns).into_pointer_type(),
+                    "int_to_ptr",
+                )
+                .into()
+        } else if matches!(from, Type::Uint(_)) && to.is_reference_type(ns) {
+            bin.builder
+                .build_int_to_ptr(
+                    val.into_int_value(),
+                    bin.llvm_var_ty(to, ns).into_pointer_type(),
+                    "int_to_ptr",
+                )
+                .into()
+        } else if let Type::Slice(elem_ty) = from {
+            // copy the elements of the slice into a new array
+            let len = bin.vector_len(val);
+
+            let elem_size = bin
+                .llvm_field_ty(elem_ty, ns)
+                .size_of()
+                .unwrap()
+                .const_cast(bin.context.i32_type(), false);
+
+            let array = bin.vector_new(len, elem_size, None);
+
+            let size = bin.builder.build_int_mul(len, elem_size, "size");
+
+            bin.builder.build_call(
+                bin.module.get_function("__memcpy").unwrap(),
+                &[
+                    bin.vector_bytes(array.into()).into(),
+                    bin.vector_bytes(val).into(),
+                    size.into(),
+                ],
+                "",
+            );
+
+            array.into()
         } else if matches!((from, to), (Type::DynamicBytes, Type::Slice(_))) {
             let slice = bin.build_alloca(function, bin.llvm_type(to, ns), "slice");
 
@@ -3408,6 +3586,49 @@ pub trait TargetRuntime<'a> {
                             bb_false,
                         );
                     }
+                    Instr::Switch {
+                        cond,
+                        cases,
+                        default,
+                    } => {
+                        let cond = self.expression(bin, cond, &w.vars, function, ns);
+
+                        let pos = bin.builder.get_insert_block().unwrap();
+
+                        let mut get_block = |block_no: usize| {
+                            let bb = blocks.entry(block_no).or_insert_with(|| {
+                                work.push_back(Work {
+                                    block_no,
+                                    vars: w.vars.clone(),
+                                });
+
+                                create_block(block_no, bin, cfg, function, ns)
+                            });
+
+                            for (v, phi) in bb.phis.iter() {
+                                phi.add_incoming(&[(&w.vars[v].value, pos)]);
+                            }
+
+                            bb.bb
+                        };
+
+                        let cases = cases
+                            .iter()
+                            .map(|(value, block_no)| {
+                                (
+                                    self.expression(bin, value, &w.vars, function, ns)
+                                        .into_int_value(),
+                                    get_block(*block_no),
+                                )
+                            })
+                            .collect::<Vec<_>>();
+
+                        let default = get_block(*default);
+
+                        bin.builder.position_at_end(pos);
+                        bin.builder
+                            .build_switch(cond.into_int_value(), default, &cases);
+                    }
                     Instr::LoadStorage { res, ty, storage } => {
                         let mut slot = self
                             .expression(bin, storage, &w.vars, function, ns)
@@ -3416,6 +3637,15 @@ pub trait TargetRuntime<'a> {
                         w.vars.get_mut(res).unwrap().value =
                             self.storage_load(bin, ty, &mut slot, function, ns);
                     }
+                    Instr::LoadImmutable { res, ty, offset } => {
+                        w.vars.get_mut(res).unwrap().value =
+                            self.immutable_load(bin, ty, *offset, function, ns);
+                    }
+                    Instr::SetImmutable { ty, value, offset } => {
+                        let value = self.expression(bin, value, &w.vars, function, ns);
+
+                        self.immutable_store(bin, ty, *offset, value, function, ns);
+                    }
                     Instr::ClearStorage { ty, storage } => {
                         let mut slot = self
                             .expression(bin, storage, &w.vars, function, ns)
@@ -3760,21 +3990,9 @@ pub trait TargetRuntime<'a> {
                         );
                     }
                     Instr::AssertFailure { expr: Some(expr) } => {
-                        let v = self.expression(bin, expr, &w.vars, function, ns);
-
-                        let selector = 0x08c3_79a0u32;
-
-                        let (data, len) = self.abi_encode(
-                            bin,
-                            Some(bin.context.i32_type().const_int(selector as u64, false)),
-                            false,
-                            function,
-                            &[v],
-                            &[Type::String],
-                            ns,
-                        );
+                        let data = self.expression(bin, expr, &w.vars, function, ns);
 
-                        self.assert_failure(bin, data, len);
+                        self.assert_failure(bin, bin.vector_bytes(data), bin.vector_len(data));
                     }
                     Instr::Print { expr } => {
                         let expr = self.expression(bin, expr, &w.vars, function, ns);
@@ -4238,7 +4456,7 @@ pub trait TargetRuntime<'a> {
                             let exception_block = blocks.get(&exception).unwrap();
 
                             let has_selector = bin.builder.build_int_compare(
-                                IntPredicate::UGT,
+                                IntPredicate::UGE,
                                 data_len,
                                 bin.context.i32_type().const_int(4, false),
                                 "has_selector",
@@ -4431,6 +4649,22 @@ pub trait TargetRuntime<'a> {
 
         let switch_block = bin.context.append_basic_block(function, "switch");
 
+        if function_ty == pt::FunctionTy::Function
+            && ns.target == Target::Solana
+            && ns.files[contract.loc.file_no()].anchor_discriminator
+        {
+            self.emit_discriminator_dispatch(
+                bin,
+                contract,
+                ns,
+                argsdata,
+                argslen,
+                function,
+                functions,
+                &nonpayable,
+            );
+        }
+
         let not_fallback = bin.builder.build_int_compare(
             IntPredicate::UGE,
             argslen,
@@ -4474,17 +4708,23 @@ pub trait TargetRuntime<'a> {
                 continue;
             }
 
-            self.add_dispatch_case(
+            let bb = self.add_dispatch_case(
                 bin,
                 cfg,
                 ns,
-                &mut cases,
                 argsdata,
                 argslen,
                 function,
                 functions[&cfg_no],
                 &nonpayable,
             );
+
+            cases.push((
+                bin.context
+                    .i32_type()
+                    .const_int(cfg.selector.to_be() as u64, false),
+                bb,
+            ));
         }
 
         bin.builder.position_at_end(switch_block);
@@ -4517,7 +4757,8 @@ pub trait TargetRuntime<'a> {
             return;
         }
 
-        if ns.target == Target::Solana {
+        // without value transfers, Solana has no receive function
+        if ns.target == Target::Solana && contract.lamports_offset.is_none() {
             match fallback {
                 Some((cfg_no, _)) => {
                     let args = if ns.target == Target::Solana {
@@ -4575,7 +4816,8 @@ pub trait TargetRuntime<'a> {
 
             bin.builder.position_at_end(receive_block);
 
-            match receive {
+            // a payable fallback function, as permitted before Solidity 0.6.0, also receives value
+            match receive.or_else(|| fallback.filter(|(_, cfg)| !cfg.nonpayable)) {
                 Some((cfg_no, _)) => {
                     let args = if ns.target == Target::Solana {
                         vec![function.get_last_param().unwrap().into()]
@@ -4594,22 +4836,115 @@ pub trait TargetRuntime<'a> {
         }
     }
 
+    /// On Solana with `pragma solana anchor_discriminator`, first try to match the 8 byte Anchor
+    /// instruction discriminator of the public functions. If none matches, the instruction data
+    /// is dispatched on the function selector as usual.
+    fn emit_discriminator_dispatch<F>(
+        &self,
+        bin: &Binary<'a>,
+        contract: &Contract,
+        ns: &Namespace,
+        argsdata: inkwell::values::PointerValue<'a>,
+        argslen: inkwell::values::IntValue<'a>,
+        function: inkwell::values::FunctionValue<'a>,
+        functions: &HashMap<usize, FunctionValue<'a>>,
+        nonpayable: &F,
+    ) where
+        F: Fn(&ControlFlowGraph) -> bool,
+    {
+        let discriminator_block = bin.context.append_basic_block(function, "discriminator");
+        let selector_block = bin.context.append_basic_block(function, "selector");
+
+        let is_discriminator = bin.builder.build_int_compare(
+            IntPredicate::UGE,
+            argslen,
+            argslen.get_type().const_int(8, false),
+            "",
+        );
+
+        bin.builder
+            .build_conditional_branch(is_discriminator, discriminator_block, selector_block);
+
+        bin.builder.position_at_end(discriminator_block);
+
+        let discriminator_ptr = bin.builder.build_pointer_cast(
+            argsdata,
+            bin.context.i64_type().ptr_type(AddressSpace::Generic),
+            "discriminator_ptr",
+        );
+
+        let discriminator = bin
+            .builder
+            .build_load(discriminator_ptr, "discriminator")
+            .into_int_value();
+
+        // step over the discriminator
+        let args = bin.builder.build_pointer_cast(
+            unsafe {
+                bin.builder.build_gep(
+                    discriminator_ptr,
+                    &[bin.context.i32_type().const_int(1, false)],
+                    "argsdata",
+                )
+            },
+            argsdata.get_type(),
+            "argsdata",
+        );
+
+        let args_len =
+            bin.builder
+                .build_int_sub(argslen, argslen.get_type().const_int(8, false), "argslen");
+
+        let mut cases = Vec::new();
+
+        for (cfg_no, cfg) in contract.cfg.iter().enumerate() {
+            if cfg.ty != pt::FunctionTy::Function || !cfg.public {
+                continue;
+            }
+
+            if let ASTFunction::SolidityFunction(function_no) = cfg.function_no {
+                let bb = self.add_dispatch_case(
+                    bin,
+                    cfg,
+                    ns,
+                    args,
+                    args_len,
+                    function,
+                    functions[&cfg_no],
+                    nonpayable,
+                );
+
+                cases.push((
+                    bin.context.i64_type().const_int(
+                        u64::from_le_bytes(ns.functions[function_no].discriminator()),
+                        false,
+                    ),
+                    bb,
+                ));
+            }
+        }
+
+        bin.builder.position_at_end(discriminator_block);
+
+        bin.builder
+            .build_switch(discriminator, selector_block, &cases);
+
+        bin.builder.position_at_end(selector_block);
+    }
+
     ///Add single case for emit_function_dispatch
     fn add_dispatch_case<F>(
         &self,
         bin: &Binary<'a>,
         f: &ControlFlowGraph,
         ns: &Namespace,
-        cases: &mut Vec<(
-            inkwell::values::IntValue<'a>,
-            inkwell::basic_block::BasicBlock<'a>,
-        )>,
         argsdata: inkwell::values::PointerValue<'a>,
         argslen: inkwell::values::IntValue<'a>,
         function: inkwell::values::FunctionValue<'a>,
         dest: inkwell::values::FunctionValue<'a>,
         nonpayable: &F,
-    ) where
+    ) -> inkwell::basic_block::BasicBlock<'a>
+    where
         F: Fn(&ControlFlowGraph) -> bool,
     {
         let bb = bin.context.append_basic_block(function, "");
@@ -4685,6 +5020,10 @@ pub trait TargetRuntime<'a> {
 
         bin.builder.position_at_end(success_block);
 
+        if f.ty == pt::FunctionTy::Constructor {
+            self.store_immutables(bin);
+        }
+
         if f.returns.is_empty() {
             // return ABI of length 0
             self.return_empty_abi(bin);
@@ -4708,12 +5047,7 @@ pub trait TargetRuntime<'a> {
 
         self.return_code(bin, ret.into_int_value());
 
-        cases.push((
-            bin.context
-                .i32_type()
-                .const_int(f.selector.to_be() as u64, false),
-            bb,
-        ));
+        bb
     }
 
     /// Emit the bin storage initializers
@@ -5308,85 +5642,162 @@ pub trait TargetRuntime<'a> {
     ) -> IntValue<'a> {
         let bits = left.get_type().get_bit_width();
 
-        if bits > 64 {
+        if !unchecked {
+            if bits > 64 {
+                let (res, overflow) = self.mul_with_overflow(bin, function, left, right, signed);
+
+                self.overflow_failure(bin, function, overflow);
+
+                res
+            } else {
+                self.build_binary_op_with_overflow_check(
+                    bin,
+                    function,
+                    left,
+                    right,
+                    BinaryOp::Multiply,
+                    signed,
+                )
+            }
+        } else if bits > 64 {
             // round up the number of bits to the next 32
             let mul_bits = (bits + 31) & !31;
-            let mul_ty = bin.context.custom_width_int_type(mul_bits);
 
-            // round up bits
-            let l = bin.build_alloca(function, mul_ty, "");
-            let r = bin.build_alloca(function, mul_ty, "");
-            let o = bin.build_alloca(function, mul_ty, "");
+            let res = self.bigint_mul(bin, function, left, right, mul_bits, signed);
 
             if mul_bits == bits {
-                bin.builder.build_store(l, left);
-                bin.builder.build_store(r, right);
-            } else if signed {
-                bin.builder
-                    .build_store(l, bin.builder.build_int_s_extend(left, mul_ty, ""));
-                bin.builder
-                    .build_store(r, bin.builder.build_int_s_extend(right, mul_ty, ""));
+                res
             } else {
-                bin.builder
-                    .build_store(l, bin.builder.build_int_z_extend(left, mul_ty, ""));
-                bin.builder
-                    .build_store(r, bin.builder.build_int_z_extend(right, mul_ty, ""));
+                bin.builder.build_int_truncate(res, left.get_type(), "")
             }
+        } else {
+            bin.builder.build_int_mul(left, right, "")
+        }
+    }
 
-            bin.builder.build_call(
-                bin.module.get_function("__mul32").unwrap(),
-                &[
-                    bin.builder
-                        .build_pointer_cast(
-                            l,
-                            bin.context.i32_type().ptr_type(AddressSpace::Generic),
-                            "left",
-                        )
-                        .into(),
-                    bin.builder
-                        .build_pointer_cast(
-                            r,
-                            bin.context.i32_type().ptr_type(AddressSpace::Generic),
-                            "right",
-                        )
-                        .into(),
-                    bin.builder
-                        .build_pointer_cast(
-                            o,
-                            bin.context.i32_type().ptr_type(AddressSpace::Generic),
-                            "output",
-                        )
-                        .into(),
-                    bin.context
-                        .i32_type()
-                        .const_int(mul_bits as u64 / 32, false)
-                        .into(),
-                ],
-                "",
-            );
+    /// Emit a multiply for any width, returning the result and a flag which is set if the
+    /// multiply overflowed
+    fn mul_with_overflow(
+        &self,
+        bin: &Binary<'a>,
+        function: FunctionValue<'a>,
+        left: IntValue<'a>,
+        right: IntValue<'a>,
+        signed: bool,
+    ) -> (IntValue<'a>, IntValue<'a>) {
+        let bits = left.get_type().get_bit_width();
 
-            let res = bin.builder.build_load(o, "mul");
+        if bits > 64 {
+            // calculate the full product of double width, rounded up to the next 32 bits
+            let mul_bits = (bits * 2 + 31) & !31;
 
-            if mul_bits == bits {
-                res.into_int_value()
+            let product = self.bigint_mul(bin, function, left, right, mul_bits, signed);
+
+            let res = bin.builder.build_int_truncate(product, left.get_type(), "");
+
+            // the product fits if extending the truncated result gives the full product
+            let extended = if signed {
+                bin.builder.build_int_s_extend(res, product.get_type(), "")
             } else {
+                bin.builder.build_int_z_extend(res, product.get_type(), "")
+            };
+
+            let overflow =
                 bin.builder
-                    .build_int_truncate(res.into_int_value(), left.get_type(), "")
-            }
-        } else if bin.math_overflow_check && !unchecked {
-            self.build_binary_op_with_overflow_check(
-                bin,
-                function,
-                left,
-                right,
-                BinaryOp::Multiply,
-                signed,
-            )
+                    .build_int_compare(IntPredicate::NE, extended, product, "overflow");
+
+            (res, overflow)
         } else {
-            bin.builder.build_int_mul(left, right, "")
+            let op_res =
+                self.build_binary_op_with_overflow(bin, left, right, BinaryOp::Multiply, signed);
+
+            let res = bin
+                .builder
+                .build_extract_value(op_res, 0, "res")
+                .unwrap()
+                .into_int_value();
+
+            let overflow = bin
+                .builder
+                .build_extract_value(op_res, 1, "overflow")
+                .unwrap()
+                .into_int_value();
+
+            (res, overflow)
+        }
+    }
+
+    /// Multiply two integers wider than 64 bits using __mul32. The arguments are extended to
+    /// mul_bits, which must be a multiple of 32, and the result has the same width.
+    fn bigint_mul(
+        &self,
+        bin: &Binary<'a>,
+        function: FunctionValue<'a>,
+        left: IntValue<'a>,
+        right: IntValue<'a>,
+        mul_bits: u32,
+        signed: bool,
+    ) -> IntValue<'a> {
+        let bits = left.get_type().get_bit_width();
+        let mul_ty = bin.context.custom_width_int_type(mul_bits);
+
+        let l = bin.build_alloca(function, mul_ty, "");
+        let r = bin.build_alloca(function, mul_ty, "");
+        let o = bin.build_alloca(function, mul_ty, "");
+
+        if mul_bits == bits {
+            bin.builder.build_store(l, left);
+            bin.builder.build_store(r, right);
+        } else if signed {
+            bin.builder
+                .build_store(l, bin.builder.build_int_s_extend(left, mul_ty, ""));
+            bin.builder
+                .build_store(r, bin.builder.build_int_s_extend(right, mul_ty, ""));
+        } else {
+            bin.builder
+                .build_store(l, bin.builder.build_int_z_extend(left, mul_ty, ""));
+            bin.builder
+                .build_store(r, bin.builder.build_int_z_extend(right, mul_ty, ""));
         }
+
+        bin.builder.build_call(
+            bin.module.get_function("__mul32").unwrap(),
+            &[
+                bin.builder
+                    .build_pointer_cast(
+                        l,
+                        bin.context.i32_type().ptr_type(AddressSpace::Generic),
+                        "left",
+                    )
+                    .into(),
+                bin.builder
+                    .build_pointer_cast(
+                        r,
+                        bin.context.i32_type().ptr_type(AddressSpace::Generic),
+                        "right",
+                    )
+                    .into(),
+                bin.builder
+                    .build_pointer_cast(
+                        o,
+                        bin.context.i32_type().ptr_type(AddressSpace::Generic),
+                        "output",
+                    )
+                    .into(),
+                bin.context
+                    .i32_type()
+                    .const_int(mul_bits as u64 / 32, false)
+                    .into(),
+            ],
+            "",
+        );
+
+        bin.builder.build_load(o, "mul").into_int_value()
     }
 
+    /// Emit a power function. Unless unchecked, the function returns the result and a flag
+    /// which is set if any of the multiplies overflowed. The caller must check this
+    /// flag, since not all targets can abort from a function which does not return a status.
     fn power(
         &self,
         bin: &Binary<'a>,
@@ -5411,6 +5822,7 @@ pub trait TargetRuntime<'a> {
                 return result;
             }
         */
+
         let name = format!(
             "__{}power{}{}",
             if signed { 's' } else { 'u' },
@@ -5425,10 +5837,16 @@ pub trait TargetRuntime<'a> {
 
         let pos = bin.builder.get_insert_block().unwrap();
 
+        let ret_ty = if unchecked {
+            ty.fn_type(&[ty.into(), ty.into()], false)
+        } else {
+            bin.context
+                .struct_type(&[ty.into(), bin.context.bool_type().into()], false)
+                .fn_type(&[ty.into(), ty.into()], false)
+        };
+
         // __upower(base, exp)
-        let function =
-            bin.module
-                .add_function(&name, ty.fn_type(&[ty.into(), ty.into()], false), None);
+        let function = bin.module.add_function(&name, ret_ty, None);
 
         let entry = bin.context.append_basic_block(function, "entry");
         let loop_block = bin.context.append_basic_block(function, "loop");
@@ -5451,6 +5869,9 @@ pub trait TargetRuntime<'a> {
         let result = bin.builder.build_phi(ty, "result");
         result.add_incoming(&[(&ty.const_int(1, false), entry)]);
 
+        let overflow = bin.builder.build_phi(bin.context.bool_type(), "overflow");
+        overflow.add_incoming(&[(&bin.context.bool_type().const_zero(), entry)]);
+
         let lowbit = bin.builder.build_int_truncate(
             exp.as_basic_value().into_int_value(),
             bin.context.bool_type(),
@@ -5462,12 +5883,13 @@ pub trait TargetRuntime<'a> {
 
         bin.builder.position_at_end(multiply);
 
-        let result2 = self.mul(
+        let (result2, overflow2) = self.power_mul(
             bin,
             function,
             unchecked,
             result.as_basic_value().into_int_value(),
             base.as_basic_value().into_int_value(),
+            overflow.as_basic_value().into_int_value(),
             signed,
         );
 
@@ -5477,6 +5899,12 @@ pub trait TargetRuntime<'a> {
         let result3 = bin.builder.build_phi(ty, "result");
         result3.add_incoming(&[(&result.as_basic_value(), loop_block), (&result2, multiply)]);
 
+        let overflow3 = bin.builder.build_phi(bin.context.bool_type(), "overflow");
+        overflow3.add_incoming(&[
+            (&overflow.as_basic_value(), loop_block),
+            (&overflow2, multiply),
+        ]);
+
         let exp2 = bin.builder.build_right_shift(
             exp.as_basic_value().into_int_value(),
             ty.const_int(1, false),
@@ -5490,22 +5918,45 @@ pub trait TargetRuntime<'a> {
         bin.builder.build_conditional_branch(zero, done, notdone);
         bin.builder.position_at_end(done);
 
-        bin.builder.build_return(Some(&result3.as_basic_value()));
+        if unchecked {
+            bin.builder.build_return(Some(&result3.as_basic_value()));
+        } else {
+            let ret = function.get_type().get_return_type().unwrap();
+
+            let ret = bin
+                .builder
+                .build_insert_value(
+                    ret.into_struct_type().get_undef(),
+                    result3.as_basic_value(),
+                    0,
+                    "result",
+                )
+                .unwrap();
+
+            let ret = bin
+                .builder
+                .build_insert_value(ret, overflow3.as_basic_value(), 1, "overflow")
+                .unwrap();
+
+            bin.builder.build_return(Some(&ret));
+        }
 
         bin.builder.position_at_end(notdone);
 
-        let base2 = self.mul(
+        let (base2, overflow4) = self.power_mul(
             bin,
             function,
             unchecked,
             base.as_basic_value().into_int_value(),
             base.as_basic_value().into_int_value(),
+            overflow3.as_basic_value().into_int_value(),
             signed,
         );
 
         base.add_incoming(&[(&base2, notdone)]);
         result.add_incoming(&[(&result3.as_basic_value(), notdone)]);
         exp.add_incoming(&[(&exp2, notdone)]);
+        overflow.add_incoming(&[(&overflow4, notdone)]);
 
         bin.builder.build_unconditional_branch(loop_block);
 
@@ -5514,6 +5965,30 @@ pub trait TargetRuntime<'a> {
         function
     }
 
+    /// Multiply within the power function, accumulating the overflow flag unless unchecked
+    #[allow(clippy::too_many_arguments)]
+    fn power_mul(
+        &self,
+        bin: &Binary<'a>,
+        function: FunctionValue<'a>,
+        unchecked: bool,
+        left: IntValue<'a>,
+        right: IntValue<'a>,
+        overflow: IntValue<'a>,
+        signed: bool,
+    ) -> (IntValue<'a>, IntValue<'a>) {
+        if unchecked {
+            (self.mul(bin, function, true, left, right, signed), overflow)
+        } else {
+            let (res, mul_overflow) = self.mul_with_overflow(bin, function, left, right, signed);
+
+            (
+                res,
+                bin.builder.build_or(overflow, mul_overflow, "overflow"),
+            )
+        }
+    }
+
     /// Convenience function for generating binary operations with overflow checking.
     fn build_binary_op_with_overflow_check(
         &self,
@@ -5524,6 +5999,32 @@ pub trait TargetRuntime<'a> {
         op: BinaryOp,
         signed: bool,
     ) -> IntValue<'a> {
+        let op_res = self.build_binary_op_with_overflow(bin, left, right, op, signed);
+
+        let overflow = bin
+            .builder
+            .build_extract_value(op_res, 1, "overflow")
+            .unwrap()
+            .into_int_value();
+
+        self.overflow_failure(bin, function, overflow);
+
+        bin.builder
+            .build_extract_value(op_res, 0, "res")
+            .unwrap()
+            .into_int_value()
+    }
+
+    /// Call the llvm overflow intrinsic for the binary operation. This returns a struct with
+    /// the result and the overflow flag.
+    fn build_binary_op_with_overflow(
+        &self,
+        bin: &Binary<'a>,
+        left: IntValue<'a>,
+        right: IntValue<'a>,
+        op: BinaryOp,
+        signed: bool,
+    ) -> StructValue<'a> {
         let ret_ty = bin.context.struct_type(
             &[
                 left.get_type().into(),
@@ -5533,20 +6034,16 @@ pub trait TargetRuntime<'a> {
         );
         let binop = bin.llvm_overflow(ret_ty.into(), left.get_type(), signed, op);
 
-        let op_res = bin
-            .builder
+        bin.builder
             .build_call(binop, &[left.into(), right.into()], "res")
             .try_as_basic_value()
             .left()
             .unwrap()
-            .into_struct_value();
-
-        let overflow = bin
-            .builder
-            .build_extract_value(op_res, 1, "overflow")
-            .unwrap()
-            .into_int_value();
+            .into_struct_value()
+    }
 
+    /// Abort execution if the overflow flag is set
+    fn overflow_failure(&self, bin: &Binary<'a>, function: FunctionValue, overflow: IntValue<'a>) {
         let success_block = bin.context.append_basic_block(function, "success");
         let error_block = bin.context.append_basic_block(function, "error");
 
@@ -5565,11 +6062,6 @@ pub trait TargetRuntime<'a> {
         );
 
         bin.builder.position_at_end(success_block);
-
-        bin.builder
-            .build_extract_value(op_res, 0, "res")
-            .unwrap()
-            .into_int_value()
     }
 }
 
@@ -45,7 +45,6 @@ impl SolanaTarget {
         ns: &'a ast::Namespace,
         filename: &'a str,
         opt: OptimizationLevel,
-        math_overflow_check: bool,
     ) -> Binary<'a> {
         let mut target = SolanaTarget {
             abi: ethabiencoder::EthAbiDecoder { bswap: true },
@@ -58,7 +57,6 @@ impl SolanaTarget {
             &contract.name,
             filename,
             opt,
-            math_overflow_check,
             std_lib,
             None,
         );
@@ -122,7 +120,6 @@ impl SolanaTarget {
         namespaces: &'a [&ast::Namespace],
         filename: &str,
         opt: OptimizationLevel,
-        math_overflow_check: bool,
     ) -> Binary<'a> {
         let mut target = SolanaTarget {
             abi: ethabiencoder::EthAbiDecoder { bswap: true },
@@ -135,7 +132,6 @@ impl SolanaTarget {
             "bundle",
             filename,
             opt,
-            math_overflow_check,
             std_lib,
             None,
         );
@@ -446,6 +442,81 @@ impl SolanaTarget {
             .into_pointer_value()
     }
 
+    /// With value transfers enabled, the value received is the increase of the lamports balance of
+    /// the account since the balance was last recorded. Record the current balance and store the
+    /// value received in the parameters, for `msg.value`. A constructor never receives value.
+    fn value_received(&self, binary: &Binary, lamports_offset: u64, constructor: bool) {
+        let parameters = self.sol_parameters(binary);
+
+        let account = self.contract_storage_account(binary);
+
+        let lamports = binary
+            .builder
+            .build_load(
+                binary
+                    .builder
+                    .build_load(
+                        binary
+                            .builder
+                            .build_struct_gep(account, 1, "lamports")
+                            .unwrap(),
+                        "lamports",
+                    )
+                    .into_pointer_value(),
+                "lamports",
+            )
+            .into_int_value();
+
+        let recorded = binary.builder.build_pointer_cast(
+            unsafe {
+                binary.builder.build_gep(
+                    self.contract_storage_data(binary),
+                    &[binary.context.i32_type().const_int(lamports_offset, false)],
+                    "recorded_lamports",
+                )
+            },
+            binary.context.i64_type().ptr_type(AddressSpace::Generic),
+            "recorded_lamports",
+        );
+
+        let value = if constructor {
+            binary.context.i64_type().const_zero()
+        } else {
+            let previous = binary
+                .builder
+                .build_load(recorded, "previous")
+                .into_int_value();
+
+            // the balance may have decreased, e.g. due to rent collection
+            let increased = binary.builder.build_int_compare(
+                IntPredicate::UGT,
+                lamports,
+                previous,
+                "increased",
+            );
+
+            binary
+                .builder
+                .build_select(
+                    increased,
+                    binary.builder.build_int_sub(lamports, previous, "value"),
+                    binary.context.i64_type().const_zero(),
+                    "value",
+                )
+                .into_int_value()
+        };
+
+        binary.builder.build_store(recorded, lamports);
+
+        binary.builder.build_store(
+            binary
+                .builder
+                .build_struct_gep(parameters, 14, "value")
+                .unwrap(),
+            value,
+        );
+    }
+
     /// Returns the account data length of the executing binary
     fn contract_storage_datalen<'b>(&self, binary: &Binary<'b>) -> IntValue<'b> {
         let parameters = self.sol_parameters(binary);
@@ -570,6 +641,16 @@ impl SolanaTarget {
 
             binary.builder.position_at_end(entry);
 
+            let lamports_offset = contract
+                .contract
+                .lamports_offset
+                .as_ref()
+                .map(|offset| offset.to_u64().unwrap());
+
+            if let Some(offset) = lamports_offset {
+                self.value_received(binary, offset, false);
+            }
+
             self.emit_function_dispatch(
                 binary,
                 contract.contract,
@@ -583,7 +664,7 @@ impl SolanaTarget {
                 dispatch_function,
                 &contract.functions,
                 None,
-                |_| false,
+                |func| lamports_offset.is_some() && func.nonpayable,
             );
 
             let function_block = binary
@@ -721,12 +802,8 @@ impl SolanaTarget {
                 "",
             );
 
-            // is there a not a payable constructor
-            if !contract.contract.functions.iter().any(|function_no| {
-                let f = &contract.ns.functions[*function_no];
-                f.is_constructor() && f.is_payable()
-            }) {
-                self.abort_if_value_transfer(binary, function, contract.ns);
+            if let Some(offset) = &contract.contract.lamports_offset {
+                self.value_received(binary, offset.to_u64().unwrap(), true);
             }
 
             // There is only one possible constructor
@@ -1670,7 +1747,8 @@ impl<'a> TargetRuntime<'a> for SolanaTarget {
         _slot: PointerValue,
         _dest: PointerValue,
     ) {
-        unimplemented!();
+        // unused
+        unreachable!();
     }
     fn get_storage_extfunc(
         &self,
@@ -1679,7 +1757,8 @@ impl<'a> TargetRuntime<'a> for SolanaTarget {
         _slot: PointerValue<'a>,
         _ns: &ast::Namespace,
     ) -> PointerValue<'a> {
-        unimplemented!();
+        // unused
+        unreachable!();
     }
 
     fn set_storage_string(
@@ -2164,6 +2243,34 @@ impl<'a> TargetRuntime<'a> for SolanaTarget {
         unreachable!();
     }
 
+    /// Solana programs cannot modify their code, so immutables are kept in the account data
+    fn immutable_load(
+        &self,
+        binary: &Binary<'a>,
+        ty: &ast::Type,
+        offset: usize,
+        function: FunctionValue,
+        ns: &ast::Namespace,
+    ) -> BasicValueEnum<'a> {
+        let mut slot = binary.context.i32_type().const_int(offset as u64, false);
+
+        self.storage_load(binary, ty, &mut slot, function, ns)
+    }
+
+    fn immutable_store(
+        &self,
+        binary: &Binary<'a>,
+        ty: &ast::Type,
+        offset: usize,
+        value: BasicValueEnum<'a>,
+        function: FunctionValue<'a>,
+        ns: &ast::Namespace,
+    ) {
+        let mut slot = binary.context.i32_type().const_int(offset as u64, false);
+
+        self.storage_store(binary, ty, true, &mut slot, value, function, ns);
+    }
+
     /// Recursively load a type from binary storage. This overrides the default method
     /// in the trait, which is for chains with 256 bit storage keys.
     fn storage_load(
@@ -2397,6 +2504,39 @@ impl<'a> TargetRuntime<'a> for SolanaTarget {
 
                 dest.into()
             }
+            ast::Type::ExternalFunction { .. } => {
+                let llvm_ty = binary.llvm_type(ty, ns).into_pointer_type();
+
+                // LLVMSizeOf() produces an i64 and malloc takes i32
+                let size = binary.builder.build_int_truncate(
+                    llvm_ty.get_element_type().size_of().unwrap(),
+                    binary.context.i32_type(),
+                    "size_of",
+                );
+
+                let new = binary
+                    .builder
+                    .build_call(
+                        binary.module.get_function("__malloc").unwrap(),
+                        &[size.into()],
+                        "",
+                    )
+                    .try_as_basic_value()
+                    .left()
+                    .unwrap()
+                    .into_pointer_value();
+
+                binary.builder.build_call(
+                    binary.module.get_function("__memcpy").unwrap(),
+                    &[new.into(), member.into(), size.into()],
+                    "",
+                );
+
+                binary
+                    .builder
+                    .build_pointer_cast(new, llvm_ty, "function_type")
+                    .into()
+            }
             _ => binary.builder.build_load(
                 binary.builder.build_pointer_cast(
                     member,
@@ -2752,6 +2892,32 @@ impl<'a> TargetRuntime<'a> for SolanaTarget {
                     ns,
                 );
             }
+        } else if let ast::Type::ExternalFunction { .. } = ty {
+            let val = val.into_pointer_value();
+
+            // LLVMSizeOf() produces an i64 and memcpy takes i32
+            let size = binary.builder.build_int_truncate(
+                val.get_type().get_element_type().size_of().unwrap(),
+                binary.context.i32_type(),
+                "size_of",
+            );
+
+            binary.builder.build_call(
+                binary.module.get_function("__memcpy").unwrap(),
+                &[
+                    member.into(),
+                    binary
+                        .builder
+                        .build_pointer_cast(
+                            val,
+                            binary.context.i8_type().ptr_type(AddressSpace::Generic),
+                            "",
+                        )
+                        .into(),
+                    size.into(),
+                ],
+                "",
+            );
         } else {
             binary.builder.build_store(
                 binary.builder.build_pointer_cast(
@@ -3542,9 +3708,20 @@ impl<'a> TargetRuntime<'a> for SolanaTarget {
         binary.builder.build_return(Some(&ret));
     }
 
-    /// Value received is not available on solana
-    fn value_transferred<'b>(&self, _binary: &Binary<'b>, _ns: &ast::Namespace) -> IntValue<'b> {
-        unreachable!();
+    /// Value received, only available with value transfers enabled
+    fn value_transferred<'b>(&self, binary: &Binary<'b>, _ns: &ast::Namespace) -> IntValue<'b> {
+        let parameters = self.sol_parameters(binary);
+
+        binary
+            .builder
+            .build_load(
+                binary
+                    .builder
+                    .build_struct_gep(parameters, 14, "value")
+                    .unwrap(),
+                "value",
+            )
+            .into_int_value()
     }
 
     /// Send value to address
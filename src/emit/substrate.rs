@@ -16,6 +16,7 @@ use num_traits::ToPrimitive;
 use solang_parser::pt;
 use std::collections::HashMap;
 use std::convert::TryFrom;
+use tiny_keccak::{Hasher, Keccak};
 
 use crate::emit::{Binary, TargetRuntime, Variable};
 
@@ -34,7 +35,6 @@ impl SubstrateTarget {
         ns: &'a ast::Namespace,
         filename: &'a str,
         opt: OptimizationLevel,
-        math_overflow_check: bool,
     ) -> Binary<'a> {
         let mut binary = Binary::new(
             context,
@@ -42,7 +42,6 @@ impl SubstrateTarget {
             &contract.name,
             filename,
             opt,
-            math_overflow_check,
             std_lib,
             None,
         );
@@ -68,6 +67,19 @@ impl SubstrateTarget {
         scratch.set_initializer(&context.i8_type().array_type(SCRATCH_SIZE).get_undef());
         binary.scratch = Some(scratch);
 
+        binary.emit_immutables(contract);
+
+        // the immutables are only read from storage once they are used
+        if binary.module.get_global("immutables").is_some() {
+            let loaded = binary.module.add_global(
+                context.bool_type(),
+                Some(AddressSpace::Generic),
+                "immutables_loaded",
+            );
+            loaded.set_linkage(Linkage::Internal);
+            loaded.set_initializer(&context.bool_type().const_zero());
+        }
+
         let mut b = SubstrateTarget {
             unique_strings: HashMap::new(),
         };
@@ -109,6 +121,8 @@ impl SubstrateTarget {
             "seal_terminate",
             "seal_deposit_event",
             "seal_transfer",
+            "seal_ecdsa_recover",
+            "seal_sr25519_verify",
         ]);
 
         binary
@@ -501,6 +515,33 @@ impl SubstrateTarget {
             ),
             Some(Linkage::External),
         );
+
+        binary.module.add_function(
+            "seal_ecdsa_recover",
+            binary.context.i32_type().fn_type(
+                &[
+                    u8_ptr, // signature_ptr
+                    u8_ptr, // message_hash_ptr
+                    u8_ptr, // output_ptr
+                ],
+                false,
+            ),
+            Some(Linkage::External),
+        );
+
+        binary.module.add_function(
+            "seal_sr25519_verify",
+            binary.context.i32_type().fn_type(
+                &[
+                    u8_ptr,  // signature_ptr
+                    u8_ptr,  // pub_key_ptr
+                    u32_val, // message_len
+                    u8_ptr,  // message_ptr
+                ],
+                false,
+            ),
+            Some(Linkage::External),
+        );
     }
 
     fn emit_deploy(&mut self, binary: &mut Binary, contract: &ast::Contract, ns: &ast::Namespace) {
@@ -517,6 +558,14 @@ impl SubstrateTarget {
         let (deploy_args, deploy_args_length) =
             self.public_function_prelude(binary, function, false, ns);
 
+        // the constructor sets the immutables, there is nothing to load
+        if let Some(loaded) = binary.module.get_global("immutables_loaded") {
+            binary.builder.build_store(
+                loaded.as_pointer_value(),
+                binary.context.bool_type().const_int(1, false),
+            );
+        }
+
         // init our storage vars
         binary.builder.build_call(initializer, &[], "");
 
@@ -578,6 +627,92 @@ impl SubstrateTarget {
         );
     }
 
+    /// The code of a contract is shared between all its instances, so the immutables are stored
+    /// under a fixed storage key which does not collide with any storage slot.
+    fn immutables_key<'b>(&self, binary: &Binary<'b>) -> PointerValue<'b> {
+        if let Some(key) = binary.module.get_global("immutables_key") {
+            return binary.builder.build_pointer_cast(
+                key.as_pointer_value(),
+                binary.context.i8_type().ptr_type(AddressSpace::Generic),
+                "immutables_key",
+            );
+        }
+
+        let mut key = [0u8; 32];
+        let mut hasher = Keccak::v256();
+        hasher.update(b"solang.immutables");
+        hasher.finalize(&mut key);
+
+        binary.emit_global_string("immutables_key", &key, true)
+    }
+
+    /// Load the immutables into their buffer, unless this has already been done. The code of a
+    /// contract is uploaded once and shared by all its instances, so unlike on ewasm, the
+    /// immutables cannot be embedded in the code. Loading them on first use means calls which
+    /// do not use them do not pay for the storage read.
+    fn load_immutables(&self, binary: &Binary, function: FunctionValue) {
+        if let Some(immutables) = binary.module.get_global("immutables") {
+            let loaded = binary
+                .module
+                .get_global("immutables_loaded")
+                .unwrap()
+                .as_pointer_value();
+
+            let load_block = binary
+                .context
+                .append_basic_block(function, "load_immutables");
+            let done_block = binary
+                .context
+                .append_basic_block(function, "immutables_loaded");
+
+            binary.builder.build_conditional_branch(
+                binary.builder.build_load(loaded, "loaded").into_int_value(),
+                done_block,
+                load_block,
+            );
+
+            binary.builder.position_at_end(load_block);
+
+            let len = immutables
+                .as_pointer_value()
+                .get_type()
+                .get_element_type()
+                .into_array_type()
+                .len();
+
+            let scratch_len = binary.scratch_len.unwrap().as_pointer_value();
+            binary.builder.build_store(
+                scratch_len,
+                binary.context.i32_type().const_int(len as u64, false),
+            );
+
+            binary.builder.build_call(
+                binary.module.get_function("seal_get_storage").unwrap(),
+                &[
+                    self.immutables_key(binary).into(),
+                    binary
+                        .builder
+                        .build_pointer_cast(
+                            immutables.as_pointer_value(),
+                            binary.context.i8_type().ptr_type(AddressSpace::Generic),
+                            "",
+                        )
+                        .into(),
+                    scratch_len.into(),
+                ],
+                "",
+            );
+
+            binary
+                .builder
+                .build_store(loaded, binary.context.bool_type().const_int(1, false));
+
+            binary.builder.build_unconditional_branch(done_block);
+
+            binary.builder.position_at_end(done_block);
+        }
+    }
+
     /// ABI decode a single primitive
     fn decode_primitive<'b>(
         &self,
@@ -596,7 +731,10 @@ impl SubstrateTarget {
                 );
                 (val.into(), 1)
             }
-            ast::Type::Uint(bits) | ast::Type::Int(bits) => {
+            ast::Type::Uint(bits)
+            | ast::Type::Int(bits)
+            | ast::Type::Fixed(bits, _)
+            | ast::Type::Ufixed(bits, _) => {
                 let int_type = binary.context.custom_width_int_type(*bits as u32);
 
                 let val = binary.builder.build_load(
@@ -729,6 +867,8 @@ impl SubstrateTarget {
             | ast::Type::Contract(_)
             | ast::Type::Int(_)
             | ast::Type::Uint(_)
+            | ast::Type::Fixed(..)
+            | ast::Type::Ufixed(..)
             | ast::Type::Bytes(_) => {
                 let (arg, arglen) = self.decode_primitive(binary, ty, *data, ns);
 
@@ -1063,9 +1203,15 @@ impl SubstrateTarget {
 
                 1
             }
-            ast::Type::Uint(_) | ast::Type::Int(_) => {
+            ast::Type::Uint(_)
+            | ast::Type::Int(_)
+            | ast::Type::Fixed(_, _)
+            | ast::Type::Ufixed(_, _) => {
                 let len = match ty {
-                    ast::Type::Uint(n) | ast::Type::Int(n) => *n as u64 / 8,
+                    ast::Type::Uint(n)
+                    | ast::Type::Int(n)
+                    | ast::Type::Fixed(n, _)
+                    | ast::Type::Ufixed(n, _) => *n as u64 / 8,
                     _ => ns.address_length as u64,
                 };
 
@@ -1080,7 +1226,7 @@ impl SubstrateTarget {
 
                 let arg = if len == power_of_two_len {
                     arg.into_int_value()
-                } else if ty.is_signed_int() {
+                } else if ty.is_signed_numeric() {
                     binary.builder.build_int_s_extend(
                         arg.into_int_value(),
                         binary
@@ -1187,6 +1333,8 @@ impl SubstrateTarget {
             | ast::Type::Contract(_)
             | ast::Type::Int(_)
             | ast::Type::Uint(_)
+            | ast::Type::Fixed(..)
+            | ast::Type::Ufixed(..)
             | ast::Type::Bytes(_) => {
                 let arglen = self.encode_primitive(binary, load, ty, *data, arg, ns);
 
@@ -1590,9 +1738,10 @@ impl SubstrateTarget {
     ) -> IntValue<'x> {
         match ty {
             ast::Type::Bool => binary.context.i32_type().const_int(1, false),
-            ast::Type::Uint(n) | ast::Type::Int(n) => {
-                binary.context.i32_type().const_int(*n as u64 / 8, false)
-            }
+            ast::Type::Uint(n)
+            | ast::Type::Int(n)
+            | ast::Type::Fixed(n, _)
+            | ast::Type::Ufixed(n, _) => binary.context.i32_type().const_int(*n as u64 / 8, false),
             ast::Type::Bytes(n) => binary.context.i32_type().const_int(*n as u64, false),
             ast::Type::Address(_) | ast::Type::Contract(_) => binary
                 .context
@@ -3005,6 +3154,54 @@ impl<'a> TargetRuntime<'a> for SubstrateTarget {
             .into_int_value()
     }
 
+    /// The immutables are kept in contract storage, see load_immutables()
+    fn immutable_load(
+        &self,
+        binary: &Binary<'a>,
+        ty: &ast::Type,
+        offset: usize,
+        function: FunctionValue,
+        ns: &ast::Namespace,
+    ) -> BasicValueEnum<'a> {
+        self.load_immutables(binary, function);
+
+        let ptr = binary.immutable_pointer(ty, offset, ns);
+
+        binary.builder.build_load(ptr, "immutable")
+    }
+
+    fn store_immutables(&self, binary: &Binary<'a>) {
+        if let Some(immutables) = binary.module.get_global("immutables") {
+            let len = immutables
+                .as_pointer_value()
+                .get_type()
+                .get_element_type()
+                .into_array_type()
+                .len();
+
+            binary.builder.build_call(
+                binary.module.get_function("seal_set_storage").unwrap(),
+                &[
+                    self.immutables_key(binary).into(),
+                    binary
+                        .builder
+                        .build_pointer_cast(
+                            immutables.as_pointer_value(),
+                            binary.context.i8_type().ptr_type(AddressSpace::Generic),
+                            "",
+                        )
+                        .into(),
+                    binary
+                        .context
+                        .i32_type()
+                        .const_int(len as u64, false)
+                        .into(),
+                ],
+                "",
+            );
+        }
+    }
+
     fn return_empty_abi(&self, binary: &Binary) {
         binary.builder.build_call(
             binary.module.get_function("seal_return").unwrap(),
@@ -4411,6 +4608,228 @@ impl<'a> TargetRuntime<'a> for SubstrateTarget {
                     "hash",
                 )
             }
+            codegen::Expression::Builtin(_, _, codegen::Builtin::ECRecover, args) => {
+                assert_eq!(args.len(), 4);
+
+                // the signature is r, s and the recovery id v; the hashes are big endian
+                let signature = binary.builder.build_array_alloca(
+                    binary.context.i8_type(),
+                    binary.context.i32_type().const_int(65, false),
+                    "signature",
+                );
+
+                let message_hash = binary.builder.build_array_alloca(
+                    binary.context.i8_type(),
+                    binary.context.i32_type().const_int(32, false),
+                    "message_hash",
+                );
+
+                let bytes32_to_be = |arg: &codegen::Expression, dest: PointerValue<'b>| {
+                    let value = binary.build_alloca(
+                        function,
+                        binary.context.custom_width_int_type(256),
+                        "value",
+                    );
+
+                    binary.builder.build_store(
+                        value,
+                        self.expression(binary, arg, vartab, function, ns)
+                            .into_int_value(),
+                    );
+
+                    binary.builder.build_call(
+                        binary.module.get_function("__leNtobeN").unwrap(),
+                        &[
+                            binary
+                                .builder
+                                .build_pointer_cast(
+                                    value,
+                                    binary.context.i8_type().ptr_type(AddressSpace::Generic),
+                                    "",
+                                )
+                                .into(),
+                            dest.into(),
+                            binary.context.i32_type().const_int(32, false).into(),
+                        ],
+                        "",
+                    );
+                };
+
+                bytes32_to_be(&args[0], message_hash);
+                bytes32_to_be(&args[2], signature);
+                bytes32_to_be(&args[3], unsafe {
+                    binary.builder.build_gep(
+                        signature,
+                        &[binary.context.i32_type().const_int(32, false)],
+                        "s",
+                    )
+                });
+
+                let v = self
+                    .expression(binary, &args[1], vartab, function, ns)
+                    .into_int_value();
+
+                binary.builder.build_store(
+                    unsafe {
+                        binary.builder.build_gep(
+                            signature,
+                            &[binary.context.i32_type().const_int(64, false)],
+                            "v",
+                        )
+                    },
+                    v,
+                );
+
+                // compressed public key
+                let public_key = binary.builder.build_array_alloca(
+                    binary.context.i8_type(),
+                    binary.context.i32_type().const_int(33, false),
+                    "public_key",
+                );
+
+                let ret = binary
+                    .builder
+                    .build_call(
+                        binary.module.get_function("seal_ecdsa_recover").unwrap(),
+                        &[signature.into(), message_hash.into(), public_key.into()],
+                        "ecdsa_recover",
+                    )
+                    .try_as_basic_value()
+                    .left()
+                    .unwrap()
+                    .into_int_value();
+
+                let success = binary.builder.build_int_compare(
+                    IntPredicate::EQ,
+                    ret,
+                    binary.context.i32_type().const_zero(),
+                    "success",
+                );
+
+                let entry = binary.builder.get_insert_block().unwrap();
+                let recovered = binary.context.append_basic_block(function, "recovered");
+                let done = binary.context.append_basic_block(function, "done");
+
+                binary
+                    .builder
+                    .build_conditional_branch(success, recovered, done);
+
+                binary.builder.position_at_end(recovered);
+
+                // the account id of an ecdsa key is the blake2_256 hash of the compressed public key
+                let address = binary.build_alloca(function, binary.address_type(ns), "address");
+
+                binary.builder.build_call(
+                    binary.module.get_function("seal_hash_blake2_256").unwrap(),
+                    &[
+                        public_key.into(),
+                        binary.context.i32_type().const_int(33, false).into(),
+                        binary
+                            .builder
+                            .build_pointer_cast(
+                                address,
+                                binary.context.i8_type().ptr_type(AddressSpace::Generic),
+                                "",
+                            )
+                            .into(),
+                    ],
+                    "",
+                );
+
+                let address = binary.builder.build_load(address, "address");
+
+                binary.builder.build_unconditional_branch(done);
+
+                binary.builder.position_at_end(done);
+
+                let signer = binary.builder.build_phi(binary.address_type(ns), "signer");
+
+                signer.add_incoming(&[
+                    (&address, recovered),
+                    (&binary.address_type(ns).const_zero(), entry),
+                ]);
+
+                signer.as_basic_value()
+            }
+            codegen::Expression::Builtin(_, _, codegen::Builtin::SignatureVerify, args) => {
+                assert_eq!(args.len(), 3);
+
+                let public_key =
+                    binary.build_alloca(function, binary.address_type(ns), "public_key");
+
+                binary.builder.build_store(
+                    public_key,
+                    self.expression(binary, &args[0], vartab, function, ns)
+                        .into_array_value(),
+                );
+
+                let message = self.expression(binary, &args[1], vartab, function, ns);
+                let signature = self.expression(binary, &args[2], vartab, function, ns);
+
+                // sr25519 signatures are always 64 bytes
+                let length_ok = binary.builder.build_int_compare(
+                    IntPredicate::EQ,
+                    binary.vector_len(signature),
+                    binary.context.i32_type().const_int(64, false),
+                    "length_ok",
+                );
+
+                let entry = binary.builder.get_insert_block().unwrap();
+                let verify = binary.context.append_basic_block(function, "verify");
+                let done = binary.context.append_basic_block(function, "done");
+
+                binary
+                    .builder
+                    .build_conditional_branch(length_ok, verify, done);
+
+                binary.builder.position_at_end(verify);
+
+                let ret = binary
+                    .builder
+                    .build_call(
+                        binary.module.get_function("seal_sr25519_verify").unwrap(),
+                        &[
+                            binary.vector_bytes(signature).into(),
+                            binary
+                                .builder
+                                .build_pointer_cast(
+                                    public_key,
+                                    binary.context.i8_type().ptr_type(AddressSpace::Generic),
+                                    "",
+                                )
+                                .into(),
+                            binary.vector_len(message).into(),
+                            binary.vector_bytes(message).into(),
+                        ],
+                        "sr25519_verify",
+                    )
+                    .try_as_basic_value()
+                    .left()
+                    .unwrap()
+                    .into_int_value();
+
+                let verified = binary.builder.build_int_compare(
+                    IntPredicate::EQ,
+                    ret,
+                    binary.context.i32_type().const_zero(),
+                    "verified",
+                );
+
+                binary.builder.build_unconditional_branch(done);
+
+                binary.builder.position_at_end(done);
+
+                let res = binary
+                    .builder
+                    .build_phi(binary.context.bool_type(), "signature_verify");
+
+                res.add_incoming(&[
+                    (&verified, verify),
+                    (&binary.context.bool_type().const_zero(), entry),
+                ]);
+
+                res.as_basic_value()
+            }
             codegen::Expression::Builtin(_, _, codegen::Builtin::GetAddress, _) => {
                 let scratch_buf = binary.builder.build_pointer_cast(
                     binary.scratch.unwrap().as_pointer_value(),
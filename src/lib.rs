@@ -156,9 +156,8 @@ pub fn compile_many<'a>(
     namespaces: &'a [&sema::ast::Namespace],
     filename: &str,
     opt: inkwell::OptimizationLevel,
-    math_overflow_check: bool,
 ) -> emit::binary::Binary<'a> {
-    emit::binary::Binary::build_bundle(context, namespaces, filename, opt, math_overflow_check)
+    emit::binary::Binary::build_bundle(context, namespaces, filename, opt)
 }
 
 /// Parse and resolve the Solidity source code provided in src, for the target chain as specified in target.
@@ -8,6 +8,8 @@ use crate::sema::Recurse;
 use crate::{codegen, Target};
 use num_bigint::BigInt;
 use num_rational::BigRational;
+use semver::Version;
+use sha2::{Digest, Sha256};
 pub use solang_parser::diagnostics::*;
 use solang_parser::pt;
 use solang_parser::pt::{CodeLocation, OptionalCodeLocation};
@@ -25,6 +27,9 @@ pub enum Type {
     Bool,
     Int(u16),
     Uint(u16),
+    /// Fixed point number, with the number of bits and the number of decimals
+    Fixed(u16, u8),
+    Ufixed(u16, u8),
     Rational,
     Bytes(u8),
     DynamicBytes,
@@ -99,7 +104,7 @@ pub trait RetrieveType {
 impl Type {
     pub fn get_type_size(&self) -> u16 {
         match self {
-            Type::Int(n) | Type::Uint(n) => *n,
+            Type::Int(n) | Type::Uint(n) | Type::Fixed(n, _) | Type::Ufixed(n, _) => *n,
             Type::Bool => 1,
             _ => unimplemented!("size of type not known"),
         }
@@ -156,6 +161,37 @@ impl EventDecl {
     }
 }
 
+#[derive(PartialEq, Clone, Debug)]
+pub struct ErrorDecl {
+    pub tags: Vec<Tag>,
+    pub name: String,
+    pub loc: pt::Loc,
+    pub contract: Option<usize>,
+    pub fields: Vec<Parameter>,
+    pub signature: String,
+}
+
+impl ErrorDecl {
+    pub fn symbol_name(&self, ns: &Namespace) -> String {
+        match &self.contract {
+            Some(c) => format!("{}.{}", ns.contracts[*c].name, self.name),
+            None => self.name.to_string(),
+        }
+    }
+
+    /// Generate the selector for this error, which is the first four bytes of the
+    /// keccak256 hash of the signature
+    pub fn selector(&self) -> u32 {
+        let mut res = [0u8; 32];
+
+        let mut hasher = Keccak::v256();
+        hasher.update(self.signature.as_bytes());
+        hasher.finalize(&mut res);
+
+        u32::from_be_bytes([res[0], res[1], res[2], res[3]])
+    }
+}
+
 impl fmt::Display for StructDecl {
     /// Make the struct name into a string for printing. The struct can be declared either
     /// inside or outside a contract.
@@ -266,6 +302,8 @@ pub struct Function {
     pub symtable: Symtable,
     // What events are emitted by the body of this function
     pub emits_events: Vec<usize>,
+    // What custom errors can the body of this function revert with
+    pub reverts_errors: Vec<usize>,
 }
 
 /// This trait provides a single interface for fetching paramenters, returns and the symbol table
@@ -290,6 +328,33 @@ impl FunctionAttributes for Function {
     }
 }
 
+/// Convert a camel case name to snake case, the same way the Anchor client does, e.g.
+/// `transferFrom` becomes `transfer_from` and `getABCValue` becomes `get_abc_value`
+pub(crate) fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut res = String::new();
+
+    for (i, ch) in chars.iter().enumerate() {
+        if i > 0 && ch.is_ascii_uppercase() {
+            let prev = chars[i - 1];
+
+            if prev.is_ascii_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_ascii_uppercase()
+                    && chars
+                        .get(i + 1)
+                        .map_or(false, |next| next.is_ascii_lowercase()))
+            {
+                res.push('_');
+            }
+        }
+
+        res.push(ch.to_ascii_lowercase());
+    }
+
+    res
+}
+
 impl Function {
     pub fn new(
         loc: pt::Loc,
@@ -337,6 +402,7 @@ impl Function {
             body: Vec::new(),
             symtable: Symtable::new(),
             emits_events: Vec::new(),
+            reverts_errors: Vec::new(),
         }
     }
 
@@ -351,6 +417,14 @@ impl Function {
         u32::from_be_bytes([res[0], res[1], res[2], res[3]])
     }
 
+    /// Generate the Anchor instruction discriminator for this function. Anchor clients convert the
+    /// method name to snake case before hashing it.
+    pub fn discriminator(&self) -> [u8; 8] {
+        let hash = Sha256::digest(format!("global:{}", snake_case(&self.name)).as_bytes());
+
+        hash[..8].try_into().unwrap()
+    }
+
     /// Is this a constructor
     pub fn is_constructor(&self) -> bool {
         self.ty == pt::FunctionTy::Constructor
@@ -396,13 +470,14 @@ impl From<&pt::Type> for Type {
             pt::Type::Payable => Type::Address(true),
             pt::Type::Int(n) => Type::Int(*n),
             pt::Type::Uint(n) => Type::Uint(*n),
+            pt::Type::Fixed(m, n) => Type::Fixed(*m, *n),
+            pt::Type::Ufixed(m, n) => Type::Ufixed(*m, *n),
             pt::Type::Bytes(n) => Type::Bytes(*n),
             pt::Type::String => Type::String,
             pt::Type::Rational => Type::Rational,
             pt::Type::DynamicBytes => Type::DynamicBytes,
-            // needs special casing
-            pt::Type::Function { .. } => unimplemented!(),
-            pt::Type::Mapping(..) => unimplemented!(),
+            // needs special casing; see Namespace::resolve_type()
+            pt::Type::Function { .. } | pt::Type::Mapping(..) => unreachable!(),
         }
     }
 }
@@ -447,6 +522,7 @@ pub enum Symbol {
     Variable(pt::Loc, Option<usize>, usize),
     Struct(pt::Loc, StructType),
     Event(Vec<(pt::Loc, usize)>),
+    Error(pt::Loc, usize),
     Contract(pt::Loc, usize),
     Import(pt::Loc, usize),
     UserType(pt::Loc, usize),
@@ -458,6 +534,7 @@ impl CodeLocation for Symbol {
             Symbol::Enum(loc, _)
             | Symbol::Variable(loc, ..)
             | Symbol::Struct(loc, _)
+            | Symbol::Error(loc, _)
             | Symbol::Contract(loc, _)
             | Symbol::Import(loc, _)
             | Symbol::UserType(loc, _) => *loc,
@@ -506,6 +583,16 @@ pub struct File {
     pub line_starts: Vec<usize>,
     /// Indicates the file number in FileResolver.files
     pub cache_no: Option<usize>,
+    /// Arithmetic is checked for overflow by default, like Solidity 0.8 and later
+    pub checked_arithmetic: bool,
+    /// The lowest Solidity version permitted by `pragma solidity`, if present, and the version
+    /// which all permitted versions are below, if there is an upper bound
+    pub solidity_version: Option<(Version, Option<Version>)>,
+    /// On Solana, `pragma solana value_transfer` permits sending value with calls and `msg.value`
+    pub value_transfer: bool,
+    /// On Solana, `pragma solana anchor_discriminator` dispatches functions on their Anchor
+    /// instruction discriminator, as well as their selector
+    pub anchor_discriminator: bool,
 }
 
 /// When resolving a Solidity file, this holds all the resolved items
@@ -515,6 +602,7 @@ pub struct Namespace {
     pub enums: Vec<EnumDecl>,
     pub structs: Vec<StructDecl>,
     pub events: Vec<EventDecl>,
+    pub errors: Vec<ErrorDecl>,
     pub contracts: Vec<Contract>,
     /// Global using declarations
     pub using: Vec<Using>,
@@ -565,7 +653,14 @@ pub struct Using {
 
 pub enum UsingList {
     Library(usize),
-    Functions(Vec<usize>),
+    Functions(Vec<UsingFunction>),
+}
+
+/// A function in a using list, possibly bound to an operator for a user defined type
+pub struct UsingFunction {
+    pub loc: pt::Loc,
+    pub function_no: usize,
+    pub oper: Option<pt::UserDefinedOperator>,
 }
 
 pub struct Contract {
@@ -577,6 +672,13 @@ pub struct Contract {
     pub using: Vec<Using>,
     pub layout: Vec<Layout>,
     pub fixed_layout_size: BigInt,
+    /// Offsets of the immutable variables in the immutables area
+    pub immutable_layout: Vec<Layout>,
+    /// Size of the immutables area in bytes; on Solana, immutables are in the account data
+    pub immutable_layout_size: BigInt,
+    /// On Solana with value transfers enabled, offset in the account data of the lamports balance
+    /// recorded after the last call, used to calculate `msg.value`
+    pub lamports_offset: Option<BigInt>,
     pub functions: Vec<usize>,
     pub all_functions: BTreeMap<usize, usize>,
     pub virtual_functions: HashMap<String, usize>,
@@ -586,6 +688,8 @@ pub struct Contract {
     pub creates: Vec<usize>,
     // List of events this contract produces
     pub sends_events: Vec<usize>,
+    // List of errors this contract can revert with
+    pub reverts_errors: Vec<usize>,
     pub initializer: Option<usize>,
     pub default_constructor: Option<(Function, usize)>,
     pub cfg: Vec<ControlFlowGraph>,
@@ -646,7 +750,7 @@ pub enum Expression {
     Add(pt::Loc, Type, bool, Box<Expression>, Box<Expression>),
     Subtract(pt::Loc, Type, bool, Box<Expression>, Box<Expression>),
     Multiply(pt::Loc, Type, bool, Box<Expression>, Box<Expression>),
-    Divide(pt::Loc, Type, Box<Expression>, Box<Expression>),
+    Divide(pt::Loc, Type, bool, Box<Expression>, Box<Expression>),
     Modulo(pt::Loc, Type, Box<Expression>, Box<Expression>),
     Power(pt::Loc, Type, bool, Box<Expression>, Box<Expression>),
     BitwiseOr(pt::Loc, Type, Box<Expression>, Box<Expression>),
@@ -692,6 +796,13 @@ pub enum Expression {
         Box<Expression>,
     ),
     Subscript(pt::Loc, Type, Type, Box<Expression>, Box<Expression>),
+    ArraySlice {
+        loc: pt::Loc,
+        ty: Type,
+        array: Box<Expression>,
+        start: Option<Box<Expression>>,
+        end: Option<Box<Expression>>,
+    },
     StructMember(pt::Loc, Type, Box<Expression>, usize),
 
     AllocDynamicArray(pt::Loc, Type, Box<Expression>, Option<Vec<u8>>),
@@ -802,7 +913,7 @@ impl Recurse for Expression {
                 Expression::Add(_, _, _, left, right)
                 | Expression::Subtract(_, _, _, left, right)
                 | Expression::Multiply(_, _, _, left, right)
-                | Expression::Divide(_, _, left, right)
+                | Expression::Divide(_, _, _, left, right)
                 | Expression::Modulo(_, _, left, right)
                 | Expression::Power(_, _, _, left, right)
                 | Expression::BitwiseOr(_, _, left, right)
@@ -848,6 +959,17 @@ impl Recurse for Expression {
                     left.recurse(cx, f);
                     right.recurse(cx, f);
                 }
+                Expression::ArraySlice {
+                    array, start, end, ..
+                } => {
+                    array.recurse(cx, f);
+                    if let Some(start) = start {
+                        start.recurse(cx, f);
+                    }
+                    if let Some(end) = end {
+                        end.recurse(cx, f);
+                    }
+                }
                 Expression::StructMember(_, _, expr, _) => expr.recurse(cx, f),
 
                 Expression::AllocDynamicArray(_, _, expr, _) => expr.recurse(cx, f),
@@ -961,6 +1083,7 @@ impl CodeLocation for Expression {
             | Expression::UnaryMinus(loc, ..)
             | Expression::Ternary(loc, ..)
             | Expression::Subscript(loc, ..)
+            | Expression::ArraySlice { loc, .. }
             | Expression::StructMember(loc, ..)
             | Expression::Or(loc, ..)
             | Expression::AllocDynamicArray(loc, ..)
@@ -1060,6 +1183,7 @@ pub enum Builtin {
     ExternalFunctionAddress,
     FunctionSelector,
     SignatureVerify,
+    ECRecover,
     ReadInt8,
     ReadInt16LE,
     ReadInt32LE,
@@ -1140,6 +1264,11 @@ pub enum Statement {
         event_loc: pt::Loc,
         args: Vec<Expression>,
     },
+    Revert {
+        loc: pt::Loc,
+        error_no: usize,
+        args: Vec<Expression>,
+    },
     TryCatch(pt::Loc, bool, TryCatch),
     Underscore(pt::Loc),
     Assembly(InlineAssembly, bool),
@@ -1150,12 +1279,25 @@ pub struct TryCatch {
     pub expr: Expression,
     pub returns: Vec<(Option<usize>, Parameter)>,
     pub ok_stmt: Vec<Statement>,
-    pub errors: Vec<(Option<usize>, Parameter, Vec<Statement>)>,
+    pub errors: Vec<CatchClause>,
     pub catch_param: Option<Parameter>,
     pub catch_param_pos: Option<usize>,
     pub catch_stmt: Vec<Statement>,
 }
 
+/// A catch clause for a specific error, e.g. `catch Error(string reason)` or a custom error
+#[derive(Clone, Debug)]
+pub struct CatchClause {
+    pub loc: pt::Loc,
+    /// Name of the error, e.g. `Error`, `Panic` or the name of a custom error
+    pub name: String,
+    /// The four byte selector which the return data should start with
+    pub selector: u32,
+    /// The variable number (if the parameter is named) and the parameter for each error field
+    pub params: Vec<(Option<usize>, Parameter)>,
+    pub stmt: Vec<Statement>,
+}
+
 #[derive(Clone, Debug)]
 #[allow(clippy::large_enum_variant)]
 pub enum DestructureField {
@@ -1223,8 +1365,8 @@ impl Recurse for Statement {
                         stmt.recurse(cx, f);
                     }
 
-                    for error_stmt in &try_catch.errors {
-                        for stmt in &error_stmt.2 {
+                    for clause in &try_catch.errors {
+                        for stmt in &clause.stmt {
                             stmt.recurse(cx, f);
                         }
                     }
@@ -1254,7 +1396,10 @@ impl Statement {
             | Statement::Emit { .. }
             | Statement::Delete(..) => true,
 
-            Statement::Continue(_) | Statement::Break(_) | Statement::Return(..) => false,
+            Statement::Continue(_)
+            | Statement::Break(_)
+            | Statement::Return(..)
+            | Statement::Revert { .. } => false,
 
             Statement::If(_, reachable, ..)
             | Statement::While(_, reachable, ..)
@@ -13,6 +13,7 @@ use crate::Target;
 use num_bigint::BigInt;
 use num_traits::One;
 use once_cell::sync::Lazy;
+use semver::Version;
 use solang_parser::pt::CodeLocation;
 use solang_parser::pt::{self, Identifier};
 use std::path::PathBuf;
@@ -31,7 +32,7 @@ pub struct Prototype {
 }
 
 // A list of all Solidity builtins functions
-static BUILTIN_FUNCTIONS: Lazy<[Prototype; 27]> = Lazy::new(|| {
+static BUILTIN_FUNCTIONS: Lazy<[Prototype; 28]> = Lazy::new(|| {
     [
         Prototype {
             builtin: Builtin::Assert,
@@ -311,8 +312,24 @@ static BUILTIN_FUNCTIONS: Lazy<[Prototype; 27]> = Lazy::new(|| {
             name: "signatureVerify",
             params: vec![Type::Address(false), Type::DynamicBytes, Type::DynamicBytes],
             ret: vec![Type::Bool],
-            target: vec![Target::Solana],
-            doc: "ed25519 signature verification",
+            target: vec![Target::Solana, Target::default_substrate()],
+            doc: "ed25519 signature verification on Solana, sr25519 on Substrate",
+            constant: false,
+        },
+        Prototype {
+            builtin: Builtin::ECRecover,
+            namespace: None,
+            method: None,
+            name: "ecrecover",
+            params: vec![
+                Type::Bytes(32),
+                Type::Uint(8),
+                Type::Bytes(32),
+                Type::Bytes(32),
+            ],
+            ret: vec![Type::Address(false)],
+            target: vec![Target::default_substrate(), Target::Ewasm],
+            doc: "Recover the signer of a secp256k1 signature",
             constant: false,
         },
         Prototype {
@@ -846,7 +863,10 @@ pub fn builtin_var(
                     ),
                 ));
             }
-            if ns.target == Target::Solana && p.builtin == Builtin::Value {
+            if ns.target == Target::Solana
+                && p.builtin == Builtin::Value
+                && !ns.files[loc.file_no()].value_transfer
+            {
                 diagnostics.push(Diagnostic::error(
                     *loc,
                     String::from(
@@ -1173,6 +1193,20 @@ pub fn resolve_namespace_call(
             }
         }
         Builtin::AbiEncodeCall => {
+            if let Some((_, Some(end))) = &ns.files[context.file_no].solidity_version {
+                if *end <= Version::new(0, 8, 11) {
+                    diagnostics.push(Diagnostic::error(
+                        *loc,
+                        format!(
+                            "'abi.encodeCall' requires Solidity 0.8.11 or later, but pragma 'solidity' only permits versions before {}",
+                            end
+                        ),
+                    ));
+
+                    return Err(());
+                }
+            }
+
             // first argument is function
             if let Some(function) = args_iter.next() {
                 let function = expression(
@@ -1292,7 +1326,13 @@ pub fn resolve_namespace_call(
             return Err(());
         }
 
-        expr = expr.cast(&arg.loc(), ty.deref_any(), true, ns, diagnostics)?;
+        expr = expr.cast(
+            &arg.loc(),
+            &ty.deref_any().slice_to_array(),
+            true,
+            ns,
+            diagnostics,
+        )?;
 
         // A string or hex literal should be encoded as a string
         if let Expression::BytesLiteral(..) = &expr {
@@ -1435,6 +1475,10 @@ impl Namespace {
             path: PathBuf::from("solana"),
             line_starts: Vec::new(),
             cache_no: None,
+            checked_arithmetic: false,
+            solidity_version: None,
+            value_transfer: false,
+            anchor_discriminator: false,
         });
 
         let id = pt::Identifier {
@@ -33,6 +33,9 @@ impl ast::Contract {
             using: Vec::new(),
             layout: Vec::new(),
             fixed_layout_size: BigInt::zero(),
+            immutable_layout: Vec::new(),
+            immutable_layout_size: BigInt::zero(),
+            lamports_offset: None,
             tags,
             functions: Vec::new(),
             all_functions: BTreeMap::new(),
@@ -41,6 +44,7 @@ impl ast::Contract {
             variables: Vec::new(),
             creates: Vec::new(),
             sends_events: Vec::new(),
+            reverts_errors: Vec::new(),
             initializer: None,
             default_constructor: None,
             cfg: Vec::new(),
@@ -56,9 +60,8 @@ impl ast::Contract {
         context: &'a inkwell::context::Context,
         filename: &'a str,
         opt: inkwell::OptimizationLevel,
-        math_overflow_check: bool,
     ) -> emit::binary::Binary {
-        emit::binary::Binary::build(context, self, ns, filename, opt, math_overflow_check)
+        emit::binary::Binary::build(context, self, ns, filename, opt)
     }
 
     /// Selector for this contract. This is used by Solana contract bundle
@@ -95,6 +98,7 @@ pub fn resolve(
     // Now we have all the declarations, we can handle base contracts
     for (contract_no, _) in contracts {
         check_inheritance(*contract_no, ns);
+        check_anchor_discriminators(*contract_no, ns);
     }
 
     // Now we can resolve the initializers
@@ -982,6 +986,48 @@ pub fn collect_base_args<'a>(
     }
 }
 
+/// With `pragma solana anchor_discriminator`, functions are dispatched on a hash of their name,
+/// so overloaded public functions cannot be told apart
+fn check_anchor_discriminators(contract_no: usize, ns: &mut ast::Namespace) {
+    let contract = &ns.contracts[contract_no];
+
+    if !contract.is_concrete() || !ns.files[contract.loc.file_no()].anchor_discriminator {
+        return;
+    }
+
+    let mut discriminators: HashMap<[u8; 8], usize> = HashMap::new();
+    let mut diagnostics = Diagnostics::default();
+
+    for function_no in contract.all_functions.keys() {
+        let func = &ns.functions[*function_no];
+
+        if func.ty != pt::FunctionTy::Function || !func.is_public() {
+            continue;
+        }
+
+        if let Some(prev_no) = discriminators.get(&func.discriminator()) {
+            let prev = &ns.functions[*prev_no];
+
+            // an overriding function has the same signature
+            if prev.signature != func.signature {
+                diagnostics.push(ast::Diagnostic::error_with_note(
+                    func.loc,
+                    format!(
+                        "function '{}' has the same Anchor discriminator as '{}'",
+                        func.signature, prev.signature
+                    ),
+                    prev.loc,
+                    format!("definition of '{}'", prev.signature),
+                ));
+            }
+        } else {
+            discriminators.insert(func.discriminator(), *function_no);
+        }
+    }
+
+    ns.diagnostics.extend(diagnostics);
+}
+
 /// Check if we have arguments for all the base contracts
 fn check_base_args(contract_no: usize, ns: &mut ast::Namespace) {
     let contract = &ns.contracts[contract_no];
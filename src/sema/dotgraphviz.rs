@@ -8,6 +8,7 @@ use crate::sema::{
         builtin::YulBuiltInFunction,
     },
 };
+use num_bigint::{BigInt, Sign};
 use solang_parser::{pt, pt::Loc};
 use std::fmt::Write;
 
@@ -273,6 +274,12 @@ impl Dot {
                 );
             }
             Expression::NumberLiteral(loc, ty, val) => {
+                let val = if ty.is_fixed() {
+                    fixed_literal_to_string(val, ty.fixed_decimals())
+                } else {
+                    val.to_string()
+                };
+
                 let labels = vec![
                     format!("{} literal: {}", ty.to_string(ns), val),
                     ns.loc_to_string(loc),
@@ -390,12 +397,15 @@ impl Dot {
                 self.add_expression(left, func, ns, node, String::from("left"));
                 self.add_expression(right, func, ns, node, String::from("right"));
             }
-            Expression::Divide(loc, ty, left, right) => {
-                let labels = vec![
+            Expression::Divide(loc, ty, unchecked, left, right) => {
+                let mut labels = vec![
                     String::from("divide"),
                     ty.to_string(ns),
                     ns.loc_to_string(loc),
                 ];
+                if *unchecked {
+                    labels.push(String::from("unchecked"));
+                }
                 let node =
                     self.add_node(Node::new("divide", labels), Some(parent), Some(parent_rel));
 
@@ -866,6 +876,33 @@ impl Dot {
                 self.add_expression(array, func, ns, node, String::from("array"));
                 self.add_expression(index, func, ns, node, String::from("index"));
             }
+            Expression::ArraySlice {
+                loc,
+                ty,
+                array,
+                start,
+                end,
+            } => {
+                let node = self.add_node(
+                    Node::new(
+                        "array_slice",
+                        vec![
+                            format!("array slice {}", ty.to_string(ns)),
+                            ns.loc_to_string(loc),
+                        ],
+                    ),
+                    Some(parent),
+                    Some(parent_rel),
+                );
+
+                self.add_expression(array, func, ns, node, String::from("array"));
+                if let Some(start) = start {
+                    self.add_expression(start, func, ns, node, String::from("start"));
+                }
+                if let Some(end) = end {
+                    self.add_expression(end, func, ns, node, String::from("end"));
+                }
+            }
             Expression::StructMember(loc, ty, var, member) => {
                 let node = self.add_node(
                     Node::new(
@@ -1430,6 +1467,24 @@ impl Dot {
                         self.add_expression(arg, Some(func), ns, parent, format!("arg #{}", no));
                     }
                 }
+                Statement::Revert {
+                    loc,
+                    error_no,
+                    args,
+                } => {
+                    let labels = vec![
+                        String::from("revert"),
+                        format!("error {}", ns.errors[*error_no].symbol_name(ns)),
+                        ns.loc_to_string(loc),
+                    ];
+
+                    parent =
+                        self.add_node(Node::new("revert", labels), Some(parent), Some(parent_rel));
+
+                    for (no, arg) in args.iter().enumerate() {
+                        self.add_expression(arg, Some(func), ns, parent, format!("arg #{}", no));
+                    }
+                }
                 Statement::TryCatch(loc, _, try_catch) => {
                     let labels = vec![String::from("try"), ns.loc_to_string(loc)];
 
@@ -1463,21 +1518,35 @@ impl Dot {
 
                     self.add_statement(&try_catch.ok_stmt, func, ns, parent, String::from("ok"));
 
-                    for (_, param, stmt) in &try_catch.errors {
-                        self.add_node(
+                    for clause in &try_catch.errors {
+                        let node = self.add_node(
                             Node::new(
-                                "error_param",
-                                vec![format!(
-                                    "{} {}",
-                                    param.ty.to_string(ns),
-                                    param.name_as_str()
-                                )],
+                                "catch_error",
+                                vec![
+                                    format!("catch {}", clause.name),
+                                    ns.loc_to_string(&clause.loc),
+                                ],
                             ),
                             Some(parent),
-                            Some(String::from("error parameter")),
+                            Some(String::from("error clause")),
                         );
 
-                        self.add_statement(stmt, func, ns, parent, String::from("error"));
+                        for (_, param) in &clause.params {
+                            self.add_node(
+                                Node::new(
+                                    "error_param",
+                                    vec![format!(
+                                        "{} {}",
+                                        param.ty.to_string(ns),
+                                        param.name_as_str()
+                                    )],
+                                ),
+                                Some(node),
+                                Some(String::from("error parameter")),
+                            );
+                        }
+
+                        self.add_statement(&clause.stmt, func, ns, node, String::from("error"));
                     }
 
                     if let Some(param) = &try_catch.catch_param {
@@ -2050,6 +2119,23 @@ impl Dot {
     }
 }
 
+/// Fixed point literals hold the value multiplied by 10^decimals; print it with the decimal point
+fn fixed_literal_to_string(val: &BigInt, decimals: u8) -> String {
+    let digits = format!(
+        "{:0>width$}",
+        val.magnitude().to_string(),
+        width = decimals as usize + 1
+    );
+    let (integer, fraction) = digits.split_at(digits.len() - decimals as usize);
+    let sign = if val.sign() == Sign::Minus { "-" } else { "" };
+
+    if fraction.is_empty() {
+        format!("{}{}", sign, integer)
+    } else {
+        format!("{}{}.{}", sign, integer, fraction)
+    }
+}
+
 impl Namespace {
     pub fn dotgraphviz(&self) -> String {
         let mut dot = Dot {
@@ -2145,6 +2231,33 @@ impl Namespace {
             }
         }
 
+        // errors
+        if !self.errors.is_empty() {
+            let errors = dot.add_node(Node::new("errors", Vec::new()), None, None);
+
+            for decl in &self.errors {
+                let mut labels = vec![format!("name:{}", decl.name), self.loc_to_string(&decl.loc)];
+
+                if let Some(contract) = &decl.contract {
+                    labels.insert(1, format!("contract: {}", self.contracts[*contract].name));
+                }
+
+                for field in &decl.fields {
+                    labels.push(format!(
+                        "field name:{} ty:{}",
+                        field.name_as_str(),
+                        field.ty.to_string(self),
+                    ));
+                }
+
+                let e = Node::new(&decl.name, labels);
+
+                let node = dot.add_node(e, Some(errors), None);
+
+                dot.add_tags(&decl.tags, node);
+            }
+        }
+
         // user types
         if !self.user_types.is_empty() {
             let types = dot.add_node(Node::new("types", Vec::new()), None, None);
@@ -2249,10 +2362,22 @@ impl Namespace {
                 let mut labels = match &using.list {
                     UsingList::Functions(functions) => functions
                         .iter()
-                        .map(|func_no| {
-                            let func = &self.functions[*func_no];
-
-                            format!("function {} {}", func.name, self.loc_to_string(&func.loc))
+                        .map(|using_function| {
+                            let func = &self.functions[using_function.function_no];
+
+                            match using_function.oper {
+                                Some(oper) => format!(
+                                    "function {} as operator {} {}",
+                                    func.name,
+                                    oper,
+                                    self.loc_to_string(&func.loc)
+                                ),
+                                None => format!(
+                                    "function {} {}",
+                                    func.name,
+                                    self.loc_to_string(&func.loc)
+                                ),
+                            }
                         })
                         .collect(),
                     UsingList::Library(library_no) => {
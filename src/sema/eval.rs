@@ -29,7 +29,7 @@ pub fn eval_const_number(
             *loc,
             eval_const_number(l, ns)?.1 * eval_const_number(r, ns)?.1,
         )),
-        Expression::Divide(loc, _, l, r) => {
+        Expression::Divide(loc, _, _, l, r) => {
             let divisor = eval_const_number(r, ns)?.1;
 
             if divisor.is_zero() {
@@ -154,7 +154,7 @@ pub fn eval_const_rational(
             *loc,
             eval_const_rational(l, ns)?.1 * eval_const_rational(r, ns)?.1,
         )),
-        Expression::Divide(loc, _, l, r) => {
+        Expression::Divide(loc, _, _, l, r) => {
             let divisor = eval_const_rational(r, ns)?.1;
 
             if divisor.is_zero() {
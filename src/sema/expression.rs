@@ -85,6 +85,7 @@ impl RetrieveType for Expression {
             | Expression::PostDecrement(_, ty, ..)
             | Expression::Assign(_, ty, ..) => ty.clone(),
             Expression::Subscript(_, ty, ..) => ty.clone(),
+            Expression::ArraySlice { ty, .. } => ty.clone(),
             Expression::StorageArrayLength { ty, .. } => ty.clone(),
             Expression::ExternalFunctionCallRaw { .. } => {
                 panic!("two return values");
@@ -203,6 +204,21 @@ impl Expression {
             );
         }
 
+        // Fixed point literals hold the scaled value, so the scale has to be taken into account
+        if let Expression::NumberLiteral(_, _, n) = self {
+            if to.is_fixed() {
+                let value = if from.is_fixed() {
+                    BigRational::new(n.clone(), BigInt::from(10).pow(from.fixed_decimals()))
+                } else {
+                    BigRational::from(n.clone())
+                };
+
+                return fixed_literal(loc, &value, to, implicit, ns, diagnostics);
+            } else if from.is_fixed() {
+                return self.cast_types(loc, &from, to, implicit, ns, diagnostics);
+            }
+        }
+
         // Special case: when converting literal sign can change if it fits
         match (self, &from, to) {
             (&Expression::NumberLiteral(_, _, ref n), p, &Type::Uint(to_len))
@@ -809,6 +825,61 @@ impl Expression {
                     ))
                 }
             }
+            (Type::Rational, Type::Fixed(..) | Type::Ufixed(..)) => {
+                match eval_const_rational(self, ns) {
+                    Ok((_, big_number)) => {
+                        fixed_literal(loc, &big_number, to, implicit, ns, diagnostics)
+                    }
+                    Err(diag) => {
+                        diagnostics.push(diag);
+                        Err(())
+                    }
+                }
+            }
+            // Between fixed point types, only conversions which do not lose precision or range are
+            // implicit
+            (
+                Type::Fixed(from_len, from_decimals) | Type::Ufixed(from_len, from_decimals),
+                Type::Fixed(to_len, to_decimals) | Type::Ufixed(to_len, to_decimals),
+            ) => {
+                let widening = match (from, to) {
+                    (Type::Ufixed(..), Type::Fixed(..)) => to_len > from_len,
+                    (Type::Fixed(..), Type::Ufixed(..)) => false,
+                    _ => to_len >= from_len,
+                };
+
+                if implicit && (from_decimals != to_decimals || !widening) {
+                    diagnostics.push(Diagnostic::cast_error(
+                        *loc,
+                        format!(
+                            "implicit conversion from {} to {} not allowed",
+                            from.to_string(ns),
+                            to.to_string(ns)
+                        ),
+                    ));
+                    Err(())
+                } else {
+                    Ok(Expression::Cast(*loc, to.clone(), Box::new(self.clone())))
+                }
+            }
+            // Conversion between integers and fixed point numbers changes the scale, so it must
+            // be explicit
+            (Type::Uint(_) | Type::Int(_), Type::Fixed(..) | Type::Ufixed(..))
+            | (Type::Fixed(..) | Type::Ufixed(..), Type::Uint(_) | Type::Int(_)) => {
+                if implicit {
+                    diagnostics.push(Diagnostic::cast_error(
+                        *loc,
+                        format!(
+                            "implicit conversion from {} to {} not allowed",
+                            from.to_string(ns),
+                            to.to_string(ns)
+                        ),
+                    ));
+                    Err(())
+                } else {
+                    Ok(Expression::Cast(*loc, to.clone(), Box::new(self.clone())))
+                }
+            }
             (Type::Rational, Type::Uint(_) | Type::Int(_) | Type::Value) => {
                 match eval_const_rational(self, ns) {
                     Ok((_, big_number)) => {
@@ -1091,6 +1162,22 @@ impl Expression {
             (Type::DynamicBytes, Type::Slice(ty)) if ty.as_ref() == &Type::Bytes(1) => {
                 Ok(Expression::Cast(*loc, to.clone(), Box::new(self.clone())))
             }
+            // A slice can be copied into a new array of the same element type
+            (Type::Slice(ty), Type::DynamicBytes) if ty.as_ref() == &Type::Bytes(1) => {
+                Ok(Expression::Cast(*loc, to.clone(), Box::new(self.clone())))
+            }
+            (Type::Slice(ty), Type::String) if !implicit && ty.as_ref() == &Type::Bytes(1) => {
+                Ok(Expression::Cast(*loc, to.clone(), Box::new(self.clone())))
+            }
+            (Type::Slice(ty), Type::Bytes(_)) if !implicit && ty.as_ref() == &Type::Bytes(1) => Ok(
+                Expression::BytesCast(*loc, from.clone(), to.clone(), Box::new(self.clone())),
+            ),
+            (Type::Slice(from_elem), Type::Array(_, to_dim))
+                if to_dim.last() == Some(&ArrayLength::Dynamic)
+                    && &to.array_elem() == from_elem.as_ref() =>
+            {
+                Ok(Expression::Cast(*loc, to.clone(), Box::new(self.clone())))
+            }
             _ => {
                 diagnostics.push(Diagnostic::cast_error(
                     *loc,
@@ -1287,6 +1374,109 @@ fn get_int_length(
     }
 }
 
+/// Fixed point numbers only support arithmetic and comparison operators
+fn fixed_point_not_allowed(
+    ty: &Type,
+    loc: &pt::Loc,
+    ns: &Namespace,
+    diagnostics: &mut Diagnostics,
+) -> Result<(), ()> {
+    if ty.is_fixed() {
+        diagnostics.push(Diagnostic::error(
+            *loc,
+            format!(
+                "operator not allowed on fixed point type {}",
+                ty.to_string(ns)
+            ),
+        ));
+        Err(())
+    } else {
+        Ok(())
+    }
+}
+
+/// Fixed point multiply and divide are done with a wider intermediate result, which has to fit
+/// into 256 bits
+fn fixed_point_intermediate(
+    ty: &Type,
+    multiply: bool,
+    loc: &pt::Loc,
+    ns: &Namespace,
+    diagnostics: &mut Diagnostics,
+) -> Result<(), ()> {
+    if ty.is_fixed() && ty.fixed_intermediate_bits(multiply, ns) > 256 {
+        diagnostics.push(Diagnostic::error(
+            *loc,
+            format!(
+                "{} of fixed point type {} not supported, as the intermediate result does not fit into 256 bits",
+                if multiply { "multiplication" } else { "division" },
+                ty.to_string(ns)
+            ),
+        ));
+        Err(())
+    } else {
+        Ok(())
+    }
+}
+
+/// Convert a constant value to a fixed point literal. The literal holds the value multiplied by
+/// 10 to the power of the number of decimals.
+fn fixed_literal(
+    loc: &pt::Loc,
+    value: &BigRational,
+    to: &Type,
+    implicit: bool,
+    ns: &Namespace,
+    diagnostics: &mut Diagnostics,
+) -> Result<Expression, ()> {
+    let scaled = value.mul(BigRational::from(BigInt::from(10).pow(to.fixed_decimals())));
+
+    if implicit && !scaled.is_integer() {
+        diagnostics.push(Diagnostic::cast_error(
+            *loc,
+            format!(
+                "literal has more than {} decimals and cannot be represented by type '{}'",
+                to.fixed_decimals(),
+                to.to_string(ns)
+            ),
+        ));
+        return Err(());
+    }
+
+    // explicit conversions round towards zero
+    let n = scaled.to_integer();
+
+    let permitted_bits = if to.is_signed_numeric() {
+        to.bits(ns) as u64 - 1
+    } else {
+        to.bits(ns) as u64
+    };
+
+    if n.sign() == Sign::Minus && !to.is_signed_numeric() {
+        diagnostics.push(Diagnostic::cast_error(
+            *loc,
+            format!(
+                "negative literal not allowed for unsigned type '{}'",
+                to.to_string(ns)
+            ),
+        ));
+        Err(())
+    } else if (n.sign() == Sign::Minus && n.clone().add(1u32).bits() > permitted_bits)
+        || (n.sign() != Sign::Minus && n.bits() > permitted_bits)
+    {
+        diagnostics.push(Diagnostic::cast_error(
+            *loc,
+            format!(
+                "literal is too large to fit into type '{}'",
+                to.to_string(ns)
+            ),
+        ));
+        Err(())
+    } else {
+        Ok(Expression::NumberLiteral(*loc, to.clone(), n))
+    }
+}
+
 pub fn coerce_number(
     l: &Type,
     l_loc: &pt::Loc,
@@ -1327,6 +1517,46 @@ pub fn coerce_number(
         (_, Type::Bytes(_)) if allow_bytes => {
             return Ok(r.clone());
         }
+        (
+            Type::Fixed(left_len, left_decimals) | Type::Ufixed(left_len, left_decimals),
+            Type::Fixed(right_len, right_decimals) | Type::Ufixed(right_len, right_decimals),
+        ) => {
+            if left_decimals != right_decimals {
+                diagnostics.push(Diagnostic::error(
+                    *r_loc,
+                    format!(
+                        "fixed point types {} and {} have a different number of decimals",
+                        l.to_string(ns),
+                        r.to_string(ns)
+                    ),
+                ));
+                return Err(());
+            }
+
+            return Ok(match (l, r) {
+                (Type::Fixed(..), Type::Fixed(..)) => {
+                    Type::Fixed(cmp::max(*left_len, *right_len), *left_decimals)
+                }
+                (Type::Ufixed(..), Type::Ufixed(..)) => {
+                    Type::Ufixed(cmp::max(*left_len, *right_len), *left_decimals)
+                }
+                (Type::Fixed(..), _) => Type::Fixed(
+                    cmp::max(*left_len, cmp::min(right_len + 8, 256)),
+                    *left_decimals,
+                ),
+                _ => Type::Fixed(
+                    cmp::max(cmp::min(left_len + 8, 256), *right_len),
+                    *left_decimals,
+                ),
+            });
+        }
+        // The other operand must be a literal, else the implicit conversion is rejected
+        (Type::Fixed(..) | Type::Ufixed(..), Type::Rational | Type::Int(_) | Type::Uint(_)) => {
+            return Ok(l.clone());
+        }
+        (Type::Rational | Type::Int(_) | Type::Uint(_), Type::Fixed(..) | Type::Ufixed(..)) => {
+            return Ok(r.clone());
+        }
         (Type::Rational, Type::Int(_)) => {
             return Ok(Type::Rational);
         }
@@ -1511,7 +1741,8 @@ pub fn bigint_to_expression(
     let bits = n.bits();
 
     if let ResolveTo::Type(resolve_to) = resolve_to {
-        if *resolve_to != Type::Unresolved {
+        // Literals for fixed point types are converted when they are cast
+        if *resolve_to != Type::Unresolved && !resolve_to.is_fixed() {
             if !resolve_to.is_integer() {
                 diagnostics.push(Diagnostic::cast_error(
                     *loc,
@@ -1740,6 +1971,16 @@ pub fn expression(
             let right = expression(r, context, ns, symtable, diagnostics, ResolveTo::Integer)?;
 
             check_var_usage_expression(ns, &left, &right, symtable);
+
+            if let Some(expr) = using::user_defined_operator(
+                loc,
+                &[&left, &right],
+                pt::UserDefinedOperator::More,
+                diagnostics,
+                ns,
+            )? {
+                return Ok(expr);
+            }
             let ty = coerce_number(
                 &left.ty(),
                 &l.loc(),
@@ -1763,6 +2004,16 @@ pub fn expression(
 
             check_var_usage_expression(ns, &left, &right, symtable);
 
+            if let Some(expr) = using::user_defined_operator(
+                loc,
+                &[&left, &right],
+                pt::UserDefinedOperator::Less,
+                diagnostics,
+                ns,
+            )? {
+                return Ok(expr);
+            }
+
             let ty = coerce_number(
                 &left.ty(),
                 &l.loc(),
@@ -1785,6 +2036,16 @@ pub fn expression(
             let right = expression(r, context, ns, symtable, diagnostics, ResolveTo::Integer)?;
             check_var_usage_expression(ns, &left, &right, symtable);
 
+            if let Some(expr) = using::user_defined_operator(
+                loc,
+                &[&left, &right],
+                pt::UserDefinedOperator::MoreEqual,
+                diagnostics,
+                ns,
+            )? {
+                return Ok(expr);
+            }
+
             let ty = coerce_number(
                 &left.ty(),
                 &l.loc(),
@@ -1807,6 +2068,16 @@ pub fn expression(
             let right = expression(r, context, ns, symtable, diagnostics, ResolveTo::Integer)?;
             check_var_usage_expression(ns, &left, &right, symtable);
 
+            if let Some(expr) = using::user_defined_operator(
+                loc,
+                &[&left, &right],
+                pt::UserDefinedOperator::LessEqual,
+                diagnostics,
+                ns,
+            )? {
+                return Ok(expr);
+            }
+
             let ty = coerce_number(
                 &left.ty(),
                 &l.loc(),
@@ -1824,12 +2095,27 @@ pub fn expression(
                 Box::new(right.cast(&r.loc(), &ty, true, ns, diagnostics)?),
             ))
         }
-        pt::Expression::Equal(loc, l, r) => equal(loc, l, r, context, ns, symtable, diagnostics),
+        pt::Expression::Equal(loc, l, r) => equal(
+            loc,
+            l,
+            r,
+            pt::UserDefinedOperator::Equal,
+            context,
+            ns,
+            symtable,
+            diagnostics,
+        ),
 
-        pt::Expression::NotEqual(loc, l, r) => Ok(Expression::Not(
-            *loc,
-            Box::new(equal(loc, l, r, context, ns, symtable, diagnostics)?),
-        )),
+        pt::Expression::NotEqual(loc, l, r) => equal(
+            loc,
+            l,
+            r,
+            pt::UserDefinedOperator::NotEqual,
+            context,
+            ns,
+            symtable,
+            diagnostics,
+        ),
         // unary expressions
         pt::Expression::Not(loc, e) => {
             let expr = expression(e, context, ns, symtable, diagnostics, resolve_to)?;
@@ -1844,6 +2130,17 @@ pub fn expression(
             let expr = expression(e, context, ns, symtable, diagnostics, resolve_to)?;
 
             used_variable(ns, &expr, symtable);
+
+            if let Some(expr) = using::user_defined_operator(
+                loc,
+                &[&expr],
+                pt::UserDefinedOperator::BitwiseNot,
+                diagnostics,
+                ns,
+            )? {
+                return Ok(expr);
+            }
+
             let expr_ty = expr.ty();
 
             get_int_length(&expr_ty, loc, true, ns, diagnostics)?;
@@ -1884,6 +2181,17 @@ pub fn expression(
                 let expr = expression(e, context, ns, symtable, diagnostics, resolve_to)?;
 
                 used_variable(ns, &expr, symtable);
+
+                if let Some(expr) = using::user_defined_operator(
+                    loc,
+                    &[&expr],
+                    pt::UserDefinedOperator::Negate,
+                    diagnostics,
+                    ns,
+                )? {
+                    return Ok(expr);
+                }
+
                 let expr_type = expr.ty();
 
                 if let Expression::NumberLiteral(_, _, n) = expr {
@@ -1891,7 +2199,9 @@ pub fn expression(
                 } else if let Expression::RationalNumberLiteral(_, ty, r) = expr {
                     Ok(Expression::RationalNumberLiteral(*loc, ty, -r))
                 } else {
-                    get_int_length(&expr_type, loc, false, ns, diagnostics)?;
+                    if !expr_type.is_fixed() {
+                        get_int_length(&expr_type, loc, false, ns, diagnostics)?;
+                    }
 
                     Ok(Expression::UnaryMinus(*loc, expr_type, Box::new(expr)))
                 }
@@ -1902,7 +2212,9 @@ pub fn expression(
             used_variable(ns, &expr, symtable);
             let expr_type = expr.ty();
 
-            get_int_length(&expr_type, loc, false, ns, diagnostics)?;
+            if !expr_type.is_fixed() {
+                get_int_length(&expr_type, loc, false, ns, diagnostics)?;
+            }
 
             Ok(expr)
         }
@@ -2046,13 +2358,8 @@ pub fn expression(
 
             Err(())
         }
-        pt::Expression::ArraySlice(loc, ..) => {
-            diagnostics.push(Diagnostic::error(
-                *loc,
-                "slice not supported yet".to_string(),
-            ));
-
-            Err(())
+        pt::Expression::ArraySlice(loc, array, start, end) => {
+            array_slice(loc, array, start, end, context, ns, symtable, diagnostics)
         }
         pt::Expression::ArraySubscript(loc, array, Some(index)) => {
             array_subscript(loc, array, index, context, ns, symtable, diagnostics)
@@ -2654,6 +2961,16 @@ fn subtract(
 
     check_var_usage_expression(ns, &left, &right, symtable);
 
+    if let Some(expr) = using::user_defined_operator(
+        loc,
+        &[&left, &right],
+        pt::UserDefinedOperator::Subtract,
+        diagnostics,
+        ns,
+    )? {
+        return Ok(expr);
+    }
+
     let ty = coerce_number(
         &left.ty(),
         &l.loc(),
@@ -2701,6 +3018,16 @@ fn bitwise_or(
 
     check_var_usage_expression(ns, &left, &right, symtable);
 
+    if let Some(expr) = using::user_defined_operator(
+        loc,
+        &[&left, &right],
+        pt::UserDefinedOperator::BitwiseOr,
+        diagnostics,
+        ns,
+    )? {
+        return Ok(expr);
+    }
+
     let ty = coerce_number(
         &left.ty(),
         &l.loc(),
@@ -2712,6 +3039,8 @@ fn bitwise_or(
         diagnostics,
     )?;
 
+    fixed_point_not_allowed(&ty, loc, ns, diagnostics)?;
+
     Ok(Expression::BitwiseOr(
         *loc,
         ty.clone(),
@@ -2735,6 +3064,16 @@ fn bitwise_and(
 
     check_var_usage_expression(ns, &left, &right, symtable);
 
+    if let Some(expr) = using::user_defined_operator(
+        loc,
+        &[&left, &right],
+        pt::UserDefinedOperator::BitwiseAnd,
+        diagnostics,
+        ns,
+    )? {
+        return Ok(expr);
+    }
+
     let ty = coerce_number(
         &left.ty(),
         &l.loc(),
@@ -2746,6 +3085,8 @@ fn bitwise_and(
         diagnostics,
     )?;
 
+    fixed_point_not_allowed(&ty, loc, ns, diagnostics)?;
+
     Ok(Expression::BitwiseAnd(
         *loc,
         ty.clone(),
@@ -2769,6 +3110,16 @@ fn bitwise_xor(
 
     check_var_usage_expression(ns, &left, &right, symtable);
 
+    if let Some(expr) = using::user_defined_operator(
+        loc,
+        &[&left, &right],
+        pt::UserDefinedOperator::BitwiseXor,
+        diagnostics,
+        ns,
+    )? {
+        return Ok(expr);
+    }
+
     let ty = coerce_number(
         &left.ty(),
         &l.loc(),
@@ -2780,6 +3131,8 @@ fn bitwise_xor(
         diagnostics,
     )?;
 
+    fixed_point_not_allowed(&ty, loc, ns, diagnostics)?;
+
     Ok(Expression::BitwiseXor(
         *loc,
         ty.clone(),
@@ -2862,6 +3215,16 @@ fn multiply(
 
     check_var_usage_expression(ns, &left, &right, symtable);
 
+    if let Some(expr) = using::user_defined_operator(
+        loc,
+        &[&left, &right],
+        pt::UserDefinedOperator::Multiply,
+        diagnostics,
+        ns,
+    )? {
+        return Ok(expr);
+    }
+
     let ty = coerce_number(
         &left.ty(),
         &l.loc(),
@@ -2885,8 +3248,10 @@ fn multiply(
         };
     }
 
+    fixed_point_intermediate(&ty, true, loc, ns, diagnostics)?;
+
     // If we don't know what type the result is going to be, make any possible result fit.
-    if resolve_to == ResolveTo::Unknown {
+    if resolve_to == ResolveTo::Unknown && !ty.is_fixed() {
         let bits = std::cmp::min(256, ty.bits(ns) * 2);
 
         if ty.is_signed_int() {
@@ -2938,6 +3303,16 @@ fn divide(
 
     check_var_usage_expression(ns, &left, &right, symtable);
 
+    if let Some(expr) = using::user_defined_operator(
+        loc,
+        &[&left, &right],
+        pt::UserDefinedOperator::Divide,
+        diagnostics,
+        ns,
+    )? {
+        return Ok(expr);
+    }
+
     let ty = coerce_number(
         &left.ty(),
         &l.loc(),
@@ -2949,9 +3324,12 @@ fn divide(
         diagnostics,
     )?;
 
+    fixed_point_intermediate(&ty, false, loc, ns, diagnostics)?;
+
     Ok(Expression::Divide(
         *loc,
         ty.clone(),
+        context.unchecked,
         Box::new(left.cast(&l.loc(), &ty, true, ns, diagnostics)?),
         Box::new(right.cast(&r.loc(), &ty, true, ns, diagnostics)?),
     ))
@@ -2972,6 +3350,16 @@ fn modulo(
 
     check_var_usage_expression(ns, &left, &right, symtable);
 
+    if let Some(expr) = using::user_defined_operator(
+        loc,
+        &[&left, &right],
+        pt::UserDefinedOperator::Modulo,
+        diagnostics,
+        ns,
+    )? {
+        return Ok(expr);
+    }
+
     let ty = coerce_number(
         &left.ty(),
         &l.loc(),
@@ -3054,6 +3442,8 @@ fn power(
         diagnostics,
     )?;
 
+    fixed_point_not_allowed(&ty, loc, ns, diagnostics)?;
+
     Ok(Expression::Power(
         *loc,
         ty.clone(),
@@ -3716,10 +4106,13 @@ pub fn new(
 }
 
 /// Test for equality; first check string equality, then integer equality
+/// Resolve == or !=; the latter is resolved as the negation of the former, unless a function is
+/// bound to != for a user defined type
 fn equal(
     loc: &pt::Loc,
     l: &pt::Expression,
     r: &pt::Expression,
+    oper: pt::UserDefinedOperator,
     context: &ExprContext,
     ns: &mut Namespace,
     symtable: &mut Symtable,
@@ -3730,6 +4123,29 @@ fn equal(
 
     check_var_usage_expression(ns, &left, &right, symtable);
 
+    if let Some(expr) = using::user_defined_operator(loc, &[&left, &right], oper, diagnostics, ns)?
+    {
+        return Ok(expr);
+    }
+
+    let expr = equal_values(loc, l, r, left, right, ns, diagnostics)?;
+
+    if oper == pt::UserDefinedOperator::NotEqual {
+        Ok(Expression::Not(*loc, Box::new(expr)))
+    } else {
+        Ok(expr)
+    }
+}
+
+fn equal_values(
+    loc: &pt::Loc,
+    l: &pt::Expression,
+    r: &pt::Expression,
+    left: Expression,
+    right: Expression,
+    ns: &Namespace,
+    diagnostics: &mut Diagnostics,
+) -> Result<Expression, ()> {
     // Comparing stringliteral against stringliteral
     if let (Expression::BytesLiteral(_, _, l), Expression::BytesLiteral(_, _, r)) = (&left, &right)
     {
@@ -3824,6 +4240,16 @@ fn addition(
     let mut right = expression(r, context, ns, symtable, diagnostics, resolve_to)?;
     check_var_usage_expression(ns, &left, &right, symtable);
 
+    if let Some(expr) = using::user_defined_operator(
+        loc,
+        &[&left, &right],
+        pt::UserDefinedOperator::Add,
+        diagnostics,
+        ns,
+    )? {
+        return Ok(expr);
+    }
+
     // Concatenate stringliteral with stringliteral
     if let (Expression::BytesLiteral(_, _, l), Expression::BytesLiteral(_, _, r)) = (&left, &right)
     {
@@ -3903,7 +4329,7 @@ fn addition(
     // If we don't know what type the result is going to be
     if resolve_to == ResolveTo::Unknown {
         let bits = std::cmp::min(256, ty.bits(ns) * 2);
-        let resolve_to = if ty.is_signed_int() {
+        let resolve_to = if ty.is_signed_numeric() {
             Type::Int(bits)
         } else {
             Type::Uint(bits)
@@ -4099,6 +4525,27 @@ fn assign_expr(
               ns: &Namespace,
               diagnostics: &mut Diagnostics|
      -> Result<Expression, ()> {
+        if !matches!(
+            expr,
+            pt::Expression::AssignAdd(..)
+                | pt::Expression::AssignSubtract(..)
+                | pt::Expression::AssignMultiply(..)
+                | pt::Expression::AssignDivide(..)
+                | pt::Expression::AssignModulo(..)
+        ) {
+            fixed_point_not_allowed(ty, loc, ns, diagnostics)?;
+        }
+
+        match expr {
+            pt::Expression::AssignMultiply(..) => {
+                fixed_point_intermediate(ty, true, loc, ns, diagnostics)?
+            }
+            pt::Expression::AssignDivide(..) => {
+                fixed_point_intermediate(ty, false, loc, ns, diagnostics)?
+            }
+            _ => (),
+        }
+
         let set = match expr {
             pt::Expression::AssignShiftLeft(..) | pt::Expression::AssignShiftRight(..) => {
                 let left_length = get_int_length(ty, loc, true, ns, diagnostics)?;
@@ -4159,9 +4606,13 @@ fn assign_expr(
                 Box::new(set),
                 ty.is_signed_int(),
             ),
-            pt::Expression::AssignDivide(..) => {
-                Expression::Divide(*loc, ty.clone(), Box::new(assign), Box::new(set))
-            }
+            pt::Expression::AssignDivide(..) => Expression::Divide(
+                *loc,
+                ty.clone(),
+                context.unchecked,
+                Box::new(assign),
+                Box::new(set),
+            ),
             pt::Expression::AssignModulo(..) => {
                 Expression::Modulo(*loc, ty.clone(), Box::new(assign), Box::new(set))
             }
@@ -4189,7 +4640,11 @@ fn assign_expr(
         }
         Expression::Variable(_, _, n) => {
             match var_ty {
-                Type::Bytes(_) | Type::Int(_) | Type::Uint(_) => (),
+                Type::Bytes(_)
+                | Type::Int(_)
+                | Type::Uint(_)
+                | Type::Fixed(..)
+                | Type::Ufixed(..) => (),
                 _ => {
                     diagnostics.push(Diagnostic::error(
                         var.loc(),
@@ -4211,7 +4666,11 @@ fn assign_expr(
         }
         _ => match &var_ty {
             Type::Ref(r_ty) => match r_ty.as_ref() {
-                Type::Bytes(_) | Type::Int(_) | Type::Uint(_) => Ok(Expression::Assign(
+                Type::Bytes(_)
+                | Type::Int(_)
+                | Type::Uint(_)
+                | Type::Fixed(..)
+                | Type::Ufixed(..) => Ok(Expression::Assign(
                     *loc,
                     Type::Void,
                     Box::new(var.clone()),
@@ -4244,7 +4703,11 @@ fn assign_expr(
                 }
 
                 match r_ty.as_ref() {
-                    Type::Bytes(_) | Type::Int(_) | Type::Uint(_) => Ok(Expression::Assign(
+                    Type::Bytes(_)
+                    | Type::Int(_)
+                    | Type::Uint(_)
+                    | Type::Fixed(..)
+                    | Type::Ufixed(..) => Ok(Expression::Assign(
                         *loc,
                         Type::Void,
                         Box::new(var.clone()),
@@ -4330,7 +4793,7 @@ fn incr_decr(
         }
         Expression::Variable(_, ty, n) => {
             match ty {
-                Type::Int(_) | Type::Uint(_) => (),
+                Type::Int(_) | Type::Uint(_) | Type::Fixed(..) | Type::Ufixed(..) => (),
                 _ => {
                     diagnostics.push(Diagnostic::error(
                         var.loc(),
@@ -4347,7 +4810,9 @@ fn incr_decr(
         }
         _ => match &var_ty {
             Type::Ref(r_ty) => match r_ty.as_ref() {
-                Type::Int(_) | Type::Uint(_) => Ok(op(var, r_ty.as_ref().clone())),
+                Type::Int(_) | Type::Uint(_) | Type::Fixed(..) | Type::Ufixed(..) => {
+                    Ok(op(var, r_ty.as_ref().clone()))
+                }
                 _ => {
                     diagnostics.push(Diagnostic::error(
                         var.loc(),
@@ -4369,7 +4834,9 @@ fn incr_decr(
                     }
                 }
                 match r_ty.as_ref() {
-                    Type::Int(_) | Type::Uint(_) => Ok(op(var, r_ty.as_ref().clone())),
+                    Type::Int(_) | Type::Uint(_) | Type::Fixed(..) | Type::Ufixed(..) => {
+                        Ok(op(var, r_ty.as_ref().clone()))
+                    }
                     _ => {
                         diagnostics.push(Diagnostic::error(
                             var.loc(),
@@ -4637,13 +5104,17 @@ fn member_access(
     }
 
     // Dereference if need to
-    let (expr, expr_ty) = if let Type::Ref(ty) = &expr_ty {
-        (
+    let (expr, expr_ty) = match &expr_ty {
+        Type::Ref(ty) => (
             Expression::Load(*loc, expr_ty.clone(), Box::new(expr)),
             ty.as_ref().clone(),
-        )
-    } else {
-        (expr, expr_ty)
+        ),
+        // function types in storage containers must be loaded for .address and .selector
+        Type::StorageRef(_, ty) if ty.is_function_pointer() => (
+            Expression::StorageLoad(*loc, ty.as_ref().clone(), Box::new(expr)),
+            ty.as_ref().clone(),
+        ),
+        _ => (expr, expr_ty),
     };
 
     match expr_ty {
@@ -4686,7 +5157,7 @@ fn member_access(
                 };
             }
         }
-        Type::String | Type::DynamicBytes => {
+        Type::String | Type::DynamicBytes | Type::Slice(_) => {
             if id.name == "length" {
                 return Ok(Expression::Builtin(
                     *loc,
@@ -5056,6 +5527,24 @@ fn array_subscript(
                 ))
             }
         }
+        Type::Slice(elem_ty) => {
+            let elem_ty = elem_ty.as_ref().clone();
+
+            let subscript = Expression::Subscript(
+                *loc,
+                Type::Ref(Box::new(elem_ty.clone())),
+                array_ty,
+                Box::new(array),
+                Box::new(index),
+            );
+
+            // a slice is a read-only view of the array, so elements cannot be assigned
+            if elem_ty.is_fixed_reference_type() {
+                Ok(subscript)
+            } else {
+                Ok(Expression::Load(*loc, elem_ty, Box::new(subscript)))
+            }
+        }
         Type::String => {
             diagnostics.push(Diagnostic::error(
                 array.loc(),
@@ -5073,6 +5562,122 @@ fn array_subscript(
     }
 }
 
+/// Resolve an array slice, e.g. `msg.data[4:]`. Only dynamic arrays in memory can be sliced,
+/// and the slice refers to the elements of the original array.
+#[allow(clippy::too_many_arguments)]
+fn array_slice(
+    loc: &pt::Loc,
+    array: &pt::Expression,
+    start: &Option<Box<pt::Expression>>,
+    end: &Option<Box<pt::Expression>>,
+    context: &ExprContext,
+    ns: &mut Namespace,
+    symtable: &mut Symtable,
+    diagnostics: &mut Diagnostics,
+) -> Result<Expression, ()> {
+    let array = expression(
+        array,
+        context,
+        ns,
+        symtable,
+        diagnostics,
+        ResolveTo::Unknown,
+    )?;
+    let array_ty = array.ty();
+
+    let elem_ty = match array_ty.deref_memory() {
+        Type::DynamicBytes => Type::Bytes(1),
+        Type::Array(_, dim) if dim.last() == Some(&ArrayLength::Dynamic) => {
+            array_ty.deref_memory().array_elem()
+        }
+        Type::Slice(elem_ty) => elem_ty.as_ref().clone(),
+        _ => {
+            diagnostics.push(Diagnostic::error(
+                array.loc(),
+                format!(
+                    "slice is only permitted on dynamic arrays in memory or calldata, not '{}'",
+                    array_ty.to_string(ns)
+                ),
+            ));
+            return Err(());
+        }
+    };
+
+    let array = array.cast(&array.loc(), array_ty.deref_memory(), true, ns, diagnostics)?;
+
+    let start = match start {
+        Some(start) => Some(slice_bound(start, context, ns, symtable, diagnostics)?),
+        None => None,
+    };
+
+    let end = match end {
+        Some(end) => Some(slice_bound(end, context, ns, symtable, diagnostics)?),
+        None => None,
+    };
+
+    if let (Some(start), Some(end)) = (&start, &end) {
+        if let (Ok((_, start_no)), Ok((_, end_no))) =
+            (eval_const_number(start, ns), eval_const_number(end, ns))
+        {
+            if start_no > end_no {
+                diagnostics.push(Diagnostic::error(
+                    *loc,
+                    format!(
+                        "slice start {} is larger than slice end {}",
+                        start_no, end_no
+                    ),
+                ));
+                return Err(());
+            }
+        }
+    }
+
+    Ok(Expression::ArraySlice {
+        loc: *loc,
+        ty: Type::Slice(Box::new(elem_ty)),
+        array: Box::new(array),
+        start: start.map(Box::new),
+        end: end.map(Box::new),
+    })
+}
+
+/// Resolve the start or end of an array slice
+fn slice_bound(
+    expr: &pt::Expression,
+    context: &ExprContext,
+    ns: &mut Namespace,
+    symtable: &mut Symtable,
+    diagnostics: &mut Diagnostics,
+) -> Result<Expression, ()> {
+    let expr = expression(
+        expr,
+        context,
+        ns,
+        symtable,
+        diagnostics,
+        ResolveTo::Type(&Type::Uint(32)),
+    )?;
+
+    let expr_ty = expr.ty();
+
+    match expr_ty.deref_any() {
+        Type::Uint(_) => {
+            // make sure we load the value if it is a reference
+            expr.cast(&expr.loc(), expr_ty.deref_any(), true, ns, diagnostics)
+        }
+        _ => {
+            diagnostics.push(Diagnostic::error(
+                expr.loc(),
+                format!(
+                    "array slice bound must be an unsigned integer, not '{}'",
+                    expr_ty.to_string(ns)
+                ),
+            ));
+            Err(())
+        }
+    }
+}
+
 /// Resolve a function call with positional arguments
 fn struct_literal(
     loc: &pt::Loc,
@@ -5141,14 +5746,43 @@ fn call_function_type(
     diagnostics: &mut Diagnostics,
     resolve_to: ResolveTo,
 ) -> Result<Expression, ()> {
-    let mut function = expression(expr, context, ns, symtable, diagnostics, ResolveTo::Unknown)?;
+    let function = expression(expr, context, ns, symtable, diagnostics, ResolveTo::Unknown)?;
 
+    call_function_value(
+        loc,
+        function,
+        &expr.loc(),
+        args,
+        call_args,
+        call_args_loc,
+        context,
+        ns,
+        symtable,
+        diagnostics,
+        resolve_to,
+    )
+}
+
+/// Call an expression of function type, e.g. a variable, a mapping value or a struct field
+fn call_function_value(
+    loc: &pt::Loc,
+    mut function: Expression,
+    function_loc: &pt::Loc,
+    args: &[pt::Expression],
+    call_args: &[&pt::NamedArgument],
+    call_args_loc: Option<pt::Loc>,
+    context: &ExprContext,
+    ns: &mut Namespace,
+    symtable: &mut Symtable,
+    diagnostics: &mut Diagnostics,
+    resolve_to: ResolveTo,
+) -> Result<Expression, ()> {
     let mut ty = function.ty();
 
     match ty {
         Type::StorageRef(_, real_ty) | Type::Ref(real_ty) => {
             ty = *real_ty;
-            function = function.cast(&expr.loc(), &ty, true, ns, diagnostics)?;
+            function = function.cast(function_loc, &ty, true, ns, diagnostics)?;
         }
         _ => (),
     };
@@ -5967,6 +6601,48 @@ fn method_call_pos_args(
 
     let var_ty = var_expr.ty();
 
+    // a struct field of function type can be called like a method
+    if let Type::Struct(struct_ty) = var_ty.deref_any() {
+        if let Some((field_no, field)) =
+            struct_ty
+                .definition(ns)
+                .fields
+                .iter()
+                .enumerate()
+                .find(|(_, field)| {
+                    field.name_as_str() == func.name
+                        && matches!(
+                            field.ty,
+                            Type::InternalFunction { .. } | Type::ExternalFunction { .. }
+                        )
+                })
+        {
+            let field_ty = match &var_ty {
+                Type::StorageRef(immutable, _) => {
+                    Type::StorageRef(*immutable, Box::new(field.ty.clone()))
+                }
+                _ => Type::Ref(Box::new(field.ty.clone())),
+            };
+
+            let function =
+                Expression::StructMember(func.loc, field_ty, Box::new(var_expr), field_no);
+
+            return call_function_value(
+                loc,
+                function,
+                &func.loc,
+                args,
+                call_args,
+                call_args_loc,
+                context,
+                ns,
+                symtable,
+                diagnostics,
+                resolve_to,
+            );
+        }
+    }
+
     if matches!(var_ty, Type::Bytes(_) | Type::String) && func.name == "format" {
         return if let pt::Expression::StringLiteral(bs) = var {
             if let Some(loc) = call_args_loc {
@@ -7174,12 +7850,26 @@ fn parse_call_args(
     for arg in args.values() {
         match arg.name.name.as_str() {
             "value" => {
-                if ns.target == Target::Solana {
+                if ns.target == Target::Solana && !ns.files[context.file_no].value_transfer {
                     diagnostics.push(Diagnostic::error(
                         arg.loc,
                 "Solana Cross Program Invocation (CPI) cannot transfer native value. See https://solang.readthedocs.io/en/latest/language/functions.html#value_transfer".to_string(),
                     ));
 
+                    expression(
+                        &arg.expr,
+                        context,
+                        ns,
+                        symtable,
+                        diagnostics,
+                        ResolveTo::Unknown,
+                    )?;
+                } else if ns.target == Target::Solana && !external_call {
+                    diagnostics.push(Diagnostic::error(
+                        arg.loc,
+                        "'value' not permitted for constructors on solana".to_string(),
+                    ));
+
                     expression(
                         &arg.expr,
                         context,
@@ -7373,6 +8063,15 @@ fn parse_call_args(
         }
     }
 
+    // the value is transferred to the account of the callee, which is not known if the accounts are given
+    if res.value.is_some() && res.accounts.is_some() {
+        diagnostics.push(Diagnostic::error(
+            args[&"accounts".to_string()].loc,
+            "'value' cannot be combined with 'accounts'".to_string(),
+        ));
+        return Err(());
+    }
+
     Ok(res)
 }
 
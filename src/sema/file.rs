@@ -18,6 +18,10 @@ impl File {
             path,
             line_starts,
             cache_no: Some(cache_no),
+            checked_arithmetic: false,
+            solidity_version: None,
+            value_transfer: false,
+            anchor_discriminator: false,
         }
     }
 
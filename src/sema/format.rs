@@ -33,8 +33,14 @@ pub fn string_format(
         let ty = expr.ty();
 
         resolved_args.push(
-            expr.cast(&arg.loc(), ty.deref_any(), true, ns, diagnostics)
-                .unwrap(),
+            expr.cast(
+                &arg.loc(),
+                &ty.deref_any().slice_to_array(),
+                true,
+                ns,
+                diagnostics,
+            )
+            .unwrap(),
         );
     }
 
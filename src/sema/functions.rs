@@ -7,6 +7,7 @@ use super::contracts::is_base;
 use super::diagnostics::Diagnostics;
 use super::tags::resolve_tags;
 use crate::Target;
+use semver::Version;
 use solang_parser::{
     doccomment::DocComment,
     pt,
@@ -24,6 +25,21 @@ pub fn contract_function(
 ) -> Option<usize> {
     let mut success = true;
 
+    // Before Solidity 0.6.0, there were no receive functions and a function without a name was
+    // the fallback function, which could be payable.
+    let before_receive = matches!(&ns.files[file_no].solidity_version, Some((_, Some(end))) if *end <= Version::new(0, 6, 0));
+
+    let legacy_fallback;
+    let func = if before_receive && func.ty == pt::FunctionTy::Function && func.name.is_none() {
+        legacy_fallback = pt::FunctionDefinition {
+            ty: pt::FunctionTy::Fallback,
+            ..func.clone()
+        };
+        &legacy_fallback
+    } else {
+        func
+    };
+
     // The parser allows constructors to have return values. This is so that we can give a
     // nicer error message than "returns unexpected"
     match func.ty {
@@ -62,6 +78,13 @@ pub fn contract_function(
             }
         }
         pt::FunctionTy::Fallback | pt::FunctionTy::Receive => {
+            if func.ty == pt::FunctionTy::Receive && before_receive {
+                ns.diagnostics.push(Diagnostic::error(
+                    func.loc,
+                    "receive function requires Solidity 0.6.0 or later, use a payable function without a name instead".to_string(),
+                ));
+                return None;
+            }
             if !func.returns.is_empty() {
                 ns.diagnostics.push(Diagnostic::error(
                     func.loc,
@@ -534,10 +557,13 @@ pub fn contract_function(
 
         Some(pos)
     } else if func.ty == pt::FunctionTy::Receive || func.ty == pt::FunctionTy::Fallback {
-        if func.ty == pt::FunctionTy::Receive && ns.target == Target::Solana {
+        if func.ty == pt::FunctionTy::Receive
+            && ns.target == Target::Solana
+            && !ns.files[file_no].value_transfer
+        {
             ns.diagnostics.push(Diagnostic::error(
                 func.loc,
-                format!("target {} does not support receive() functions, see https://solang.readthedocs.io/en/latest/language/functions.html#fallback-and-receive-function", ns.target),
+                format!("target {} does not support receive() functions without 'pragma solana value_transfer', see https://solang.readthedocs.io/en/latest/language/functions.html#fallback-and-receive-function", ns.target),
             ));
         } else {
             if let Some(prev_func_no) = ns.contracts[contract_no]
@@ -567,7 +593,7 @@ pub fn contract_function(
             }
 
             if fdecl.is_payable() {
-                if func.ty == pt::FunctionTy::Fallback {
+                if func.ty == pt::FunctionTy::Fallback && !before_receive {
                     ns.diagnostics.push(Diagnostic::error(
                     func.loc,
                     format!("{} function must not be declare payable, use 'receive() external payable' instead", func.ty),
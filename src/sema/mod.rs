@@ -7,7 +7,9 @@ use self::{
 };
 use crate::file_resolver::{FileResolver, ResolvedFile};
 use crate::sema::unused_variable::{check_unused_events, check_unused_namespace_variables};
+use crate::Target;
 use num_bigint::BigInt;
+use semver::{Version, VersionReq};
 use solang_parser::{doccomment::parse_doccomments, parse, pt};
 use std::ffi::OsStr;
 
@@ -353,39 +355,214 @@ fn resolve_import(
     }
 }
 
-/// Resolve pragma. We don't do anything with pragmas for now
+/// Solidity language versions Solang understands, as the first and last minor version of the
+/// 0.x series
+const SOLIDITY_VERSIONS: (u64, u64) = (4, 8);
+
+/// Resolve pragma. `pragma solidity` sets the range of Solidity versions the file is written for,
+/// which switches version dependent behaviour like checked arithmetic. `pragma abicoder` and
+/// `pragma experimental` are recognised, but Solang always uses ABI encoder v2. On Solana,
+/// `pragma solana value_transfer` enables sending value with calls and `msg.value`, and
+/// `pragma solana anchor_discriminator` enables dispatch on Anchor instruction discriminators.
 fn resolve_pragma(
     loc: &pt::Loc,
     name: &pt::Identifier,
     value: &pt::StringLiteral,
     ns: &mut ast::Namespace,
 ) {
-    if name.name == "solidity" {
-        ns.diagnostics.push(ast::Diagnostic::debug(
-            *loc,
-            "pragma 'solidity' is ignored".to_string(),
-        ));
-    } else if name.name == "experimental" && value.string == "ABIEncoderV2" {
-        ns.diagnostics.push(ast::Diagnostic::debug(
-            *loc,
-            "pragma 'experimental' with value 'ABIEncoderV2' is ignored".to_string(),
-        ));
-    } else if name.name == "abicoder" && value.string == "v2" {
-        ns.diagnostics.push(ast::Diagnostic::debug(
-            *loc,
-            "pragma 'abicoder' with value 'v2' is ignored".to_string(),
-        ));
-    } else {
-        ns.diagnostics.push(ast::Diagnostic::warning(
-            *loc,
-            format!(
-                "unknown pragma '{}' with value '{}' ignored",
-                name.name, value.string
-            ),
-        ));
+    match (name.name.as_str(), value.string.as_str()) {
+        ("solidity", version) => match solidity_versions(version) {
+            Ok(versions) => {
+                let (first_minor, last_minor) = SOLIDITY_VERSIONS;
+
+                let supported = match &versions {
+                    Some((lowest, end)) => {
+                        *lowest < Version::new(0, last_minor + 1, 0)
+                            && end
+                                .as_ref()
+                                .map_or(true, |end| *end > Version::new(0, first_minor, 0))
+                    }
+                    None => false,
+                };
+
+                if !supported {
+                    ns.diagnostics.push(ast::Diagnostic::warning(
+                        value.loc,
+                        format!(
+                            "pragma 'solidity' version '{}' does not match any Solidity version supported by Solang, which are 0.{}.0 to 0.{}.x",
+                            version, first_minor, last_minor
+                        ),
+                    ));
+                }
+
+                if let Some((lowest, end)) = versions {
+                    let file = &mut ns.files[loc.file_no()];
+
+                    file.checked_arithmetic = lowest >= Version::new(0, 8, 0);
+                    file.solidity_version = Some((lowest, end));
+                }
+            }
+            Err(err) => {
+                ns.diagnostics.push(ast::Diagnostic::warning(
+                    value.loc,
+                    format!(
+                        "pragma 'solidity' version '{}' cannot be parsed: {}",
+                        version, err
+                    ),
+                ));
+            }
+        },
+        ("abicoder", "v2") | ("experimental", "ABIEncoderV2") => {
+            ns.diagnostics.push(ast::Diagnostic::debug(
+                *loc,
+                format!(
+                    "pragma '{}' with value '{}' is the default, ABI encoder v2 is always used",
+                    name.name, value.string
+                ),
+            ));
+        }
+        ("abicoder", "v1") => {
+            ns.diagnostics.push(ast::Diagnostic::warning(
+                *loc,
+                "pragma 'abicoder' with value 'v1' is not supported, ABI encoder v2 is always used"
+                    .to_string(),
+            ));
+        }
+        ("abicoder", _) => {
+            ns.diagnostics.push(ast::Diagnostic::warning(
+                value.loc,
+                format!(
+                    "unknown abicoder '{}', only 'v1' and 'v2' exist",
+                    value.string
+                ),
+            ));
+        }
+        ("experimental", "SMTChecker") => {
+            ns.diagnostics.push(ast::Diagnostic::warning(
+                *loc,
+                "pragma 'experimental' with value 'SMTChecker' is not supported".to_string(),
+            ));
+        }
+        ("experimental", _) => {
+            ns.diagnostics.push(ast::Diagnostic::warning(
+                value.loc,
+                format!("unknown experimental feature '{}'", value.string),
+            ));
+        }
+        ("solana", "value_transfer") => {
+            if ns.target == Target::Solana {
+                ns.files[loc.file_no()].value_transfer = true;
+            } else {
+                ns.diagnostics.push(ast::Diagnostic::warning(
+                    *loc,
+                    format!(
+                        "pragma 'solana' with value 'value_transfer' has no effect on target {}",
+                        ns.target
+                    ),
+                ));
+            }
+        }
+        ("solana", "anchor_discriminator") => {
+            if ns.target == Target::Solana {
+                ns.files[loc.file_no()].anchor_discriminator = true;
+            } else {
+                ns.diagnostics.push(ast::Diagnostic::warning(
+                    *loc,
+                    format!(
+                        "pragma 'solana' with value 'anchor_discriminator' has no effect on target {}",
+                        ns.target
+                    ),
+                ));
+            }
+        }
+        ("solana", _) => {
+            ns.diagnostics.push(ast::Diagnostic::warning(
+                value.loc,
+                format!("unknown solana feature '{}'", value.string),
+            ));
+        }
+        _ => {
+            ns.diagnostics.push(ast::Diagnostic::warning(
+                *loc,
+                format!(
+                    "unknown pragma '{}' with value '{}' ignored",
+                    name.name, value.string
+                ),
+            ));
+        }
     }
 }
 
+/// Parse a `pragma solidity` version constraint and return the lowest Solidity version it
+/// permits, and the version which all permitted versions are below, if there is an upper bound.
+/// Unlike Cargo, Solidity separates comparators with whitespace, supports hyphen ranges like
+/// `0.6.0 - 0.8.0`, and a version without an operator is an exact match.
+fn solidity_versions(value: &str) -> Result<Option<(Version, Option<Version>)>, semver::Error> {
+    let requirements = value
+        .split("||")
+        .map(|range| {
+            let mut comparators: Vec<String> = Vec::new();
+            let mut tokens = range.split_whitespace().peekable();
+
+            while let Some(token) = tokens.next() {
+                let comparator = if token.chars().all(|c| "<>=^~".contains(c)) {
+                    // operator separated from its version by whitespace
+                    format!("{}{}", token, tokens.next().unwrap_or_default())
+                } else if tokens.peek() == Some(&"-") {
+                    tokens.next();
+                    comparators.push(format!(">={}", token));
+                    format!("<={}", tokens.next().unwrap_or_default())
+                } else if token.starts_with(|c: char| c.is_ascii_digit()) {
+                    format!("={}", token)
+                } else {
+                    token.to_owned()
+                };
+
+                comparators.push(comparator);
+            }
+
+            VersionReq::parse(&comparators.join(", "))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(requirements
+        .iter()
+        .filter_map(|req| {
+            // The versions permitted by each comparator are a continuous range, which starts
+            // and ends at the version given or the next patch, minor or major version after it.
+            // So, the range permitted by all the comparators starts and ends at one of those.
+            let mut bounds = vec![Version::new(0, 0, 0)];
+
+            for comparator in &req.comparators {
+                let major = comparator.major;
+                let minor = comparator.minor.unwrap_or(0);
+                let patch = comparator.patch.unwrap_or(0);
+
+                bounds.push(Version::new(major, minor, patch));
+                bounds.push(Version::new(major, minor, patch + 1));
+                bounds.push(Version::new(major, minor + 1, 0));
+                bounds.push(Version::new(major + 1, 0, 0));
+            }
+
+            bounds.sort();
+
+            let lowest = bounds.iter().position(|version| req.matches(version))?;
+            let end = bounds[lowest..]
+                .iter()
+                .find(|version| !req.matches(version))
+                .cloned();
+
+            Some((bounds[lowest].clone(), end))
+        })
+        .reduce(|(lowest, end), (other_lowest, other_end)| {
+            (
+                lowest.min(other_lowest),
+                end.zip(other_end)
+                    .map(|(end, other_end)| end.max(other_end)),
+            )
+        }))
+}
+
 pub trait Recurse {
     type ArgType;
     /// recurse over a structure
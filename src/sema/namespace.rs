@@ -40,6 +40,7 @@ impl Namespace {
             enums: Vec::new(),
             structs: Vec::new(),
             events: Vec::new(),
+            errors: Vec::new(),
             using: Vec::new(),
             contracts: Vec::new(),
             user_types: Vec::new(),
@@ -138,6 +139,14 @@ impl Namespace {
                         "location of previous definition".to_string(),
                     ));
                 }
+                Symbol::Error(c, _) => {
+                    self.diagnostics.push(Diagnostic::error_with_note(
+                        id.loc,
+                        format!("{} is already defined as an error", id.name),
+                        *c,
+                        "location of previous definition".to_string(),
+                    ));
+                }
                 Symbol::Variable(c, _, _) => {
                     self.diagnostics.push(Diagnostic::error_with_note(
                         id.loc,
@@ -227,6 +236,14 @@ impl Namespace {
                             "location of previous definition".to_string(),
                         ));
                     }
+                    Symbol::Error(c, _) => {
+                        self.diagnostics.push(Diagnostic::warning_with_note(
+                            id.loc,
+                            format!("{} is already defined as an error", id.name),
+                            *c,
+                            "location of previous definition".to_string(),
+                        ));
+                    }
                     Symbol::Variable(c, _, _) => {
                         self.diagnostics.push(Diagnostic::warning_with_note(
                             id.loc,
@@ -484,6 +501,60 @@ impl Namespace {
         }
     }
 
+    /// Resolve a custom error, as used in a revert statement
+    pub fn resolve_error(
+        &self,
+        file_no: usize,
+        contract_no: Option<usize>,
+        path: &pt::IdentifierPath,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<usize, ()> {
+        let (id, namespace) = path
+            .identifiers
+            .split_last()
+            .expect("identifier path should not be empty");
+
+        let s = if namespace.is_empty() {
+            // without a namespace, look in the current contract, its bases and then global scope
+            let mut s = None;
+
+            if let Some(contract_no) = contract_no {
+                s = self
+                    .variable_symbols
+                    .get(&(file_no, Some(contract_no), id.name.to_owned()))
+                    .or_else(|| self.resolve_var_base_contract(contract_no, id));
+            }
+
+            s.or_else(|| {
+                self.variable_symbols
+                    .get(&(file_no, None, id.name.to_owned()))
+            })
+        } else {
+            self.resolve_namespace(
+                namespace.iter().collect(),
+                file_no,
+                contract_no,
+                id,
+                diagnostics,
+            )?
+        };
+
+        match s {
+            Some(Symbol::Error(_, error_no)) => Ok(*error_no),
+            None => {
+                diagnostics.push(Diagnostic::decl_error(
+                    id.loc,
+                    format!("error '{}' not found", id.name),
+                ));
+                Err(())
+            }
+            sym => {
+                diagnostics.push(Namespace::wrong_symbol(sym, id));
+                Err(())
+            }
+        }
+    }
+
     pub fn wrong_symbol(sym: Option<&Symbol>, id: &pt::Identifier) -> Diagnostic {
         match sym {
             None => Diagnostic::decl_error(id.loc, format!("'{}' not found", id.name)),
@@ -496,6 +567,9 @@ impl Namespace {
             Some(Symbol::Event(_)) => {
                 Diagnostic::decl_error(id.loc, format!("'{}' is an event", id.name))
             }
+            Some(Symbol::Error(..)) => {
+                Diagnostic::decl_error(id.loc, format!("'{}' is an error", id.name))
+            }
             Some(Symbol::Function(_)) => {
                 Diagnostic::decl_error(id.loc, format!("'{}' is a function", id.name))
             }
@@ -693,6 +767,15 @@ impl Namespace {
                     notes,
                 ));
             }
+            Some(Symbol::Error(loc, _)) => {
+                let loc = *loc;
+                self.diagnostics.push(Diagnostic::warning_with_note(
+                    id.loc,
+                    format!("declaration of '{}' shadows error definition", id.name),
+                    loc,
+                    "previous definition of error".to_string(),
+                ));
+            }
             Some(Symbol::Function(v)) => {
                 let notes = v
                     .iter()
@@ -1066,6 +1149,13 @@ impl Namespace {
                 ));
                 Err(())
             }
+            Some(Symbol::Error(..)) => {
+                diagnostics.push(Diagnostic::decl_error(
+                    id.loc,
+                    format!("'{}' is an error", id.name),
+                ));
+                Err(())
+            }
             Some(Symbol::Function(_)) => {
                 diagnostics.push(Diagnostic::decl_error(
                     id.loc,
@@ -1162,6 +1252,13 @@ impl Namespace {
                     ));
                     return Err(());
                 }
+                Some(Symbol::Error(..)) => {
+                    diagnostics.push(Diagnostic::decl_error(
+                        contract_name.loc,
+                        format!("'{}' is an error", contract_name.name),
+                    ));
+                    return Err(());
+                }
                 Some(Symbol::Struct(..)) => {
                     diagnostics.push(Diagnostic::decl_error(
                         contract_name.loc,
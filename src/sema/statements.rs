@@ -15,7 +15,6 @@ use crate::sema::unused_variable::{assigned_variable, check_function_call, used_
 use crate::sema::yul::resolve_inline_assembly;
 use crate::sema::Recurse;
 use solang_parser::pt;
-use solang_parser::pt::CatchClause;
 use solang_parser::pt::CodeLocation;
 use solang_parser::pt::OptionalCodeLocation;
 use std::collections::{BTreeMap, HashMap, HashSet};
@@ -725,6 +724,14 @@ fn statement(
                             return Err(());
                         }
 
+                        if let Type::StorageRef(true, _) = expr.ty() {
+                            ns.diagnostics.push(Diagnostic::error(
+                                *loc,
+                                "'delete' cannot be applied to immutable variable".to_string(),
+                            ));
+                            return Err(());
+                        }
+
                         res.push(Statement::Delete(*loc, ty.as_ref().clone(), expr));
 
                         Ok(true)
@@ -852,15 +859,14 @@ fn statement(
             res.push(Statement::Assembly(resolved_asm.0, resolved_asm.1));
             Ok(resolved_asm.1)
         }
-        pt::Statement::Revert(loc, error, args) => {
-            if let Some(error) = error {
-                ns.diagnostics.push(Diagnostic::error(
-                    error.loc,
-                    format!("revert with custom error '{}' not supported yet", error),
-                ));
-                return Err(());
-            }
+        pt::Statement::Revert(loc, Some(error), args) => {
+            let stmt = revert_custom_error(loc, error, args, context, symtable, ns, diagnostics)?;
+
+            res.push(stmt);
 
+            Ok(false)
+        }
+        pt::Statement::Revert(loc, None, args) => {
             let id = pt::Identifier {
                 loc: pt::Loc::File(loc.file_no(), loc.start(), loc.start() + 6),
                 name: "revert".to_string(),
@@ -883,16 +889,285 @@ fn statement(
 
             Ok(reachable)
         }
-        pt::Statement::RevertNamedArgs(loc, _, _) => {
-            ns.diagnostics.push(Diagnostic::error(
+        pt::Statement::RevertNamedArgs(loc, Some(error), args) => {
+            let stmt = revert_custom_error_named_args(
+                loc,
+                error,
+                args,
+                context,
+                symtable,
+                ns,
+                diagnostics,
+            )?;
+
+            res.push(stmt);
+
+            Ok(false)
+        }
+        pt::Statement::RevertNamedArgs(loc, None, args) => {
+            for arg in args {
+                let _ = expression(
+                    &arg.expr,
+                    context,
+                    ns,
+                    symtable,
+                    diagnostics,
+                    ResolveTo::Unknown,
+                );
+            }
+
+            diagnostics.push(Diagnostic::error(
                 *loc,
-                "revert with custom errors or named arguments not supported yet".to_string(),
+                "revert with named arguments requires an error type".to_string(),
             ));
             Err(())
         }
     }
 }
 
+/// Resolve revert statement with a custom error and positional arguments
+fn revert_custom_error(
+    loc: &pt::Loc,
+    error: &pt::IdentifierPath,
+    args: &[pt::Expression],
+    context: &ExprContext,
+    symtable: &mut Symtable,
+    ns: &mut Namespace,
+    diagnostics: &mut Diagnostics,
+) -> Result<Statement, ()> {
+    let error_no = match ns.resolve_error(context.file_no, context.contract_no, error, diagnostics)
+    {
+        Ok(no) => no,
+        Err(_) => {
+            for arg in args {
+                if let Ok(exp) =
+                    expression(arg, context, ns, symtable, diagnostics, ResolveTo::Unknown)
+                {
+                    used_variable(ns, &exp, symtable);
+                }
+            }
+            return Err(());
+        }
+    };
+
+    let fields = ns.errors[error_no].fields.clone();
+
+    if args.len() != fields.len() {
+        diagnostics.push(Diagnostic::error_with_note(
+            *loc,
+            format!(
+                "error '{}' has {} fields, {} provided",
+                ns.errors[error_no].name,
+                fields.len(),
+                args.len()
+            ),
+            ns.errors[error_no].loc,
+            format!("definition of '{}'", ns.errors[error_no].name),
+        ));
+    }
+
+    let mut cast_args = Vec::new();
+    let mut matches = args.len() == fields.len();
+
+    for (i, arg) in args.iter().enumerate() {
+        let ty = fields.get(i).map(|field| field.ty.clone());
+
+        let resolve_to = ty
+            .as_ref()
+            .map(ResolveTo::Type)
+            .unwrap_or(ResolveTo::Unknown);
+
+        let arg = match expression(arg, context, ns, symtable, diagnostics, resolve_to) {
+            Ok(e) => e,
+            Err(()) => {
+                matches = false;
+                continue;
+            }
+        };
+
+        used_variable(ns, &arg, symtable);
+
+        if let Some(ty) = &ty {
+            match arg.cast(&arg.loc(), ty, true, ns, diagnostics) {
+                Ok(expr) => cast_args.push(expr),
+                Err(_) => {
+                    matches = false;
+                }
+            }
+        }
+    }
+
+    if !matches {
+        return Err(());
+    }
+
+    add_revert_error(context, error_no, ns);
+
+    Ok(Statement::Revert {
+        loc: *loc,
+        error_no,
+        args: cast_args,
+    })
+}
+
+/// Resolve revert statement with a custom error and named arguments
+fn revert_custom_error_named_args(
+    loc: &pt::Loc,
+    error: &pt::IdentifierPath,
+    args: &[pt::NamedArgument],
+    context: &ExprContext,
+    symtable: &mut Symtable,
+    ns: &mut Namespace,
+    diagnostics: &mut Diagnostics,
+) -> Result<Statement, ()> {
+    // Keep the arguments in the order they were written, so that diagnostics are deterministic
+    let mut arguments = HashMap::new();
+    let mut unique_args = Vec::new();
+
+    for arg in args {
+        if arguments.contains_key(arg.name.name.as_str()) {
+            diagnostics.push(Diagnostic::error(
+                arg.name.loc,
+                format!("duplicate argument with name '{}'", arg.name.name),
+            ));
+
+            let _ = expression(
+                &arg.expr,
+                context,
+                ns,
+                symtable,
+                diagnostics,
+                ResolveTo::Unknown,
+            );
+
+            continue;
+        }
+
+        arguments.insert(arg.name.name.as_str(), &arg.expr);
+        unique_args.push(arg);
+    }
+
+    let error_no = match ns.resolve_error(context.file_no, context.contract_no, error, diagnostics)
+    {
+        Ok(no) => no,
+        Err(_) => {
+            for arg in &unique_args {
+                if let Ok(exp) = expression(
+                    &arg.expr,
+                    context,
+                    ns,
+                    symtable,
+                    diagnostics,
+                    ResolveTo::Unknown,
+                ) {
+                    used_variable(ns, &exp, symtable);
+                }
+            }
+            return Err(());
+        }
+    };
+
+    let fields = ns.errors[error_no].fields.clone();
+    let unnamed_fields = fields.iter().filter(|p| p.id.is_none()).count();
+
+    if unnamed_fields > 0 {
+        diagnostics.push(Diagnostic::error_with_note(
+            *loc,
+            format!(
+                "error cannot be used with named arguments as {} of its fields do not have names",
+                unnamed_fields,
+            ),
+            ns.errors[error_no].loc,
+            format!("definition of '{}'", ns.errors[error_no].name),
+        ));
+        return Err(());
+    }
+
+    let mut matches = true;
+
+    for arg in &unique_args {
+        if !fields
+            .iter()
+            .any(|field| field.name_as_str() == arg.name.name)
+        {
+            diagnostics.push(Diagnostic::error(
+                arg.name.loc,
+                format!(
+                    "error '{}' has no field called '{}'",
+                    ns.errors[error_no].name, arg.name.name
+                ),
+            ));
+            matches = false;
+        }
+    }
+
+    let mut cast_args = Vec::new();
+
+    for field in &fields {
+        let arg = match arguments.get(field.name_as_str()) {
+            Some(a) => a,
+            None => {
+                diagnostics.push(Diagnostic::error(
+                    *loc,
+                    format!(
+                        "missing argument '{}' to error '{}'",
+                        field.name_as_str(),
+                        ns.errors[error_no].name,
+                    ),
+                ));
+                matches = false;
+                continue;
+            }
+        };
+
+        let arg = match expression(
+            arg,
+            context,
+            ns,
+            symtable,
+            diagnostics,
+            ResolveTo::Type(&field.ty),
+        ) {
+            Ok(e) => e,
+            Err(()) => {
+                matches = false;
+                continue;
+            }
+        };
+
+        used_variable(ns, &arg, symtable);
+
+        match arg.cast(&arg.loc(), &field.ty, true, ns, diagnostics) {
+            Ok(expr) => cast_args.push(expr),
+            Err(_) => {
+                matches = false;
+            }
+        }
+    }
+
+    if !matches {
+        return Err(());
+    }
+
+    add_revert_error(context, error_no, ns);
+
+    Ok(Statement::Revert {
+        loc: *loc,
+        error_no,
+        args: cast_args,
+    })
+}
+
+/// Record that the current function can revert with the given error, so that it can be
+/// included in the ABI of the contract
+fn add_revert_error(context: &ExprContext, error_no: usize, ns: &mut Namespace) {
+    if let Some(function_no) = context.function_no {
+        if !ns.functions[function_no].reverts_errors.contains(&error_no) {
+            ns.functions[function_no].reverts_errors.push(error_no);
+        }
+    }
+}
+
 /// Resolve emit event
 fn emit_event(
     loc: &pt::Loc,
@@ -2070,6 +2345,7 @@ fn try_catch(
     symtable.leave_scope();
 
     let mut clauses_unique = HashSet::new();
+    let mut errors_unique = HashSet::new();
     let mut errors_resolved = Vec::new();
     let mut catch_param = None;
     let mut catch_param_pos = None;
@@ -2077,10 +2353,12 @@ fn try_catch(
 
     clause_stmts.iter().try_for_each(|clause_stmt| {
         let (loc, name) = match clause_stmt {
-            CatchClause::Simple(loc, _, _) => (loc, ""),
-            CatchClause::Named(loc, id, _, _) => (loc, id.name.as_str()),
+            pt::CatchClause::Simple(loc, _, _) => (loc, String::new()),
+            pt::CatchClause::Named(loc, id, _, _) => (loc, id.to_string()),
         };
-        if !clauses_unique.insert(name) {
+        // custom errors are checked for duplicates once resolved, since the same error can
+        // be named in different ways
+        if matches!(name.as_str(), "" | "Error" | "Panic") && !clauses_unique.insert(name.clone()) {
             ns.diagnostics.push(Diagnostic::error(
                 *loc,
                 if name.is_empty() {
@@ -2093,7 +2371,7 @@ fn try_catch(
         }
 
         match clause_stmt {
-            CatchClause::Simple(_, param, stmt) => {
+            pt::CatchClause::Simple(_, param, stmt) => {
                 symtable.new_scope();
 
                 if let Some(param) = param {
@@ -2155,73 +2433,13 @@ fn try_catch(
 
                 Ok(())
             }
-            CatchClause::Named(_, id, param, stmt) => {
-                if !matches!(id.name.as_str(), "Error" | "Panic") {
-                    ns.diagnostics.push(Diagnostic::error(
-                        id.loc,
-                        format!(
-                            "only catch 'Error' or 'Panic' is supported, not '{}'",
-                            id.name
-                        ),
-                    ));
-                    return Err(());
-                }
-
-                let (error_ty, ty_loc) =
-                    resolve_var_decl_ty(&param.ty, &param.storage, context, ns, diagnostics)?;
-
-                if id.name == "Error" && error_ty != Type::String {
-                    ns.diagnostics.push(Diagnostic::error(
-                        param.ty.loc(),
-                        format!(
-                            "catch Error(...) can only take 'string memory', not '{}'",
-                            error_ty.to_string(ns)
-                        ),
-                    ));
-                }
-                if id.name == "Panic" && error_ty != Type::Uint(256) {
-                    ns.diagnostics.push(Diagnostic::error(
-                        param.ty.loc(),
-                        format!(
-                            "catch Panic(...) can only take 'uint256', not '{}'",
-                            error_ty.to_string(ns)
-                        ),
-                    ));
-                }
-
-                symtable.new_scope();
-
-                let mut error_pos = None;
-                let mut error_stmt_resolved = Vec::new();
-                let mut error_param = Parameter {
-                    loc: id.loc,
-                    ty: Type::String,
-                    ty_loc: Some(ty_loc),
-                    id: None,
-                    indexed: false,
-                    readonly: false,
-                    recursive: false,
-                };
-
-                if let Some(name) = &param.name {
-                    if let Some(pos) = symtable.add(
-                        name,
-                        Type::String,
-                        ns,
-                        VariableInitializer::Solidity(None),
-                        VariableUsage::TryCatchErrorString,
-                        param.storage.clone(),
-                    ) {
-                        ns.check_shadowing(context.file_no, context.contract_no, name);
-
-                        error_pos = Some(pos);
-                        error_param.id = Some(name.clone());
-                    }
-                }
-
-                let reachable = statement(
+            pt::CatchClause::Named(loc, id, params, stmt) => {
+                let clause = catch_error_clause(
+                    loc,
+                    id,
+                    params,
                     stmt,
-                    &mut error_stmt_resolved,
+                    &mut errors_unique,
                     context,
                     symtable,
                     loops,
@@ -2229,11 +2447,9 @@ fn try_catch(
                     diagnostics,
                 )?;
 
-                finally_reachable |= reachable;
-
-                symtable.leave_scope();
+                finally_reachable |= clause.1;
 
-                errors_resolved.push((error_pos, error_param, error_stmt_resolved));
+                errors_resolved.push(clause.0);
 
                 Ok(())
             }
@@ -2256,3 +2472,157 @@ fn try_catch(
 
     Ok((stmt, finally_reachable))
 }
+
+/// Resolve a catch clause for a specific error, e.g. `catch Error(string reason)` or
+/// `catch MyError(uint a, bool b)`. Returns the clause and whether the end of the clause
+/// is reachable.
+fn catch_error_clause(
+    loc: &pt::Loc,
+    id: &pt::IdentifierPath,
+    params: &[pt::Parameter],
+    stmt: &pt::Statement,
+    errors_unique: &mut HashSet<usize>,
+    context: &ExprContext,
+    symtable: &mut Symtable,
+    loops: &mut LoopScopes,
+    ns: &mut Namespace,
+    diagnostics: &mut Diagnostics,
+) -> Result<(CatchClause, bool), ()> {
+    let error_name = id.to_string();
+
+    let (selector, fields) = match error_name.as_str() {
+        "Error" => (0x08c3_79a0, vec![Type::String]),
+        "Panic" => (0x4e48_7b71, vec![Type::Uint(256)]),
+        _ => {
+            let error_no =
+                ns.resolve_error(context.file_no, context.contract_no, id, diagnostics)?;
+
+            if !errors_unique.insert(error_no) {
+                ns.diagnostics.push(Diagnostic::error(
+                    *loc,
+                    format!("duplicate '{}' catch clause", error_name),
+                ));
+                return Err(());
+            }
+
+            let error = &ns.errors[error_no];
+
+            (
+                error.selector(),
+                error.fields.iter().map(|field| field.ty.clone()).collect(),
+            )
+        }
+    };
+
+    if params.len() != fields.len() {
+        diagnostics.push(Diagnostic::error(
+            *loc,
+            format!(
+                "catch {}(...) takes {} parameters, {} provided",
+                error_name,
+                fields.len(),
+                params.len()
+            ),
+        ));
+        return Err(());
+    }
+
+    let mut param_tys = Vec::new();
+
+    for param in params {
+        param_tys.push(resolve_var_decl_ty(
+            &param.ty,
+            &param.storage,
+            context,
+            ns,
+            diagnostics,
+        )?);
+    }
+
+    symtable.new_scope();
+
+    let mut resolved_params = Vec::new();
+
+    for ((param, field_ty), (param_ty, ty_loc)) in params.iter().zip(fields).zip(param_tys) {
+        if param_ty != field_ty {
+            diagnostics.push(Diagnostic::error(
+                param.ty.loc(),
+                match error_name.as_str() {
+                    "Error" => format!(
+                        "catch Error(...) can only take 'string memory', not '{}'",
+                        param_ty.to_string(ns)
+                    ),
+                    "Panic" => format!(
+                        "catch Panic(...) can only take 'uint256', not '{}'",
+                        param_ty.to_string(ns)
+                    ),
+                    _ => format!(
+                        "type '{}' does not match field of error '{}' of type '{}'",
+                        param_ty.to_string(ns),
+                        error_name,
+                        field_ty.to_string(ns)
+                    ),
+                },
+            ));
+        }
+
+        let mut error_param = Parameter {
+            loc: param.loc,
+            ty: field_ty,
+            ty_loc: Some(ty_loc),
+            id: None,
+            indexed: false,
+            readonly: false,
+            recursive: false,
+        };
+
+        let mut error_pos = None;
+
+        if let Some(name) = &param.name {
+            if let Some(pos) = symtable.add(
+                name,
+                error_param.ty.clone(),
+                ns,
+                VariableInitializer::Solidity(None),
+                if error_name == "Error" {
+                    VariableUsage::TryCatchErrorString
+                } else {
+                    VariableUsage::TryCatchErrorField
+                },
+                param.storage.clone(),
+            ) {
+                ns.check_shadowing(context.file_no, context.contract_no, name);
+
+                error_pos = Some(pos);
+                error_param.id = Some(name.clone());
+            }
+        }
+
+        resolved_params.push((error_pos, error_param));
+    }
+
+    let mut error_stmt_resolved = Vec::new();
+
+    let reachable = statement(
+        stmt,
+        &mut error_stmt_resolved,
+        context,
+        symtable,
+        loops,
+        ns,
+        diagnostics,
+    )?;
+
+    symtable.leave_scope();
+
+    Ok((
+        CatchClause {
+            loc: *loc,
+            name: error_name,
+            selector,
+            params: resolved_params,
+            stmt: error_stmt_resolved,
+        },
+        reachable,
+    ))
+}
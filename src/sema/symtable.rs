@@ -73,6 +73,7 @@ pub enum VariableUsage {
     TryCatchReturns,
     TryCatchErrorString,
     TryCatchErrorBytes,
+    TryCatchErrorField,
     YulLocalVariable,
 }
 
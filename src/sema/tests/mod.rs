@@ -1,9 +1,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 #![cfg(test)]
-use crate::sema::ast::{Expression, Parameter, Statement, TryCatch, Type};
+use crate::sema::ast::{snake_case, Expression, Parameter, Statement, TryCatch, Type};
 use crate::sema::diagnostics::Diagnostics;
 use crate::sema::expression::unescape;
+use crate::sema::solidity_versions;
 use crate::sema::yul::ast::InlineAssembly;
 use solang_parser::pt::Loc;
 
@@ -20,6 +21,44 @@ fn test_unescape() {
     assert_eq!(res, vec![255]);
 }
 
+#[test]
+fn test_solidity_versions() {
+    let range = |value: &str| {
+        solidity_versions(value).unwrap().map(|(lowest, end)| {
+            (
+                lowest.to_string(),
+                end.map(|end| end.to_string()).unwrap_or_default(),
+            )
+        })
+    };
+
+    let range_of = |lowest: &str, end: &str| Some((lowest.to_string(), end.to_string()));
+
+    assert_eq!(range("^0.8.4"), range_of("0.8.4", "0.9.0"));
+    assert_eq!(range("^0.8.20"), range_of("0.8.20", "0.9.0"));
+    assert_eq!(range(">=0.8.0 <0.9.0"), range_of("0.8.0", "0.9.0"));
+    assert_eq!(range(">= 0.6.0 < 0.9.0"), range_of("0.6.0", "0.9.0"));
+    assert_eq!(range("0.7.6 || ^0.8.0"), range_of("0.7.6", "0.9.0"));
+    assert_eq!(range("0.6.12"), range_of("0.6.12", "0.6.13"));
+    assert_eq!(range("~0.5"), range_of("0.5.0", "0.6.0"));
+    assert_eq!(range("0.5.0 - 0.6.2"), range_of("0.5.0", "0.6.3"));
+    assert_eq!(range(">0.7"), range_of("0.8.0", ""));
+    assert_eq!(range("0"), range_of("0.0.0", "1.0.0"));
+    assert_eq!(range("<0.8.0"), range_of("0.0.0", "0.8.0"));
+    assert_eq!(range(">=0.9.0"), range_of("0.9.0", ""));
+    assert_eq!(range(">=0.8.0 <0.8.0"), None);
+    assert!(solidity_versions("x.y").is_err());
+}
+
+#[test]
+fn test_snake_case() {
+    assert_eq!(snake_case("initialize"), "initialize");
+    assert_eq!(snake_case("transferFrom"), "transfer_from");
+    assert_eq!(snake_case("getABCValue"), "get_abc_value");
+    assert_eq!(snake_case("setX2Y"), "set_x2_y");
+    assert_eq!(snake_case("already_snake"), "already_snake");
+}
+
 #[test]
 fn test_statement_reachable() {
     let loc = Loc::File(0, 1, 2);
@@ -4,8 +4,8 @@ use super::tags::resolve_tags;
 use super::SOLANA_BUCKET_SIZE;
 use super::{
     ast::{
-        ArrayLength, Contract, Diagnostic, EnumDecl, EventDecl, Namespace, Parameter, StructDecl,
-        StructType, Symbol, Tag, Type, UserTypeDecl,
+        ArrayLength, Contract, Diagnostic, EnumDecl, ErrorDecl, EventDecl, Namespace, Parameter,
+        StructDecl, StructType, Symbol, Tag, Type, UserTypeDecl,
     },
     diagnostics::Diagnostics,
     SOLANA_SPARSE_ARRAY_SIZE,
@@ -25,6 +25,14 @@ use std::{collections::HashMap, fmt::Write, ops::Mul};
 pub struct ResolveFields<'a> {
     structs: Vec<ResolveStructFields<'a>>,
     events: Vec<ResolveEventFields<'a>>,
+    errors: Vec<ResolveErrorFields<'a>>,
+}
+
+struct ResolveErrorFields<'a> {
+    error_no: usize,
+    pt: &'a pt::ErrorDefinition,
+    comments: Vec<DocComment>,
+    contract: Option<usize>,
 }
 
 struct ResolveEventFields<'a> {
@@ -52,6 +60,7 @@ pub fn resolve_typenames<'a>(
     let mut delay = ResolveFields {
         structs: Vec::new(),
         events: Vec::new(),
+        errors: Vec::new(),
     };
 
     // Find all the types: contracts, enums, and structs. Either in a contract or not
@@ -137,6 +146,11 @@ pub fn resolve_typenames<'a>(
                     contract: None,
                 });
             }
+            pt::SourceUnitPart::ErrorDefinition(def) => {
+                let tags = parse_doccomments(comments, doc_comment_start, def.loc.start());
+
+                error_typename(def, file_no, tags, None, &mut delay, ns);
+            }
             pt::SourceUnitPart::TypeDefinition(ty) => {
                 let tags = parse_doccomments(comments, doc_comment_start, ty.loc.start());
 
@@ -157,6 +171,54 @@ pub fn resolve_typenames<'a>(
     delay
 }
 
+/// Add the symbol for an error definition; its fields are resolved later. Returns false
+/// if the symbol could not be added.
+fn error_typename<'a>(
+    def: &'a pt::ErrorDefinition,
+    file_no: usize,
+    comments: Vec<DocComment>,
+    contract_no: Option<usize>,
+    delay: &mut ResolveFields<'a>,
+    ns: &mut Namespace,
+) -> bool {
+    if def.name.name == "Error" || def.name.name == "Panic" {
+        ns.diagnostics.push(Diagnostic::error(
+            def.name.loc,
+            format!("built-in error '{}' cannot be redeclared", def.name.name),
+        ));
+        return false;
+    }
+
+    let error_no = ns.errors.len();
+
+    if !ns.add_symbol(
+        file_no,
+        contract_no,
+        &def.name,
+        Symbol::Error(def.name.loc, error_no),
+    ) {
+        return false;
+    }
+
+    ns.errors.push(ErrorDecl {
+        tags: Vec::new(),
+        name: def.name.name.to_owned(),
+        loc: def.name.loc,
+        contract: contract_no,
+        fields: Vec::new(),
+        signature: String::new(),
+    });
+
+    delay.errors.push(ResolveErrorFields {
+        error_no,
+        pt: def,
+        comments,
+        contract: contract_no,
+    });
+
+    true
+}
+
 fn type_decl(
     def: &pt::TypeDefinition,
     file_no: usize,
@@ -179,7 +241,13 @@ fn type_decl(
     // - Need for circular checks (type a is b; type b is a;)
     if !matches!(
         ty,
-        Type::Address(_) | Type::Bool | Type::Int(_) | Type::Uint(_) | Type::Bytes(_)
+        Type::Address(_)
+            | Type::Bool
+            | Type::Int(_)
+            | Type::Uint(_)
+            | Type::Fixed(..)
+            | Type::Ufixed(..)
+            | Type::Bytes(_)
     ) {
         ns.diagnostics.push(Diagnostic::error(
             def.ty.loc(),
@@ -268,6 +336,16 @@ pub fn resolve_fields(delay: ResolveFields, file_no: usize, ns: &mut Namespace)
         ns.events[event.event_no].fields = fields;
         ns.events[event.event_no].tags = tags;
     }
+
+    // and the fields for the errors
+    for error in delay.errors {
+        let (tags, fields) = error_decl(error.pt, file_no, &error.comments, error.contract, ns);
+
+        ns.errors[error.error_no].signature =
+            ns.signature(&ns.errors[error.error_no].name, &fields);
+        ns.errors[error.error_no].fields = fields;
+        ns.errors[error.error_no].tags = tags;
+    }
 }
 
 /// Resolve all the types in a contract
@@ -392,6 +470,13 @@ fn resolve_contract<'a>(
                     contract: Some(contract_no),
                 });
             }
+            pt::ContractPart::ErrorDefinition(def) => {
+                let tags = parse_doccomments(comments, doc_comment_start, def.loc.start());
+
+                if !error_typename(def, file_no, tags, Some(contract_no), delay, ns) {
+                    broken = true;
+                }
+            }
             pt::ContractPart::TypeDefinition(ty) => {
                 let tags = parse_doccomments(comments, doc_comment_start, ty.loc.start());
 
@@ -610,6 +695,88 @@ fn event_decl(
     (doc, fields)
 }
 
+/// Resolve a parsed error definition. Like events, the fields which could be resolved
+/// are returned even if some of them are invalid.
+fn error_decl(
+    def: &pt::ErrorDefinition,
+    file_no: usize,
+    tags: &[DocComment],
+    contract_no: Option<usize>,
+    ns: &mut Namespace,
+) -> (Vec<Tag>, Vec<Parameter>) {
+    let mut fields: Vec<Parameter> = Vec::new();
+
+    for field in &def.fields {
+        let mut diagnostics = Diagnostics::default();
+
+        let mut ty = match ns.resolve_type(file_no, contract_no, false, &field.ty, &mut diagnostics)
+        {
+            Ok(s) => s,
+            Err(()) => {
+                ns.diagnostics.extend(diagnostics);
+                Type::Unresolved
+            }
+        };
+
+        if ty.contains_mapping(ns) {
+            ns.diagnostics.push(Diagnostic::error(
+                field.loc,
+                "mapping type is not permitted as error field".to_string(),
+            ));
+            ty = Type::Unresolved;
+        }
+
+        let name = if let Some(name) = &field.name {
+            if let Some(other) = fields
+                .iter()
+                .find(|f| f.id.as_ref().map(|id| id.name.as_str()) == Some(name.name.as_str()))
+            {
+                ns.diagnostics.push(Diagnostic::error_with_note(
+                    name.loc,
+                    format!(
+                        "error '{}' has duplicate field name '{}'",
+                        def.name.name, name.name
+                    ),
+                    other.loc,
+                    format!(
+                        "location of previous declaration of '{}'",
+                        other.name_as_str()
+                    ),
+                ));
+                continue;
+            }
+            Some(pt::Identifier {
+                name: name.name.to_owned(),
+                loc: name.loc,
+            })
+        } else {
+            None
+        };
+
+        fields.push(Parameter {
+            loc: field.loc,
+            id: name,
+            ty,
+            ty_loc: Some(field.ty.loc()),
+            indexed: false,
+            readonly: false,
+            recursive: false,
+        });
+    }
+
+    let doc = resolve_tags(
+        def.name.loc.file_no(),
+        "error",
+        tags,
+        Some(&fields),
+        None,
+        None,
+        ns,
+    );
+
+    (doc, fields)
+}
+
 /// Parse enum declaration. If the declaration is invalid, it is still generated
 /// so that we can continue parsing, with errors recorded.
 fn enum_decl(
@@ -784,6 +951,8 @@ impl Type {
             Type::Address(true) => "address payable".to_string(),
             Type::Int(n) => format!("int{}", n),
             Type::Uint(n) => format!("uint{}", n),
+            Type::Fixed(m, n) => format!("fixed{}x{}", m, n),
+            Type::Ufixed(m, n) => format!("ufixed{}x{}", m, n),
             Type::Rational => "rational".to_string(),
             Type::Value => format!("uint{}", ns.value_length * 8),
             Type::Bytes(n) => format!("bytes{}", n),
@@ -864,6 +1033,8 @@ impl Type {
             Type::Address(_) => true,
             Type::Int(_) => true,
             Type::Uint(_) => true,
+            Type::Fixed(..) => true,
+            Type::Ufixed(..) => true,
             Type::Bytes(_) => true,
             Type::Rational => true,
             Type::Value => true,
@@ -883,6 +1054,8 @@ impl Type {
             Type::Contract(_) | Type::Address(_) => "address".to_string(),
             Type::Int(n) => format!("int{}", n),
             Type::Uint(n) => format!("uint{}", n),
+            Type::Fixed(m, n) => format!("fixed{}x{}", m, n),
+            Type::Ufixed(m, n) => format!("ufixed{}x{}", m, n),
             Type::Rational => "rational".to_string(),
             Type::Bytes(n) => format!("bytes{}", n),
             Type::DynamicBytes => "bytes".to_string(),
@@ -934,6 +1107,7 @@ impl Type {
             ))),
             Type::Array(ty, dim) if dim.len() == 1 => Type::Ref(ty.clone()),
             Type::Bytes(_) => Type::Bytes(1),
+            Type::Slice(ty) => Type::Ref(ty.clone()),
             _ => panic!("deref on non-array"),
         }
     }
@@ -953,6 +1127,8 @@ impl Type {
             Type::Address(_) => false,
             Type::Int(_) => false,
             Type::Uint(_) => false,
+            Type::Fixed(..) => false,
+            Type::Ufixed(..) => false,
             Type::Rational => false,
             Type::Bytes(_) => false,
             Type::Enum(_) => false,
@@ -1021,7 +1197,9 @@ impl Type {
             Type::Contract(_) | Type::Address(_) => BigInt::from(ns.address_length),
             Type::Bytes(n) => BigInt::from(*n),
             Type::Value => BigInt::from(ns.value_length),
-            Type::Uint(n) | Type::Int(n) => BigInt::from(n / 8),
+            Type::Uint(n) | Type::Int(n) | Type::Fixed(n, _) | Type::Ufixed(n, _) => {
+                BigInt::from(n / 8)
+            }
             Type::Rational => unreachable!(),
             Type::Array(ty, dims) => {
                 let pointer_size = BigInt::from(ns.target.ptr_size() / 8);
@@ -1095,10 +1273,10 @@ impl Type {
     /// Calculate the alignment
     pub fn align_of(&self, ns: &Namespace) -> usize {
         match self {
-            Type::Uint(8) | Type::Int(8) => 1,
-            Type::Uint(n) | Type::Int(n) if *n <= 16 => 2,
-            Type::Uint(n) | Type::Int(n) if *n <= 32 => 4,
-            Type::Uint(_) | Type::Int(_) => 8,
+            Type::Uint(8) | Type::Int(8) | Type::Fixed(8, _) | Type::Ufixed(8, _) => 1,
+            Type::Uint(n) | Type::Int(n) | Type::Fixed(n, _) | Type::Ufixed(n, _) if *n <= 16 => 2,
+            Type::Uint(n) | Type::Int(n) | Type::Fixed(n, _) | Type::Ufixed(n, _) if *n <= 32 => 4,
+            Type::Uint(_) | Type::Int(_) | Type::Fixed(..) | Type::Ufixed(..) => 8,
             Type::Struct(str_ty) => str_ty
                 .definition(ns)
                 .fields
@@ -1117,6 +1295,7 @@ impl Type {
             Type::Bool => 1,
             Type::Int(n) => *n,
             Type::Uint(n) => *n,
+            Type::Fixed(n, _) | Type::Ufixed(n, _) => *n,
             Type::Rational => unreachable!(),
             Type::Bytes(n) => *n as u16 * 8,
             Type::Enum(n) => ns.enums[*n].ty.bits(ns),
@@ -1136,6 +1315,16 @@ impl Type {
         }
     }
 
+    /// Is this a signed integer or a signed fixed point type
+    pub fn is_signed_numeric(&self) -> bool {
+        match self {
+            Type::Int(_) | Type::Fixed(..) => true,
+            Type::Ref(r) => r.is_signed_numeric(),
+            Type::StorageRef(_, r) => r.is_signed_numeric(),
+            _ => false,
+        }
+    }
+
     pub fn is_integer(&self) -> bool {
         match self {
             Type::Int(_) => true,
@@ -1148,6 +1337,43 @@ impl Type {
         }
     }
 
+    /// Is this a fixed point number type
+    pub fn is_fixed(&self) -> bool {
+        match self {
+            Type::Fixed(..) | Type::Ufixed(..) => true,
+            Type::Ref(r) => r.is_fixed(),
+            Type::StorageRef(_, r) => r.is_fixed(),
+            _ => false,
+        }
+    }
+
+    /// Number of decimals of a fixed point number type
+    pub fn fixed_decimals(&self) -> u8 {
+        match self {
+            Type::Fixed(_, n) | Type::Ufixed(_, n) => *n,
+            Type::Ref(r) => r.fixed_decimals(),
+            Type::StorageRef(_, r) => r.fixed_decimals(),
+            _ => 0,
+        }
+    }
+
+    /// Number of bits needed for the intermediate result of multiplying two fixed point values,
+    /// which is the full product, or of dividing them, which is the dividend multiplied by
+    /// 10^decimals. This is rounded up to whole bytes.
+    pub fn fixed_intermediate_bits(&self, multiply: bool, ns: &Namespace) -> u16 {
+        let bits = self.bits(ns);
+
+        let extra_bits = if multiply {
+            bits
+        } else {
+            BigInt::from(10)
+                .pow(u32::from(self.fixed_decimals()))
+                .bits() as u16
+        };
+
+        (bits + extra_bits + 7) & !7
+    }
+
     pub fn is_rational(&self) -> bool {
         match self {
             Type::Rational => true,
@@ -1170,7 +1396,9 @@ impl Type {
                 Type::Contract(_) | Type::Address(_) => BigInt::from(ns.address_length),
                 Type::Bytes(n) => BigInt::from(*n),
                 Type::Value => BigInt::from(ns.value_length),
-                Type::Uint(n) | Type::Int(n) => BigInt::from(n / 8),
+                Type::Uint(n) | Type::Int(n) | Type::Fixed(n, _) | Type::Ufixed(n, _) => {
+                    BigInt::from(n / 8)
+                }
                 Type::Rational => unreachable!(),
                 Type::Array(_, dims) if dims.last() == Some(&ArrayLength::Dynamic) => {
                     BigInt::from(4)
@@ -1254,7 +1482,9 @@ impl Type {
                 Type::Contract(_) | Type::Address(_) => BigInt::from(ns.address_length),
                 Type::Bytes(n) => BigInt::from(*n),
                 Type::Value => BigInt::from(ns.value_length),
-                Type::Uint(n) | Type::Int(n) => BigInt::from(n / 8),
+                Type::Uint(n) | Type::Int(n) | Type::Fixed(n, _) | Type::Ufixed(n, _) => {
+                    BigInt::from(n / 8)
+                }
                 Type::Rational => unreachable!(),
                 Type::Array(_, dims) if dims.last() == Some(&ArrayLength::Dynamic) => {
                     BigInt::from(4)
@@ -1305,6 +1535,8 @@ impl Type {
             Type::Address(_) => false,
             Type::Int(_) => false,
             Type::Uint(_) => false,
+            Type::Fixed(..) => false,
+            Type::Ufixed(..) => false,
             Type::Rational => false,
             Type::Bytes(_) => false,
             Type::Enum(_) => false,
@@ -1323,6 +1555,15 @@ impl Type {
         }
     }
 
+    /// Is this an internal or external function pointer
+    pub fn is_function_pointer(&self) -> bool {
+        match self {
+            Type::InternalFunction { .. } | Type::ExternalFunction { .. } => true,
+            Type::Ref(r) | Type::StorageRef(_, r) => r.is_function_pointer(),
+            _ => false,
+        }
+    }
+
     /// Does this type contain any types which are variable-length
     pub fn is_dynamic(&self, ns: &Namespace) -> bool {
         match self {
@@ -1414,6 +1655,25 @@ impl Type {
         }
     }
 
+    /// Can a variable of this type be immutable. Immutables are stored in a fixed size
+    /// area outside of contract storage, so only value types are allowed
+    pub fn is_immutable_value(&self, ns: &Namespace) -> bool {
+        match self {
+            Type::Bool
+            | Type::Address(_)
+            | Type::Int(_)
+            | Type::Uint(_)
+            | Type::Fixed(..)
+            | Type::Ufixed(..)
+            | Type::Bytes(_)
+            | Type::Enum(_)
+            | Type::Contract(_)
+            | Type::InternalFunction { .. } => true,
+            Type::UserType(no) => ns.user_types[*no].ty.is_immutable_value(ns),
+            _ => false,
+        }
+    }
+
     /// Does the type contain any internal function type
     pub fn contains_internal_function(&self, ns: &Namespace) -> bool {
         match self {
@@ -1487,6 +1747,23 @@ impl Type {
         }
     }
 
+    /// A slice used as a value is copied into an array of the same element type. Give that
+    /// array type for a slice, or the type itself otherwise.
+    pub fn slice_to_array(&self) -> Self {
+        match self {
+            Type::Slice(elem_ty) if elem_ty.as_ref() == &Type::Bytes(1) => Type::DynamicBytes,
+            Type::Slice(elem_ty) => match elem_ty.as_ref() {
+                Type::Array(ty, dims) => {
+                    let mut dims = dims.clone();
+                    dims.push(ArrayLength::Dynamic);
+                    Type::Array(ty.clone(), dims)
+                }
+                _ => Type::Array(elem_ty.clone(), vec![ArrayLength::Dynamic]),
+            },
+            _ => self.clone(),
+        }
+    }
+
     /// If the type is Ref, get the underlying type
     pub fn deref_memory(&self) -> &Self {
         match self {
@@ -1502,6 +1779,8 @@ impl Type {
             Type::Address(_) => "address".to_string(),
             Type::Int(n) => format!("int{}", n),
             Type::Uint(n) => format!("uint{}", n),
+            Type::Fixed(m, n) => format!("fixed{}x{}", m, n),
+            Type::Ufixed(m, n) => format!("ufixed{}x{}", m, n),
             Type::Bytes(n) => format!("bytes{}", n),
             Type::DynamicBytes => "bytes".to_string(),
             Type::String => "string".to_string(),
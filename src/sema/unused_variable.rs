@@ -71,6 +71,18 @@ pub fn used_variable(ns: &mut Namespace, exp: &Expression, symtable: &mut Symtab
             used_variable(ns, index, symtable);
         }
 
+        Expression::ArraySlice {
+            array, start, end, ..
+        } => {
+            used_variable(ns, array, symtable);
+            if let Some(start) = start {
+                used_variable(ns, start, symtable);
+            }
+            if let Some(end) = end {
+                used_variable(ns, end, symtable);
+            }
+        }
+
         Expression::Builtin(_, _, Builtin::ArrayLength, args) => {
             //We should not eliminate an array from the code when 'length' is called
             //So the variable is also assigned
@@ -333,6 +345,20 @@ pub fn emit_warning_local_variable(variable: &symtable::Variable) -> Option<Diag
 
             None
         }
+
+        VariableUsage::TryCatchErrorField => {
+            if !variable.read {
+                return Some(Diagnostic::warning(
+                    variable.id.loc,
+                    format!(
+                        "try-catch error field '{}' has never been used",
+                        variable.id.name
+                    ),
+                ));
+            }
+
+            None
+        }
         VariableUsage::YulLocalVariable => {
             let has_value = variable.assigned || variable.initializer.has_initializer();
             if !variable.read && !has_value {
@@ -1,7 +1,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::{
-    ast::{Diagnostic, Expression, Namespace, Note, Type, Using, UsingList},
+    ast::{
+        Diagnostic, Expression, Mutability, Namespace, Note, RetrieveType, Type, Using,
+        UsingFunction, UsingList,
+    },
     diagnostics::Diagnostics,
     expression::{expression, function_returns, function_type, ExprContext, ResolveTo},
     symtable::Symtable,
@@ -24,7 +27,7 @@ pub(crate) fn using_decl(
             Ok(Type::Contract(contract_no)) if ns.contracts[contract_no].is_library() => {
                 ns.diagnostics.push(Diagnostic::error(
                     expr.loc(),
-                    format!("using for library '{}' type not permitted", expr),
+                    format!("using for library '{:?}' type not permitted", expr),
                 ));
                 return Err(());
             }
@@ -46,7 +49,7 @@ pub(crate) fn using_decl(
         None
     };
 
-    let list = match &using.list {
+    let mut list = match &using.list {
         pt::UsingList::Library(library) => {
             if let Ok(library_no) =
                 ns.resolve_contract_with_namespace(file_no, library, &mut diagnostics)
@@ -71,7 +74,9 @@ pub(crate) fn using_decl(
         pt::UsingList::Functions(functions) => {
             let mut res = Vec::new();
 
-            for function_name in functions {
+            for using_function in functions {
+                let function_name = &using_function.path;
+
                 if let Ok(list) = ns.resolve_free_function_with_namespace(
                     file_no,
                     function_name,
@@ -123,7 +128,11 @@ pub(crate) fn using_decl(
                         }
                     }
 
-                    res.push(func_no);
+                    res.push(UsingFunction {
+                        loc: using_function.loc,
+                        function_no: func_no,
+                        oper: using_function.oper,
+                    });
                 }
             }
 
@@ -161,11 +170,211 @@ pub(crate) fn using_decl(
         }
     }
 
+    if let UsingList::Functions(functions) = &mut list {
+        let mut checked = Vec::new();
+
+        for mut using_function in functions.drain(..) {
+            if check_user_defined_operator(
+                &mut using_function,
+                &ty,
+                file_no,
+                &checked,
+                &mut diagnostics,
+                ns,
+            ) {
+                checked.push(using_function);
+            }
+        }
+
+        *functions = checked;
+    }
+
     ns.diagnostics.extend(diagnostics);
 
     Ok(Using { list, ty, file_no })
 }
 
+/// Check that a function bound to an operator can be used for that operator. An operator can
+/// only be bound globally for a user defined type, to a pure function which takes one or two
+/// arguments of that type. Returns false if the operator cannot be used.
+fn check_user_defined_operator(
+    using_function: &mut UsingFunction,
+    ty: &Option<Type>,
+    file_no: Option<usize>,
+    previous_functions: &[UsingFunction],
+    diagnostics: &mut Diagnostics,
+    ns: &Namespace,
+) -> bool {
+    let mut oper = match using_function.oper {
+        Some(oper) => oper,
+        None => return true,
+    };
+
+    let func = &ns.functions[using_function.function_no];
+
+    let ty = match ty {
+        Some(ty @ Type::UserType(_)) => ty,
+        _ => {
+            diagnostics.push(Diagnostic::error(
+                using_function.loc,
+                format!(
+                    "user defined operator can only be used with user defined types, not '{}'",
+                    ty.as_ref()
+                        .map(|ty| ty.to_string(ns))
+                        .unwrap_or_else(|| "*".to_string())
+                ),
+            ));
+            return false;
+        }
+    };
+
+    if file_no.is_some() {
+        diagnostics.push(Diagnostic::error(
+            using_function.loc,
+            "user defined operator can only be set in a global 'using for' directive".to_string(),
+        ));
+        return false;
+    }
+
+    if !matches!(func.mutability, Mutability::Pure(_)) {
+        diagnostics.push(Diagnostic::error_with_note(
+            using_function.loc,
+            format!(
+                "user defined operator function '{}' must have pure mutability",
+                func.name
+            ),
+            func.loc,
+            format!("definition of '{}'", func.name),
+        ));
+        return false;
+    }
+
+    // '-' can be both negation and subtraction
+    if oper == pt::UserDefinedOperator::Subtract && func.params.len() == 1 {
+        oper = pt::UserDefinedOperator::Negate;
+    }
+
+    if func.params.len() != oper.args() || func.params.iter().any(|param| param.ty != *ty) {
+        diagnostics.push(Diagnostic::error_with_note(
+            using_function.loc,
+            format!(
+                "user defined operator function for '{}' must have {} of type '{}'",
+                oper,
+                if oper.args() == 1 {
+                    "one argument"
+                } else {
+                    "two arguments"
+                },
+                ty.to_string(ns)
+            ),
+            func.loc,
+            format!("definition of '{}'", func.name),
+        ));
+        return false;
+    }
+
+    let return_ty = if oper.is_comparison() {
+        Type::Bool
+    } else {
+        ty.clone()
+    };
+
+    if func.returns.len() != 1 || func.returns[0].ty != return_ty {
+        diagnostics.push(Diagnostic::error_with_note(
+            using_function.loc,
+            format!(
+                "user defined operator function for '{}' must have a single return value of type '{}'",
+                oper,
+                return_ty.to_string(ns)
+            ),
+            func.loc,
+            format!("definition of '{}'", func.name),
+        ));
+        return false;
+    }
+
+    if let Some(previous) = previous_functions
+        .iter()
+        .find(|using_function| using_function.oper == Some(oper))
+        .or_else(|| find_user_defined_operator(&ns.using, ty, oper))
+    {
+        diagnostics.push(Diagnostic::error_with_note(
+            using_function.loc,
+            format!(
+                "user defined operator '{}' for type '{}' already defined",
+                oper,
+                ty.to_string(ns)
+            ),
+            previous.loc,
+            "previous definition of operator".to_string(),
+        ));
+        return false;
+    }
+
+    using_function.oper = Some(oper);
+
+    true
+}
+
+/// Find the function bound to an operator for the given type
+fn find_user_defined_operator<'a>(
+    using: &'a [Using],
+    ty: &Type,
+    oper: pt::UserDefinedOperator,
+) -> Option<&'a UsingFunction> {
+    using
+        .iter()
+        .filter(|using| using.file_no.is_none() && using.ty.as_ref() == Some(ty))
+        .find_map(|using| match &using.list {
+            UsingList::Functions(functions) => functions
+                .iter()
+                .find(|using_function| using_function.oper == Some(oper)),
+            UsingList::Library(_) => None,
+        })
+}
+
+/// If the first operand is of a user defined type, and a function is bound to the operator for
+/// this type with `using {f as op} for T global`, return a call to that function.
+pub(super) fn user_defined_operator(
+    loc: &pt::Loc,
+    args: &[&Expression],
+    oper: pt::UserDefinedOperator,
+    diagnostics: &mut Diagnostics,
+    ns: &Namespace,
+) -> Result<Option<Expression>, ()> {
+    let ty = args[0].ty();
+    let ty = ty.deref_any();
+
+    if !matches!(ty, Type::UserType(_)) {
+        return Ok(None);
+    }
+
+    let function_no = match find_user_defined_operator(&ns.using, ty, oper) {
+        Some(using_function) => using_function.function_no,
+        None => return Ok(None),
+    };
+
+    let mut cast_args = Vec::new();
+
+    for arg in args {
+        cast_args.push(arg.cast(&arg.loc(), ty, true, ns, diagnostics)?);
+    }
+
+    let func = &ns.functions[function_no];
+
+    Ok(Some(Expression::InternalFunctionCall {
+        loc: *loc,
+        returns: vec![func.returns[0].ty.clone()],
+        function: Box::new(Expression::InternalFunction {
+            loc: *loc,
+            ty: function_type(func, false, ResolveTo::Unknown),
+            function_no,
+            signature: None,
+        }),
+        args: cast_args,
+    }))
+}
+
 /// Given the using declarations, find all the possible functions that could be called via using
 /// for the given name, type and file scope
 fn possible_functions(
@@ -195,15 +404,19 @@ fn possible_functions(
             }
         })
         .flat_map(|using| match &using.list {
-            UsingList::Library(library_no) => ns.contracts[*library_no].functions.iter(),
-            UsingList::Functions(functions) => functions.iter(),
+            UsingList::Library(library_no) => ns.contracts[*library_no].functions.clone(),
+            // functions bound to an operator cannot be called as a method
+            UsingList::Functions(functions) => functions
+                .iter()
+                .filter(|using_function| using_function.oper.is_none())
+                .map(|using_function| using_function.function_no)
+                .collect(),
         })
         .filter(|func_no| {
-            let func = &ns.functions[**func_no];
+            let func = &ns.functions[*func_no];
 
             func.name == function_name && func.ty == pt::FunctionTy::Function
         })
-        .cloned()
         .collect()
 }
 
@@ -250,6 +250,17 @@ pub fn variable_decl<'a>(
         }
     }
 
+    if has_immutable.is_some() && !ty.is_immutable_value(ns) {
+        // immutables are embedded in the deployed code, so they must have a fixed size value
+        ns.diagnostics.push(Diagnostic::error(
+            def.ty.loc(),
+            format!(
+                "immutable variable cannot be of type '{}'",
+                ty.to_string(ns)
+            ),
+        ));
+    }
+
     let visibility = match visibility {
         Some(v) => v,
         None => pt::Visibility::Internal(Some(def.ty.loc())),
@@ -104,12 +104,13 @@ pub enum YulBuiltInFunction {
     Number = 73,
     Difficulty = 74,
     GasLimit = 75,
+    LoadImmutable = 76,
+    SetImmutable = 77,
 }
 
 // These are functions that do high level stuff in a contract and are not yet implemented.
 static UNSUPPORTED_BUILTINS: phf::Set<&'static str> = phf_set! {
-    "datasize", "dataoffset", "datacopy", "setimmutable", "loadimmutable",
-    "linkersymbol", "memoryguard"
+    "datasize", "dataoffset", "datacopy", "linkersymbol", "memoryguard"
 };
 
 /// Checks if bultin function is unsupported
@@ -194,6 +195,8 @@ static BUILTIN_YUL_FUNCTIONS: phf::Map<&'static str, YulBuiltInFunction> = phf_m
     "number" => YulBuiltInFunction::Number,
     "difficulty" => YulBuiltInFunction::Difficulty,
     "gaslimit" => YulBuiltInFunction::GasLimit,
+    "loadimmutable" => YulBuiltInFunction::LoadImmutable,
+    "setimmutable" => YulBuiltInFunction::SetImmutable,
 };
 
 /// Retrieved the builtin function type from an identifier name
@@ -223,6 +226,7 @@ impl YulBuiltInFunction {
                 | YulBuiltInFunction::DelegateCall
                 | YulBuiltInFunction::Create2
                 | YulBuiltInFunction::SelfDestruct
+                | YulBuiltInFunction::SetImmutable
         )
     }
 
@@ -250,6 +254,7 @@ impl YulBuiltInFunction {
                 | YulBuiltInFunction::GasLimit
                 | YulBuiltInFunction::StaticCall
                 | YulBuiltInFunction::SLoad
+                | YulBuiltInFunction::LoadImmutable
         )
     }
 }
@@ -263,7 +268,7 @@ impl ToString for YulBuiltInFunction {
 
 // Yul built-in functions.
 // Descriptions copied and slightly modified from: https://docs.soliditylang.org/en/v0.8.12/yul.html
-static YUL_BUILTIN: [YulBuiltinPrototype; 76] =
+static YUL_BUILTIN: [YulBuiltinPrototype; 78] =
     [
         YulBuiltinPrototype {
             name: "stop",
@@ -506,7 +511,7 @@ static YUL_BUILTIN: [YulBuiltinPrototype; 76] =
             doc: "keccak256(p, n) performs keccak(mem[p...(p+n)])",
             ty: YulBuiltInFunction::Keccak256,
             stops_execution: false,
-            availability: [false, false, false],
+            availability: [true, true, true],
         },
         YulBuiltinPrototype {
             name: "pc",
@@ -533,7 +538,7 @@ static YUL_BUILTIN: [YulBuiltinPrototype; 76] =
             doc: "mload(p) returns mem[p...(p+32)]",
             ty: YulBuiltInFunction::MLoad,
             stops_execution: false,
-            availability: [false, false, false],
+            availability: [true, true, true],
         },
         YulBuiltinPrototype {
             name: "mstore",
@@ -542,7 +547,7 @@ static YUL_BUILTIN: [YulBuiltinPrototype; 76] =
             doc: "mstore(p, v) stores v into mem[p...(p+32)]",
             ty: YulBuiltInFunction::MStore,
             stops_execution: false,
-            availability: [false, false, false],
+            availability: [true, true, true],
         },
         YulBuiltinPrototype {
             name: "mstore8",
@@ -551,7 +556,7 @@ static YUL_BUILTIN: [YulBuiltinPrototype; 76] =
             doc: "mstore8(p, v) stores (v & 0xff) into mem[p] (modified a single byte of v)",
             ty: YulBuiltInFunction::MStore8,
             stops_execution: false,
-            availability: [false, false, false],
+            availability: [true, true, true],
         },
         YulBuiltinPrototype {
             name: "sload",
@@ -560,7 +565,7 @@ static YUL_BUILTIN: [YulBuiltinPrototype; 76] =
             doc: "sload(p) returns storage[p], i.e. memory on contract's storage",
             ty: YulBuiltInFunction::SLoad,
             stops_execution: false,
-            availability: [false, false, false],
+            availability: [true, true, true],
         },
         YulBuiltinPrototype {
             name: "sstore",
@@ -569,7 +574,7 @@ static YUL_BUILTIN: [YulBuiltinPrototype; 76] =
             doc: "sstore(p) stores v into storage[p]",
             ty: YulBuiltInFunction::SStore,
             stops_execution: false,
-            availability: [false, false, false],
+            availability: [true, true, true],
         },
         YulBuiltinPrototype {
             name: "msize",
@@ -641,7 +646,7 @@ static YUL_BUILTIN: [YulBuiltinPrototype; 76] =
             doc: "calldataload(p) returns call data starting from position p (32 bytes)",
             ty: YulBuiltInFunction::CallDataLoad,
             stops_execution: false,
-            availability: [false, false, false],
+            availability: [true, true, true],
         },
         YulBuiltinPrototype {
             name: "calldatasize",
@@ -650,7 +655,7 @@ static YUL_BUILTIN: [YulBuiltinPrototype; 76] =
             doc: "Returns the size of call data in bytes",
             ty: YulBuiltInFunction::CallDataSize,
             stops_execution: false,
-            availability: [false, false, false],
+            availability: [true, true, true],
         },
         YulBuiltinPrototype {
             name: "calldatacopy",
@@ -659,7 +664,7 @@ static YUL_BUILTIN: [YulBuiltinPrototype; 76] =
             doc: "calldatacopy(t, f, s) copies s bytes from calldata at position f to mem at position t",
             ty: YulBuiltInFunction::CallDataCopy,
             stops_execution: false,
-            availability: [false, false, false],
+            availability: [true, true, true],
         },
         YulBuiltinPrototype {
             name: "codesize",
@@ -704,7 +709,7 @@ static YUL_BUILTIN: [YulBuiltinPrototype; 76] =
             doc: "Returns the size of the last returndata",
             ty: YulBuiltInFunction::ReturnDataSize,
             stops_execution: false,
-            availability: [false, false, false],
+            availability: [true, true, true],
         },
         YulBuiltinPrototype {
             name: "returndatacopy",
@@ -713,7 +718,7 @@ static YUL_BUILTIN: [YulBuiltinPrototype; 76] =
             doc: "returndatacopy(t, f, s) copy s bytes from return data at position f to mem at position t",
             ty: YulBuiltInFunction::ReturnDataCopy,
             stops_execution: false,
-            availability: [false, false, false],
+            availability: [true, true, true],
         },
         YulBuiltinPrototype {
             name: "extcodehash",
@@ -949,6 +954,24 @@ static YUL_BUILTIN: [YulBuiltinPrototype; 76] =
             stops_execution: false,
             availability: [true, false, false],
         },
+        YulBuiltinPrototype {
+            name: "loadimmutable",
+            no_args: 1,
+            no_returns: 1,
+            doc: "loadimmutable(\"name\") returns the value of the immutable variable name",
+            ty: YulBuiltInFunction::LoadImmutable,
+            stops_execution: false,
+            availability: [true, true, true],
+        },
+        YulBuiltinPrototype {
+            name: "setimmutable",
+            no_args: 3,
+            no_returns: 0,
+            doc: "setimmutable(offset, \"name\", v) assigns v to the immutable variable name. The offset is ignored",
+            ty: YulBuiltInFunction::SetImmutable,
+            stops_execution: false,
+            availability: [true, true, true],
+        },
     ];
 
 #[test]
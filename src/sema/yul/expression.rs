@@ -5,7 +5,9 @@ use crate::sema::diagnostics::Diagnostics;
 use crate::sema::expression::{unescape, ExprContext};
 use crate::sema::symtable::{Symtable, VariableUsage};
 use crate::sema::yul::ast::{YulExpression, YulSuffix};
-use crate::sema::yul::builtin::{parse_builtin_keyword, yul_unsupported_builtin};
+use crate::sema::yul::builtin::{
+    parse_builtin_keyword, yul_unsupported_builtin, YulBuiltInFunction,
+};
 use crate::sema::yul::functions::FunctionsTable;
 use crate::sema::yul::types::{
     get_default_type_from_identifier, get_type_from_string, verify_type_from_expression,
@@ -431,6 +433,31 @@ pub(crate) fn resolve_function_call(
             check_function_argument(&default_builtin_parameter, item, function_table, ns);
         }
 
+        match built_in {
+            YulBuiltInFunction::LoadImmutable => {
+                resolved_arguments[0] =
+                    resolve_immutable_name(&resolved_arguments[0], context, ns)?;
+            }
+            YulBuiltInFunction::SetImmutable => {
+                let in_constructor = match context.function_no {
+                    Some(function_no) => ns.functions[function_no].is_constructor(),
+                    None => false,
+                };
+
+                if !in_constructor {
+                    ns.diagnostics.push(Diagnostic::error(
+                        func_call.loc,
+                        "builtin 'setimmutable' can only be used in a constructor".to_string(),
+                    ));
+                    return Err(());
+                }
+
+                resolved_arguments[1] =
+                    resolve_immutable_name(&resolved_arguments[1], context, ns)?;
+            }
+            _ => (),
+        }
+
         return Ok(YulExpression::BuiltInCall(
             func_call.loc,
             *built_in,
@@ -475,6 +502,62 @@ pub(crate) fn resolve_function_call(
     Err(())
 }
 
+/// The name argument of loadimmutable and setimmutable is a string literal, which must name an
+/// immutable variable of the contract. It is replaced by a reference to the variable.
+fn resolve_immutable_name(
+    arg: &YulExpression,
+    context: &ExprContext,
+    ns: &mut Namespace,
+) -> Result<YulExpression, ()> {
+    let (loc, name) = match arg {
+        YulExpression::StringLiteral(loc, value, _) => (*loc, String::from_utf8_lossy(value)),
+        _ => {
+            ns.diagnostics.push(Diagnostic::error(
+                arg.loc(),
+                "the name of the immutable variable must be a string literal".to_string(),
+            ));
+            return Err(());
+        }
+    };
+
+    let id = Identifier {
+        loc,
+        name: name.to_string(),
+    };
+
+    if !context.yul_function {
+        if let Some(Symbol::Variable(_, Some(var_contract_no), var_no)) =
+            ns.resolve_var(context.file_no, context.contract_no, &id, false)
+        {
+            let var = &ns.contracts[*var_contract_no].variables[*var_no];
+
+            if var.immutable && matches!(var.ty, Type::InternalFunction { .. }) {
+                ns.diagnostics.push(Diagnostic::error(
+                    loc,
+                    format!(
+                        "immutable variable '{}' of function type cannot be accessed from assembly",
+                        id.name
+                    ),
+                ));
+                return Err(());
+            } else if var.immutable {
+                return Ok(YulExpression::StorageVariable(
+                    loc,
+                    var.ty.clone(),
+                    *var_contract_no,
+                    *var_no,
+                ));
+            }
+        }
+    }
+
+    ns.diagnostics.push(Diagnostic::error(
+        loc,
+        format!("'{}' is not an immutable variable", id.name),
+    ));
+    Err(())
+}
+
 /// Check if the provided argument is compatible with the declared parameters of a function.
 fn check_function_argument(
     parameter: &Parameter,
@@ -584,6 +667,21 @@ fn resolve_suffix_access(
             }
         }
 
+        YulExpression::SolidityLocalVariable(
+            _,
+            Type::Array(_, ref dims),
+            Some(StorageLocation::Memory(_)),
+            _,
+        ) if dims.last() == Some(&ArrayLength::Dynamic) => {
+            if id.name != "length" {
+                ns.diagnostics.push(Diagnostic::error(
+                    resolved_expr.loc(),
+                    "memory arrays only support '.length'".to_string(),
+                ));
+                return Err(());
+            }
+        }
+
         YulExpression::SolidityLocalVariable(_, Type::InternalFunction { .. }, ..)
         | YulExpression::ConstantVariable(_, Type::InternalFunction { .. }, ..)
         | YulExpression::StorageVariable(_, Type::InternalFunction { .. }, ..) => {
@@ -687,26 +785,19 @@ pub(crate) fn check_type(
             }
 
             YulExpression::SuffixAccess(_, member, YulSuffix::Length) => {
-                return if matches!(
+                if !matches!(
                     **member,
                     YulExpression::SolidityLocalVariable(
                         _,
                         _,
-                        Some(StorageLocation::Calldata(_)),
+                        Some(StorageLocation::Calldata(_) | StorageLocation::Memory(_)),
                         _
                     )
                 ) {
-                    Some(Diagnostic::error(
-                        expr.loc(),
-                        "assignment to length is not implemented. If there is need for this feature, please file a Github issue \
-                        at https://github.com/hyperledger-labs/solang/issues\
-                        ".to_string(),
-                    ))
-                } else {
-                    Some(Diagnostic::error(
+                    return Some(Diagnostic::error(
                         expr.loc(),
                         "this expression does not support the '.length' suffix".to_string(),
-                    ))
+                    ));
                 }
             }
 
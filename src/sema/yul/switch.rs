@@ -8,6 +8,7 @@ use crate::sema::yul::block::resolve_yul_block;
 use crate::sema::yul::expression::{check_type, resolve_yul_expression};
 use crate::sema::yul::functions::FunctionsTable;
 use crate::sema::yul::types::verify_type_from_expression;
+use num_bigint::{BigInt, Sign};
 use solang_parser::pt::{CodeLocation, YulSwitchOptions};
 use solang_parser::{diagnostics::Diagnostic, pt};
 
@@ -26,14 +27,14 @@ pub(crate) fn resolve_switch(
         resolve_condition(&yul_switch.condition, context, symtable, function_table, ns)?;
     let mut default_block: Option<YulBlock> = None;
     let mut case_blocks: Vec<CaseBlock> = Vec::with_capacity(yul_switch.cases.len());
-    let mut next_reachable = reachable;
+    let mut next_reachable = false;
     for item in &yul_switch.cases {
         let block_reachable = resolve_case_or_default(
             item,
             &mut default_block,
             &mut case_blocks,
             context,
-            next_reachable,
+            reachable,
             function_table,
             loop_scope,
             symtable,
@@ -42,6 +43,23 @@ pub(crate) fn resolve_switch(
         next_reachable |= block_reachable;
     }
 
+    for (case_no, case) in case_blocks.iter().enumerate() {
+        let value = case_value(&case.condition);
+
+        if let Some(prev) = case_blocks[..case_no]
+            .iter()
+            .find(|prev| case_value(&prev.condition) == value)
+        {
+            ns.diagnostics.push(Diagnostic::error_with_note(
+                case.condition.loc(),
+                format!("duplicate case for value '{}'", value),
+                prev.condition.loc(),
+                "previous case with same value".to_string(),
+            ));
+            return Err(());
+        }
+    }
+
     if yul_switch.default.is_some() && default_block.is_some() {
         ns.diagnostics.push(Diagnostic::error(
             yul_switch.default.as_ref().unwrap().loc(),
@@ -54,7 +72,7 @@ pub(crate) fn resolve_switch(
             &mut default_block,
             &mut case_blocks,
             context,
-            next_reachable,
+            reachable,
             function_table,
             loop_scope,
             symtable,
@@ -62,7 +80,8 @@ pub(crate) fn resolve_switch(
         )?;
         next_reachable |= block_reachable;
     } else if yul_switch.default.is_none() && default_block.is_none() {
-        next_reachable |= true;
+        // without a default block, execution continues after the switch if no case matches
+        next_reachable |= reachable;
     }
 
     Ok((
@@ -201,3 +220,13 @@ fn resolve_case_block(
         case_block.1,
     ))
 }
+
+/// The value a case literal is compared with
+fn case_value(condition: &YulExpression) -> BigInt {
+    match condition {
+        YulExpression::NumberLiteral(_, value, _) => value.clone(),
+        YulExpression::StringLiteral(_, value, _) => BigInt::from_bytes_be(Sign::Plus, value),
+        YulExpression::BoolLiteral(_, value, _) => BigInt::from(*value as u8),
+        _ => unreachable!("case condition must be a literal"),
+    }
+}
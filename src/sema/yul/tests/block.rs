@@ -186,7 +186,29 @@ contract testTypes {
     "#;
 
     let ns = parse(file);
-    assert!(ns.diagnostics.contains_message("switch statements have no implementation in code generation yet. Please, file a GitHub issue if there is urgent need for such a feature"));
+    assert!(ns.diagnostics.contains_message("builtin 'stop' is not available for target ewasm. Please, open a GitHub issue at https://github.com/hyperledger-labs/solang/issues if there is need to support this function"));
+
+    let file = r#"
+contract testTypes {
+    function testAsm() public {
+        assembly {
+            {
+                let a := 0
+
+                switch a
+                case 0 {invalid()}
+                case 1 {invalid()}
+                default {invalid()}
+
+                let b := shr(a, 1)
+            }
+        }
+    }
+}
+    "#;
+
+    let ns = parse(file);
+    assert!(ns.diagnostics.contains_message("unreachable yul statement"));
 
     let file = r#"
 contract testTypes {
@@ -212,9 +234,7 @@ contract testTypes {
     assert!(ns
         .diagnostics
         .contains_message("found contract 'testTypes'"));
-    assert!(ns
-        .diagnostics
-        .contains_message("switch statements have no implementation in code generation yet. Please, file a GitHub issue if there is urgent need for such a feature"));
+    assert!(!ns.diagnostics.contains_message("unreachable yul statement"));
 
     let file = r#"
     contract testTypes {
@@ -240,9 +260,7 @@ contract testTypes {
     assert!(ns
         .diagnostics
         .contains_message("found contract 'testTypes'"));
-    assert!(ns
-        .diagnostics
-        .contains_message("switch statements have no implementation in code generation yet. Please, file a GitHub issue if there is urgent need for such a feature"));
+    assert!(!ns.diagnostics.contains_message("unreachable yul statement"));
 }
 
 #[test]
@@ -1036,10 +1036,51 @@ contract testTypes {
 }
     "#;
 
+    let ns = parse(file);
+    assert!(!ns.diagnostics.any_errors());
+
+    let file = r#"
+    contract testTypes {
+    function testAsm(uint[] memory vl) public pure {
+        assembly {
+            vl.length := sub(vl.length, 1)
+        }
+    }
+}
+    "#;
+
+    let ns = parse(file);
+    assert!(!ns.diagnostics.any_errors());
+
+    let file = r#"
+    contract testTypes {
+    function testAsm(uint[] memory vl) public pure {
+        assembly {
+            vl.offset := 2
+        }
+    }
+}
+    "#;
+
+    let ns = parse(file);
+    assert!(ns
+        .diagnostics
+        .contains_message("memory arrays only support '.length'"));
+
+    let file = r#"
+    contract testTypes {
+    function testAsm(uint[2] memory vl) public pure {
+        assembly {
+            vl.length := 2
+        }
+    }
+}
+    "#;
+
     let ns = parse(file);
     assert!(ns
         .diagnostics
-        .contains_message("assignment to length is not implemented. If there is need for this feature, please file a Github issue at https://github.com/hyperledger-labs/solang/issues"));
+        .contains_message("the given expression does not support '.length' suffixes"));
 
     let file = r#"
 contract testTypes {
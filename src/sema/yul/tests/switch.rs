@@ -74,7 +74,6 @@ contract testTypes {
 
 #[test]
 fn correct_switch() {
-    // TODO: switch statements are not yet implemented
     let file = r#"
 contract testTypes {
     function testAsm() public pure {
@@ -100,8 +99,30 @@ contract testTypes {
 }
     "#;
 
+    let ns = parse(file);
+    assert!(!ns.diagnostics.any_errors());
+}
+
+#[test]
+fn duplicate_case() {
+    let file = r#"
+contract testTypes {
+    function testAsm(uint256 x) public pure {
+        assembly {
+            switch x
+            case 0x61 {
+                x := 1
+            }
+            case "a" {
+                x := 2
+            }
+        }
+    }
+}
+    "#;
+
     let ns = parse(file);
     assert!(ns
         .diagnostics
-        .contains_message("switch statements have no implementation in code generation yet. Please, file a GitHub issue if there is urgent need for such a feature"));
+        .contains_message("duplicate case for value '97'"));
 }
@@ -31,3 +31,19 @@ fn create_output_dir() {
     .assert()
     .failure();
 }
+
+#[test]
+fn anchor_idl_only_on_solana() {
+    let mut cmd = Command::cargo_bin("solang").unwrap();
+
+    cmd.args(&[
+        "examples/flipper.sol",
+        "--target",
+        "substrate",
+        "--anchor-idl",
+        "--output",
+        "tests/create_me",
+    ])
+    .assert()
+    .failure();
+}
@@ -35,11 +35,11 @@ contract Array_bound_Test {
         bool[] b = new bool[](210);
 
         if (cond) {
-            // CHECK: ty:uint32 %array_length.temp.39 = uint32 211
+            // CHECK: ty:uint32 %array_length.temp.38 = uint32 211
             b.push(true);
         }
 
-        // CHECK: return %array_length.temp.39
+        // CHECK: return %array_length.temp.38
         return b.length;
     }
 
@@ -50,8 +50,8 @@ contract Array_bound_Test {
         uint256 sesa = 0;
 
 
-        // CHECK: branchcond (uint32 20 >= uint32 21), block5, block6
-        // CHECK: branchcond (unsigned less %i < uint256 21), block1, block4
+        // CHECK: branchcond (uint32 20 >= uint32 21), block4, block5
+        // CHECK: branchcond (unsigned less %i < uint64 21), block1, block3
         for (uint256 i = 0; i < a.length; i++) {
             sesa = sesa + a[20];
         }
@@ -79,14 +79,14 @@ contract Array_bound_Test {
         int256[] vec = new int256[](10);
 
         for (int256 i = 0; i < 5; i++) {
-            // CHECK: branchcond (unsigned more %array_length.temp.49 > uint32 20), block5, block6
+            // CHECK: branchcond (unsigned more %array_length.temp.48 > uint32 20), block4, block5
             if (vec.length > 20) {
                 break;
             }
             vec.push(3);
         }
 
-        // CHECK: branchcond (%array_length.temp.49 == uint32 15), block7, block8
+        // CHECK: branchcond (%array_length.temp.48 == uint32 15), block6, block7
         assert(vec.length == 15);
     }
 
@@ -95,27 +95,22 @@ contract Array_bound_Test {
         int32[] memory vec;
         vec = [a, b];
         // CHECK: ty:int32[] %vec = undef
-	    // CHECK: ty:uint32 %array_length.temp.52 = uint32 0
-	    // CHECK: ty:int32[] %temp.53 = (alloc int32[] len uint32 2)
-        // CHECK: ty:uint32 %array_length.temp.54 = uint32 2
-	    // CHECK: ty:int32[] %vec = %temp.53
+	    // CHECK: ty:int32[] %temp.52 = (alloc int32[] len uint32 2)
+	    // CHECK: ty:int32[] %vec = %temp.52
 
 
         vec.push(5);
-        // CHECK: ty:uint32 %array_length.temp.54 = uint32 3
         // CHECK: return uint32 3
         return vec.length;
     }
 
     // BEGIN-CHECK: Array_bound_Test::Array_bound_Test::function::testVec__uint32_uint32_uint32
     function testVec(uint32 a, uint32 b, uint32 c) public pure returns (uint32) {
-        // CHECK: ty:uint32[] %temp.56 = (alloc uint32[] len uint32 3)
-        // CHECK: ty:uint32 %array_length.temp.57 = uint32 3
+        // CHECK: ty:uint32[] %temp.55 = (alloc uint32[] len uint32 3)
         uint32[] memory vec = [a, b, b];
-        // CHECK: ty:uint32[] %vec = %temp.56
+        // CHECK: ty:uint32[] %vec = %temp.55
 
         vec.pop();
-        // CHECK: ty:uint32 %array_length.temp.57 = uint32 2
         // CHECK: return uint32 2
         return vec.length;
     }
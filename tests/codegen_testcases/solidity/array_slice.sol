@@ -0,0 +1,29 @@
+// RUN: --target substrate --emit cfg
+
+contract c {
+    // BEGIN-CHECK: c::c::function::tail__bytes
+    function tail(bytes calldata data) public returns (bytes memory) {
+        // CHECK: branchcond (unsigned more uint32 4 > (builtin ArrayLength ((arg #0)))), block1, block2
+        // CHECK: block1: # slice_out_of_bounds
+        // CHECK: assert-failure
+        // CHECK: block2: # slice_in_bounds
+        // CHECK: return bytes((slice bytes1 slice (arg #0)[uint32 4:(builtin ArrayLength ((arg #0)))]))
+        return data[4:];
+    }
+
+    // BEGIN-CHECK: c::c::function::window__uint32:_uint64_uint32
+    function window(uint32[] memory a, uint64 s, uint32 e) public returns (uint32) {
+        // CHECK: > (zext uint64 (builtin ArrayLength ((arg #0))))), block1, block2
+        // CHECK: block2: # slice_end_in_bounds
+        // CHECK: branchcond (unsigned more (arg #1) > %slice_end.temp.
+        // CHECK: block3: # slice_in_bounds
+        // CHECK: return (builtin ArrayLength ((slice uint32 slice (arg #0)[(trunc uint32 (arg #1)):(trunc uint32 (zext uint64 (arg #2)))])))
+        return a[s:e].length;
+    }
+
+    // BEGIN-CHECK: c::c::function::all__bytes
+    function all(bytes calldata data) public returns (bytes memory) {
+        // CHECK: return bytes((slice bytes1 slice (arg #0)[uint32 0:(builtin ArrayLength ((arg #0)))]))
+        return data[:];
+    }
+}
@@ -18,39 +18,33 @@ contract EncodingTest {
 	    // CHECK: branch block1
 
         // CHECK: block1: # cond
-        // CHECK: branchcond (unsigned less %for_i_0.temp.9 < (builtin ArrayLength (%temp.7))), block3, block4
+        // CHECK: branchcond (unsigned less %for_i_0.temp.9 < (builtin ArrayLength (%temp.7))), block2, block3
 
-        // CHECK: block2: # next
+        // CHECK: block2: # body
+        // CHECK: ty:uint32 %array_bytes_size_0.temp.8 = (%array_bytes_size_0.temp.8 + ((builtin ArrayLength ((load (subscript string[] %temp.7[%for_i_0.temp.9])))) + uint32 4))
 	    // CHECK: ty:uint32 %for_i_0.temp.9 = (%for_i_0.temp.9 + uint32 1)
 	    // CHECK: branch block1
 
-        // CHECK: block3: # body
-        // CHECK: ty:uint32 %array_bytes_size_0.temp.8 = (%array_bytes_size_0.temp.8 + ((builtin ArrayLength ((load (subscript string[] %temp.7[%for_i_0.temp.9])))) + uint32 4))
-	    // CHECK: branch block2
-
-        // CHECK: block4: # end_for
+        // CHECK: block3: # end_for
         // CHECK: ty:bytes %abi_encoded.temp.10 = (alloc bytes len %array_bytes_size_0.temp.8)
 		// CHECK: ty:uint32 %temp.11 = uint32 0
 	    // CHECK: writebuffer buffer:%abi_encoded.temp.10 offset:%temp.11 value:(builtin ArrayLength (%temp.7))
 	    // CHECK: ty:uint32 %temp.11 = uint32 4
 	    // CHECK: ty:uint32 %for_i_0.temp.12 = uint32 0
-	    // CHECK: branch block5
+	    // CHECK: branch block4
 
-        // CHECK: block5: # cond
-	    // CHECK: branchcond (unsigned less %for_i_0.temp.12 < (builtin ArrayLength (%temp.7))), block7, block8
-
-        // CHECK: block6: # next
-	    // CHECK: ty:uint32 %for_i_0.temp.12 = (%for_i_0.temp.12 + uint32 1)
-	    // CHECK: branch block5
+        // CHECK: block4: # cond
+	    // CHECK: branchcond (unsigned less %for_i_0.temp.12 < (builtin ArrayLength (%temp.7))), block5, block6
 
-        // CHECK: block7: # body
+        // CHECK: block5: # body
 	    // CHECK: ty:uint32 %temp.13 = (builtin ArrayLength ((load (subscript string[] %temp.7[%for_i_0.temp.12]))))
 	    // CHECK: writebuffer buffer:%abi_encoded.temp.10 offset:%temp.11 value:%temp.13
 	    // CHECK: memcpy src: (load (subscript string[] %temp.7[%for_i_0.temp.12])), dest: (advance ptr: %abi_encoded.temp.10, by: (%temp.11 + uint32 4)), bytes_len: %temp.13
 	    // CHECK: ty:uint32 %temp.11 = ((%temp.13 + uint32 4) + %temp.11)
-	    // CHECK: branch block6   
+	    // CHECK: ty:uint32 %for_i_0.temp.12 = (%for_i_0.temp.12 + uint32 1)
+	    // CHECK: branch block4
 
-        // CHECK: block8: # end_for
+        // CHECK: block6: # end_for
 	    // CHECK: ty:uint32 %temp.11 = (%temp.11 - uint32 0)
 	    // CHECK: ty:bytes %b = %abi_encoded.temp.10
         return b;
@@ -66,40 +60,34 @@ contract EncodingTest {
 	    // CHECK: branch block1
 
         // CHECK: block1: # cond
-	    // CHECK: branchcond (unsigned less %for_i_0.temp.16 < (builtin ArrayLength ((load (struct %cte field 1))))), block3, block4
+	    // CHECK: branchcond (unsigned less %for_i_0.temp.16 < (builtin ArrayLength ((load (struct %cte field 1))))), block2, block3
 
-        // CHECK: block2: # next
+        // CHECK: block2: # body
+	    // CHECK: ty:uint32 %array_bytes_size_0.temp.15 = (%array_bytes_size_0.temp.15 + ((builtin ArrayLength ((load (subscript string[] (load (struct %cte field 1))[%for_i_0.temp.16])))) + uint32 4))
 	    // CHECK: ty:uint32 %for_i_0.temp.16 = (%for_i_0.temp.16 + uint32 1)
 	    // CHECK: branch block1
 
-        // CHECK: block3: # body
-	    // CHECK: ty:uint32 %array_bytes_size_0.temp.15 = (%array_bytes_size_0.temp.15 + ((builtin ArrayLength ((load (subscript string[] (load (struct %cte field 1))[%for_i_0.temp.16])))) + uint32 4))
-	    // CHECK: branch block2
-
-        // CHECK: block4: # end_for
+        // CHECK: block3: # end_for
 	    // CHECK: ty:bytes %abi_encoded.temp.17 = (alloc bytes len (uint32 8 + %array_bytes_size_0.temp.15))
 	    // CHECK: writebuffer buffer:%abi_encoded.temp.17 offset:uint32 0 value:(load (struct %cte field 0))
 		// CHECK: ty:uint32 %temp.18 = uint32 8
 	    // CHECK: writebuffer buffer:%abi_encoded.temp.17 offset:%temp.18 value:(builtin ArrayLength ((load (struct %cte field 1))))
 	    // CHECK: ty:uint32 %temp.18 = uint32 12
 	    // CHECK: ty:uint32 %for_i_0.temp.19 = uint32 0
-	    // CHECK: branch block5
+	    // CHECK: branch block4
 
-        // CHECK: block5: # cond
-	    // CHECK: branchcond (unsigned less %for_i_0.temp.19 < (builtin ArrayLength ((load (struct %cte field 1))))), block7, block8
+        // CHECK: block4: # cond
+	    // CHECK: branchcond (unsigned less %for_i_0.temp.19 < (builtin ArrayLength ((load (struct %cte field 1))))), block5, block6
 
-        // CHECK: block6: # next
-	    // CHECK: ty:uint32 %for_i_0.temp.19 = (%for_i_0.temp.19 + uint32 1)
-	    // CHECK: branch block5
-
-        // CHECK: block7: # body
+        // CHECK: block5: # body
 	    // CHECK: ty:uint32 %temp.20 = (builtin ArrayLength ((load (subscript string[] (load (struct %cte field 1))[%for_i_0.temp.19]))))
 	    // CHECK: writebuffer buffer:%abi_encoded.temp.17 offset:%temp.18 value:%temp.20
 	    // CHECK: memcpy src: (load (subscript string[] (load (struct %cte field 1))[%for_i_0.temp.19])), dest: (advance ptr: %abi_encoded.temp.17, by: (%temp.18 + uint32 4)), bytes_len: %temp.20
 	    // CHECK: ty:uint32 %temp.18 = ((%temp.20 + uint32 4) + %temp.18)
-	    // CHECK: branch block6
+	    // CHECK: ty:uint32 %for_i_0.temp.19 = (%for_i_0.temp.19 + uint32 1)
+	    // CHECK: branch block4
 
-        // CHECK: block8: # end_for
+        // CHECK: block6: # end_for
 	    // CHECK: ty:uint32 %temp.18 = (%temp.18 - uint32 8)
 	    // CHECK: ty:bytes %b = %abi_encoded.temp.17
 
@@ -116,77 +104,65 @@ contract EncodingTest {
 	    // CHECK: branch block1
 
         // CHECK: block1: # cond
-	    // CHECK: branchcond (unsigned less %for_i_0.temp.23 < (builtin ArrayLength (%temp.21))), block3, block4
-
-        // CHECK: block2: # next
-	    // CHECK: ty:uint32 %for_i_0.temp.23 = (%for_i_0.temp.23 + uint32 1)
-	    // CHECK: branch block1
+	    // CHECK: branchcond (unsigned less %for_i_0.temp.23 < (builtin ArrayLength (%temp.21))), block2, block3
 
-        // CHECK: block3: # body
+        // CHECK: block2: # body
 	    // CHECK: ty:uint32 %array_bytes_size_0.temp.24 = uint32 0
 	    // CHECK: ty:uint32 %for_i_0.temp.25 = uint32 0
-	    // CHECK: branch block5
+	    // CHECK: branch block4
 
-        // CHECK: block4: # end_for
+        // CHECK: block3: # end_for
 	    // CHECK: ty:bytes %abi_encoded.temp.26 = (alloc bytes len %array_bytes_size_0.temp.22)
 		// CHECK: ty:uint32 %temp.27 = uint32 0
 	    // CHECK: writebuffer buffer:%abi_encoded.temp.26 offset:%temp.27 value:(builtin ArrayLength (%temp.21))
 	    // CHECK: ty:uint32 %temp.27 = uint32 4
 	    // CHECK: ty:uint32 %for_i_0.temp.28 = uint32 0	
-        // CHECK: branch block9
+        // CHECK: branch block7
 
-        // CHECK: block5: # cond
-	    // CHECK: branchcond (unsigned less %for_i_0.temp.25 < (builtin ArrayLength ((load (struct (subscript struct EncodingTest.NonConstantStruct[] %temp.21[%for_i_0.temp.23]) field 1))))), block7, block8
-
-        // CHECK: block6: # next
-	    // CHECK: ty:uint32 %for_i_0.temp.25 = (%for_i_0.temp.25 + uint32 1)
-	    // CHECK: branch block5
+        // CHECK: block4: # cond
+	    // CHECK: branchcond (unsigned less %for_i_0.temp.25 < (builtin ArrayLength ((load (struct (subscript struct EncodingTest.NonConstantStruct[] %temp.21[%for_i_0.temp.23]) field 1))))), block5, block6
 
-        // CHECK: block7: # body
+        // CHECK: block5: # body
 	    // CHECK: ty:uint32 %array_bytes_size_0.temp.24 = (%array_bytes_size_0.temp.24 + ((builtin ArrayLength ((load (subscript string[] (load (struct (subscript struct EncodingTest.NonConstantStruct[] %temp.21[%for_i_0.temp.23]) field 1))[%for_i_0.temp.25])))) + uint32 4))
-	    // CHECK: branch block6
+	    // CHECK: ty:uint32 %for_i_0.temp.25 = (%for_i_0.temp.25 + uint32 1)
+	    // CHECK: branch block4
 
-        // CHECK: block8: # end_for
+        // CHECK: block6: # end_for
 	    // CHECK: ty:uint32 %array_bytes_size_0.temp.22 = (%array_bytes_size_0.temp.22 + (uint32 8 + %array_bytes_size_0.temp.24))
-	    // CHECK: branch block2
-
-        // CHECK: block9: # cond
-	    // CHECK: branchcond (unsigned less %for_i_0.temp.28 < (builtin ArrayLength (%temp.21))), block11, block12
+	    // CHECK: ty:uint32 %for_i_0.temp.23 = (%for_i_0.temp.23 + uint32 1)
+	    // CHECK: branch block1
 
-        // CHECK: block10: # next
-	    // CHECK: ty:uint32 %for_i_0.temp.28 = (%for_i_0.temp.28 + uint32 1)
-	    // CHECK: branch block9
+        // CHECK: block7: # cond
+	    // CHECK: branchcond (unsigned less %for_i_0.temp.28 < (builtin ArrayLength (%temp.21))), block8, block9
 
-        // CHECK: block11: # body
+        // CHECK: block8: # body
 	    // CHECK: writebuffer buffer:%abi_encoded.temp.26 offset:%temp.27 value:(load (struct (subscript struct EncodingTest.NonConstantStruct[] %temp.21[%for_i_0.temp.28]) field 0))
 		// CHECK: ty:uint32 %temp.29 = (%temp.27 + uint32 8)
 	    // CHECK: writebuffer buffer:%abi_encoded.temp.26 offset:%temp.29 value:(builtin ArrayLength ((load (struct (subscript struct EncodingTest.NonConstantStruct[] %temp.21[%for_i_0.temp.28]) field 1))))
 	    // CHECK: ty:uint32 %temp.29 = (%temp.29 + uint32 4)
 	    // CHECK: ty:uint32 %for_i_0.temp.30 = uint32 0
-	    // CHECK: branch block13
+	    // CHECK: branch block10
 
-        // CHECK: block12: # end_for
+        // CHECK: block9: # end_for
 	    // CHECK: ty:uint32 %temp.27 = (%temp.27 - uint32 0)
 	    // CHECK: ty:bytes %b = %abi_encoded.temp.26
 
-        // CHECK: block13: # cond
-	    // CHECK: branchcond (unsigned less %for_i_0.temp.30 < (builtin ArrayLength ((load (struct (subscript struct EncodingTest.NonConstantStruct[] %temp.21[%for_i_0.temp.28]) field 1))))), block15, block16
+        // CHECK: block10: # cond
+	    // CHECK: branchcond (unsigned less %for_i_0.temp.30 < (builtin ArrayLength ((load (struct (subscript struct EncodingTest.NonConstantStruct[] %temp.21[%for_i_0.temp.28]) field 1))))), block11, block12
 
-        // CHECK: block14: # next
-	    // CHECK: ty:uint32 %for_i_0.temp.30 = (%for_i_0.temp.30 + uint32 1)
-	    // CHECK: branch block13
-
-        // CHECK: block15: # body
+        // CHECK: block11: # body
 	    // CHECK: ty:uint32 %temp.31 = (builtin ArrayLength ((load (subscript string[] (load (struct (subscript struct EncodingTest.NonConstantStruct[] %temp.21[%for_i_0.temp.28]) field 1))[%for_i_0.temp.30]))))
 	    // CHECK: writebuffer buffer:%abi_encoded.temp.26 offset:%temp.29 value:%temp.31
 	    // CHECK: memcpy src: (load (subscript string[] (load (struct (subscript struct EncodingTest.NonConstantStruct[] %temp.21[%for_i_0.temp.28]) field 1))[%for_i_0.temp.30])), dest: (advance ptr: %abi_encoded.temp.26, by: (%temp.29 + uint32 4)), bytes_len: %temp.31
 	    // CHECK: ty:uint32 %temp.29 = ((%temp.31 + uint32 4) + %temp.29)
-	    // CHECK: branch block14
+	    // CHECK: ty:uint32 %for_i_0.temp.30 = (%for_i_0.temp.30 + uint32 1)
+	    // CHECK: branch block10
 
-        // CHECK: block16: # end_for
+        // CHECK: block12: # end_for
 	    // CHECK: ty:uint32 %temp.29 = (%temp.29 - (%temp.27 + uint32 8))
 	    // CHECK: ty:uint32 %temp.27 = ((uint32 8 + %temp.29) + %temp.27)
-	    // CHECK: branch block10
+	    // CHECK: ty:uint32 %for_i_0.temp.28 = (%for_i_0.temp.28 + uint32 1)
+	    // CHECK: branch block7
 
         return b;
     }
@@ -0,0 +1,54 @@
+// RUN: --target solana --emit cfg --no-strength-reduce
+
+contract EncodingTest {
+    struct Order {
+        uint64 id;
+        uint64[] amounts;
+    }
+
+    // BEGIN-CHECK: EncodingTest::EncodingTest::function::encodeNested__int16::
+    function encodeNested(int16[][] memory vec) public pure returns (bytes memory) {
+        // CHECK: block2: # body
+        // CHECK: ty:uint32 %array_bytes_size_0.temp.9 = (%array_bytes_size_0.temp.9 + uint32 4)
+        // CHECK: block3: # end_for
+        // CHECK: ty:uint32 %array_bytes_size_0.temp.9 = (%array_bytes_size_0.temp.9 + uint32 4)
+        // CHECK: ty:bytes %abi_encoded.temp.12 = (alloc bytes len %array_bytes_size_0.temp.9)
+        // CHECK: writebuffer buffer:%abi_encoded.temp.12 offset:%temp.13 value:(builtin ArrayLength (%vec))
+        // CHECK: branchcond (unsigned less %for_i_0.temp.11 < (builtin ArrayLength ((load (subscript int16[][] (arg #0)[%for_i_1.temp.10]))))), block5, block6
+        // CHECK: block8: # body
+        // CHECK: writebuffer buffer:%abi_encoded.temp.12 offset:%temp.13 value:(builtin ArrayLength ((load (subscript int16[][] %vec[%for_i_1.temp.14]))))
+        // CHECK: ty:uint32 %temp.13 = (%temp.13 + uint32 4)
+        // CHECK: block11: # body
+        // CHECK: writebuffer buffer:%abi_encoded.temp.12 offset:%temp.13 value:(load (subscript int16[] (load (subscript int16[][] %vec[%for_i_1.temp.14]))[%for_i_0.temp.15]))
+        // CHECK: ty:uint32 %temp.13 = (uint32 2 + %temp.13)
+        bytes memory b = abi.encode(vec);
+        return b;
+    }
+
+    // BEGIN-CHECK: EncodingTest::EncodingTest::function::encodeMixed__int16:2::3
+    function encodeMixed(int16[2][][3] memory vec) public pure returns (bytes memory) {
+        // CHECK: branchcond (unsigned less %for_i_2.temp.17 < uint32 3), block2, block3
+        // CHECK: block2: # body
+        // CHECK: ty:uint32 %array_bytes_size_0.temp.16 = (%array_bytes_size_0.temp.16 + uint32 4)
+        // CHECK: block11: # body
+        // CHECK: writebuffer buffer:%abi_encoded.temp.20 offset:%temp.21 value:(builtin ArrayLength ((load (subscript int16[2][][3] %vec[%for_i_2.temp.22]))))
+        // CHECK: block17: # body
+        // CHECK: writebuffer buffer:%abi_encoded.temp.20 offset:%temp.21 value:(load (subscript int16[2] (subscript int16[2][] (load (subscript int16[2][][3] %vec[%for_i_2.temp.22]))[%for_i_1.temp.23])[%for_i_0.temp.24]))
+        bytes memory b = abi.encode(vec);
+        return b;
+    }
+
+    // BEGIN-CHECK: EncodingTest::EncodingTest::function::encodeOrders__EncodingTest.Order::
+    function encodeOrders(Order[][] memory orders) public pure returns (bytes memory) {
+        // CHECK: block5: # body
+        // CHECK: ty:uint32 %temp.28 = ((builtin ArrayLength ((load (struct (subscript struct EncodingTest.Order[] (load (subscript struct EncodingTest.Order[][] (arg #0)[%for_i_1.temp.26]))[%for_i_0.temp.27]) field 1)))) * uint32 8)
+        // CHECK: ty:uint32 %temp.28 = (%temp.28 + uint32 4)
+        // CHECK: ty:uint32 %array_bytes_size_0.temp.25 = (%array_bytes_size_0.temp.25 + (uint32 8 + %temp.28))
+        // CHECK: block11: # body
+        // CHECK: writebuffer buffer:%abi_encoded.temp.29 offset:%temp.30 value:(load (struct (subscript struct EncodingTest.Order[] (load (subscript struct EncodingTest.Order[][] %orders[%for_i_1.temp.31]))[%for_i_0.temp.32]) field 0))
+        // CHECK: memcpy src: (load (struct (subscript struct EncodingTest.Order[] (load (subscript struct EncodingTest.Order[][] %orders[%for_i_1.temp.31]))[%for_i_0.temp.32]) field 1)), dest: (advance ptr: %abi_encoded.temp.29, by: (%1.cse_temp + uint32 4)), bytes_len: %2.cse_temp
+        // CHECK: ty:uint32 %temp.30 = ((uint32 8 + (%2.cse_temp + uint32 4)) + %temp.30)
+        bytes memory b = abi.encode(orders);
+        return b;
+    }
+}
@@ -77,34 +77,34 @@ contract EncodingTest {
         bytes32 i = "coffe_is_tastier_than_tea";
  
         bytes memory k = abi.encode(a, b, c, d, p, q, r, s, t, e, f, g, h, i);
-        // CHECK: ty:bytes %abi_encoded.temp.70 = (alloc bytes len (((((((((((((uint32 1 + uint32 2) + uint32 3) + uint32 4) + uint32 16) + uint32 17) + uint32 18) + uint32 19) + uint32 20) + uint32 28) + uint32 29) + uint32 30) + uint32 31) + uint32 32))
-        // CHECK: writebuffer buffer:%abi_encoded.temp.70 offset:uint32 0 value:%a
+        // CHECK: ty:bytes %abi_encoded.temp.56 = (alloc bytes len (((((((((((((uint32 1 + uint32 2) + uint32 3) + uint32 4) + uint32 16) + uint32 17) + uint32 18) + uint32 19) + uint32 20) + uint32 28) + uint32 29) + uint32 30) + uint32 31) + uint32 32))
+        // CHECK: writebuffer buffer:%abi_encoded.temp.56 offset:uint32 0 value:%a
 	    // CHECK: ty:uint32 %1.cse_temp = (uint32 0 + uint32 1)
-	    // CHECK: writebuffer buffer:%abi_encoded.temp.70 offset:%1.cse_temp value:%b
+	    // CHECK: writebuffer buffer:%abi_encoded.temp.56 offset:%1.cse_temp value:%b
 	    // CHECK: ty:uint32 %2.cse_temp = (%1.cse_temp + uint32 2)
-	    // CHECK: writebuffer buffer:%abi_encoded.temp.70 offset:%2.cse_temp value:%c
+	    // CHECK: writebuffer buffer:%abi_encoded.temp.56 offset:%2.cse_temp value:%c
 	    // CHECK: ty:uint32 %3.cse_temp = (%2.cse_temp + uint32 3)
-	    // CHECK: writebuffer buffer:%abi_encoded.temp.70 offset:%3.cse_temp value:%d
+	    // CHECK: writebuffer buffer:%abi_encoded.temp.56 offset:%3.cse_temp value:%d
 	    // CHECK: ty:uint32 %4.cse_temp = (%3.cse_temp + uint32 4)
-	    // CHECK: writebuffer buffer:%abi_encoded.temp.70 offset:%4.cse_temp value:%p
+	    // CHECK: writebuffer buffer:%abi_encoded.temp.56 offset:%4.cse_temp value:%p
 	    // CHECK: ty:uint32 %5.cse_temp = (%4.cse_temp + uint32 16)
-	    // CHECK: writebuffer buffer:%abi_encoded.temp.70 offset:%5.cse_temp value:%q
+	    // CHECK: writebuffer buffer:%abi_encoded.temp.56 offset:%5.cse_temp value:%q
 	    // CHECK: ty:uint32 %6.cse_temp = (%5.cse_temp + uint32 17)
-	    // CHECK: writebuffer buffer:%abi_encoded.temp.70 offset:%6.cse_temp value:%r
+	    // CHECK: writebuffer buffer:%abi_encoded.temp.56 offset:%6.cse_temp value:%r
 	    // CHECK: ty:uint32 %7.cse_temp = (%6.cse_temp + uint32 18)
-	    // CHECK: writebuffer buffer:%abi_encoded.temp.70 offset:%7.cse_temp value:%s
+	    // CHECK: writebuffer buffer:%abi_encoded.temp.56 offset:%7.cse_temp value:%s
 	    // CHECK: ty:uint32 %8.cse_temp = (%7.cse_temp + uint32 19)
-	    // CHECK: writebuffer buffer:%abi_encoded.temp.70 offset:%8.cse_temp value:%t
+	    // CHECK: writebuffer buffer:%abi_encoded.temp.56 offset:%8.cse_temp value:%t
 	    // CHECK: ty:uint32 %9.cse_temp = (%8.cse_temp + uint32 20)
-	    // CHECK: writebuffer buffer:%abi_encoded.temp.70 offset:%9.cse_temp value:%e
+	    // CHECK: writebuffer buffer:%abi_encoded.temp.56 offset:%9.cse_temp value:%e
 	    // CHECK: ty:uint32 %10.cse_temp = (%9.cse_temp + uint32 28)
-	    // CHECK: writebuffer buffer:%abi_encoded.temp.70 offset:%10.cse_temp value:%f
+	    // CHECK: writebuffer buffer:%abi_encoded.temp.56 offset:%10.cse_temp value:%f
 	    // CHECK: ty:uint32 %11.cse_temp = (%10.cse_temp + uint32 29)
-	    // CHECK: writebuffer buffer:%abi_encoded.temp.70 offset:%11.cse_temp value:%g
+	    // CHECK: writebuffer buffer:%abi_encoded.temp.56 offset:%11.cse_temp value:%g
 	    // CHECK: ty:uint32 %12.cse_temp = (%11.cse_temp + uint32 30)
-	    // CHECK: writebuffer buffer:%abi_encoded.temp.70 offset:%12.cse_temp value:%h
-	    // CHECK: writebuffer buffer:%abi_encoded.temp.70 offset:(%12.cse_temp + uint32 31) value:%i
-	    // CHECK: ty:bytes %k = %abi_encoded.temp.70
+	    // CHECK: writebuffer buffer:%abi_encoded.temp.56 offset:%12.cse_temp value:%h
+	    // CHECK: writebuffer buffer:%abi_encoded.temp.56 offset:(%12.cse_temp + uint32 31) value:%i
+	    // CHECK: ty:bytes %k = %abi_encoded.temp.56
 
 
         return k;
@@ -115,15 +115,15 @@ contract EncodingTest {
         string memory a = "coffe_is_tastier_than_tea";
         bytes memory b = "who_said_tea_is_better?";
         bytes memory c = abi.encode(a, b);
-        // CHECK: ty:bytes %abi_encoded.temp.83 = (alloc bytes len (((builtin ArrayLength (%a)) + uint32 4) + ((builtin ArrayLength (%b)) + uint32 4)))
-        // CHECK: ty:uint32 %temp.84 = (builtin ArrayLength (%a))
-        // CHECK: writebuffer buffer:%abi_encoded.temp.83 offset:uint32 0 value:%temp.84
-        // CHECK: memcpy src: %a, dest: (advance ptr: %abi_encoded.temp.83, by: (uint32 0 + uint32 4)), bytes_len: %temp.84
-        // CHECK: ty:uint32 %temp.85 = (builtin ArrayLength (%b))
-        // CHECK: ty:uint32 %1.cse_temp = (uint32 0 + (%temp.84 + uint32 4))
-        // CHECK: writebuffer buffer:%abi_encoded.temp.83 offset:%1.cse_temp value:%temp.85
-        // CHECK: memcpy src: %b, dest: (advance ptr: %abi_encoded.temp.83, by: (%1.cse_temp + uint32 4)), bytes_len: %temp.85
-        // CHECK: ty:bytes %c = %abi_encoded.temp.83
+        // CHECK: ty:bytes %abi_encoded.temp.57 = (alloc bytes len (((builtin ArrayLength (%a)) + uint32 4) + ((builtin ArrayLength (%b)) + uint32 4)))
+        // CHECK: ty:uint32 %temp.58 = (builtin ArrayLength (%a))
+        // CHECK: writebuffer buffer:%abi_encoded.temp.57 offset:uint32 0 value:%temp.58
+        // CHECK: memcpy src: %a, dest: (advance ptr: %abi_encoded.temp.57, by: (uint32 0 + uint32 4)), bytes_len: %temp.58
+        // CHECK: ty:uint32 %temp.59 = (builtin ArrayLength (%b))
+        // CHECK: ty:uint32 %1.cse_temp = (uint32 0 + (%temp.58 + uint32 4))
+        // CHECK: writebuffer buffer:%abi_encoded.temp.57 offset:%1.cse_temp value:%temp.59
+        // CHECK: memcpy src: %b, dest: (advance ptr: %abi_encoded.temp.57, by: (%1.cse_temp + uint32 4)), bytes_len: %temp.59
+        // CHECK: ty:bytes %c = %abi_encoded.temp.57
         return c;
     }
 
@@ -136,12 +136,12 @@ contract EncodingTest {
         WeekDays[3] memory vec = [WeekDays.sunday, WeekDays.tuesday, WeekDays.friday];
         WeekDays elem = WeekDays.saturday;
         bytes memory b = abi.encode(WeekDays.sunday, elem, vec[2]);
-        // CHECK: ty:bytes %abi_encoded.temp.88 = (alloc bytes len ((uint32 1 + uint32 1) + uint32 1))
-        // CHECK: writebuffer buffer:%abi_encoded.temp.88 offset:uint32 0 value:enum EncodingTest.WeekDays 0
+        // CHECK: ty:bytes %abi_encoded.temp.61 = (alloc bytes len ((uint32 1 + uint32 1) + uint32 1))
+        // CHECK: writebuffer buffer:%abi_encoded.temp.61 offset:uint32 0 value:enum EncodingTest.WeekDays 0
         // CHECK: ty:uint32 %1.cse_temp = (uint32 0 + uint32 1)
-        // CHECK: writebuffer buffer:%abi_encoded.temp.88 offset:%1.cse_temp value:%elem
-        // CHECK: writebuffer buffer:%abi_encoded.temp.88 offset:(%1.cse_temp + uint32 1) value:(load (subscript enum EncodingTest.WeekDays[3] %vec[%index.temp.87]))
-        // CHECK: ty:bytes %b = %abi_encoded.temp.88
+        // CHECK: writebuffer buffer:%abi_encoded.temp.61 offset:%1.cse_temp value:%elem
+        // CHECK: writebuffer buffer:%abi_encoded.temp.61 offset:(%1.cse_temp + uint32 1) value:(load (subscript enum EncodingTest.WeekDays[3] %vec[%index.temp.60]))
+        // CHECK: ty:bytes %b = %abi_encoded.temp.61
         return b;
     }
 
@@ -161,15 +161,15 @@ contract EncodingTest {
     function encodeStruct() public view returns (bytes memory) {
         PaddedStruct memory ss = PaddedStruct(1, 3, "there_is_padding_here");
         bytes memory b = abi.encode(test_vec_1[2], ss);
-        // CHECK: %temp.91 = load storage slot((subscript struct EncodingTest.noPadStruct[] storage uint32 16[uint32 2])) ty:struct EncodingTest.noPadStruct
-        // CHECK: ty:bytes %abi_encoded.temp.92 = (alloc bytes len (uint32 8 + uint32 49))
-        // CHECK: memcpy src: %temp.91, dest: (advance ptr: %abi_encoded.temp.92, by: uint32 0), bytes_len: uint32 8
+        // CHECK: %temp.63 = load storage slot((subscript struct EncodingTest.noPadStruct[] storage uint32 16[uint32 2])) ty:struct EncodingTest.noPadStruct
+        // CHECK: ty:bytes %abi_encoded.temp.64 = (alloc bytes len (uint32 8 + uint32 49))
+        // CHECK: memcpy src: %temp.63, dest: (advance ptr: %abi_encoded.temp.64, by: uint32 0), bytes_len: uint32 8
         // CHECK: ty:uint32 %1.cse_temp = (uint32 0 + uint32 8)
-        // CHECK: writebuffer buffer:%abi_encoded.temp.92 offset:%1.cse_temp value:(load (struct %ss field 0))
+        // CHECK: writebuffer buffer:%abi_encoded.temp.64 offset:%1.cse_temp value:(load (struct %ss field 0))
         // CHECK: ty:uint32 %2.cse_temp = (%1.cse_temp + uint32 16)
-        // CHECK: writebuffer buffer:%abi_encoded.temp.92 offset:%2.cse_temp value:(load (struct %ss field 1))
-        // CHECK: writebuffer buffer:%abi_encoded.temp.92 offset:(%2.cse_temp + uint32 1) value:(load (struct %ss field 2))
-        // CHECK: ty:bytes %b = %abi_encoded.temp.92
+        // CHECK: writebuffer buffer:%abi_encoded.temp.64 offset:%2.cse_temp value:(load (struct %ss field 1))
+        // CHECK: writebuffer buffer:%abi_encoded.temp.64 offset:(%2.cse_temp + uint32 1) value:(load (struct %ss field 2))
+        // CHECK: ty:bytes %b = %abi_encoded.temp.64
         return b;
     }
 
@@ -178,54 +178,48 @@ contract EncodingTest {
         uint32[4] memory mem_vec = [uint32(1), 2, 3, 4];
         noPadStruct[2] memory str_vec = [noPadStruct(1,2), noPadStruct(3, 4)];
         bytes memory b1 = abi.encode(test_vec_1, mem_vec, str_vec);
-        // CHECK: %temp.95 = load storage slot(uint32 16) ty:struct EncodingTest.noPadStruct[]
-	    // CHECK: ty:uint32 %temp.96 = ((builtin ArrayLength (%temp.95)) * uint32 8)
-	    // CHECK: ty:uint32 %temp.96 = (%temp.96 + uint32 4)
-	    // CHECK: ty:uint32 %temp.97 = uint32 16
-	    // CHECK: ty:uint32 %temp.98 = uint32 16
-	    // CHECK: ty:bytes %abi_encoded.temp.99 = (alloc bytes len ((%temp.96 + %temp.97) + %temp.98))
-        // CHECK: ty:uint32 %temp.100 = uint32 0
-        // CHECK: writebuffer buffer:%abi_encoded.temp.99 offset:%temp.100 value:(builtin ArrayLength (%temp.95))
-
-        // CHECK: ty:uint32 %temp.100 = uint32 4
-        // CHECK: ty:uint32 %for_i_0.temp.101 = uint32 0
+        // CHECK: %temp.65 = load storage slot(uint32 16) ty:struct EncodingTest.noPadStruct[]
+	    // CHECK: ty:uint32 %temp.66 = ((builtin ArrayLength (%temp.65)) * uint32 8)
+	    // CHECK: ty:uint32 %temp.66 = (%temp.66 + uint32 4)
+	    // CHECK: ty:uint32 %temp.67 = uint32 16
+	    // CHECK: ty:uint32 %temp.68 = uint32 16
+	    // CHECK: ty:bytes %abi_encoded.temp.69 = (alloc bytes len ((%temp.66 + %temp.67) + %temp.68))
+        // CHECK: ty:uint32 %temp.70 = uint32 0
+        // CHECK: writebuffer buffer:%abi_encoded.temp.69 offset:%temp.70 value:(builtin ArrayLength (%temp.65))
+
+        // CHECK: ty:uint32 %temp.70 = uint32 4
+        // CHECK: ty:uint32 %for_i_0.temp.71 = uint32 0
         // CHECK: branch block1
 
         // CHECK: block1: # cond
-        // CHECK: branchcond (unsigned less %for_i_0.temp.101 < (builtin ArrayLength (%temp.95))), block3, block4
+        // CHECK: branchcond (unsigned less %for_i_0.temp.71 < (builtin ArrayLength (%temp.65))), block2, block3
 
-        // CHECK: block2: # next
-        // CHECK: ty:uint32 %for_i_0.temp.101 = (%for_i_0.temp.101 + uint32 1)
+        // CHECK: block2: # body
+        // CHECK: memcpy src: (subscript struct EncodingTest.noPadStruct[] %temp.65[%for_i_0.temp.71]), dest: (advance ptr: %abi_encoded.temp.69, by: %temp.70), bytes_len: uint32 8
+        // CHECK: ty:uint32 %temp.70 = (uint32 8 + %temp.70)
+        // CHECK: ty:uint32 %for_i_0.temp.71 = (%for_i_0.temp.71 + uint32 1)
         // CHECK: branch block1
-
-        // CHECK: block3: # body
-        // CHECK: memcpy src: (subscript struct EncodingTest.noPadStruct[] %temp.95[%for_i_0.temp.101]), dest: (advance ptr: %abi_encoded.temp.99, by: %temp.100), bytes_len: uint32 8
-        // CHECK: ty:uint32 %temp.100 = (uint32 8 + %temp.100)
-        // CHECK: branch block2
         
-        // CHECK: block4: # end_for
-        // CHECK: ty:uint32 %temp.100 = (%temp.100 - uint32 0)
-        // CHECK: memcpy src: %mem_vec, dest: (advance ptr: %abi_encoded.temp.99, by: (uint32 0 + %temp.100)), bytes_len: uint32 16
-        // CHECK: ty:uint32 %2.cse_temp = ((uint32 0 + %temp.100) + uint32 16)
-        // CHECK: ty:uint32 %temp.102 = %2.cse_temp
-        // CHECK: ty:uint32 %for_i_0.temp.103 = uint32 0
-        // CHECK: branch block5
-
-        // CHECK: block5: # cond
-        // CHECK: branchcond (unsigned less %for_i_0.temp.103 < uint32 2), block7, block8
-
-        // CHECK: block6: # next
-        // CHECK: ty:uint32 %for_i_0.temp.103 = (%for_i_0.temp.103 + uint32 1)
-        // CHECK: branch block5
-
-        // CHECK: block7: # body
-        // CHECK: memcpy src: (subscript struct EncodingTest.noPadStruct[2] %str_vec[%for_i_0.temp.103]), dest: (advance ptr: %abi_encoded.temp.99, by: %temp.102), bytes_len: uint32 8
-        // CHECK: ty:uint32 %temp.102 = (uint32 8 + %temp.102)
-        // CHECK: branch block6
-
-        // CHECK: block8: # end_for
-        // CHECK: ty:uint32 %temp.102 = (%temp.102 - %2.cse_temp)
-        // CHECK: ty:bytes %b1 = %abi_encoded.temp.99
+        // CHECK: block3: # end_for
+        // CHECK: ty:uint32 %temp.70 = (%temp.70 - uint32 0)
+        // CHECK: memcpy src: %mem_vec, dest: (advance ptr: %abi_encoded.temp.69, by: (uint32 0 + %temp.70)), bytes_len: uint32 16
+        // CHECK: ty:uint32 %2.cse_temp = ((uint32 0 + %temp.70) + uint32 16)
+        // CHECK: ty:uint32 %temp.72 = %2.cse_temp
+        // CHECK: ty:uint32 %for_i_0.temp.73 = uint32 0
+        // CHECK: branch block4
+
+        // CHECK: block4: # cond
+        // CHECK: branchcond (unsigned less %for_i_0.temp.73 < uint32 2), block5, block6
+
+        // CHECK: block5: # body
+        // CHECK: memcpy src: (subscript struct EncodingTest.noPadStruct[2] %str_vec[%for_i_0.temp.73]), dest: (advance ptr: %abi_encoded.temp.69, by: %temp.72), bytes_len: uint32 8
+        // CHECK: ty:uint32 %temp.72 = (uint32 8 + %temp.72)
+        // CHECK: ty:uint32 %for_i_0.temp.73 = (%for_i_0.temp.73 + uint32 1)
+        // CHECK: branch block4
+
+        // CHECK: block6: # end_for
+        // CHECK: ty:uint32 %temp.72 = (%temp.72 - %2.cse_temp)
+        // CHECK: ty:bytes %b1 = %abi_encoded.temp.69
         // CHECK: return %b1
         return b1;
     }
@@ -239,10 +233,10 @@ contract EncodingTest {
         function (int64, int64) external returns (int64) fPtr = this.doThis;
         uint64 pr = 9234;
 
-        // CHECK: ty:bytes %abi_encoded.temp.106 = (alloc bytes len (uint32 36 + uint32 8))
-        // CHECK: writebuffer buffer:%abi_encoded.temp.106 offset:uint32 0 value:(load (struct %fPtr field 0))
-        // CHECK: writebuffer buffer:%abi_encoded.temp.106 offset:(uint32 0 + uint32 4) value:(load (struct %fPtr field 1))
-        // CHECK: writebuffer buffer:%abi_encoded.temp.106 offset:(uint32 0 + uint32 36) value:%pr
+        // CHECK: ty:bytes %abi_encoded.temp.74 = (alloc bytes len (uint32 36 + uint32 8))
+        // CHECK: writebuffer buffer:%abi_encoded.temp.74 offset:uint32 0 value:(load (struct %fPtr field 0))
+        // CHECK: writebuffer buffer:%abi_encoded.temp.74 offset:(uint32 0 + uint32 4) value:(load (struct %fPtr field 1))
+        // CHECK: writebuffer buffer:%abi_encoded.temp.74 offset:(uint32 0 + uint32 36) value:%pr
 
         bytes memory b = abi.encode(fPtr, pr);
         return b;
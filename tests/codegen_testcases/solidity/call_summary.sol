@@ -0,0 +1,82 @@
+// RUN: --target substrate --emit cfg --no-dce
+contract test {
+    function scale(uint x, uint factor) private pure returns (uint) {
+        // factor is 100 at every call site
+        return x * factor;
+    }
+
+    // BEGIN-CHECK: test::test::function::f1__uint256
+    function f1(uint a) public pure returns (uint) {
+        // scale() is inlined at both call sites
+        return scale(a, 100) + scale(10, 100);
+    // CHECK: ty:uint256 %.temp.26 = ((arg #0) * uint256 100)
+    // CHECK: ty:uint256 %factor = uint256 100
+    // CHECK: return (%.temp.26 + uint256 1000)
+    }
+
+    function bounded(uint x) private pure returns (uint) {
+        // x is at most 255 at every call site, so 64 bit multiply will do
+        return x * 1000;
+    }
+
+    // BEGIN-CHECK: test::test::function::f2__uint8_uint16
+    function f2(uint8 a, uint16 b) public pure returns (uint) {
+        return bounded(a) + bounded(b & 255);
+    // CHECK: ty:uint256 %.temp.28 = (zext uint256 ((trunc uint64 %x.arg.51) * uint64 1000))
+    // CHECK: ty:uint256 %.temp.29 = (zext uint256 ((trunc uint64 %x.arg) * uint64 1000))
+    }
+
+    function small(bool b) private pure returns (uint) {
+        if (b) {
+            return 1;
+        }
+        return 2000;
+    }
+
+    // BEGIN-CHECK: test::test::function::f3__bool
+    function f3(bool b) public pure returns (uint) {
+        uint v = small(b);
+        // the return value of small() is known to fit
+        return v * 1000;
+    // CHECK: return (zext uint256 ((trunc uint64 %v) * uint64 1000))
+    }
+
+    function unknown(uint x) private pure returns (uint) {
+        // one of the call sites passes an unknown value
+        return x * 1000;
+    }
+
+    // BEGIN-CHECK: test::test::function::f4__uint256
+    function f4(uint a) public pure returns (uint) {
+        return unknown(a) + unknown(1);
+    // CHECK: ty:uint256 %.temp.31 = ((arg #0) * uint256 1000)
+    // CHECK: return (%.temp.31 + uint256 1000)
+    }
+
+    // BEGIN-CHECK: test::test::function::public_fn__uint256
+    function public_fn(uint x) public pure returns (uint) {
+        // public functions can be called from anywhere
+        return x * 1000;
+    // CHECK: return ((arg #0) * uint256 1000)
+    }
+
+    // BEGIN-CHECK: test::test::function::f5
+    function f5() public pure returns (uint) {
+        return public_fn(1);
+    }
+
+    // BEGIN-CHECK: test::test::function::pointer__uint256
+    function pointer(uint x) private pure returns (uint) {
+        // this function is called through a function pointer too
+        return x * 1000;
+    // CHECK: return ((arg #0) * uint256 1000)
+    }
+
+    // BEGIN-CHECK: test::test::function::f6
+    function f6() public pure returns (uint) {
+        function (uint) internal pure returns (uint) p = pointer;
+
+        return pointer(1) + p(2);
+    // CHECK: return (uint256 1000 + %.temp.35)
+    }
+}
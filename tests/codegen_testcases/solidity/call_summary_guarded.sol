@@ -0,0 +1,20 @@
+// RUN: --target substrate --emit cfg --no-inline
+contract test {
+    // BEGIN-CHECK: test::test::function::ratio__uint256_uint256
+    function ratio(uint a, uint b) private pure returns (uint) {
+        // b is 0 at every call site, so the division is never reached and
+        // must not be reported as a division by zero
+        if (b != 0) {
+            return a / b;
+        }
+        return 0;
+    // CHECK: branchcond !(uint256 0 == uint256 0), block1, block2
+    // CHECK: return (unsigned divide (arg #0) / uint256 0)
+    }
+
+    // BEGIN-CHECK: test::test::function::f__uint256
+    function f(uint x) public pure returns (uint) {
+        return ratio(x, 0);
+    // CHECK: call test::test::function::ratio__uint256_uint256 (arg #0), uint256 0
+    }
+}
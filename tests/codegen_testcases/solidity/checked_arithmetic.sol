@@ -0,0 +1,17 @@
+// RUN: --target substrate --emit cfg
+pragma solidity ^0.8.0;
+
+contract c {
+	function checked() public pure returns (uint8) {
+		uint8 x = 255;
+		return x + 1;
+// CHECK: arithmetic overflow: 256 does not fit into type 'uint8', this will revert at runtime
+	}
+
+	function wraps() public pure returns (uint8) {
+		uint8 x = 255;
+		unchecked {
+			return x + 1;
+		}
+	}
+}
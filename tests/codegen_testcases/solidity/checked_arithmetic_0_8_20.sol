@@ -0,0 +1,18 @@
+// RUN: --target substrate --emit cfg
+pragma solidity ^0.8.20;
+
+// arithmetic is checked by default for any 0.8 release
+contract c {
+	function checked() public pure returns (uint8) {
+		uint8 x = 0;
+		return x - 1;
+// CHECK: arithmetic overflow: -1 does not fit into type 'uint8', this will revert at runtime
+	}
+
+	function wraps() public pure returns (uint8) {
+		uint8 x = 0;
+		unchecked {
+			return x - 1;
+		}
+	}
+}
@@ -14,14 +14,10 @@ contract c1 {
 
         // CHECK: ty:int256 %2.cse_temp = ((arg #0) - (arg #1))
         if (x + d > 0) {
-            // NOT-CHECK: ty:int256 %t = ((arg #0) - (arg #1))
-            // CHECK: ty:int256 %t = %2.cse_temp
 			int t = a-b;
 			bool e1 = t>3;
 		}
 		 else {
-            // NOT-CHECK: ty:int256 %e = ((arg #0) - (arg #1))
-            // CHECK: ty:int256 %e = %2.cse_temp
             int e = a-b;
             bool e2 = e > 3;
         }
@@ -43,17 +39,14 @@ contract c1 {
         // CEHCK: branchcond (signed more %2.cse_temp > int256 0), block1, block2
         if (x + d > 0) {
 			int t = a-b;
-            // CHECK: ty:int256 %t = %3.cse_temp
 			bool e1 = t>3;
         // CHECK: return ((%x - %d) + %3.cse_temp)
 		}
 		 else if (x+d < 0) {
             int e = a-b;
-            // CHECK: ty:int256 %e = %3.cse_temp
             bool e2 = e > 3;
         } else {
             int k = a-b;
-            // CHECK: ty:int256 %k = %3.cse_temp
             bool e3 = k < 4;
         }
 
@@ -69,19 +62,16 @@ contract c1 {
 
         // CHECK: branchcond (signed more %2.cse_temp > int256 0), block1, block2
         if (x + d > 0) {
-            // CHECK: ty:int256 %t = ((arg #0) - (arg #1))
 			int t = a-b;
 			bool e1 = t>3;
             // CHECK: branchcond (signed less %2.cse_temp < int256 0), block4, block5
             // CHECK: return ((%x - %d) + ((arg #0) - (arg #1)))
 		}
 		 else if (x+d < 0) {
-            // CHECK: ty:int256 %e = ((arg #0) - (arg #1))
             int e = a-b;
             bool e2 = e > 3;
             // CHECK: branchcond (%2.cse_temp == int256 0), block7, block8
         } else if (x + d == 0){
-            // CHECK: ty:int256 %k = %1.cse_temp
             int k = a+b;
             bool e3 = k < 4;
         }
@@ -101,17 +91,18 @@ contract c1 {
 	    // CHECK: ty:int256 %d = (%1.cse_temp * %2.cse_temp)
 	    // CHECK: ty:int256 %p = (%1.cse_temp + %d)
 
+        // a-b is loop invariant, so it is calculated before the loop
+        // CHECK: ty:int256 %temp.251 = ((arg #0) - (arg #1))
         // CHECK: ty:int256 %3.cse_temp = (%x + %d)
         // CHECK: branchcond (signed more %3.cse_temp > int256 0), block2, block3
         while (x+d > 0) {
-            // CHECK: ty:int256 %t = ((arg #0) - (arg #1))
             int t = a-b;
             bool e1 = t > 3;
             // CHECK: ty:int256 %x = %3.cse_temp
 			x = x+d;
         }
 
-        // CHECK: return (((%x - %d) + ((arg #0) - (arg #1))) - %p)
+        // CHECK: return (((%x - %d) + %temp.251) - %p)
         return x-d + (a-b) - p;
     }
 
@@ -121,14 +112,14 @@ contract c1 {
         x = a+b-54;
         int d = x*(a+b);
 
+        // CHECK: ty:int256 %temp.255 = ((arg #0) - (arg #1))
        	for(int i=0; i<10; i++) {
-            // CHECK: ty:int256 %t = ((arg #0) - (arg #1))
 			int t = a-b;
-            // CHECK: ty:int256 %i = (%temp.187 + int256 1)
+            // CHECK: ty:int64 %i = (%temp.177 + int64 1)
 			bool e1 = t > 3;
 		}
 
-// CHECK: return ((%x - %d) + ((arg #0) - (arg #1)))
+// CHECK: return ((%x - %d) + %temp.255)
         return x-d + (a-b);
     }
 
@@ -138,6 +129,7 @@ contract c1 {
         x = a+b-54;
         int d = x*(a+b);
 
+        // CHECK: ty:int256 %temp.257 = ((arg #0) - (arg #1))
        	do {
 			int t = a-b;
 			bool e1 = t > 3;
@@ -148,7 +140,7 @@ contract c1 {
         int t = 3;
         bool p = t < 2;
 
-        // CHECK: return ((%x - %d) + %t
+        // CHECK: return ((%x - %d) + %temp.257)
         return x-d + (a-b);
     }
 
@@ -165,21 +157,17 @@ contract c1 {
         // CEHCK: branchcond (%2.cse_temp > int256 0), block1, block2
         if (x + d > 0) {
 			int t = a-b;
-            // CHECK: ty:int256 %t = %3.cse_temp
 			bool e1 = t>3;
         // CHECK: return ((%x - %d) + %3.cse_temp)
 		}
 		 else if (x+d < 0) {
             int e = a-b;
-            // CHECK: ty:int256 %e = %3.cse_temp
             bool e2 = e > 3;
         } else if (x+d == 0){
             int k = a-b;
-            // CHECK: ty:int256 %k = %3.cse_temp
             bool e3 = k < 4;
         } else {
             int k1 = a-b;
-            // CHECK: ty:int256 %k1 = %3.cse_temp
             bool e4 = k1 < 4;
         }
 
@@ -194,7 +182,6 @@ contract c1 {
         int x = a + b +k;
         // CHECK: ty:int256 %x = (%1.cse_temp + %temp.
         if(x  + k < 0) {
-            // CHECK: ty:uint256 %p = uint256((%1.cse_temp + %temp.
             uint p = uint(a+b+k);
             bool e = p > 50;
         }
@@ -229,7 +216,6 @@ contract c1 {
         // CHECK: ty:int256 %2.cse_temp = ((arg #0) * (arg #1))
         // CHECK: branchcond (signed less (%x + int256((load (struct %instance field 1)))) < int256 0)
         if(x  + int(instance.b) < 0) {
-            // CHECK: ty:uint256 %p = uint256((%1.cse_temp + (load (struct %instance field 0))))
             uint p = uint(a+b+instance.a);
             bool e = p > 50;
         }
@@ -244,7 +230,6 @@ contract c1 {
         // CHECK: branchcond %e3, block3, block4
         if (trunc2 < trunc && trunc > 2) {
             // CHECK: = %e2
-            // CHECK: ty:int256 %p2 = %1.cse_temp
             int p2 = a+b;
             int p3 = p2 - x + a + b;
             int p4 = p2-x;
@@ -253,14 +238,11 @@ contract c1 {
 
             // CHECK: return %2.cse_temp
             if (p5 !=0) {
-                // CHECK: ty:uint16 %t1 = (trunc uint16 %p5)
                 uint16 t1 = uint16(p3 + a*b +45);
-                // CHECK: ty:uint32 %t2 = (trunc uint32 %2.cse_temp)
                 uint32 t2 = uint32(a*b);
                 bool e5 = t2 < t1;
             }
 
-            // CHECK: ty:int256 %ret = %p5
             ret = p3 + a*b + 45;
         }
 
@@ -275,6 +257,7 @@ contract c1 {
         int k = x+d;
         bool e = k < 0;
 
+        // CHECK: ty:int256 %temp.268 = ((arg #0) - (arg #1))
        	do {
 			int t = a-b;
 			bool e1 = t > 3;
@@ -285,7 +268,7 @@ contract c1 {
         int t = 3;
         bool p = t < 2;
 
-        // CHECK: return ((%x - %d) + %t
+        // CHECK: return ((%x - %d) + %temp.268)
         return x-d + (a-b);
     }
 
@@ -300,33 +283,34 @@ contract c1 {
         string ast = "Hello!";
         string bst = "from Solang";
         string cst = ast + bst;
-        // CHECK: ty:int256 %1.cse_temp = (signed divide (arg #0) / (int256 2 * (arg #1)))
-        // CHECK: call c1::c1::function::get__int256_int256 %1.cse_temp, (arg #1)
+        // get() is inlined, so its arguments are part of the expressions available to CSE
+        // CHECK: ty:int256 %a.arg.234 = (signed divide (arg #0) / (int256 2 * (arg #1)))
+        // CHECK: ty:int256 %.temp.183 = ((%a.arg.234 + (arg #1)) + int256 1)
         int p = a + get(a/(2*b), b);
 
         bool e = (ast == bst) || p < 2;
-        // CHECK: ty:bool %2.cse_temp = (strcmp (%ast) (%bst))
-        // CHECK: branchcond %2.cse_temp, block2, block1
+        // CHECK: ty:bool %1.cse_temp = (strcmp (%ast) (%bst))
+        // CHECK: branchcond %1.cse_temp, block2, block1
         bool e2 = e;
         // CHECK: branchcond (strcmp (%cst) (%cst)), block3, block4
         if (ast + bst == cst) {
-            // CHECK: call c1::c1::function::get__int256_int256 %1.cse_temp, (arg #1)
+            // CHECK: branchcond (signed less %p < int256 0), block5, block6
             require(a + get(a/(2*b), b) < 0);
             emit testEvent(a + get(a/(2*b) -p, b), p, ast+bst);
         }
 
-        // CHECK: branchcond %2.cse_temp, block7, block8
+        // CHECK: branchcond %1.cse_temp, block7, block8
         if (ast == bst) {
             ast = ast + "b";
         }
-        // CHECK: call c1::c1::function::get__int256_int256 (%1.cse_temp - %p), (arg #1)
+        // CHECK: ty:int256 %a.arg.224 = (%a.arg.234 - %p)
 
         // CHECK: branchcond (strcmp (%ast) (%bst)), block10, block11
         while (ast == bst) {
             ast = ast + "a";
         }
 
-        // CHECK: call c1::c1::function::get__int256_int256 (arg #1), (signed divide (arg #0) / (arg #1))
+        // CHECK: ty:int256 %b.arg = (signed divide (arg #0) / (arg #1))
         return get(b, a/b);
     }
 
@@ -366,7 +350,6 @@ contract c1 {
         int[4] vec = [1, 2, 3, 4];
         // CHECK: ty:int256 %1.cse_temp = ((arg #0) + (arg #1))
         int x = (a+b) - (vec[1]-vec[2]);
-        // CHECK: ty:int256 %x = (%1.cse_temp - ((load (subscript int256[4] %vec[uint32 1])) - (load (subscript int256[4] %vec[uint32 2]))))
         bool k3 = x < 1;
         // CHECK: = uint256(%1.cse_temp)
         vec[uint(a+b)] = 54*(a+b);
@@ -382,7 +365,6 @@ contract c1 {
             // CHECK: ty:string %p = (concat ((alloc string uint32 1 "a")) (%k))
             // CHECK: branchcond ((builtin ArrayLength (%p)) == uint32 2), block11, block12
             if(p.length == 2) {
-                // CHECK: ty:string %p1 = (concat ((alloc string uint32 1 "a")) (%k))
                 string p1 = "a" + k;
                 string l = p1;
             }
@@ -397,11 +379,11 @@ contract c1 {
         }
 
         do {
-            // CHECK: branchcond (strcmp (%c) ("a")), block21, block22
+            // CHECK: branchcond (strcmp (%c) ("a")), block20, block21
             if("a" == c) {
                 a = a+b;
             }
-            // CHECK: branchcond (signed more (%a + (arg #1)) > int256 0), block18, block20
+            // CHECK: branchcond (signed more (%a + (arg #1)) > int256 0), block18, block19
         } while(a+b > 0);
 
         for(int p=0; p<a; ++p) {
@@ -427,7 +409,6 @@ contract c1 {
 
         for(int p=0; p<a; ++p) {
             doNothing(b1);
-            // CHECK: ty:bytes32 %b2 = %b1
             bytes32 b2 = bytes32(b3);
             doNothing(b2);
         }
@@ -435,11 +416,11 @@ contract c1 {
         b3 = bytes("d");
         for(int p=0; p<a; ++p) {
             doNothing(b1);
-            // CHECK: ty:bytes32 %b2.155 = bytes from:bytes32 (%b3)
             bytes32 b2 = bytes32(b3);
             doNothing(b2);
         }
 
+        // CHECK: return int256 2
         return 2;
     }
 
@@ -465,17 +446,17 @@ contract c1 {
             return a << b + 1;
         }
 
-        // CHECK: branchcond (unsigned more %c > uint256 0), block11, block12
+        // CHECK: branchcond (unsigned more %c > uint256 0), block10, block11
         for(int i=0; c > 0 && i<10; ++i) {
             c++;
         }
 
-        // CHECK: branchcond (%3.cse_temp == uint256 0), block13, block14
+        // CHECK: branchcond (%3.cse_temp == uint256 0), block12, block13
         if (a & b == 0) {
             return c--;
         }
 
-        // CHECK: branchcond (unsigned more %3.cse_temp > uint256 1), block15, block16
+        // CHECK: branchcond (unsigned more %3.cse_temp > uint256 1), block14, block15
         if (a & b > 1) {
             return a;
         }
@@ -489,16 +470,15 @@ contract c1 {
          bool e = k>0;
 
         for(int i=1; a-b < 0; i++) {
-            // CHECK: ty:int256 %4.cse_temp = (signed divide %k / (arg #0))
-            // CHECK: ty:int256 %p = ((%1.cse_temp * int256 5) - %4.cse_temp)
+            // CHECK: ty:int256 %p = ((%1.cse_temp * int256 5) - (signed divide %k / (arg #0)))
             int p = (a-b)*5-k/a;
             b++;
             // CHECK: ty:int256 %1.cse_temp = ((arg #0) - %b)
-            // CHECK: branchcond (signed less %1.cse_temp < int256 0), block1, block4
+            // CHECK: branchcond (signed less %1.cse_temp < int256 0), block1, block3
+            // CHECK: ty:int256 %temp.278 = (signed divide %k / (arg #0))
             // CHECK: 	ty:int256 %2.cse_temp = ((arg #0) - %b)
-            // CHECK: branchcond (signed more %2.cse_temp > int256 0), block6, block7
+            // CHECK: branchcond (signed more %2.cse_temp > int256 0), block5, block6
             while(a-b > 0) {
-                // CHECK: ty:int256 %p = (%2.cse_temp * int256 5)
                 p = (a-b)*5;
                 b--;
             }
@@ -506,28 +486,27 @@ contract c1 {
         }
 
         do {
-            // CHECK: ty:int256 %p.170 = ((((arg #0) - %b) * int256 5) - %4.cse_temp)
             int p = (a-b)*5-k/a;
             b++;
             bool e2 = p<1;
-            // CHECK: branchcond (signed less ((arg #0) - %b) < int256 0), block8, block10
+            // CHECK: branchcond (signed less ((arg #0) - %b) < int256 0), block7, block9
         }while(a - b < 0);
 
         int g = b;
         // CHECK: ty:uint256 %p1 = (uint256((arg #0)) ** uint256(%g))
         uint p1 = uint(a)**uint(g);
         bool e9 = p1 == 0;
-        // CHECK: ty:int256 %3.cse_temp = ((arg #0) - %b)
-        // CHECK: branchcond (signed less %3.cse_temp < int256 0), block12, block13
+        // CHECK: ty:int256 %3.cse_temp = ((arg #0) - int256 4)
+        // CHECK: ty:bool %temp.279 = (signed more %3.cse_temp > int256 0)
+        // CHECK: ty:int256 %4.cse_temp = ((arg #0) - %b)
+        // CHECK: branchcond (signed less %4.cse_temp < int256 0), block11, block12
         while(a - b < 0) {
-            // CHECK: = ((%3.cse_temp * int256 5) - %4.cse_temp)
+            // CHECK: = ((%4.cse_temp * int256 5) - (signed divide %k / (arg #0)))
             int p = (a-b)*5-k/a;
             b=4;
-            // CHECK: ty:int256 %5.cse_temp = ((arg #0) - int256 4)
-            // CHECK: branchcond (signed more %5.cse_temp > int256 0), block14, block15
+            // CHECK: branchcond %temp.279, block13, block14
             if (a-b > 0) {
-                // CHECK: return (%4.cse_temp + int256(%p1))
-                // CHECK:  = (%5.cse_temp * int256 4)
+                // CHECK: return (%temp.278 + int256(%p1))
                 p = (a-b)*4;
                 b++;
             }
@@ -0,0 +1,29 @@
+// RUN: --target substrate --emit cfg --math-overflow
+contract c {
+	function add() public pure returns (uint256) {
+		return type(uint256).max + 1;
+// FAIL: arithmetic overflow: 115792089237316195423570985008687907853269984665640564039457584007913129639936 does not fit into type 'uint256'
+	}
+
+	function sub() public pure returns (int128) {
+		return type(int128).min - 1;
+// FAIL: arithmetic overflow: -170141183460469231731687303715884105729 does not fit into type 'int128'
+	}
+
+	function mul() public pure returns (uint128) {
+		return uint128(18446744073709551616) * uint128(18446744073709551616);
+// FAIL: arithmetic overflow: 340282366920938463463374607431768211456 does not fit into type 'uint128'
+	}
+
+	function pow() public pure returns (uint256) {
+		return uint256(2) ** 256;
+// FAIL: arithmetic overflow: 115792089237316195423570985008687907853269984665640564039457584007913129639936 does not fit into type 'uint256'
+	}
+
+	function wraps() public pure returns (uint256) {
+		uint256 x = type(uint256).max;
+		unchecked {
+			return x + 1;
+		}
+	}
+}
@@ -0,0 +1,16 @@
+// RUN: --target substrate --emit cfg
+pragma solidity ^0.8.0;
+
+// An overflow on a path which might not be taken must not fail the compile; it reverts at runtime
+contract c {
+	// BEGIN-CHECK: c::c::function::guarded__uint8
+	function guarded(uint8 a) public pure returns (uint8) {
+		uint8 x = 255;
+		if (a > 1) {
+			// CHECK: ty:uint8 %x = (uint8 255 + uint8 1)
+			x++;
+		}
+		return x;
+	}
+// CHECK: arithmetic overflow: 256 does not fit into type 'uint8', this will revert at runtime
+}
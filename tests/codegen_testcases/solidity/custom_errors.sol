@@ -0,0 +1,32 @@
+// RUN: --target ewasm --emit cfg
+
+error InsufficientBalance(uint256 available, uint256 required);
+
+contract c {
+	error Unauthorized(address caller);
+
+	// BEGIN-CHECK: c::c::function::withdraw__uint256_uint256
+	function withdraw(uint256 balance, uint256 amount) public pure {
+		if (balance < amount) {
+			// selector of InsufficientBalance(uint256,uint256) is 0xcf479181
+			// CHECK: ty:bytes %encoded.temp.2 = (abiencode packed:bytes4 3477574017 non-packed:(arg #0), (arg #1))
+			// CHECK: assert-failure:%encoded.temp.2
+			revert InsufficientBalance(balance, amount);
+		}
+	}
+
+	// BEGIN-CHECK: c::c::function::close
+	function close() public view {
+		// CHECK: ty:bytes %encoded.temp.3 = (abiencode packed:bytes4 2387223510 non-packed:address((builtin Sender ())))
+		// CHECK: assert-failure:%encoded.temp.3
+		revert Unauthorized({ caller: msg.sender });
+	}
+
+	// BEGIN-CHECK: c::c::function::reason
+	function reason() public pure {
+		// selector of Error(string) is 0x08c379a0
+		// CHECK: ty:bytes %encoded.temp.4 = (abiencode packed:bytes4 147028384 non-packed:(alloc string uint32 6 "reason"))
+		// CHECK: assert-failure:%encoded.temp.4
+		revert("reason");
+	}
+}
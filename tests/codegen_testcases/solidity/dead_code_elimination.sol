@@ -0,0 +1,86 @@
+// RUN: --target substrate --emit cfg
+contract test {
+    bool constant DEBUG = false;
+
+    // BEGIN-CHECK: test::test::function::constant_branch__uint64
+    function constant_branch(uint64 a) public pure returns (uint64) {
+        // DEBUG is false, so the print is never executed
+        if (DEBUG) {
+            print("a:{}".format(a));
+        }
+
+        return a * 2;
+    // CHECK: block0: # entry
+    // NOT-CHECK: print
+    // CHECK: return ((arg #0) * uint64 2)
+    }
+
+    // BEGIN-CHECK: test::test::function::chain__uint64
+    function chain(uint64 a) public pure returns (uint64 r) {
+        // the body of the loop and the increment of i are one block
+        for (uint64 i = 0; i < a; i++) {
+            r += i;
+        }
+    // CHECK: block1: # body
+    // CHECK: ty:uint64 %r = (%r + %i)
+    // CHECK: ty:uint64 %i = (%temp.19 + uint64 1)
+    // CHECK: branch block2
+    // CHECK: block2: # cond
+    // CHECK: branchcond (unsigned less %i < (arg #0)), block1, block3
+    }
+
+    // BEGIN-CHECK: test::test::function::unused__uint64_uint64
+    function unused(uint64 a, uint64 b) public pure returns (uint64) {
+        // x and y are only used by the print, which is removed
+        uint64 x = a | b;
+        uint64 y = x << 2;
+
+        if (DEBUG) {
+            print("y:{}".format(y));
+        }
+
+        return a;
+    // CHECK: block0: # entry
+    // NOT-CHECK: %x
+    // CHECK: return (arg #0)
+    }
+
+    // BEGIN-CHECK: test::test::function::checked__uint64_uint64
+    function checked(uint64 a, uint64 b) public pure returns (uint64) {
+        // dividing by zero fails, so the division is not removed
+        uint64 x = a / b;
+
+        if (DEBUG) {
+            print("x:{}".format(x));
+        }
+
+        return a;
+    // CHECK: block0: # entry
+    // CHECK: ty:uint64 %x = (unsigned divide (arg #0) / (arg #1))
+    // CHECK: return (arg #0)
+    }
+
+    // BEGIN-CHECK: test::test::function::forever__uint64
+    function forever(uint64 a) public pure returns (uint64 r) {
+        // the loop body is only reached from the condition, but it is not merged into it
+        // because the merged block would branch to itself
+        while (true) {
+            r += a;
+        }
+    // CHECK: block1: # cond
+    // CHECK: branch block2
+    // CHECK: block2: # body
+    // CHECK: branch block1
+    }
+
+    // BEGIN-CHECK: test::test::function::write_buffer__uint32
+    function write_buffer(uint32 x) public pure returns (bytes) {
+        // writebuffer uses the buffer, so its allocation must be kept
+        bytes b = new bytes(32);
+        b.writeUint32LE(x, 0);
+        return b;
+    // CHECK: ty:bytes %b = (alloc bytes len uint32 32)
+    // CHECK: writebuffer buffer:%b offset:uint32 0 value:%x
+    // CHECK: return %b
+    }
+}
@@ -163,7 +163,9 @@ contract foo {
         S[] storage ptrArr = arr;
         ptrArr.push(S({f1: 1}));
         ptrArr.push(S({f1: 2}));
-// CHECK: %.temp.76, %.temp.77 = call foo::foo::function::g
+// g() is inlined, so the storage references it returns are known
+// CHECK: ty:struct foo.S storage %.temp.76 = (hex"63e5f30e16932f36f608404895bca64bc86f3888a94503d6a8628b54d9ec0d29" + uint256 0)
+// CHECK: ty:struct foo.S storage %.temp.77 = (hex"63e5f30e16932f36f608404895bca64bc86f3888a94503d6a8628b54d9ec0d29" + uint256 1)
 // CHECK: %temp.78 = load storage slot(%.temp.76) ty:struct foo.S
 // CHECK: %temp.79 = load storage slot(%.temp.77) ty:struct foo.S
         return g();
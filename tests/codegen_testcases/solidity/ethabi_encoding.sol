@@ -0,0 +1,68 @@
+// RUN: --target ewasm --emit cfg --no-strength-reduce
+
+contract EncodingTest {
+    enum WeekDay {
+        Sunday, Monday, Tuesday
+    }
+
+    struct S {
+        int24 a;
+        bytes3 b;
+        string c;
+    }
+
+    // BEGIN-CHECK: EncodingTest::EncodingTest::function::encodePrimitive__uint16_int24_bool_bytes4_EncodingTest.WeekDay_address
+    function encodePrimitive(uint16 a, int24 b, bool c, bytes4 d, WeekDay e, address f) public pure returns (bytes memory) {
+        // CHECK: ty:bytes %abi_encoded.temp.18 = (alloc bytes len ((((((uint32 0 + uint32 32) + uint32 32) + uint32 32) + uint32 32) + uint32 32) + uint32 32))
+        // CHECK: writebuffer buffer:%abi_encoded.temp.18 offset:(uint32 0 + uint32 30) value:(builtin ByteSwap (%a))
+        // CHECK: ty:uint32 %1.cse_temp = (uint32 0 + uint32 32)
+        // CHECK: writebuffer buffer:%abi_encoded.temp.18 offset:%1.cse_temp value:(builtin ByteSwap ((sext int256 %b)))
+        // CHECK: ty:uint32 %2.cse_temp = (%1.cse_temp + uint32 32)
+        // CHECK: writebuffer buffer:%abi_encoded.temp.18 offset:(%2.cse_temp + uint32 31) value:(zext uint8 %c)
+        // CHECK: ty:uint32 %3.cse_temp = (%2.cse_temp + uint32 32)
+        // CHECK: writebuffer buffer:%abi_encoded.temp.18 offset:%3.cse_temp value:(builtin ByteSwap (%d))
+        // CHECK: ty:uint32 %4.cse_temp = (%3.cse_temp + uint32 32)
+        // CHECK: writebuffer buffer:%abi_encoded.temp.18 offset:(%4.cse_temp + uint32 31) value:%e
+        // CHECK: writebuffer buffer:%abi_encoded.temp.18 offset:((%4.cse_temp + uint32 32) + uint32 12) value:%f
+        bytes memory res = abi.encode(a, b, c, d, e, f);
+        return res;
+    }
+
+    // BEGIN-CHECK: EncodingTest::EncodingTest::function::encodeString__string
+    function encodeString(string memory a) public pure returns (bytes memory) {
+        // CHECK: ty:uint32 %temp.21 = uint32 64
+        // CHECK: writebuffer buffer:%abi_encoded.temp.20 offset:(uint32 0 + uint32 28) value:(builtin ByteSwap (%temp.21))
+        // CHECK: ty:uint32 %temp.22 = (builtin ArrayLength ((arg #0)))
+        // CHECK: ty:uint32 %1.cse_temp = (uint32 0 + %temp.21)
+        // CHECK: writebuffer buffer:%abi_encoded.temp.20 offset:(%1.cse_temp + uint32 28) value:(builtin ByteSwap (%temp.22))
+        // CHECK: memcpy src: %a, dest: (advance ptr: %abi_encoded.temp.20, by: (%1.cse_temp + uint32 32)), bytes_len: %temp.22
+        // CHECK: ty:uint32 %temp.21 = (uint32 64 + (uint32 32 + ((%temp.22 + uint32 31) & uint32 4294967264)))
+        // CHECK: writebuffer buffer:%abi_encoded.temp.20 offset:((uint32 0 + uint32 32) + uint32 28) value:(builtin ByteSwap (%temp.21))
+        bytes memory res = abi.encode(a, "foobar");
+        return res;
+    }
+
+    // BEGIN-CHECK: EncodingTest::EncodingTest::function::encodeArray__uint32:_int24:2
+    function encodeArray(uint32[] memory a, int24[2] memory b) public pure returns (bytes memory) {
+        // CHECK: ty:uint32 %temp.26 = uint32 96
+        // CHECK: writebuffer buffer:%abi_encoded.temp.25 offset:(uint32 0 + uint32 28) value:(builtin ByteSwap (%temp.26))
+        // CHECK: ty:uint32 %temp.40 = (%1.cse_temp + uint32 32)
+        // CHECK: block2: # body
+        // CHECK: writebuffer buffer:%abi_encoded.temp.25 offset:((%temp.40 + (%for_i_0.temp.28 * uint32 32)) + uint32 28) value:(builtin ByteSwap ((load (subscript uint32[] %a[%for_i_0.temp.28]))))
+        // CHECK: block5: # body
+        // CHECK: writebuffer buffer:%abi_encoded.temp.25 offset:((uint32 0 + uint32 32) + (%for_i_0.temp.29 * uint32 32)) value:(builtin ByteSwap ((sext int256 (load (subscript int24[2] %b[%for_i_0.temp.29])))))
+        bytes memory res = abi.encode(a, b);
+        return res;
+    }
+
+    // BEGIN-CHECK: EncodingTest::EncodingTest::function::encodeStruct__EncodingTest.S
+    function encodeStruct(S memory s) public pure returns (bytes memory) {
+        // CHECK: ty:uint32 %temp.31 = uint32 32
+        // CHECK: writebuffer buffer:%abi_encoded.temp.30 offset:(uint32 0 + uint32 28) value:(builtin ByteSwap (%temp.31))
+        // CHECK: ty:uint32 %temp.32 = uint32 96
+        // CHECK: writebuffer buffer:%abi_encoded.temp.30 offset:((%2.cse_temp + uint32 32) + uint32 28) value:(builtin ByteSwap (%temp.32))
+        // CHECK: memcpy src: (load (struct %s field 2)), dest: (advance ptr: %abi_encoded.temp.30, by: (%3.cse_temp + uint32 32)), bytes_len: %temp.33
+        bytes memory res = abi.encode(s);
+        return res;
+    }
+}
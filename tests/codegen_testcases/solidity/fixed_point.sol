@@ -0,0 +1,45 @@
+// RUN: --target substrate --emit cfg
+contract fixed_point {
+// BEGIN-CHECK: fixed_point::fixed_point::function::mul__fixed128x18_fixed128x18
+    function mul(fixed a, fixed b) public pure returns (fixed) {
+        // CHECK: return (trunc fixed128x18 (signed divide ((sext int256 (arg #0)) * (sext int256 (arg #1))) / int256 1000000000000000000))
+        return a * b;
+    }
+
+// BEGIN-CHECK: fixed_point::fixed_point::function::div__ufixed64x4_ufixed64x4
+    function div(ufixed64x4 a, ufixed64x4 b) public pure returns (ufixed64x4) {
+        // CHECK: return (trunc ufixed64x4 (unsigned divide ((zext uint80 (arg #0)) * uint80 10000) / (zext uint80 (arg #1))))
+        return a / b;
+    }
+
+// BEGIN-CHECK: fixed_point::fixed_point::function::add__fixed64x4
+    function add(fixed64x4 a) public pure returns (fixed64x4) {
+        // CHECK: return (((arg #0) + fixed64x4 12500) - fixed64x4 20000)
+        return a + 1.25 - 2;
+    }
+
+// BEGIN-CHECK: fixed_point::fixed_point::function::to_fixed__int32
+    function to_fixed(int32 a) public pure returns (fixed64x2) {
+        // CHECK: return (trunc fixed64x2 ((sext int256 (arg #0)) * int256 100))
+        return fixed64x2(a);
+    }
+
+// BEGIN-CHECK: fixed_point::fixed_point::function::to_int__ufixed64x2
+    function to_int(ufixed64x2 a) public pure returns (uint32) {
+        // CHECK: (unsigned divide (trunc uint64 (zext uint256 (arg #0))) / uint64 100)
+        return uint32(a);
+    }
+
+// BEGIN-CHECK: fixed_point::fixed_point::function::rescale__fixed32x2
+    function rescale(fixed32x2 a) public pure returns (fixed64x6) {
+        // CHECK: return (trunc fixed64x6 ((sext int256 (arg #0)) * int256 10000))
+        return fixed64x6(a);
+    }
+
+// BEGIN-CHECK: fixed_point::fixed_point::function::increment__fixed32x2
+    function increment(fixed32x2 a) public pure returns (fixed32x2) {
+        // CHECK: ty:fixed32x2 %a = ((arg #0) + fixed32x2 100)
+        a++;
+        return a;
+    }
+}
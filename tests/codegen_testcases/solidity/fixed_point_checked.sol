@@ -0,0 +1,41 @@
+// RUN: --target substrate --emit cfg
+pragma solidity ^0.8.0;
+
+// The wide result of a fixed point multiply or divide is range checked before it is truncated
+contract fixed_point {
+// BEGIN-CHECK: fixed_point::fixed_point::function::mul__fixed128x18_fixed128x18
+    function mul(fixed a, fixed b) public pure returns (fixed) {
+        // CHECK: = (signed divide ((sext int256 (arg #0)) * (sext int256 (arg #1))) / int256 1000000000000000000)
+        // CHECK: < int256 -170141183460469231731687303715884105728), block1, block3
+        // CHECK: block1: # fixed_out_of_range
+        // CHECK: assert-failure
+        // CHECK: block3: # fixed_check_max
+        // CHECK: > int256 170141183460469231731687303715884105727), block1, block2
+        return a * b;
+    }
+
+// BEGIN-CHECK: fixed_point::fixed_point::function::div__ufixed64x4_ufixed64x4
+    function div(ufixed64x4 a, ufixed64x4 b) public pure returns (ufixed64x4) {
+        // CHECK: > uint80 18446744073709551615), block1, block2
+        // CHECK: assert-failure
+        // CHECK: return (trunc ufixed64x4 %fixed_result.temp
+        return a / b;
+    }
+
+// BEGIN-CHECK: fixed_point::fixed_point::function::wraps__fixed128x18_fixed128x18
+    function wraps(fixed a, fixed b) public pure returns (fixed) {
+        // CHECK: return (trunc fixed128x18 (signed divide ((sext int256 (arg #0)) * (sext int256 (arg #1))) / int256 1000000000000000000))
+        unchecked {
+            return a * b;
+        }
+    }
+
+// BEGIN-CHECK: fixed_point::fixed_point::function::div_wraps__ufixed64x4_ufixed64x4
+    function div_wraps(ufixed64x4 a, ufixed64x4 b) public pure returns (ufixed64x4) {
+        // CHECK: return (trunc ufixed64x4 (unsigned divide ((zext uint80 (arg #0)) * uint80 10000) / (zext uint80 (arg #1))))
+        // NOT-CHECK: fixed_out_of_range
+        unchecked {
+            return a / b;
+        }
+    }
+}
@@ -0,0 +1,102 @@
+// RUN: --target solana --emit cfg
+contract test {
+    uint64 value;
+    address owner;
+
+    // BEGIN-CHECK: test::test::function::get
+    function get() private view returns (uint64) {
+        return value;
+    }
+
+    // BEGIN-CHECK: test::test::function::double
+    function double() public view returns (uint64) {
+        // the getter is small, so it is inlined at both call sites
+        return get() + get();
+    // NOT-CHECK: call test::test::function::get
+    // CHECK: ty:uint64 %.temp.25 = %temp.24.41
+    // CHECK: ty:uint64 %.temp.26 = %temp.24
+    // CHECK: return (%.temp.25 + %.temp.26)
+    }
+
+    // BEGIN-CHECK: test::test::function::add__uint64_uint64
+    function add(uint64 a, uint64 b) private pure returns (uint64) {
+        return a + b;
+    }
+
+    // BEGIN-CHECK: test::test::function::constant_args
+    function constant_args() public pure returns (uint64) {
+        // once inlined, the arguments are constants and the call is folded
+        return add(1, 2);
+    // CHECK: return uint64 3
+    }
+
+    // BEGIN-CHECK: test::test::function::classify__uint64
+    function classify(uint64 x) private pure returns (uint64) {
+        if (x > 100) {
+            return 2;
+        }
+
+        return 1;
+    }
+
+    // BEGIN-CHECK: test::test::function::two_returns__uint64
+    function two_returns(uint64 x) public pure returns (uint64) {
+        // the return value comes from two different returns, so it needs a phi
+        return classify(x) * 3;
+    // CHECK: # phis: .temp.
+    // CHECK: return (%.temp.
+    }
+
+    modifier onlyOwner() {
+        require(msg.sender == owner);
+        _;
+    }
+
+    // BEGIN-CHECK: test::test::set__uint64::modifier0::onlyOwner public:true
+    function set(uint64 v) public onlyOwner {
+        // the function body is only called from the modifier, so it is inlined into it
+        value = v;
+    // CHECK: block1: # noassert
+    // CHECK: store storage slot(uint32 16) ty:uint64 =
+    }
+
+    // BEGIN-CHECK: test::test::function::recursive__uint64
+    function recursive(uint64 x) private pure returns (uint64) {
+        if (x == 0) {
+            return 0;
+        }
+        // recursive functions are inlined only once
+        return recursive(x - 1) + 1;
+    // CHECK: call test::test::function::recursive__uint64
+    }
+
+    // BEGIN-CHECK: test::test::function::call_recursive__uint64
+    function call_recursive(uint64 x) public pure returns (uint64) {
+        return recursive(x);
+    // CHECK: call test::test::function::recursive__uint64
+    }
+
+    // BEGIN-CHECK: test::test::function::pointer__uint64
+    function pointer(uint64 x) private pure returns (uint64) {
+        uint64 y = x;
+        for (uint64 i = 0; i < x; i++) {
+            y += i * x;
+        }
+        for (uint64 i = 0; i < x; i++) {
+            y -= i * x;
+        }
+        for (uint64 i = 0; i < x; i++) {
+            y ^= i * x;
+        }
+        return y;
+    }
+
+    // BEGIN-CHECK: test::test::function::call_pointer__uint64
+    function call_pointer(uint64 x) public pure returns (uint64) {
+        // a large function which is also called through a function pointer is not inlined
+        function (uint64) internal pure returns (uint64) p = pointer;
+
+        return pointer(x) + p(x);
+    // CHECK: call test::test::function::pointer__uint64
+    }
+}
@@ -0,0 +1,37 @@
+// RUN: --target substrate --emit cfg
+contract c {
+	uint64 immutable a;
+	bool immutable b = true;
+	int32 immutable d;
+
+// BEGIN-CHECK: c::c::constructor::045d62c7
+	constructor(uint64 x) {
+// CHECK: store immutable offset(0) ty:uint64 = (arg #0)
+		a = x;
+// CHECK: store immutable offset(12) ty:int32 = int32 -5
+		d = -5;
+		assembly {
+// CHECK: store immutable offset(12) ty:int32 = int32 7
+			setimmutable(0, "d", 7)
+		}
+	}
+
+// BEGIN-CHECK: c::c::function::get
+	function get() public view returns (uint64, bool, int32) {
+// CHECK: = load immutable offset(0) ty:uint64
+// CHECK: = load immutable offset(8) ty:bool
+// CHECK: = load immutable offset(12) ty:int32
+		return (a, b, d);
+	}
+
+// BEGIN-CHECK: c::c::function::yul
+	function yul() public view returns (uint256 r) {
+		assembly {
+// CHECK: = load immutable offset(0) ty:uint64
+			r := loadimmutable("a")
+		}
+	}
+
+// BEGIN-CHECK: c:storage_initializer
+// CHECK: store immutable offset(8) ty:bool = true
+}
@@ -0,0 +1,126 @@
+// RUN: --target substrate --emit cfg
+contract test {
+    uint[] arr;
+    int256 x;
+
+    // BEGIN-CHECK: test::test::function::sum
+    function sum() public view returns (uint s) {
+        // the length of arr is loaded from storage once, before the loop
+        for (uint i = 0; i < arr.length; i++) {
+            s += arr[i];
+        }
+    // CHECK: block0: # entry
+    // CHECK: %temp.53 = load storage slot(uint256 0) ty:uint256
+    // CHECK: branch block2
+    // CHECK: block2: # cond
+    // CHECK: ty:uint256 %temp.31 = %temp.53
+    }
+
+    // BEGIN-CHECK: test::test::function::fill__uint256
+    function fill(uint v) public {
+        // storing to the elements of arr does not change its length
+        for (uint i = 0; i < arr.length; i++) {
+            arr[i] = v;
+        }
+    // CHECK: block0: # entry
+    // CHECK: %temp.54 = load storage slot(uint256 0) ty:uint256
+    // CHECK: branch block2
+    // CHECK: block2: # cond
+    // CHECK: ty:uint256 %temp.36 = %temp.54
+    }
+
+    // BEGIN-CHECK: test::test::function::grow__uint256
+    function grow(uint n) public {
+        // push() changes the length of arr, so it must be loaded on every iteration
+        for (uint i = 0; i < arr.length; i++) {
+            if (arr.length < n) {
+                arr.push(i);
+            }
+        }
+    // CHECK: block0: # entry
+    // CHECK: block2: # cond
+    // CHECK: %temp.41 = load storage slot(uint256 0) ty:uint256
+    }
+
+    // BEGIN-CHECK: test::test::function::product__int256_int256
+    function product(int256 a, int256 b) public view returns (int256 r) {
+        // neither a * b nor the storage variable x change in the loop
+        for (int i = 0; i < a; i++) {
+            r += a * b + x;
+        }
+    // CHECK: block0: # entry
+    // CHECK: %temp.55 = load storage slot(uint256 1) ty:int256
+    // CHECK: ty:int256 %temp.56 = ((arg #0) * (arg #1))
+    // CHECK: branch block2
+    // CHECK: block1: # body
+    // CHECK: ty:int256 %temp.47 = %temp.55
+    // CHECK: ty:int256 %r = (%r + (%temp.56 + %temp.47))
+    }
+
+    // BEGIN-CHECK: test::test::function::changed__int256_int256
+    function changed(int256 a, int256 b) public pure returns (int256 r) {
+        // a is assigned in the loop, so a * b is not invariant
+        for (int i = 0; i < 10; i++) {
+            r += a * b;
+            a = i;
+        }
+    // CHECK: block1: # body
+    // CHECK: ty:int256 %r = (%r + (%a * (arg #1)))
+    }
+
+    // BEGIN-CHECK: test::test::function::nested__uint256_uint256
+    function nested(uint a, uint b) public pure returns (uint s) {
+        // a | b is moved out of both loops, and adding i out of the inner loop
+        for (uint i = 0; i < 10; i++) {
+            for (uint j = 0; j < 10; j++) {
+                s += (a | b) + i;
+            }
+        }
+    // CHECK: block0: # entry
+    // CHECK: ty:uint256 %temp.58 = ((arg #0) | (arg #1))
+    // CHECK: branch block2
+    // CHECK: block1: # body
+    // CHECK: ty:uint256 %temp.57 = (%temp.58 + (zext uint256 %i))
+    // CHECK: block4: # body
+    // CHECK: ty:uint256 %s = (%s + %temp.57)
+    }
+
+    // BEGIN-CHECK: test::test::function::divide__uint256_uint256
+    function divide(uint a, uint b) public pure returns (uint s) {
+        // dividing by zero fails, so the division must not be done unless the loop would do it
+        for (uint i = 0; i < 10; i++) {
+            if (i == 3) {
+                s += a / b;
+            }
+        }
+    // CHECK: block0: # entry
+    // CHECK: block4: # then
+    // CHECK: ty:uint256 %s = (%s + (unsigned divide (arg #0) / (arg #1)))
+    }
+
+    // BEGIN-CHECK: test::test::function::early_return__bool_uint256_uint256
+    function early_return(bool c, uint a, uint b) public pure returns (uint x) {
+        // the loop can be left by returning before the division, so it must stay in the loop
+        while (true) {
+            if (c) return;
+            x = a / b;
+        }
+    // CHECK: block0: # entry
+    // NOT-CHECK: divide
+    // CHECK: ty:uint256 %x = (unsigned divide (arg #1) / (arg #2))
+    }
+
+    // BEGIN-CHECK: test::test::function::forever__bool_uint256_uint256
+    function forever(bool c, uint a, uint b) public pure returns (uint x) {
+        // the loop is never left, but the division is only done when c is true
+        while (true) {
+            if (c) {
+                x += a / b;
+            }
+        }
+    // CHECK: block0: # entry
+    // NOT-CHECK: divide
+    // CHECK: block2: # then
+    // CHECK: unsigned divide (arg #1) / (arg #2)
+    }
+}
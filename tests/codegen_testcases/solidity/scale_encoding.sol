@@ -0,0 +1,78 @@
+// RUN: --target substrate --emit cfg --no-strength-reduce
+
+contract EncodingTest {
+    enum WeekDay {
+        Sunday, Monday, Tuesday
+    }
+
+    struct S {
+        int24 a;
+        bytes3 b;
+        string c;
+    }
+
+    // BEGIN-CHECK: EncodingTest::EncodingTest::function::encodePrimitive__uint16_int24_bool_bytes4_EncodingTest.WeekDay_address
+    function encodePrimitive(uint16 a, int24 b, bool c, bytes4 d, WeekDay e, address f) public pure returns (bytes memory) {
+        // CHECK: ty:bytes %abi_encoded.temp.18 = (alloc bytes len (((((uint32 2 + uint32 4) + uint32 1) + uint32 4) + uint32 1) + uint32 32))
+        // CHECK: writebuffer buffer:%abi_encoded.temp.18 offset:uint32 0 value:%a
+        // CHECK: ty:uint32 %1.cse_temp = (uint32 0 + uint32 2)
+        // CHECK: writebuffer buffer:%abi_encoded.temp.18 offset:%1.cse_temp value:(sext int32 %b)
+        // CHECK: ty:uint32 %2.cse_temp = (%1.cse_temp + uint32 4)
+        // CHECK: writebuffer buffer:%abi_encoded.temp.18 offset:%2.cse_temp value:(zext uint8 %c)
+        // CHECK: ty:uint32 %3.cse_temp = (%2.cse_temp + uint32 1)
+        // CHECK: writebuffer buffer:%abi_encoded.temp.18 offset:%3.cse_temp value:(builtin ByteSwap (%d))
+        // CHECK: ty:uint32 %4.cse_temp = (%3.cse_temp + uint32 4)
+        // CHECK: writebuffer buffer:%abi_encoded.temp.18 offset:%4.cse_temp value:%e
+        // CHECK: writebuffer buffer:%abi_encoded.temp.18 offset:(%4.cse_temp + uint32 1) value:%f
+        bytes memory res = abi.encode(a, b, c, d, e, f);
+        return res;
+    }
+
+    // BEGIN-CHECK: EncodingTest::EncodingTest::function::encodeString__string
+    function encodeString(string memory a) public pure returns (bytes memory) {
+        // CHECK: ty:uint32 %temp.20 = (builtin ArrayLength ((arg #0)))
+        // CHECK: ty:uint32 %1.cse_temp = (((uint32 1 + (zext uint32 (unsigned more %temp.20 > uint32 63))) + ((zext uint32 (unsigned more %temp.20 > uint32 16383)) << uint32 1)) + (zext uint32 (unsigned more %temp.20 > uint32 1073741823)))
+        // CHECK: branchcond (unsigned less %temp.20 < uint32 64), block1, block2
+        // CHECK: block1: # compact_small
+        // CHECK: writebuffer buffer:%abi_encoded.temp.19 offset:uint32 0 value:(trunc uint8 (%temp.20 << uint32 2))
+        // CHECK: block2: # compact_not_small
+        // CHECK: branchcond (unsigned less %temp.20 < uint32 16384), block3, block4
+        // CHECK: block3: # compact_medium
+        // CHECK: writebuffer buffer:%abi_encoded.temp.19 offset:uint32 0 value:(trunc uint16 ((%temp.20 << uint32 2) | uint32 1))
+        // CHECK: block4: # compact_not_medium
+        // CHECK: branchcond (unsigned less %temp.20 < uint32 1073741824), block5, block6
+        // CHECK: block5: # compact_large
+        // CHECK: writebuffer buffer:%abi_encoded.temp.19 offset:uint32 0 value:((%temp.20 << uint32 2) | uint32 2)
+        // CHECK: block6: # compact_big
+        // CHECK: writebuffer buffer:%abi_encoded.temp.19 offset:uint32 0 value:uint8 3
+        // CHECK: writebuffer buffer:%abi_encoded.temp.19 offset:(uint32 0 + uint32 1) value:%temp.20
+        // CHECK: block7: # compact_done
+        // CHECK: memcpy src: %a, dest: (advance ptr: %abi_encoded.temp.19, by: (uint32 0 + %1.cse_temp)), bytes_len: %temp.20
+        bytes memory res = abi.encode(a, "foobar");
+        return res;
+    }
+
+    // BEGIN-CHECK: EncodingTest::EncodingTest::function::encodeArray__uint32:_int24:2
+    function encodeArray(uint32[] memory a, int24[2] memory b) public pure returns (bytes memory) {
+        // CHECK: ty:uint32 %temp.23 = uint32 8
+        // CHECK: ty:uint32 %temp.25 = (builtin ArrayLength ((arg #0)))
+        // CHECK: block7: # compact_done
+        // CHECK: memcpy src: %a, dest: (advance ptr: %abi_encoded.temp.24, by: (uint32 0 + (((uint32 1 + (zext uint32 (unsigned more %temp.25 > uint32 63))) + ((zext uint32 (unsigned more %temp.25 > uint32 16383)) << uint32 1)) + (zext uint32 (unsigned more %temp.25 > uint32 1073741823))))), bytes_len: (%temp.25 * uint32 4)
+        // CHECK: block9: # body
+        // CHECK: writebuffer buffer:%abi_encoded.temp.24 offset:%temp.26 value:(sext int32 (load (subscript int24[2] %b[%for_i_0.temp.27])))
+        // CHECK: ty:uint32 %temp.26 = (uint32 4 + %temp.26)
+        bytes memory res = abi.encode(a, b);
+        return res;
+    }
+
+    // BEGIN-CHECK: EncodingTest::EncodingTest::function::encodeStruct__EncodingTest.S
+    function encodeStruct(S memory s) public pure returns (bytes memory) {
+        // CHECK: writebuffer buffer:%abi_encoded.temp.28 offset:uint32 0 value:(sext int32 (load (struct %s field 0)))
+        // CHECK: ty:uint32 %1.cse_temp = (uint32 0 + uint32 4)
+        // CHECK: writebuffer buffer:%abi_encoded.temp.28 offset:%1.cse_temp value:(builtin ByteSwap ((load (struct %s field 1))))
+        // CHECK: ty:uint32 %temp.29 = (builtin ArrayLength ((load (struct (arg #0) field 2))))
+        // CHECK: writebuffer buffer:%abi_encoded.temp.28 offset:(%1.cse_temp + uint32 3) value:(trunc uint8 (%temp.29 << uint32 2))
+        bytes memory res = abi.encode(s);
+        return res;
+    }
+}
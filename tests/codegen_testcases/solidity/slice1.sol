@@ -1,11 +1,12 @@
 // RUN: --target substrate --emit cfg
 contract c {
 // BEGIN-CHECK: c::function::test1
-	function test1() public pure{
+	function test1() public pure returns (uint32) {
 		bytes x = "foo1";
-		// x is not being used, so it can be a bytes1 slice
+		// x is not being modified, so it can be a bytes1 slice
 // CHECK: alloc bytes1 slice uint32 4 "foo1"
 	bytes y = x;
+	return y.length;
 	}
 
 // BEGIN-CHECK: c::function::test2
@@ -18,17 +19,17 @@ contract c {
 // CHECK: alloc bytes uint32 4 "foo2"
 	}
 
-	function foo(bytes x) pure internal {
-
+	function foo(bytes x) pure internal returns (uint32) {
+		return x.length;
 	}
 
 // BEGIN-CHECK: c::function::test3
-	function test3() public pure {
+	function test3() public pure returns (uint32) {
 		bytes x = "foo3";
 
-		foo(x);
-		// no bytes1 slices for function arguments yet, so it must be a vector
-// CHECK: alloc bytes uint32 4 "foo3"
+		return foo(x);
+		// foo() is inlined and does not modify its argument, so this does not need a vector
+// CHECK: alloc bytes1 slice uint32 4 "foo3"
 	}
 
 
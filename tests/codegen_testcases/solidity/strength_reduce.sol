@@ -10,7 +10,10 @@ contract test {
             // this multiply can be done with a 64 bit instruction
             print("i:{}".format(i * 100));
         }
-// CHECK: zext uint256 ((trunc uint64 %i) * uint64 100)
+// CHECK: ty:uint64 %i = uint64 0
+// CHECK: zext uint256 (%i * uint64 100)
+// CHECK: ty:uint64 %i = (%temp.22 + uint64 1)
+// CHECK: branchcond (unsigned less %i < uint64 10)
     }
 
 // BEGIN-CHECK: test::function::f2
@@ -46,7 +49,7 @@ contract test {
             // this multiply can be done with a 64 bit instruction
             print("i:{}".format(i * 32768));
         }
-// CHECK: (%i << uint256 15)
+// CHECK: ((zext uint256 %i) << uint256 15)
     }
 
 // BEGIN-CHECK: test::function::f5
@@ -55,7 +58,7 @@ contract test {
             // this multiply can be done with a 64 bit instruction
             print("i:{}".format(i * 32769));
         }
-// CHECK: sext int256 ((trunc int64 %i) * int64 32769)
+// CHECK: (sext int256 (%i * int64 32769))
     }
 
 /******************/
@@ -67,7 +70,7 @@ contract test {
         for (uint i = 1E9; i < 1E9+10; i++) {
             print("i:{}".format(i / 32768));
         }
-// CHECK: (%i >> uint256 15)
+// CHECK: ((zext uint256 %i) >> uint256 15)
     }
 
 // BEGIN-CHECK: test::function::f7
@@ -80,11 +83,12 @@ contract test {
 
 // BEGIN-CHECK: test::function::f8
     function f8() pure public {
-        // too many values to track; (101 values)
+        // too many values to track; (101 values) but all values have the same upper bits
         for (uint i = 1e9; i < 1e9+101; i++) {
             print("i:{}".format(i / 1e6));
         }
-// CHECK: (unsigned divide %i / uint256 1000000)
+// CHECK: ty:uint64 %i = uint64 1000000000
+// CHECK: (zext uint256 (unsigned divide %i / uint64 1000000))
     }
 
 
@@ -98,7 +102,7 @@ contract test {
         for (uint i = 1e9; i < 1e9+101; i++) {
             print("i:{}".format(i % 0x1_0000_0000));
         }
-// CHECK: (%i & uint256 4294967295)
+// CHECK: ((zext uint256 %i) & uint256 4294967295)
     }
 
 // BEGIN-CHECK: test::function::f10
@@ -106,7 +110,7 @@ contract test {
         for (int i = 30; i >= 0; i--) {
             print("i:{}".format(i % 0x1_0000_0001));
         }
-// CHECK: sext int256 (signed modulo (trunc int64 %i) % int64 4294967297)
+// CHECK: (sext int256 (signed modulo %i % int64 4294967297))
     }
 
 // BEGIN-CHECK: test::function::f11
@@ -116,4 +120,72 @@ contract test {
         }
 // CHECK: (signed modulo %i % int256 4294967297)
     }
+
+/*******************************/
+/* Branch condition refinement */
+/*******************************/
+
+// BEGIN-CHECK: test::function::f12
+    function f12(uint x) pure public {
+        if (x < 1000) {
+            print("x:{}".format(x * 100));
+        }
+// CHECK: zext uint256 ((trunc uint64 %x) * uint64 100)
+    }
+
+// BEGIN-CHECK: test::function::f13
+    function f13(uint x) pure public {
+        if (x >= 1000) {
+            return;
+        }
+
+        print("x:{}".format(x * 100));
+// CHECK: zext uint256 ((trunc uint64 %x) * uint64 100)
+    }
+
+// BEGIN-CHECK: test::function::f14
+    function f14(uint x) pure public {
+        require(x <= 1000, "too large");
+
+        print("x:{}".format(x * 100));
+// CHECK: zext uint256 ((trunc uint64 %x) * uint64 100)
+    }
+
+// BEGIN-CHECK: test::function::f15
+    function f15(uint x) pure public {
+        if ((x & ~uint(0xffff)) == 0) {
+            print("x:{}".format(x * 100));
+        }
+// CHECK: zext uint256 ((trunc uint64 %x) * uint64 100)
+    }
+
+// BEGIN-CHECK: test::function::f16
+    function f16(int x) pure public {
+        if (x >= 0) {
+            if (x < 1000) {
+                print("x:{}".format(x * 100));
+            }
+        }
+// CHECK: sext int256 ((trunc int64 %x) * int64 100)
+    }
+
+// BEGIN-CHECK: test::function::f17
+    function f17(uint x) pure public {
+        // nothing known about x on the true edge
+        if (x > 1000) {
+            print("x:{}".format(x * 100));
+        }
+// CHECK: (%x * uint256 100)
+    }
+
+// BEGIN-CHECK: test::function::f18
+    function f18(uint32 n) pure public {
+        // the loop bound is the bound of i
+        for (uint i = 0; i < n; i++) {
+            print("i:{}".format(i / 1e6));
+        }
+// CHECK: ty:uint64 %i = uint64 0
+// CHECK: (zext uint256 (unsigned divide %i / uint64 1000000))
+// CHECK: branchcond (unsigned less %i < (zext uint64 (arg #0)))
+    }
 }
@@ -0,0 +1,31 @@
+// RUN: --target substrate --emit cfg
+
+contract c {
+	// BEGIN-CHECK: c::c::function::test
+	function test() public returns (int32 x) {
+		other o = new other();
+		try o.test(1) returns (int32 bla) {
+			x = bla;
+		// selector of Denied(address,uint64) is 0xedeaba8a
+		// CHECK: %who, %amount = (abidecode:(%(external call return data), selector:0xedeaba8a  exception: block4  (address, uint64))
+		} catch other.Denied(address who, uint64 amount) {
+			x = int32(int64(amount));
+		// CHECK: block4: # no_reason
+		// CHECK: %reason = (abidecode:(%(external call return data), selector:0x08c379a0  exception: block5  (string))
+		} catch Error(string reason) {
+			x = int32(bytes(reason).length);
+		// CHECK: block5: # no_reason
+		// CHECK: %raw = (external call return data)
+		} catch (bytes raw) {
+			x = int32(raw.length);
+		}
+	}
+}
+
+contract other {
+	error Denied(address who, uint64 amount);
+
+	function test(uint64 amount) public returns (int32) {
+		revert Denied(msg.sender, amount);
+	}
+}
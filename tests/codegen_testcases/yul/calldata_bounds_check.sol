@@ -0,0 +1,20 @@
+// RUN: --target substrate --emit cfg -Onone --no-cse
+
+contract testing {
+// BEGIN-CHECK: testing::testing::function::calldata_large_offsets
+    function calldata_large_offsets() public pure returns (uint256 ret) {
+        bytes memory buf = new bytes(32);
+        assembly {
+            // the offset is not truncated to 32 bits before the bounds check
+            // CHECK: branchcond (uint256 4294967300 <= (zext uint256 (builtin ArrayLength (%temp.2)))), block2, block1
+            // CHECK: ty:uint256 %ret = (builtin ReadFromBuffer (%temp.2, uint32 4))
+            ret := calldataload(0x100000004)
+
+            // offset + length does not fit into 32 bits
+            // CHECK: branchcond (uint256 4294967295 <= (zext uint256 (builtin ArrayLength (%temp.3)))), block5, block4
+            // CHECK: branchcond (uint256 2 <= ((zext uint256 (builtin ArrayLength (%temp.3))) - uint256 4294967295)), block6, block4
+            // CHECK: memcpy src: (advance ptr: %temp.3, by: uint32 4294967295), dest: uint8((trunc uint32 uint256(%buf))), bytes_len: uint32 2
+            calldatacopy(buf, 0xffffffff, 2)
+        }
+    }
+}
@@ -0,0 +1,42 @@
+// RUN: --target solana --emit cfg -Onone --no-cse
+
+contract testing {
+    // BEGIN-CHECK: testing::testing::function::calldata_suffixes__uint64:_uint64:_uint32
+    function calldata_suffixes(uint64[] calldata vl, uint64[] calldata vl2, uint32 len) public pure returns (uint64[] memory) {
+        assembly {
+            function pair(a, b) -> x, y {
+                x := a
+                y := b
+            }
+
+            // CHECK: branchcond ((zext uint256 (arg #2)) <= (zext uint256 (builtin ArrayLength ((arg #0))))), block2, block1
+            // CHECK: block1: # out_of_bounds
+            // CHECK: assert-failure
+            // CHECK: block2: # in_bounds
+            // CHECK: store (struct (arg #0) field 0), (trunc uint32 (zext uint256 (arg #2)))
+            vl.length := len
+
+            // CHECK: = call testing::yul_function_0::pair uint256((arg #1)), uint256 1
+            // CHECK: ty:uint64[] %vl = uint64[]((trunc uint64 %x.temp
+            // CHECK: store (struct %vl field 0), (trunc uint32 %y.temp
+            vl.offset, vl.length := pair(vl2.offset, 1)
+        }
+        return vl;
+    }
+
+    // BEGIN-CHECK: testing::testing::function::memory_length__uint32
+    function memory_length(uint32 len) public pure returns (uint64[] memory) {
+        uint64[] memory vl = new uint64[](4);
+        assembly {
+            // CHECK: branchcond ((zext uint256 (arg #0)) <= (zext uint256 (builtin ArrayLength (%vl)))), block2, block1
+            // CHECK: block1: # out_of_bounds
+            // CHECK: assert-failure
+            // CHECK: block2: # in_bounds
+            // CHECK: store (struct %vl field 0), (trunc uint32 (zext uint256 (arg #0)))
+            // CHECK: ty:uint32 %array_length.temp.13 = (trunc uint32 (zext uint256 (arg #0)))
+            vl.length := len
+        }
+        // CHECK: return %vl
+        return vl;
+    }
+}
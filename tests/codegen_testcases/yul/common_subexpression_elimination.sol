@@ -15,22 +15,23 @@ contract testing  {
                 ret2 := add(a, b)
             }
 
-            // CHECK: block1: # cond
             // CHECK: ty:uint256 %1.cse_temp = (zext uint256 (arg #0))
+            // CHECK: ty:bool %temp.27 = (%1.cse_temp == uint256 259)
+            // CHECK: ty:bool %temp.28 = (unsigned more %1.cse_temp > uint256 10)
             // CHECK: branch block1
             for {let i := 0} lt(i, 10) {i := add(i, 1)} {
                 // CHECK: block3: # body
-                // CHECK: branchcond (%1.cse_temp == uint256 259), block5, block6
+                // CHECK: branchcond %temp.27, block5, block6
                 if eq(a, 259) {
                     break
                 }
 
                 // This is the if-condition after the loop
                 // block4: # end_for
-                // CHECK: branchcond ((unsigned less %1.cse_temp < uint256 10) | (%1.cse_temp == uint256 259)), block9, block10
+                // CHECK: branchcond ((unsigned less %1.cse_temp < uint256 10) | %temp.27), block9, block10
                 g := sum(g, 2)
                 // CHECK: block6: # endif
-                // CHECK: branchcond (unsigned more %1.cse_temp > uint256 10), block7, block8
+                // CHECK: branchcond %temp.28, block7, block8
                 if gt(a, 10) {
                     continue
                 }
@@ -96,25 +96,25 @@ contract testing {
             // CHECK: ty:uint256 %k = uint256 1
             let k := a
 
-            // CHECK: ty:uint256 %l = %vec
+            // CHECK: ty:uint256 %l = uint256(%vec)
             let l := vec
 
-            // CHECK: ty:uint256 %m = %mem_vec
+            // CHECK: ty:uint256 %m = uint256(%mem_vec)
             let m := mem_vec
 
-            // CHECK: ty:uint256 %n = %cte_vec
+            // CHECK: ty:uint256 %n = uint256(%cte_vec)
             let n := cte_vec
 
-            // CHECK: ty:uint256 %o = %mem_cte_vec
+            // CHECK: ty:uint256 %o = uint256(%mem_cte_vec)
             let o := mem_cte_vec
 
-            // CHECK: ty:uint256 %p = %b
+            // CHECK: ty:uint256 %p = uint256(%b)
             let p := b
 
-            // CHECK: ty:uint256 %r = %struct_test
+            // CHECK: ty:uint256 %r = uint256(%struct_test)
             let r := struct_test
 
-            // CHECK: ty:uint256 %s = %mem_struct_test
+            // CHECK: ty:uint256 %s = uint256(%mem_struct_test)
             let s := mem_struct_test
         }
     }
@@ -150,7 +150,7 @@ contract testing {
             // CHECK: ty:uint256 %r = (zext uint256 (load (struct %fPtr field 0)))
             let r := fPtr.selector
 
-            // CHECK: ty:uint256 %s = (arg #1)
+            // CHECK: ty:uint256 %s = uint256((arg #1))
             let s := vl_2
         }
     }
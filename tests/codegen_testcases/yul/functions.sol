@@ -1,9 +1,7 @@
 // RUN: --target solana --emit cfg -Onone --no-cse
 
 contract testing {
-    function yul_function() public pure returns (uint256) {
-        uint256 c;
-        uint256 d;
+    function yul_function(uint256 c, uint256 d) public pure returns (uint256) {
         assembly {
             // BEGIN-CHECK: # function testing::yul_function_0::early_leave public:false selector:00000000 nonpayable:true
             // CHECK: # params: uint256,uint256
@@ -60,8 +58,6 @@ contract testing {
                 // CHECK: return (trunc uint64 (arg #1)), (arg #0)
             }
 
-            c := 1
-            d := 2
             early_leave(c, d)
 
             c := single_return(c, d)
@@ -0,0 +1,81 @@
+// RUN: --target substrate --emit cfg -Onone --no-cse
+
+contract testing {
+    uint256 stored;
+
+// BEGIN-CHECK: testing::testing::function::storage_builtins__uint64
+    function storage_builtins(uint64 val) public returns (uint256 ret) {
+        assembly {
+            // CHECK: store storage slot(uint256 0) ty:uint256 = (zext uint256 (arg #0))
+            sstore(stored.slot, val)
+
+            // CHECK: %temp.10 = load storage slot(uint256 0) ty:uint256
+            // CHECK: ty:uint256 %ret = %temp.10
+            ret := sload(stored.slot)
+        }
+    }
+
+// BEGIN-CHECK: testing::testing::function::memory_builtins__bytes
+    function memory_builtins(bytes memory buf) public pure returns (uint256 ret, uint256 hash) {
+        assembly {
+            // CHECK: store uint256((trunc uint32 uint256((arg #0)))), uint256 258
+            mstore(buf, 0x102)
+
+            // CHECK: store uint8((trunc uint32 (uint256((arg #0)) + uint256 8))), uint8 4
+            mstore8(add(buf, 8), 0x304)
+
+            // CHECK: ty:uint256 %temp.11 = (load uint256((trunc uint32 uint256((arg #0)))))
+            // CHECK: ty:uint256 %ret = %temp.11
+            ret := mload(buf)
+
+            // CHECK: ty:bytes %temp.12 = (alloc bytes len uint32 32)
+            // CHECK: memcpy src: uint8((trunc uint32 uint256(%buf))), dest: (advance ptr: %temp.12, by: uint32 0), bytes_len: uint32 32
+            // CHECK: ty:uint256 %hash = uint256((builtin Keccak256 (%temp.12)))
+            hash := keccak256(buf, 32)
+        }
+    }
+
+// BEGIN-CHECK: testing::testing::function::calldata_builtins
+    function calldata_builtins() public pure returns (uint256 ret) {
+        bytes memory buf = new bytes(32);
+        assembly {
+            // CHECK: ty:uint256 %size = (zext uint256 (builtin ArrayLength ((builtin Calldata ()))))
+            let size := calldatasize()
+
+            // CHECK: ty:bytes %temp.13 = (builtin Calldata ())
+            // CHECK: branchcond (uint256 4 <= (zext uint256 (builtin ArrayLength (%temp.13)))), block2, block1
+            // CHECK: block1: # out_of_bounds
+            // CHECK: assert-failure
+            // CHECK: block2: # offset_in_bounds
+            // CHECK: branchcond (uint256 32 <= ((zext uint256 (builtin ArrayLength (%temp.13))) - uint256 4)), block3, block1
+            // CHECK: block3: # in_bounds
+            // CHECK: ty:uint256 %ret = (builtin ReadFromBuffer (%temp.13, uint32 4))
+            ret := calldataload(4)
+
+            // CHECK: ty:bytes %temp.14 = (builtin Calldata ())
+            // CHECK: branchcond (uint256 0 <= (zext uint256 (builtin ArrayLength (%temp.14)))), block5, block4
+            // CHECK: block5: # offset_in_bounds
+            // CHECK: branchcond (%size <= ((zext uint256 (builtin ArrayLength (%temp.14))) - uint256 0)), block6, block4
+            // CHECK: block6: # in_bounds
+            // CHECK: memcpy src: (advance ptr: %temp.14, by: uint32 0), dest: uint8((trunc uint32 uint256(%buf))), bytes_len: (trunc uint32 %size)
+            calldatacopy(buf, 0, size)
+        }
+    }
+
+// BEGIN-CHECK: testing::testing::function::returndata_builtins
+    function returndata_builtins() public pure {
+        bytes memory buf = new bytes(32);
+        assembly {
+            // CHECK: ty:uint256 %size = (zext uint256 (builtin ArrayLength ((external call return data))))
+            let size := returndatasize()
+
+            // CHECK: ty:bytes %temp.15 = (external call return data)
+            // CHECK: branchcond (uint256 0 <= (zext uint256 (builtin ArrayLength (%temp.15)))), block2, block1
+            // CHECK: block2: # offset_in_bounds
+            // CHECK: branchcond (%size <= ((zext uint256 (builtin ArrayLength (%temp.15))) - uint256 0)), block3, block1
+            // CHECK: block3: # in_bounds
+            // CHECK: memcpy src: (advance ptr: %temp.15, by: uint32 0), dest: uint8((trunc uint32 uint256(%buf))), bytes_len: (trunc uint32 %size)
+            returndatacopy(buf, 0, size)
+        }
+    }
+}
@@ -131,139 +131,125 @@ contract testing {
         assembly {
 
             for {
-            // CHECK: ty:uint256 %i = uint256 1
+            // CHECK: ty:uint64 %i = uint64 1
                 let i := 1
             // CHECK: branch block1
             // CHECK: block1: # cond
-            // CHECK: branchcond (uint256 0 != (%i + uint256 1)), block3, block4
+            // CHECK: branchcond (uint256 0 != ((zext uint256 %i) + uint256 1)), block2, block3
             } add(i, 1) {
-                // CHECK: block2: # next
-                // CHECK: ty:uint256 %i = (%i - uint256 1)
                 i := sub(i, 1)
-                // CHECK: branch block1
             } {
-                // CHECK: block3: # body
-                // CHECK: ty:uint256 %i = (%i << uint256 1)
+                // CHECK: block2: # body
+                // CHECK: ty:uint64 %i = (trunc uint64 ((zext uint256 %i) << uint256 1))
                 i := mul(i, 2)
-                // CHECK: branch block2
+                // the post-iteration block is joined to the end of the body
+                // CHECK: ty:uint64 %i = (%i - uint64 1)
+                // CHECK: branch block1
             }
-            // CHECK: block4: # end_for
+            // CHECK: block3: # end_for
 
             for {
                 // CHECK: ty:uint256 %i.27 = uint256 1
-                // CHECK: branch block5
+                // CHECK: branch block4
                 let i := 1
-                // CHECK: block5: # cond
-                // CHECK: branchcond (unsigned less %i.27 < uint256 10), block7, block8
+                // CHECK: block4: # cond
+                // CHECK: branchcond (unsigned less %i.27 < uint256 10), block5, block6
             } lt(i, 10) {
-                // CHECK: block6: # next
                 i := add(i, 1)
-                // CHECK: ty:uint256 %i.27 = (%i.27 + uint256 1)
-                // CHECK: branch block5
             } {
-                // CHECK: block7: # body
+                // CHECK: block5: # body
                 // CHECK: ty:uint256 %i.27 = (uint256 2 >> %i.27)
                 i := shr(i, 2)
-                // CHECK: branch block6
+                // CHECK: ty:uint256 %i.27 = (%i.27 + uint256 1)
+                // CHECK: branch block4
             }
-            // CHECK: block8: # end_for
+            // CHECK: block6: # end_for
 
             for {
                 // CHECK: ty:uint256 %i.28 = uint256 1
                 let i := 1
-                // CHECK: branch block9
-                // CHECK: block9: # cond
-                // CHECK: branchcond %a, block11, block12
+                // CHECK: branch block7
+                // CHECK: block7: # cond
+                // CHECK: branchcond %a, block8, block9
             } a {
-                // CHECK: block10: # next
-                // CHECK: ty:uint256 %i.28 = (uint256(%a) + uint256 1)
                 i := add(a, 1)
-                // CHECK: ty:bool %a = false
                 a := false
             } {
-                // CHECK: block11: # body
+                // CHECK: block8: # body
                 // CHECK: ty:bool %a = (uint256 0 != (%i.28 + uint256 2))
                 a := add(i, 2)
-                // CHECK: branch block10
+                // CHECK: ty:uint256 %i.28 = (uint256(%a) + uint256 1)
+                // CHECK: ty:bool %a = false
             }
-            // CHECK: block12: # end_for
+            // CHECK: block9: # end_for
 
             for {
                 // CHECK: ty:uint256 %i.29 = uint256 2
                 let i := 2
-                // CHECK: branch block13
-                // CHECK: block13: # cond
-                // CHECK: branchcond (uint256 2 == uint256 0), block15, block16
+                // CHECK: branchcond (uint256 2 == uint256 0), block10, block11
             } eq(i, 0) {
-                // CHECK: block14: # next
                 // NOT-CHECK: ty:uint256 %i.29 = 
                 i := sub(i, 2)
             } {
-                // CHECK: block15: # body
+                // CHECK: block10: # body
                 i := add(i, 3)
                 // CHECK: ty:uint256 %i.29 = uint256 5
                 invalid()
                 // CHECK: assert-failure
                 // NOT-CHECK: branch
             }
-            // CHECK: block16: # end_for
+            // CHECK: block11: # end_for
 
             for {
                 // CHECK: ty:uint256 %j = uint256 2
                 let j := 2
-                // CHECK: branch block17
-                // CHECK: block17: # cond
-                // CHECK: branchcond (uint256 2 == uint256 3), block19, block20
+                // CHECK: branchcond (uint256 2 == uint256 3), block12, block13
             } eq(j, 3) {
-                // CHECK: block18: # next
                 j := shr(j, 2)
-                // CHECK: ty:uint256 %j = (uint256 2 >> %j)
                 invalid()
-                // CHECK: assert-failure
             } {
-                // CHECK: block19: # body
+                // CHECK: block12: # body
+                // CHECK: ty:uint256 %j = uint256 0
                 j := sar(j, 3)
-                // CHECK: branch block18
+                // CHECK: ty:uint256 %j = (uint256 2 >> %j)
+                // CHECK: assert-failure
             }
-            // CHECK: block20: # end_for
+            // CHECK: block13: # end_for
 
             for {
                 // CHECK: ty:uint256 %i.31 = uint256 0
                 let i := 0
-                // CHECK: branch block21
-                // CHECK: block21: # cond
-                // CHECK: branchcond (unsigned less %i.31 < uint256 10), block23, block24
+                // CHECK: branch block14
+                // CHECK: block14: # cond
+                // CHECK: branchcond (unsigned less %i.31 < uint256 10), block15, block16
             } lt(i, 10) {
-                // CHECK: block22: # next
                 i := add(i, 1)
-                // CHECK: ty:uint256 %i.31 = (%i.31 + uint256 1)
-                // CHECK: branch block21
             } {
-                // CHECK: block23: # body
+                // CHECK: block15: # body
                 for {
                     // CHECK: ty:uint256 %j.32 = uint256 0
                     let j :=0
-                    // CHECK: branch block25
-// ---- block 24 contains the for-loop with the invalid function
-// CHECK: block24: # end_for
+                    // CHECK: branch block17
+// ---- block 16 contains the for-loop with the invalid function
+// CHECK: block16: # end_for
 // CHECK: ty:uint256 %i.33 = uint256 2
 // CHECK: assert-failure
 // NOT-CHECK: branch
 
-                    // CHECK: block25: # cond
-                    // CHECK: branchcond (unsigned less %j.32 < uint256 10), block27, block28
+                    // CHECK: block17: # cond
+                    // CHECK: branchcond (unsigned less %j.32 < uint256 10), block18, block19
                 } lt(j, 10) {
-                    // CHECK: ty:uint256 %j.32 = (%j.32 + uint256 1)
                     j := add(j, 1)
-                    // CHECK: branch block25
                 } {
-                    // CHECK: block27: # body
+                    // CHECK: block18: # body
                     // CHECK: ty:bool %a = (uint256 0 != (%i.31 + %j.32))
                     a := add(i, j)
-                    // CHECK: branch block26
+                    // CHECK: ty:uint256 %j.32 = (%j.32 + uint256 1)
+                    // CHECK: branch block17
                 }
-                // CHECK: block28: # end_for
-                // CHECK: branch block22
+                // CHECK: block19: # end_for
+                // CHECK: ty:uint256 %i.31 = (%i.31 + uint256 1)
+                // CHECK: branch block14
             }
 
             for {
@@ -285,30 +271,28 @@ contract testing {
             // CHECK: ty:uint256 %i = uint256 1
             // CHECK: branch block1
             // CHECK: block1: # cond
-            // CHECK: branchcond (unsigned less %i < uint256 10), block3, block4
+            // CHECK: branchcond (unsigned less %i < uint256 10), block2, block3
             } lt(i, 10) {i := add(i, 1)
-            // CHECK: block2: # next
-            // CHECK: ty:uint256 %i = (%i + uint256 1)
-            // CHECK: branch block1
             } {
-                // CHECK: block3: # body
+                // CHECK: block2: # body
                 i := shr(i, 2)
                 // CHECK: ty:uint256 %i = (uint256 2 >> %i)
-                // CHECK: branchcond (unsigned more %i > uint256 10), block5, block6
+                // CHECK: branchcond (unsigned more %i > uint256 10), block4, block5
                 if gt(i, 10) {
                     break
                 }
             }
-            // CHECK: block4: # end_for
+            // CHECK: block3: # end_for
             // CHECK: return
 
             // IF-block:
-            // CHECK: block5: # then
-            // CHECK: branch block4
+            // CHECK: block4: # then
+            // CHECK: branch block3
 
-            // End of for for loop after IF
-            // CHECK: block6: # endif
-            // CHECK: branch block2
+            // End of for loop after IF, joined with the post-iteration block
+            // CHECK: block5: # endif
+            // CHECK: ty:uint256 %i = (%i + uint256 1)
+            // CHECK: branch block1
         }
     }
 
@@ -319,59 +303,56 @@ contract testing {
                 // CHECK: ty:uint256 %i = uint256 1
                 let i := 1
                 // CHECK: branch block1
-                // CHECK: branchcond (unsigned less %i < uint256 10), block3, block4
+                // CHECK: branchcond (unsigned less %i < uint256 10), block2, block3
             } lt(i, 10) {
-                // CHECK: block2: # next
                 i := add(i, 1)
-                // CHECK: ty:uint256 %i = (%i + uint256 1)
-                // CHECK: branch block1
             } {
                 for {
-                    // CHECK: block3: # body
+                    // CHECK: block2: # body
                     let j := 2
                     // CHECK: ty:uint256 %j = uint256 2
-                    // CHECK: branch block5
+                    // CHECK: ty:uint256 %temp.43 = (%i - uint256 2)
+                    // CHECK: branch block4
                 } lt(j, 10) {
                     // after outer for:
-                    // CHECK: block4: # end_for
+                    // CHECK: block3: # end_for
                     // CHECK: return
 
                     // inner for condition
-                    // CHECK: block5: # cond
-                    // CHECK: branchcond (unsigned less %j < uint256 10), block7, block8
-                    // CHECK: block6: # next
-                    // CHECK: ty:uint256 %j = (%j + uint256 1)
-                    // CHECK: branch block5
+                    // CHECK: block4: # cond
+                    // CHECK: branchcond (unsigned less %j < uint256 10), block5, block6
                     j := add(j, 1)
                 } {
-                    // CHECK: block7: # body
-                    // CHECK: branchcond (unsigned more %j > uint256 5), block9, block10
+                    // CHECK: block5: # body
+                    // CHECK: branchcond (unsigned more %j > uint256 5), block7, block8
                     if gt(j, 5) {
                         break
                     }
                     // After inner for:
-                    // CHECK: block8: # end_for
-                    // CHECK: branchcond (unsigned more %i > uint256 5), block11, block12
+                    // CHECK: block6: # end_for
+                    // CHECK: branchcond (unsigned more %i > uint256 5), block9, block10
                     
                     // Inside inner if:
-                    // CHECK: block9: # then
-                    // CHECK: branch block8
+                    // CHECK: block7: # then
+                    // CHECK: branch block6
 
                     // After inner if:
-                    // CHECK: block10: # endif
-                    // CHECK: ty:uint256 %j = (%i - uint256 2)
+                    // CHECK: block8: # endif
+                    // CHECK: ty:uint256 %j = %temp.43
                     j := sub(i, 2)
-                    // CHECK: branch block6
+                    // CHECK: ty:uint256 %j = (%j + uint256 1)
+                    // CHECK: branch block4
                 }
                 if gt(i, 5) {
-                    // CHECK: block11: # then
+                    // CHECK: block9: # then
                     break
-                    // CHECK: branch block4
+                    // CHECK: branch block3
                 }
-                // CHECK: block12: # endif
+                // CHECK: block10: # endif
                 // CHECK: ty:uint256 %i = (%i - uint256 4)
                 i := sub(i, 4)
-                // CHECK: branch block2
+                // CHECK: ty:uint256 %i = (%i + uint256 1)
+                // CHECK: branch block1
             }
         }
     }
@@ -428,6 +409,7 @@ contract testing {
                     // CHECK: block3: # body
                     let j := 2
                     // CHECK: ty:uint256 %j = uint256 2
+                    // CHECK: ty:uint256 %temp.44 = (%i - uint256 2)
                     // CHECK: branch block5
                 } lt(j, 10) {
                     // after outer for:
@@ -457,7 +439,7 @@ contract testing {
 
                     // After inner if:
                     // CHECK: block10: # endif
-                    // CHECK: ty:uint256 %j = (%i - uint256 2)
+                    // CHECK: ty:uint256 %j = %temp.44
                     j := sub(i, 2)
                     // CHECK: branch block6
                 }
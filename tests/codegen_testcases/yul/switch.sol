@@ -0,0 +1,68 @@
+// RUN: --target substrate --emit cfg
+
+contract testing {
+    // BEGIN-CHECK: testing::testing::function::small_switch__uint256
+    function small_switch(uint256 x) public pure returns (uint256 r) {
+        assembly {
+            // CHECK: branchcond ((arg #0) == uint256 0), block1, block5
+            // CHECK: block1: # case
+            // CHECK: ty:uint256 %r = uint256 5
+            // CHECK: block3: # default
+            // CHECK: ty:uint256 %r = uint256 7
+            // CHECK: block4: # end_switch
+            // CHECK: block5: # case_cond
+            // CHECK: branchcond ((arg #0) == uint256 1), block2, block3
+            switch x
+            case 0 { r := 5 }
+            case 1 { r := 6 }
+            default { r := 7 }
+        }
+    }
+
+    // BEGIN-CHECK: testing::testing::function::large_switch__uint256
+    function large_switch(uint256 x) public pure returns (uint256 r) {
+        assembly {
+            // CHECK: switch (arg #0):
+            // CHECK: case: uint256 0 => block1
+            // CHECK: case: uint256 1 => block2
+            // CHECK: case: uint256 2 => block3
+            // CHECK: case: uint256 97 => block4
+            // CHECK: default: block5
+            // CHECK: block5: # default
+            // CHECK: ty:uint256 %r = uint256 14
+            // CHECK: block6: # end_switch
+            switch x
+            case 0 { r := 10 }
+            case 1 { r := 11 }
+            case 2 { r := 12 }
+            case "a" { r := 13 }
+            default { r := 14 }
+        }
+    }
+
+    // BEGIN-CHECK: testing::testing::function::no_default__uint256
+    function no_default(uint256 x) public pure returns (uint256 r) {
+        r = 1;
+        assembly {
+            // CHECK: branchcond ((arg #0) == uint256 5), block1, block2
+            // CHECK: block2: # end_switch
+            switch x
+            case 5 { r := 2 }
+        }
+    }
+
+    // BEGIN-CHECK: testing::testing::function::constant_switch
+    function constant_switch() public pure returns (uint256 r) {
+        assembly {
+            // the other cases can never be reached, so they are removed
+            // CHECK: ty:uint256 %r = uint256 3
+            // CHECK: return %r
+            let x := 2
+            switch x
+            case 0 { r := 1 }
+            case 1 { r := 2 }
+            case 2 { r := 3 }
+            default { r := 4 }
+        }
+    }
+}
@@ -0,0 +1,35 @@
+// RUN: --target substrate --emit cfg
+
+contract testing {
+    // BEGIN-CHECK: testing::testing::function::assembly_locals__uint256
+    function assembly_locals(uint256 c) public returns (uint256 ret) {
+        uint256 unused;
+        assembly {
+            function f(v) -> r {
+                let dead := mul(v, 2)
+                r := add(v, 1)
+                sstore(0, r)
+            }
+
+            // f() is inlined
+            // CHECK: ty:uint256 %r = ((arg #0) + uint256 1)
+            let x := f(c)
+
+            // NOT-CHECK: ty:uint256 %y
+            let y := add(c, 1)
+            y := 5
+
+            // NOT-CHECK: ty:uint256 %unused
+            unused := 7
+
+            // CHECK: return (arg #0)
+            ret := c
+        }
+    }
+
+// BEGIN-CHECK: testing::yul_function_0::f
+    // NOT-CHECK: ty:uint256 %dead
+    // CHECK: ty:uint256 %r = ((arg #0) + uint256 1)
+    // CHECK: store storage slot(uint256 0) ty:uint256 = %r
+    // CHECK: return %r
+}
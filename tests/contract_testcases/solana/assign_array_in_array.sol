@@ -1,6 +1,6 @@
 // Ensure that subscript is assignable when member is array
 contract C {
-	function fixed() public {
+	function fixed_length() public {
 		uint256[3][4] memory iPj;
 		iPj[0] = [1,2,3];
 	}
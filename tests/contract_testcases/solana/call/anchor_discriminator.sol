@@ -0,0 +1,14 @@
+pragma solana anchor_discriminator;
+
+abstract contract base {
+	function transfer(address to) public virtual {}
+	function transfer(address to, uint64 amount) public {}
+}
+
+contract token is base {
+	function transfer(address to) public override {}
+	function transferFrom(address from) public {}
+	function transfer_from(address from) external {}
+	function _internal() internal {}
+	function _internal(int x) internal {}
+}
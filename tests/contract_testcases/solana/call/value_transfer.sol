@@ -0,0 +1,29 @@
+pragma solana value_transfer;
+import "solana" as sol;
+
+contract C {
+	function f() public {
+		D d = new D{value: 1}();
+	}
+
+	function g(D d) public {
+		d.func{value: 1}();
+		d.nonpay{value: 2}();
+		(bool success, ) = address(d).call{value: 3}("");
+	}
+
+	function h(address x) public {
+		sol.AccountMeta[1] metas = [ sol.AccountMeta(x, true, false) ];
+		x.call{value: 4, accounts: metas}("");
+	}
+
+	function i() public payable returns (uint64) {
+		return msg.value;
+	}
+}
+
+contract D {
+	constructor() payable {}
+	function func() payable public {}
+	function nonpay() public {}
+}
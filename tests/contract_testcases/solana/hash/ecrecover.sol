@@ -0,0 +1,5 @@
+        contract tester {
+            function test(bytes32 hash, uint8 v, bytes32 r, bytes32 s) public returns (address) {
+                return ecrecover(hash, v, r, s);
+            }
+        }
@@ -0,0 +1,21 @@
+contract proxy {
+    function tail(bytes calldata data) public returns (bytes memory) {
+        return data[4:];
+    }
+
+    function selector() public returns (bytes4) {
+        return bytes4(msg.data[:4]);
+    }
+
+    function window(uint32[] memory a, uint64 s, uint32 e) public returns (uint32, uint32) {
+        return (a[1:][0], a[s:e].length);
+    }
+
+    function args() public returns (uint32) {
+        return abi.decode(msg.data[4:], (uint32));
+    }
+
+    function encode(bytes calldata data) public returns (bytes memory) {
+        return abi.encode(data[1:3], string(data[1:]));
+    }
+}
@@ -0,0 +1,30 @@
+contract c {
+	bytes s;
+	function f0() public {
+		bytes memory a = s[1:];
+	}
+	function f1(string memory str) public {
+		bytes memory b = str[1:];
+	}
+	function f2(bytes calldata data, int32 i) public {
+		bytes memory d = data[i:];
+	}
+	function f3(bytes calldata data) public {
+		bytes memory e = data[4:2];
+	}
+	function f4(bytes calldata data) public {
+		data[1:][0] = 1;
+	}
+	function f5(bytes calldata data) public {
+		data[1:].push(1);
+	}
+	function f6(bytes calldata data) public {
+		bytes4 x = data[1:];
+	}
+	function f7(bytes calldata data) public {
+		string memory y = data[1:];
+	}
+	function f8(bytes calldata data) public {
+		uint8[] memory z = data[1:];
+	}
+}
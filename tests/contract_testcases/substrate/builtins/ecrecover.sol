@@ -0,0 +1,13 @@
+        contract tester {
+            function test(bytes32 hash, uint8 v, bytes32 r, bytes32 s) public pure returns (address) {
+                return ecrecover(hash, v, r, s);
+            }
+
+            function verify(address key, bytes message, bytes signature) public pure returns (bool) {
+                return signatureVerify(key, message, signature);
+            }
+
+            function wrong(bytes32 hash, bytes32 r, bytes32 s) public pure returns (address) {
+                return ecrecover(hash, r, s);
+            }
+        }
@@ -0,0 +1,24 @@
+contract c {
+	function test() public returns (int32 x) {
+		other o = new other();
+		try o.test(1) returns (int32 bla, bool) {
+			x = bla;
+		} catch other.Denied(address who, uint64 amount) {
+			if (who == address(0)) {
+				x = int32(uint32(amount));
+			}
+		} catch Error(string reason) {
+			x = 2;
+		} catch (bytes raw) {
+			x = int32(uint32(raw.length));
+		}
+	}
+}
+
+contract other {
+	error Denied(address who, uint64 amount);
+
+	function test(uint64 amount) public returns (int32, bool) {
+		revert Denied(msg.sender, amount);
+	}
+}
@@ -0,0 +1,47 @@
+contract c {
+	error Denied(address who, uint64 amount);
+
+	function test1() public returns (int32 x) {
+		other o = new other();
+		try o.test(1) returns (int32 bla, bool) {
+			x = bla;
+		} catch Denied(address who) {
+			x = 1;
+		}
+	}
+
+	function test2() public returns (int32 x) {
+		other o = new other();
+		try o.test(1) returns (int32 bla, bool) {
+			x = bla;
+		} catch other.Denied(address who, int64 amount) {
+			x = 2;
+		}
+	}
+
+	function test3() public returns (int32 x) {
+		other o = new other();
+		try o.test(1) returns (int32 bla, bool) {
+			x = bla;
+		} catch Unknown(bool a) {
+			x = 3;
+		}
+	}
+
+	function test4() public returns (int32 x) {
+		other o = new other();
+		try o.test(1) returns (int32 bla, bool) {
+			x = bla;
+		} catch Panic(bool a) {
+			x = 4;
+		}
+	}
+}
+
+contract other {
+	error Denied(address who, uint64 amount);
+
+	function test(uint64 amount) public returns (int32, bool) {
+		revert Denied(msg.sender, amount);
+	}
+}
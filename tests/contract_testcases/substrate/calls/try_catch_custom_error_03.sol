@@ -0,0 +1,20 @@
+contract c is other {
+	function test() public returns (int32 x) {
+		other o = new other();
+		try o.test(1) returns (int32 bla, bool) {
+			x = bla;
+		} catch Denied(address who, uint64 amount) {
+			x = 1;
+		} catch other.Denied(address, uint64) {
+			x = 2;
+		}
+	}
+}
+
+contract other {
+	error Denied(address who, uint64 amount);
+
+	function test(uint64 amount) public returns (int32, bool) {
+		revert Denied(msg.sender, amount);
+	}
+}
@@ -0,0 +1,11 @@
+error Error(string);
+error Panic(uint256);
+
+contract c {
+	error foo(bool a, int a);
+	error bar(mapping(uint => bool) m);
+	error foo(bool);
+
+	function f(foo x) public {
+	}
+}
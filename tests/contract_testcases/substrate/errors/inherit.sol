@@ -0,0 +1,10 @@
+abstract contract a {
+	/// The caller is not permitted
+	error Denied(address who);
+}
+
+contract b is a {
+	function f() public view {
+		revert Denied(msg.sender);
+	}
+}
@@ -0,0 +1,19 @@
+error InsufficientBalance(uint256 available, uint256 required);
+
+contract c {
+	error Unauthorized(address caller, string reason);
+
+	function withdraw(uint256 balance, uint256 amount) public pure {
+		if (balance < amount) {
+			revert InsufficientBalance(balance, amount);
+		}
+	}
+
+	function close() public view {
+		revert Unauthorized({ reason: "not the owner", caller: msg.sender });
+	}
+
+	function other() public pure {
+		revert c.Unauthorized(address(0), "foo");
+	}
+}
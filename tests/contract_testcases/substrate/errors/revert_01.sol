@@ -0,0 +1,23 @@
+contract c {
+	error foo(bool a, uint32 b);
+
+	function f() public {
+		revert foo(true);
+	}
+
+	function g() public {
+		revert foo({ a: true, c: 1 });
+	}
+
+	function h() public {
+		revert foo({ a: true, a: false, b: 1 });
+	}
+
+	function i() public {
+		revert foo({ a: true });
+	}
+
+	function j() public {
+		revert foo("ab", 1);
+	}
+}
@@ -0,0 +1,15 @@
+contract c {
+	event bar(bool);
+
+	function f() public {
+		revert foo();
+	}
+
+	function g() public {
+		revert bar(true);
+	}
+
+	function h() public {
+		revert({ foo: 1 });
+	}
+}
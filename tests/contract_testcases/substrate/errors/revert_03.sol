@@ -0,0 +1,11 @@
+contract c {
+	error foo(bool a);
+
+	function f() public {
+		revert foo({ a: true, c: 1, d: 2 });
+	}
+
+	function g() public {
+		revert bar({ x: y, z: w });
+	}
+}
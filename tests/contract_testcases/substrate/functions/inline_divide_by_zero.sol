@@ -0,0 +1,21 @@
+contract c {
+    function d(uint a, uint b) private pure returns (uint) {
+        return a / b;
+    }
+
+    function f() public pure returns (uint) {
+        // d() is inlined, which must not make this an error
+        return d(1, 0);
+    }
+
+    function g(uint x) public pure returns (uint) {
+        return h(x, 0);
+    }
+
+    function h(uint a, uint b) private pure returns (uint) {
+        if (b != 0) {
+            return a / b;
+        }
+        return 0;
+    }
+}
@@ -0,0 +1,42 @@
+type Price is uint64;
+type Amount is uint64;
+
+using {add as +, add as +} for Price global;
+using {view_add as -, eq as ==, lt as <, neg as ~, three as *} for Price global;
+using {add as /} for uint64 global;
+using {add as %} for Price;
+
+function add(Price a, Price b) pure returns (Price) {
+    return Price.wrap(Price.unwrap(a) + Price.unwrap(b));
+}
+
+function view_add(Price a, Price b) view returns (Price) {
+    return Price.wrap(Price.unwrap(a) + Price.unwrap(b) + uint64(block.number));
+}
+
+function eq(Price a, Price b) pure returns (Price) {
+    return a;
+}
+
+function lt(Price a, Amount b) pure returns (bool) {
+    return Price.unwrap(a) < Amount.unwrap(b);
+}
+
+function neg(Price a) pure returns (Price) {
+    return a;
+}
+
+function three(Price a, Price b, Price c) pure returns (Price) {
+    return a;
+}
+
+contract C {
+    using {add as &} for Price;
+
+    function test(Price a, Price b) public pure returns (Price) {
+        Price c = ~a;
+        c = a + b;
+        c = a.add(b);
+        return c | b;
+    }
+}
@@ -0,0 +1,9 @@
+pragma solidity ^0.7.0;
+
+contract c {
+	function f(int32 a) public {}
+
+	function g() public view returns (bytes memory) {
+		return abi.encodeCall(this.f, (1));
+	}
+}
@@ -0,0 +1,11 @@
+pragma solidity >=0.4.22 <0.6.0;
+
+contract c {
+	function() external payable {}
+}
+
+contract d {
+	function() external {}
+
+	receive() external payable {}
+}
@@ -0,0 +1,10 @@
+pragma solidity >=0.9.0;
+pragma abicoder v1;
+pragma abicoder v2;
+pragma abicoder v3;
+pragma experimental ABIEncoderV2;
+pragma experimental SMTChecker;
+pragma experimental foo;
+pragma foo bar;
+
+contract c {}
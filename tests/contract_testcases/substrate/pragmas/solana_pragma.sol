@@ -0,0 +1,9 @@
+pragma solana value_transfer;
+pragma solana foo;
+pragma solana anchor_discriminator;
+
+contract C {
+	function f() public payable returns (uint128) {
+		return msg.value;
+	}
+}
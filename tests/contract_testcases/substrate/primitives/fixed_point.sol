@@ -0,0 +1,23 @@
+contract oracle {
+    ufixed128x18 price = 1.5;
+    fixed64x4 constant HALF = -0.5;
+
+    function scale(ufixed128x18 amount) public view returns (ufixed128x18) {
+        return amount * price / 2;
+    }
+
+    function convert(int64 a) public pure returns (fixed64x4, int64) {
+        fixed64x4 f = fixed64x4(a) + HALF;
+        f *= 2.5;
+        f++;
+        return (f, int64(f));
+    }
+
+    function compare(ufixed a) public view returns (bool) {
+        return a > price && a != 2.25;
+    }
+
+    function widen(fixed32x2 a, ufixed16x2 b) public pure returns (fixed64x2, fixed32x4) {
+        return (a + b, fixed32x4(a));
+    }
+}
@@ -0,0 +1,34 @@
+contract c {
+    function f1(fixed a) public pure returns (fixed) {
+        return a | a;
+    }
+
+    function f2(fixed a, int b) public pure returns (fixed) {
+        return a + b;
+    }
+
+    function f3() public pure returns (ufixed64x2) {
+        return 1.234;
+    }
+
+    function f4() public pure returns (ufixed) {
+        return -1.5;
+    }
+
+    function f5() public pure returns (fixed8x1) {
+        return 12.8;
+    }
+
+    function f6(fixed a, fixed64x4 b) public pure returns (fixed) {
+        return a + b;
+    }
+
+    function f7(fixed a) public pure returns (int) {
+        return a;
+    }
+
+    function f8(fixed a) public pure returns (fixed) {
+        a <<= 1;
+        return a;
+    }
+}
@@ -0,0 +1,22 @@
+contract c {
+	function mul(fixed256x18 a, fixed256x18 b) public pure returns (fixed256x18) {
+		return a * b;
+	}
+
+	function div(ufixed256x18 a, ufixed256x18 b) public pure returns (ufixed256x18) {
+		return a / b;
+	}
+
+	function div_decimals(ufixed8x80 a, ufixed8x80 b) public pure returns (ufixed8x80) {
+		return a / b;
+	}
+
+	function assign(ufixed160x2 a, ufixed160x2 b) public pure returns (ufixed160x2) {
+		a *= b;
+		return a;
+	}
+
+	function ok(ufixed128x18 a, ufixed128x18 b) public pure returns (ufixed128x18) {
+		return a * b / a;
+	}
+}
@@ -0,0 +1,15 @@
+contract x {
+    struct S {
+        int f;
+    }
+
+    string immutable s;
+    S immutable t;
+    int[2] immutable u;
+    function() internal returns (int) immutable v;
+    bytes32 immutable w;
+
+    function foo() public {
+        delete w;
+    }
+}
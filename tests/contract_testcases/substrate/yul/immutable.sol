@@ -0,0 +1,32 @@
+contract x {
+    int immutable y;
+    function() internal returns (int) immutable f;
+    int z;
+
+    constructor() {
+        assembly {
+            setimmutable(0, "z", 2)
+        }
+        assembly {
+            setimmutable(0, "foo", 3)
+        }
+        assembly {
+            setimmutable(0, "f", 3)
+        }
+        assembly {
+            setimmutable(0, "y", 1)
+        }
+    }
+
+    function foo() public view returns (uint r) {
+        assembly {
+            setimmutable(0, "y", 1)
+        }
+        assembly {
+            r := loadimmutable(1)
+        }
+        assembly {
+            r := loadimmutable("y")
+        }
+    }
+}
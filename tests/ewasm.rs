@@ -19,6 +19,47 @@ mod ewasm_tests;
 
 type Address = [u8; 20];
 
+/// The ecrecover precompile; on failure no data is returned
+fn ecrecover(input: &[u8]) -> Vec<u8> {
+    let mut input = input.to_vec();
+
+    input.resize(128, 0);
+
+    let mut message_hash = [0u8; 32];
+    message_hash.copy_from_slice(&input[0..32]);
+
+    if input[32..63].iter().any(|v| *v != 0) || !(27..=28).contains(&input[63]) {
+        return Vec::new();
+    }
+
+    let public_key = match (
+        libsecp256k1::Signature::parse_standard_slice(&input[64..128]),
+        libsecp256k1::RecoveryId::parse(input[63] - 27),
+    ) {
+        (Ok(signature), Ok(recovery_id)) => libsecp256k1::recover(
+            &libsecp256k1::Message::parse(&message_hash),
+            &signature,
+            &recovery_id,
+        ),
+        _ => return Vec::new(),
+    };
+
+    match public_key {
+        Ok(public_key) => {
+            let mut hasher = Keccak::v256();
+            let mut hash = [0u8; 32];
+            // skip the 0x04 prefix of the uncompressed key
+            hasher.update(&public_key.serialize()[1..]);
+            hasher.finalize(&mut hash);
+
+            let mut res = vec![0u8; 12];
+            res.extend_from_slice(&hash[12..]);
+            res
+        }
+        Err(_) => Vec::new(),
+    }
+}
+
 pub fn address_new() -> Address {
     let mut rng = rand::thread_rng();
 
@@ -375,6 +416,10 @@ impl Externals for TestRuntime {
                 // if the first 19 bytes are 0, it's a precompile
                 if addr[0..19].iter().all(|v| *v == 0) {
                     match addr[19] {
+                        1 => {
+                            self.vm.returndata = ecrecover(&buf);
+                            return Ok(Some(RuntimeValue::I32(0)));
+                        }
                         20 => {
                             let mut hasher = Keccak::v256();
                             let mut hash = [0u8; 32];
@@ -719,6 +764,27 @@ impl TestRuntime {
     }
 
     fn function_revert(&mut self, name: &str, args: &[Token]) -> Option<String> {
+        let output = self.function_revert_data(name, args);
+
+        if output.is_empty() {
+            return None;
+        }
+
+        assert_eq!(output[..4], 0x08c3_79a0u32.to_be_bytes());
+
+        if let Ok(v) = decode(&[ethabi::ParamType::String], &output[4..]) {
+            assert_eq!(v.len(), 1);
+
+            if let ethabi::Token::String(r) = &v[0] {
+                return Some(r.to_owned());
+            }
+        }
+
+        panic!("failed to decode");
+    }
+
+    /// Call a function which is expected to revert, and return the raw revert data
+    fn function_revert_data(&mut self, name: &str, args: &[Token]) -> Vec<u8> {
         let calldata = match self.abi.functions[name][0].encode_input(args) {
             Ok(n) => n,
             Err(x) => panic!("{}", x),
@@ -750,21 +816,7 @@ impl TestRuntime {
 
         println!("RETURNDATA: {}", hex::encode(&self.vm.output));
 
-        if self.vm.output.is_empty() {
-            return None;
-        }
-
-        assert_eq!(self.vm.output[..4], 0x08c3_79a0u32.to_be_bytes());
-
-        if let Ok(v) = decode(&[ethabi::ParamType::String], &self.vm.output[4..]) {
-            assert_eq!(v.len(), 1);
-
-            if let ethabi::Token::String(r) = &v[0] {
-                return Some(r.to_owned());
-            }
-        }
-
-        panic!("failed to decode");
+        self.vm.output.clone()
     }
 
     fn constructor_expect_revert(&mut self, args: &[Token]) {
@@ -1439,6 +1491,81 @@ fn array_bounds_int_pos() {
     array_bounds_int(ethabi::Token::Int(ethereum_types::U256::from(4)))
 }
 
+#[test]
+fn array_slice() {
+    let mut runtime = build_solidity(
+        r##"
+        contract foo {
+            function args(uint32 a) public returns (bytes) {
+                return msg.data[4:];
+            }
+
+            function window(int32[] a, uint32 start, uint32 end) public returns (int32[]) {
+                return a[start:end];
+            }
+        }"##,
+    );
+
+    runtime.constructor(&[]);
+
+    let ret = runtime.function(
+        "args",
+        &[ethabi::Token::Uint(ethereum_types::U256::from(0x1234))],
+    );
+
+    assert_eq!(
+        ret,
+        [ethabi::Token::Bytes(ethabi::encode(&[
+            ethabi::Token::Uint(ethereum_types::U256::from(0x1234))
+        ]))]
+    );
+
+    let ret = runtime.function(
+        "window",
+        &[
+            ethabi::Token::Array(vec![
+                ethabi::Token::Int(ethereum_types::U256::from(0x20)),
+                ethabi::Token::Int(ethereum_types::U256::from(0x40)),
+                ethabi::Token::Int(ethereum_types::U256::from(0x80)),
+                ethabi::Token::Int(ethereum_types::U256::from(0x100)),
+            ]),
+            ethabi::Token::Uint(ethereum_types::U256::from(1)),
+            ethabi::Token::Uint(ethereum_types::U256::from(3)),
+        ],
+    );
+
+    assert_eq!(
+        ret,
+        [ethabi::Token::Array(vec![
+            ethabi::Token::Int(ethereum_types::U256::from(0x40)),
+            ethabi::Token::Int(ethereum_types::U256::from(0x80)),
+        ])]
+    );
+}
+
+#[test]
+#[should_panic]
+fn array_slice_bounds() {
+    let mut runtime = build_solidity(
+        r##"
+        contract foo {
+            function f(bytes a, uint32 end) public returns (bytes) {
+                return a[:end];
+            }
+        }"##,
+    );
+
+    runtime.constructor(&[]);
+
+    runtime.function(
+        "f",
+        &[
+            ethabi::Token::Bytes(vec![1, 2, 3]),
+            ethabi::Token::Uint(ethereum_types::U256::from(4)),
+        ],
+    );
+}
+
 #[test]
 fn array_array() {
     let mut runtime = build_solidity(
@@ -1554,6 +1681,52 @@ fn storage_structs() {
     runtime.function("test", &[]);
 }
 
+#[test]
+fn function_types_in_storage_containers() {
+    let mut runtime = build_solidity(
+        r##"
+        contract ft {
+            struct Handler {
+                function(int64) internal returns (int64) f;
+                function(int64) external returns (int64) ext;
+                uint32 calls;
+            }
+
+            mapping(uint32 => Handler) handlers;
+            mapping(uint32 => function(int64) external returns (int64)) callbacks;
+            function(int64) internal returns (int64)[2] ops;
+
+            function double(int64 x) internal returns (int64) {
+                return x * 2;
+            }
+
+            function inc(int64 x) public returns (int64) {
+                return x + 1;
+            }
+
+            function test() public {
+                handlers[7] = Handler({ f: double, ext: this.inc, calls: 0 });
+                callbacks[7] = this.inc;
+                ops[1] = double;
+
+                handlers[7].calls += 1;
+                int64 r = handlers[7].f(10);
+                r = handlers[7].ext(r);
+                r = callbacks[7](r);
+                r = ops[1](r);
+
+                assert(r == 44);
+                assert(handlers[7].calls == 1);
+                assert(callbacks[7].address == address(this));
+            }
+        }"##,
+    );
+
+    runtime.constructor(&[]);
+
+    runtime.function("test", &[]);
+}
+
 #[test]
 fn struct_encode() {
     let mut runtime = build_solidity(
@@ -1905,6 +2078,54 @@ fn revert() {
     assert_eq!(ret, Some("Hello, World!".to_owned()));
 }
 
+#[test]
+fn revert_custom_error() {
+    let mut runtime = build_solidity(
+        r##"
+        error InsufficientBalance(uint256 available, uint256 required);
+
+        contract foo {
+            error Unauthorized(string reason, int64 code);
+
+            function f(uint256 balance) public {
+                revert InsufficientBalance(balance, balance + 1);
+            }
+
+            function g() public {
+                revert Unauthorized({ code: 102, reason: "no way" });
+            }
+        }"##,
+    );
+
+    runtime.constructor(&[]);
+
+    let output = runtime.function_revert_data("f", &[Token::Uint(ethereum_types::U256::from(100))]);
+
+    let error = &runtime.abi.errors["InsufficientBalance"][0];
+
+    assert_eq!(output[..4], error.signature()[..4]);
+    assert_eq!(
+        error.decode(&output[4..]).unwrap(),
+        vec![
+            Token::Uint(ethereum_types::U256::from(100)),
+            Token::Uint(ethereum_types::U256::from(101))
+        ]
+    );
+
+    let output = runtime.function_revert_data("g", &[]);
+
+    let error = &runtime.abi.errors["Unauthorized"][0];
+
+    assert_eq!(output[..4], error.signature()[..4]);
+    assert_eq!(
+        error.decode(&output[4..]).unwrap(),
+        vec![
+            Token::String("no way".to_owned()),
+            Token::Int(ethereum_types::U256::from(102))
+        ]
+    );
+}
+
 #[test]
 fn constructor_args() {
     let mut runtime = build_solidity(
@@ -48,3 +48,79 @@ fn abi_encode() {
 
     assert_eq!(returns, vec![Token::Bytes(bytes)]);
 }
+
+#[test]
+fn abi_encode_types() {
+    let mut vm = build_solidity(
+        r#"
+        struct S {
+            int32 f1;
+            uint16[] f2;
+            string f3;
+        }
+
+        contract foo {
+            function primitives() public returns (bytes) {
+                return abi.encode(int8(-2), uint24(0x123456), true, bytes3(hex"abcdef"));
+            }
+
+            function arrays() public returns (bytes) {
+                uint16[] a = new uint16[](3);
+                a[0] = 1;
+                a[1] = 0xffff;
+                a[2] = 300;
+                int64[2] b = [int64(-1), 0x7fff_ffff_ffff_ffff];
+                return abi.encode(a, b, "foobar");
+            }
+
+            function structs() public returns (bytes) {
+                S x = S({ f1: -50, f2: new uint16[](2), f3: "foo" });
+                x.f2[1] = 7;
+                return abi.encode(x, uint8(5));
+            }
+        }"#,
+    );
+
+    vm.constructor(&[]);
+
+    let returns = vm.function("primitives", &[]);
+
+    let bytes = encode(&[
+        Token::Int(U256::MAX - U256::from(1)),
+        Token::Uint(U256::from(0x123456)),
+        Token::Bool(true),
+        Token::FixedBytes(vec![0xab, 0xcd, 0xef]),
+    ]);
+
+    assert_eq!(returns, vec![Token::Bytes(bytes)]);
+
+    let returns = vm.function("arrays", &[]);
+
+    let bytes = encode(&[
+        Token::Array(vec![
+            Token::Uint(U256::from(1)),
+            Token::Uint(U256::from(0xffff)),
+            Token::Uint(U256::from(300)),
+        ]),
+        Token::FixedArray(vec![
+            Token::Int(U256::MAX),
+            Token::Int(U256::from(0x7fff_ffff_ffff_ffffu64)),
+        ]),
+        Token::String(String::from("foobar")),
+    ]);
+
+    assert_eq!(returns, vec![Token::Bytes(bytes)]);
+
+    let returns = vm.function("structs", &[]);
+
+    let bytes = encode(&[
+        Token::Tuple(vec![
+            Token::Int(U256::MAX - U256::from(49)),
+            Token::Array(vec![Token::Uint(U256::zero()), Token::Uint(U256::from(7))]),
+            Token::String(String::from("foo")),
+        ]),
+        Token::Uint(U256::from(5)),
+    ]);
+
+    assert_eq!(returns, vec![Token::Bytes(bytes)]);
+}
@@ -1,6 +1,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::build_solidity;
+use ethabi::{ethereum_types, ethereum_types::U256, Token};
+use tiny_keccak::{Hasher, Keccak};
 
 #[test]
 fn hash_tests() {
@@ -61,3 +63,58 @@ fn hash_tests() {
     runtime.constructor(&[]);
     runtime.function("test", &[]);
 }
+
+#[test]
+fn ecrecover() {
+    let mut runtime = build_solidity(
+        r##"
+        contract tester {
+            function recover(bytes32 hash, uint8 v, bytes32 r, bytes32 s) public returns (address) {
+                return ecrecover(hash, v, r, s);
+            }
+        }"##,
+    );
+
+    runtime.constructor(&[]);
+
+    let secret_key = libsecp256k1::SecretKey::parse(&[7u8; 32]).unwrap();
+    let public_key = libsecp256k1::PublicKey::from_secret_key(&secret_key);
+
+    let mut hasher = Keccak::v256();
+    let mut hash = [0u8; 32];
+    hasher.update(&public_key.serialize()[1..]);
+    hasher.finalize(&mut hash);
+
+    let signer = &hash[12..];
+
+    let mut hasher = Keccak::v256();
+    let mut message_hash = [0u8; 32];
+    hasher.update(b"permit");
+    hasher.finalize(&mut message_hash);
+
+    let (signature, recovery_id) =
+        libsecp256k1::sign(&libsecp256k1::Message::parse(&message_hash), &secret_key);
+
+    let signature = signature.serialize();
+
+    let args = |v: u8| {
+        vec![
+            Token::FixedBytes(message_hash.to_vec()),
+            Token::Uint(U256::from(v)),
+            Token::FixedBytes(signature[..32].to_vec()),
+            Token::FixedBytes(signature[32..].to_vec()),
+        ]
+    };
+
+    let ret = runtime.function("recover", &args(recovery_id.serialize() + 27));
+
+    assert_eq!(
+        ret,
+        vec![Token::Address(ethereum_types::Address::from_slice(signer))]
+    );
+
+    // invalid v results in the zero address
+    let ret = runtime.function("recover", &args(5));
+
+    assert_eq!(ret, vec![Token::Address(ethereum_types::Address::zero())]);
+}
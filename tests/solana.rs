@@ -140,7 +140,6 @@ fn build_solidity(src: &str) -> VirtualMachine {
         namespaces,
         "bundle.sol",
         inkwell::OptimizationLevel::Default,
-        false,
     );
 
     let code = binary
@@ -354,6 +353,14 @@ fn update_parameters(
 ) {
     for r in refs {
         if let Some(entry) = accounts_data.get(&r.account) {
+            unsafe {
+                std::ptr::copy(
+                    entry.lamports.to_le_bytes().as_ptr(),
+                    input[r.offset - 16..].as_ptr() as *mut u8,
+                    8,
+                );
+            }
+
             unsafe {
                 std::ptr::copy(
                     r.length.to_le_bytes().as_ptr(),
@@ -1361,6 +1368,13 @@ impl<'a> SyscallObject<UserError> for SyscallInvokeSignedC<'a> {
                     .unwrap()
                     .clone();
 
+                // the callee should see changes made by the caller, like lamports transferred
+                deserialize_parameters(
+                    self.context.input,
+                    &self.context.refs.try_borrow().unwrap(),
+                    &mut vm.account_data,
+                );
+
                 vm.stack.insert(0, p);
 
                 let res = vm.execute(&instruction.data, &[]);
@@ -1,6 +1,16 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::build_solidity;
+use crate::{build_solidity, VirtualMachine};
+use ethabi::{ethereum_types::U256, ParamType, Token};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use solang::{
+    abi::generate_idl,
+    codegen::{codegen, Options},
+    file_resolver::FileResolver,
+    Target,
+};
+use std::ffi::OsStr;
 
 #[test]
 fn packed() {
@@ -73,3 +83,216 @@ fn inherited() {
 
     vm.function("test", &[], &[], None);
 }
+
+#[test]
+fn anchor_discriminator() {
+    let mut vm = build_solidity(
+        r#"
+        pragma solana anchor_discriminator;
+
+        contract counter {
+            uint64 count;
+
+            function incrementBy(uint64 x) public returns (uint64) {
+                count += x;
+                return count;
+            }
+
+            fallback() external {
+                print("fallback {}".format(msg.data.length));
+            }
+        }"#,
+    );
+
+    vm.constructor("counter", &[]);
+
+    // the function selector still works
+    let returns = vm.function("incrementBy", &[Token::Uint(U256::from(3))], &[], None);
+
+    assert_eq!(returns, vec![Token::Uint(U256::from(3))]);
+
+    let mut calldata = VirtualMachine::input(&vm.stack[0].data, &vm.origin, "incrementBy", &[]);
+
+    calldata.extend_from_slice(&Sha256::digest(b"global:increment_by")[..8]);
+    calldata.extend(ethabi::encode(&[Token::Uint(U256::from(5))]));
+
+    assert_eq!(vm.execute(&calldata, &[]), Ok(0));
+
+    let (_, return_data) = vm.return_data.as_ref().unwrap();
+
+    assert_eq!(
+        ethabi::decode(&[ParamType::Uint(64)], return_data).unwrap(),
+        vec![Token::Uint(U256::from(8))]
+    );
+
+    // instruction data which matches neither a discriminator nor a selector goes to fallback
+    let mut calldata = VirtualMachine::input(&vm.stack[0].data, &vm.origin, "counter", &[]);
+
+    calldata.extend_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+    assert_eq!(vm.execute(&calldata, &[]), Ok(0));
+
+    assert_eq!(vm.logs, "fallback 9");
+}
+
+#[test]
+fn anchor_idl() {
+    let mut cache = FileResolver::new();
+
+    cache.set_file_contents(
+        "test.sol",
+        r#"
+        pragma solana value_transfer;
+
+        contract c {
+            enum State { Open, Closed }
+            struct Pair { uint64 a; int24 b; }
+
+            event Changed(address indexed who, State state);
+            error Unauthorized(address who);
+
+            bool flag;
+            uint64 count;
+            string name;
+
+            constructor(string n) {
+                name = n;
+            }
+
+            /// @notice Set the state
+            function set(State s, Pair[] p, bytes4 b) public {
+                if (p.length == 0) {
+                    revert Unauthorized(msg.sender);
+                }
+                emit Changed(msg.sender, s);
+            }
+
+            function get() public view returns (uint64, string) {
+                return (count, name);
+            }
+        }"#
+        .to_string(),
+    );
+
+    let mut ns = solang::parse_and_resolve(OsStr::new("test.sol"), &mut cache, Target::Solana);
+
+    codegen(&mut ns, &Options::default());
+
+    assert!(!ns.diagnostics.any_errors());
+
+    let (idl, ext) = generate_idl(0, &ns, false);
+
+    assert_eq!(ext, "json");
+
+    let idl: serde_json::Value = serde_json::from_str(&idl).unwrap();
+
+    assert_eq!(idl["name"], "c");
+    assert_eq!(
+        idl["instructions"],
+        json!([
+            {
+                "name": "new",
+                "accounts": [{ "name": "dataAccount", "isMut": true, "isSigner": false }],
+                "args": [{ "name": "n", "type": "string" }]
+            },
+            {
+                "name": "set",
+                "docs": ["Set the state"],
+                "accounts": [{ "name": "dataAccount", "isMut": true, "isSigner": false }],
+                "args": [
+                    { "name": "s", "type": { "defined": "State" } },
+                    { "name": "p", "type": { "vec": { "defined": "Pair" } } },
+                    { "name": "b", "type": { "array": ["u8", 4] } }
+                ]
+            },
+            {
+                "name": "get",
+                "accounts": [{ "name": "dataAccount", "isMut": false, "isSigner": false }],
+                "args": [],
+                "returns": { "defined": "getReturns" }
+            }
+        ])
+    );
+    assert_eq!(
+        idl["types"],
+        json!([
+            { "name": "State", "type": { "kind": "enum", "variants": [{ "name": "Open" }, { "name": "Closed" }] } },
+            { "name": "Pair", "type": { "kind": "struct", "fields": [
+                { "name": "a", "type": "u64" },
+                { "name": "b", "type": { "array": ["u8", 3] } }
+            ] } },
+            { "name": "getReturns", "type": { "kind": "struct", "fields": [
+                { "name": "return0", "type": "u64" },
+                { "name": "return1", "type": "string" }
+            ] } }
+        ])
+    );
+    assert_eq!(
+        idl["events"],
+        json!([{ "name": "Changed", "fields": [
+            { "name": "who", "type": "publicKey", "index": true },
+            { "name": "state", "type": { "defined": "State" }, "index": false }
+        ] }])
+    );
+    assert_eq!(idl["errors"][0]["name"], "Unauthorized");
+    assert_eq!(idl["errors"][0]["msg"], "Unauthorized(bytes32)");
+
+    let fields: Vec<&str> = idl["accounts"][0]["type"]["fields"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|field| field["name"].as_str().unwrap())
+        .collect();
+
+    assert_eq!(
+        fields,
+        vec![
+            "magic",
+            "returnDataLength",
+            "returnDataOffset",
+            "heapOffset",
+            "lamports",
+            "flag",
+            "padding0",
+            "count",
+            "name"
+        ]
+    );
+    assert_eq!(idl["metadata"]["fixedLayoutSize"], 44);
+}
+
+#[test]
+fn anchor_idl_fixed_point() {
+    let mut cache = FileResolver::new();
+
+    cache.set_file_contents(
+        "test.sol",
+        r#"
+        contract c {
+            function scale(fixed64x4 a, ufixed128x18 b, fixed24x2 d) public pure returns (ufixed256x18) {
+                return ufixed256x18(b);
+            }
+        }"#
+        .to_string(),
+    );
+
+    let mut ns = solang::parse_and_resolve(OsStr::new("test.sol"), &mut cache, Target::Solana);
+
+    codegen(&mut ns, &Options::default());
+
+    assert!(!ns.diagnostics.any_errors());
+
+    let (idl, _) = generate_idl(0, &ns, false);
+
+    let idl: serde_json::Value = serde_json::from_str(&idl).unwrap();
+
+    assert_eq!(
+        idl["instructions"][0]["args"],
+        json!([
+            { "name": "a", "type": "i64" },
+            { "name": "b", "type": "u128" },
+            { "name": "d", "type": { "array": ["u8", 3] } }
+        ])
+    );
+    assert_eq!(idl["instructions"][0]["returns"], "u256");
+}
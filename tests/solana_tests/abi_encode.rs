@@ -2,7 +2,7 @@
 
 use crate::build_solidity;
 use borsh::BorshDeserialize;
-use ethabi::Token;
+use ethabi::{ethereum_types::U256, Token};
 
 #[test]
 fn integers_bool_enum() {
@@ -818,3 +818,148 @@ fn external_function() {
     assert_eq!(decoded.item_1, &selector[..]);
     assert_eq!(decoded.item_2, &address[..]);
 }
+
+#[test]
+fn multi_dimensional_dynamic_array() {
+    #[derive(Debug, BorshDeserialize)]
+    struct Res1 {
+        item: Vec<Vec<i16>>,
+    }
+
+    let mut vm = build_solidity(
+        r#"
+contract Testing {
+    function nested() public pure returns (bytes memory) {
+        int16[][] memory vec = new int16[][](3);
+        vec[0] = new int16[](2);
+        vec[0][0] = -1;
+        vec[0][1] = 2;
+        vec[1] = new int16[](0);
+        vec[2] = new int16[](3);
+        vec[2][0] = 3;
+        vec[2][1] = -4;
+        vec[2][2] = 5;
+        return abi.encode(vec);
+    }
+
+    function mixed() public pure returns (bytes memory) {
+        uint8[2][][3] memory vec;
+        vec[0] = new uint8[2][](1);
+        vec[0][0] = [uint8(1), 2];
+        vec[1] = new uint8[2][](2);
+        vec[1][0] = [uint8(3), 4];
+        vec[1][1] = [uint8(5), 6];
+        vec[2] = new uint8[2][](0);
+        return abi.encode(vec, uint8(7));
+    }
+}
+        "#,
+    );
+
+    vm.constructor("Testing", &[]);
+    let returns = vm.function("nested", &[], &[], None);
+    let encoded = returns[0].clone().into_bytes().unwrap();
+    let decoded = Res1::try_from_slice(&encoded).unwrap();
+
+    assert_eq!(decoded.item, vec![vec![-1, 2], vec![], vec![3, -4, 5]]);
+
+    let returns = vm.function("mixed", &[], &[], None);
+    let encoded = returns[0].clone().into_bytes().unwrap();
+
+    // borsh cannot derive a fixed length array of vectors, so decode the elements one by one
+    let mut buf = &encoded[..];
+    let item_1: Vec<Vec<[u8; 2]>> = (0..3)
+        .map(|_| BorshDeserialize::deserialize(&mut buf).unwrap())
+        .collect();
+    let item_2 = u8::deserialize(&mut buf).unwrap();
+
+    assert!(buf.is_empty());
+    assert_eq!(item_1[0], vec![[1, 2]]);
+    assert_eq!(item_1[1], vec![[3, 4], [5, 6]]);
+    assert!(item_1[2].is_empty());
+    assert_eq!(item_2, 7);
+}
+
+#[test]
+fn array_of_struct_arrays() {
+    #[derive(Debug, BorshDeserialize, PartialEq)]
+    struct Order {
+        id: u64,
+        amounts: Vec<u64>,
+    }
+
+    #[derive(Debug, BorshDeserialize)]
+    struct Res {
+        orders: Vec<Vec<Order>>,
+        count: u32,
+    }
+
+    let mut vm = build_solidity(
+        r#"
+contract OrderBook {
+    struct Order {
+        uint64 id;
+        uint64[] amounts;
+    }
+
+    function encodeOrders(Order[][] memory orders) public pure returns (bytes memory) {
+        return abi.encode(orders, uint32(orders.length));
+    }
+
+    function echoOrders(Order[][] memory orders) public pure returns (Order[][] memory) {
+        return orders;
+    }
+}
+        "#,
+    );
+
+    vm.constructor("OrderBook", &[]);
+
+    let order = |id: u64, amounts: &[u64]| {
+        Token::Tuple(vec![
+            Token::Uint(U256::from(id)),
+            Token::Array(
+                amounts
+                    .iter()
+                    .map(|amount| Token::Uint(U256::from(*amount)))
+                    .collect(),
+            ),
+        ])
+    };
+
+    let orders = Token::Array(vec![
+        Token::Array(vec![order(1, &[100, 200]), order(2, &[])]),
+        Token::Array(vec![]),
+        Token::Array(vec![order(3, &[300])]),
+    ]);
+
+    let returns = vm.function("encodeOrders", &[orders.clone()], &[], None);
+    let encoded = returns[0].clone().into_bytes().unwrap();
+    let decoded = Res::try_from_slice(&encoded).unwrap();
+
+    assert_eq!(
+        decoded.orders,
+        vec![
+            vec![
+                Order {
+                    id: 1,
+                    amounts: vec![100, 200]
+                },
+                Order {
+                    id: 2,
+                    amounts: vec![]
+                }
+            ],
+            vec![],
+            vec![Order {
+                id: 3,
+                amounts: vec![300]
+            }]
+        ]
+    );
+    assert_eq!(decoded.count, 3);
+
+    let returns = vm.function("echoOrders", &[orders.clone()], &[], None);
+
+    assert_eq!(returns, vec![orders]);
+}
@@ -1024,3 +1024,67 @@ fn initialization_with_literal() {
         vec![Token::Uint(Uint::from(563)), Token::Uint(Uint::from(895))]
     );
 }
+
+#[test]
+fn array_slice() {
+    let mut vm = build_solidity(
+        r#"
+        contract foo {
+            function tail(bytes memory data) public returns (bytes memory) {
+                return data[4:];
+            }
+
+            function window(uint64[] memory a, uint64 start, uint64 end) public returns (uint64[] memory) {
+                return a[start:end];
+            }
+        }"#,
+    );
+
+    vm.constructor("foo", &[]);
+
+    let returns = vm.function(
+        "tail",
+        &[Token::Bytes(vec![0xde, 0xad, 0xbe, 0xef, 1, 2, 3])],
+        &[],
+        None,
+    );
+
+    assert_eq!(returns, vec![Token::Bytes(vec![1, 2, 3])]);
+
+    let res = vm.function_must_fail("tail", &[Token::Bytes(vec![0xde, 0xad])], &[], None);
+    assert_eq!(res, Ok(4294967296));
+
+    let a = Token::Array(
+        (1..=5)
+            .map(|n| Token::Uint(U256::from(n)))
+            .collect::<Vec<Token>>(),
+    );
+
+    let returns = vm.function(
+        "window",
+        &[
+            a.clone(),
+            Token::Uint(U256::from(1)),
+            Token::Uint(U256::from(4)),
+        ],
+        &[],
+        None,
+    );
+
+    assert_eq!(
+        returns,
+        vec![Token::Array(vec![
+            Token::Uint(U256::from(2)),
+            Token::Uint(U256::from(3)),
+            Token::Uint(U256::from(4)),
+        ])]
+    );
+
+    let res = vm.function_must_fail(
+        "window",
+        &[a, Token::Uint(U256::from(4)), Token::Uint(U256::from(3))],
+        &[],
+        None,
+    );
+    assert_eq!(res, Ok(4294967296));
+}
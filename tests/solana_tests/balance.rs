@@ -398,3 +398,140 @@ fn value_overflows() {
         103
     );
 }
+
+#[test]
+fn value_transfer_call() {
+    let mut vm = build_solidity(
+        r#"
+        pragma solana value_transfer;
+
+        contract escrow {
+            function deposit(receiver r, uint64 amount) public {
+                r.pay{value: amount}();
+            }
+        }
+
+        contract receiver {
+            uint64 public total;
+
+            function pay() public payable {
+                total += msg.value;
+            }
+
+            function nopay() public {}
+        }"#,
+    );
+
+    vm.constructor("receiver", &[]);
+
+    let receiver_account = vm.stack[0].data;
+
+    vm.set_program(0);
+
+    // lamports present before the constructor are not value received
+    vm.account_data.get_mut(&vm.stack[0].data).unwrap().lamports = 1000;
+
+    vm.constructor("escrow", &[]);
+
+    vm.function(
+        "deposit",
+        &[
+            Token::FixedBytes(receiver_account.to_vec()),
+            Token::Uint(U256::from(100)),
+        ],
+        &[],
+        None,
+    );
+
+    vm.function(
+        "deposit",
+        &[
+            Token::FixedBytes(receiver_account.to_vec()),
+            Token::Uint(U256::from(50)),
+        ],
+        &[],
+        None,
+    );
+
+    assert_eq!(
+        vm.account_data.get_mut(&vm.stack[0].data).unwrap().lamports,
+        850
+    );
+
+    assert_eq!(
+        vm.account_data.get_mut(&receiver_account).unwrap().lamports,
+        150
+    );
+
+    vm.set_program(1);
+
+    let returns = vm.function("total", &[], &[], None);
+
+    assert_eq!(returns, vec![Token::Uint(U256::from(150))]);
+
+    // lamports credited outside a call count towards the value of the next call
+    vm.account_data.get_mut(&receiver_account).unwrap().lamports += 5;
+
+    vm.function("pay", &[], &[], None);
+
+    let returns = vm.function("total", &[], &[], None);
+
+    assert_eq!(returns, vec![Token::Uint(U256::from(155))]);
+
+    vm.account_data.get_mut(&receiver_account).unwrap().lamports += 7;
+
+    let res = vm.function_must_fail("nopay", &[], &[], None);
+
+    assert_ne!(res.ok(), Some(0));
+}
+
+#[test]
+fn receive() {
+    let mut vm = build_solidity(
+        r#"
+        pragma solana value_transfer;
+
+        contract sender {
+            function give(receiver r) public {
+                (bool success, ) = address(r).call{value: 40}("");
+                require(success, "call failed");
+            }
+        }
+
+        contract receiver {
+            uint64 public received;
+
+            receive() external payable {
+                received += msg.value;
+            }
+        }"#,
+    );
+
+    vm.constructor("receiver", &[]);
+
+    let receiver_account = vm.stack[0].data;
+
+    vm.set_program(0);
+
+    vm.account_data.get_mut(&vm.stack[0].data).unwrap().lamports = 1000;
+
+    vm.constructor("sender", &[]);
+
+    vm.function(
+        "give",
+        &[Token::FixedBytes(receiver_account.to_vec())],
+        &[],
+        None,
+    );
+
+    assert_eq!(
+        vm.account_data.get_mut(&receiver_account).unwrap().lamports,
+        40
+    );
+
+    vm.set_program(1);
+
+    let returns = vm.function("received", &[], &[], None);
+
+    assert_eq!(returns, vec![Token::Uint(U256::from(40))]);
+}
@@ -355,6 +355,77 @@ fn internal_function_storage() {
     assert_eq!(res, vec![Token::Int(U256::from(8))]);
 }
 
+#[test]
+fn function_types_in_storage_containers() {
+    let mut vm = build_solidity(
+        r#"
+        contract ft {
+            struct Handler {
+                function(int64) internal returns (int64) f;
+                function(int64) external returns (int64) ext;
+                uint32 calls;
+            }
+
+            mapping(uint32 => Handler) handlers;
+            mapping(uint32 => function(int64) external returns (int64)) callbacks;
+            function(int64) internal returns (int64)[] ops;
+            function(int64) external returns (int64)[2] exts;
+
+            function double(int64 x) internal returns (int64) {
+                return x * 2;
+            }
+
+            function triple(int64 x) internal returns (int64) {
+                return x * 3;
+            }
+
+            function inc(int64 x) public returns (int64) {
+                return x + 1;
+            }
+
+            function register(uint32 id) public {
+                handlers[id] = Handler({ f: double, ext: this.inc, calls: 0 });
+                callbacks[id] = this.inc;
+                ops.push(triple);
+                ops.push(double);
+                exts[1] = this.inc;
+            }
+
+            function dispatch(uint32 id, int64 x) public returns (int64) {
+                handlers[id].calls += 1;
+                int64 r = handlers[id].f(x);
+                r = handlers[id].ext(r);
+                r = callbacks[id](r);
+                r = ops[0](r);
+                r = ops[1](r);
+                return exts[1](r);
+            }
+
+            function calls(uint32 id) public view returns (uint32) {
+                return handlers[id].calls;
+            }
+        }"#,
+    );
+
+    vm.constructor("ft", &[]);
+
+    vm.function("register", &[Token::Uint(U256::from(7))], &[], None);
+
+    let res = vm.function(
+        "dispatch",
+        &[Token::Uint(U256::from(7)), Token::Int(U256::from(10))],
+        &[],
+        None,
+    );
+
+    // ((((10 * 2) + 1 + 1) * 3) * 2) + 1
+    assert_eq!(res, vec![Token::Int(U256::from(133))]);
+
+    let res = vm.function("calls", &[Token::Uint(U256::from(7))], &[], None);
+
+    assert_eq!(res, vec![Token::Uint(U256::from(1))]);
+}
+
 #[test]
 fn raw_call_accounts() {
     let mut vm = build_solidity(
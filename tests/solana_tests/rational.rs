@@ -150,3 +150,45 @@ fn rational() {
         ]
     );
 }
+
+#[test]
+fn fixed_point() {
+    let mut vm = build_solidity(
+        r#"
+        contract foo {
+            function wad(uint64 a, uint64 b) public returns (uint64) {
+                ufixed128x18 x = ufixed128x18(a) / 1000;
+                ufixed128x18 y = ufixed128x18(b) / 1000;
+
+                return uint64((x * y) * 1000);
+            }
+
+            function negate(int64 a) public returns (int64) {
+                fixed64x2 f = fixed64x2(a) * -1.5;
+
+                assert(f < 0 && f == -1.5 * 3);
+
+                return int64(f);
+            }
+        }"#,
+    );
+
+    vm.constructor("foo", &[]);
+
+    let returns = vm.function(
+        "wad",
+        &[
+            ethabi::Token::Uint(U256::from(1500)),
+            ethabi::Token::Uint(U256::from(2500)),
+        ],
+        &[],
+        None,
+    );
+
+    assert_eq!(returns, vec![ethabi::Token::Uint(U256::from(3750))]);
+
+    let returns = vm.function("negate", &[ethabi::Token::Int(U256::from(3))], &[], None);
+
+    // -4.5 is rounded towards zero
+    assert_eq!(returns, vec![ethabi::Token::Int(U256::MAX - 3)]);
+}
@@ -1,6 +1,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::build_solidity;
+use ethabi::{ethereum_types::U256, Token};
 
 #[test]
 fn using_for_contracts() {
@@ -77,3 +78,72 @@ fn using_for_contracts() {
 
     assert_eq!(runtime.logs, "X libX contractx:2");
 }
+
+#[test]
+fn user_defined_operators() {
+    let mut runtime = build_solidity(
+        r#"
+        type Fixed18 is int128;
+
+        using {add as +, sub as -, neg as -, mul as *, eq as ==, lt as <} for Fixed18 global;
+
+        function add(Fixed18 a, Fixed18 b) pure returns (Fixed18) {
+            return Fixed18.wrap(Fixed18.unwrap(a) + Fixed18.unwrap(b));
+        }
+
+        function sub(Fixed18 a, Fixed18 b) pure returns (Fixed18) {
+            return Fixed18.wrap(Fixed18.unwrap(a) - Fixed18.unwrap(b));
+        }
+
+        function neg(Fixed18 a) pure returns (Fixed18) {
+            return Fixed18.wrap(-Fixed18.unwrap(a));
+        }
+
+        function mul(Fixed18 a, Fixed18 b) pure returns (Fixed18) {
+            return Fixed18.wrap(Fixed18.unwrap(a) * Fixed18.unwrap(b) / 1e18);
+        }
+
+        function eq(Fixed18 a, Fixed18 b) pure returns (bool) {
+            return Fixed18.unwrap(a) == Fixed18.unwrap(b);
+        }
+
+        function lt(Fixed18 a, Fixed18 b) pure returns (bool) {
+            return Fixed18.unwrap(a) < Fixed18.unwrap(b);
+        }
+
+        contract C {
+            Fixed18 price = Fixed18.wrap(2e18);
+
+            function test(int128 a, int128 b) public view returns (int128, bool, bool) {
+                Fixed18 x = Fixed18.wrap(a);
+                Fixed18 y = Fixed18.wrap(b);
+
+                Fixed18 r = -(x + y) * price - y;
+
+                return (Fixed18.unwrap(r), x == y, x < y);
+            }
+        }"#,
+    );
+
+    runtime.constructor("C", &[]);
+
+    let returns = runtime.function(
+        "test",
+        &[
+            Token::Int(U256::from(3_000_000_000_000_000_000u128)),
+            Token::Int(U256::from(1_000_000_000_000_000_000u128)),
+        ],
+        &[],
+        None,
+    );
+
+    assert_eq!(
+        returns,
+        vec![
+            // -9e18
+            Token::Int(U256::MAX - U256::from(8_999_999_999_999_999_999u128)),
+            Token::Bool(false),
+            Token::Bool(false),
+        ]
+    );
+}
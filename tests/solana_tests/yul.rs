@@ -293,3 +293,232 @@ fn external_function() {
     let addr = returns[1].clone().into_fixed_bytes().unwrap();
     assert_eq!(addr[26], 90);
 }
+
+#[test]
+fn switch_statement() {
+    let mut vm = build_solidity(
+        r#"
+contract testing  {
+    function power(uint64 base, uint64 exponent) public pure returns (uint256 ret) {
+        assembly {
+            function power(b, e) -> result {
+                switch e
+                case 0 {
+                    result := 1
+                }
+                case 1 {
+                    result := b
+                }
+                default {
+                    result := power(mul(b, b), div(e, 2))
+                    switch mod(e, 2)
+                    case 1 {
+                        result := mul(b, result)
+                    }
+                }
+            }
+
+            ret := power(base, exponent)
+        }
+    }
+
+    function classify(uint64 a) public pure returns (uint64 ret) {
+        assembly {
+            switch a
+            case 0 { ret := 10 }
+            case 1 { ret := 11 }
+            case 2 { ret := 12 }
+            case 3 { ret := 13 }
+            case 0x61 { ret := 14 }
+            default { ret := 15 }
+        }
+    }
+}
+      "#,
+    );
+
+    vm.constructor("testing", &[]);
+
+    let returns = vm.function(
+        "power",
+        &[Token::Uint(Uint::from(3)), Token::Uint(Uint::from(0))],
+        &[],
+        None,
+    );
+    assert_eq!(returns, vec![Token::Uint(Uint::from(1))]);
+
+    let returns = vm.function(
+        "power",
+        &[Token::Uint(Uint::from(3)), Token::Uint(Uint::from(5))],
+        &[],
+        None,
+    );
+    assert_eq!(returns, vec![Token::Uint(Uint::from(243))]);
+
+    let returns = vm.function(
+        "power",
+        &[Token::Uint(Uint::from(2)), Token::Uint(Uint::from(64))],
+        &[],
+        None,
+    );
+    assert_eq!(
+        returns,
+        vec![Token::Uint(U256::from(2).pow(U256::from(64)))]
+    );
+
+    for (arg, expected) in [
+        (0, 10),
+        (1, 11),
+        (2, 12),
+        (3, 13),
+        (97, 14),
+        (4, 15),
+        (98, 15),
+    ] {
+        let returns = vm.function("classify", &[Token::Uint(Uint::from(arg))], &[], None);
+        assert_eq!(returns, vec![Token::Uint(Uint::from(expected))]);
+    }
+}
+
+#[test]
+fn memory_and_storage_builtins() {
+    let mut vm = build_solidity(
+        r#"
+contract testing  {
+    uint256 stored;
+
+    function set(uint256 val) public {
+        assembly {
+            sstore(stored.slot, val)
+        }
+    }
+
+    function get() public view returns (uint256 ret) {
+        assembly {
+            ret := sload(stored.slot)
+        }
+    }
+
+    function getter() public view returns (uint256) {
+        return stored;
+    }
+
+    function memory_access(uint256 val) public pure returns (uint256[] memory arr, uint256 ret) {
+        arr = new uint256[](2);
+        assembly {
+            // skip the length and size fields of the vector
+            let data := add(arr, 8)
+            mstore(data, val)
+            mstore8(add(data, 32), 0x1ff)
+            ret := mload(data)
+        }
+    }
+
+    function hash(bytes memory b) public pure returns (bool) {
+        uint256 h;
+        assembly {
+            h := keccak256(add(b, 8), and(mload(b), 0xffffffff))
+        }
+        return h == uint256(keccak256(b));
+    }
+}
+      "#,
+    );
+
+    vm.constructor("testing", &[]);
+
+    vm.function("set", &[Token::Uint(Uint::from(102))], &[], None);
+
+    let returns = vm.function("get", &[], &[], None);
+    assert_eq!(returns, vec![Token::Uint(Uint::from(102))]);
+
+    let returns = vm.function("getter", &[], &[], None);
+    assert_eq!(returns, vec![Token::Uint(Uint::from(102))]);
+
+    let returns = vm.function("memory_access", &[Token::Uint(Uint::from(7))], &[], None);
+    assert_eq!(
+        returns,
+        vec![
+            Token::Array(vec![
+                Token::Uint(Uint::from(7)),
+                Token::Uint(Uint::from(0xff))
+            ]),
+            Token::Uint(Uint::from(7))
+        ]
+    );
+
+    let returns = vm.function(
+        "hash",
+        &[Token::Bytes(b"Hello, World!".to_vec())],
+        &[],
+        None,
+    );
+    assert_eq!(returns, vec![Token::Bool(true)]);
+}
+
+#[test]
+fn calldata_suffix_assignment() {
+    let mut vm = build_solidity(
+        r#"
+contract testing {
+    function shrink(uint64[] calldata vl, uint32 len) public pure returns (uint64[] memory) {
+        assembly {
+            vl.length := len
+        }
+        return vl;
+    }
+
+    function swap(uint64[] calldata vl, uint64[] calldata vl2) public pure returns (uint64[] memory) {
+        assembly {
+            function pair(a, b) -> x, y {
+                x := a
+                y := b
+            }
+
+            vl.offset, vl.length := pair(vl2.offset, 1)
+        }
+        return vl;
+    }
+}
+      "#,
+    );
+
+    vm.constructor("testing", &[]);
+
+    let array = Token::Array(
+        (1..=4)
+            .map(|n| Token::Uint(U256::from(n)))
+            .collect::<Vec<Token>>(),
+    );
+
+    let returns = vm.function(
+        "shrink",
+        &[array.clone(), Token::Uint(U256::from(2))],
+        &[],
+        None,
+    );
+    assert_eq!(
+        returns,
+        vec![Token::Array(vec![
+            Token::Uint(U256::from(1)),
+            Token::Uint(U256::from(2))
+        ])]
+    );
+
+    let res = vm.function_must_fail("shrink", &[array, Token::Uint(U256::from(5))], &[], None);
+    assert_eq!(res, Ok(4294967296));
+
+    let returns = vm.function(
+        "swap",
+        &[
+            Token::Array(vec![Token::Uint(U256::from(1)), Token::Uint(U256::from(2))]),
+            Token::Array(vec![Token::Uint(U256::from(3)), Token::Uint(U256::from(4))]),
+        ],
+        &[],
+        None,
+    );
+    assert_eq!(
+        returns,
+        vec![Token::Array(vec![Token::Uint(U256::from(3))])]
+    );
+}
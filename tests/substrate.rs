@@ -80,6 +80,8 @@ enum SubstrateExternal {
     seal_tombstone_deposit,
     seal_deposit_event,
     seal_transfer,
+    seal_ecdsa_recover,
+    seal_sr25519_verify,
 }
 
 pub struct Event {
@@ -897,6 +899,108 @@ impl Externals for MockSubstrate {
 
                 Ok(None)
             }
+            Some(SubstrateExternal::seal_ecdsa_recover) => {
+                let signature_ptr: u32 = args.nth_checked(0)?;
+                let message_hash_ptr: u32 = args.nth_checked(1)?;
+                let output_ptr: u32 = args.nth_checked(2)?;
+
+                let mut signature = [0u8; 65];
+                let mut message_hash = [0u8; 32];
+
+                if let Err(e) = self.vm.memory.get_into(signature_ptr, &mut signature) {
+                    panic!("seal_ecdsa_recover: signature: {}", e);
+                }
+
+                if let Err(e) = self.vm.memory.get_into(message_hash_ptr, &mut message_hash) {
+                    panic!("seal_ecdsa_recover: message_hash: {}", e);
+                }
+
+                // the recovery id may be given as 27 or 28 like ethereum
+                let v = if signature[64] > 26 {
+                    signature[64] - 27
+                } else {
+                    signature[64]
+                };
+
+                let public_key = match (
+                    libsecp256k1::Signature::parse_standard_slice(&signature[..64]),
+                    libsecp256k1::RecoveryId::parse(v),
+                ) {
+                    (Ok(sig), Ok(recovery_id)) => libsecp256k1::recover(
+                        &libsecp256k1::Message::parse(&message_hash),
+                        &sig,
+                        &recovery_id,
+                    )
+                    .ok(),
+                    _ => None,
+                };
+
+                println!(
+                    "seal_ecdsa_recover: signature: {} message_hash: {} public_key: {:?}",
+                    hex::encode(&signature),
+                    hex::encode(&message_hash),
+                    public_key.map(|k| hex::encode(k.serialize_compressed()))
+                );
+
+                if let Some(public_key) = public_key {
+                    if let Err(e) = self
+                        .vm
+                        .memory
+                        .set(output_ptr, &public_key.serialize_compressed())
+                    {
+                        panic!("seal_ecdsa_recover: {}", e);
+                    }
+
+                    Ok(Some(RuntimeValue::I32(0)))
+                } else {
+                    // EcdsaRecoverFailed
+                    Ok(Some(RuntimeValue::I32(11)))
+                }
+            }
+            Some(SubstrateExternal::seal_sr25519_verify) => {
+                let signature_ptr: u32 = args.nth_checked(0)?;
+                let pub_key_ptr: u32 = args.nth_checked(1)?;
+                let message_len: u32 = args.nth_checked(2)?;
+                let message_ptr: u32 = args.nth_checked(3)?;
+
+                let mut signature = [0u8; 64];
+                let mut pub_key = [0u8; 32];
+                let mut message = Vec::new();
+
+                message.resize(message_len as usize, 0);
+
+                if let Err(e) = self.vm.memory.get_into(signature_ptr, &mut signature) {
+                    panic!("seal_sr25519_verify: signature: {}", e);
+                }
+
+                if let Err(e) = self.vm.memory.get_into(pub_key_ptr, &mut pub_key) {
+                    panic!("seal_sr25519_verify: pub_key: {}", e);
+                }
+
+                if let Err(e) = self.vm.memory.get_into(message_ptr, &mut message) {
+                    panic!("seal_sr25519_verify: message: {}", e);
+                }
+
+                let verified = match (
+                    schnorrkel::PublicKey::from_bytes(&pub_key),
+                    schnorrkel::Signature::from_bytes(&signature),
+                ) {
+                    (Ok(pub_key), Ok(signature)) => pub_key
+                        .verify_simple(b"substrate", &message, &signature)
+                        .is_ok(),
+                    _ => false,
+                };
+
+                println!(
+                    "seal_sr25519_verify: pub_key: {} message: {} verified: {}",
+                    hex::encode(&pub_key),
+                    hex::encode(&message),
+                    verified
+                );
+
+                // Sr25519VerifyFailed
+                Ok(Some(RuntimeValue::I32(if verified { 0 } else { 12 })))
+            }
             _ => panic!("external {} unknown", index),
         }
     }
@@ -931,6 +1035,8 @@ impl ModuleImportResolver for MockSubstrate {
             "seal_tombstone_deposit" => SubstrateExternal::seal_tombstone_deposit,
             "seal_deposit_event" => SubstrateExternal::seal_deposit_event,
             "seal_transfer" => SubstrateExternal::seal_transfer,
+            "seal_ecdsa_recover" => SubstrateExternal::seal_ecdsa_recover,
+            "seal_sr25519_verify" => SubstrateExternal::seal_sr25519_verify,
             _ => {
                 panic!("{} not implemented", field_name);
             }
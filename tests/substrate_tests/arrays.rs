@@ -1459,3 +1459,51 @@ fn alloc_size_from_storage() {
     runtime.function("contfunc", Vec::new());
     assert_eq!(runtime.vm.output, vec![0u64].encode());
 }
+
+#[test]
+fn array_slice() {
+    let mut runtime = build_solidity(
+        r#"
+        contract proxy {
+            function tail(bytes calldata data) public returns (bytes memory) {
+                return data[4:];
+            }
+
+            function window(uint32[] memory a, uint32 start, uint32 end) public returns (uint32[] memory) {
+                return a[start:end];
+            }
+
+            function sum(uint32[] memory a, uint32 start) public returns (uint32 total) {
+                uint32[] memory s = a[start:];
+
+                for (uint32 i = 0; i < s.length; i++) {
+                    total += a[start:][i];
+                }
+            }
+        }"#,
+    );
+
+    runtime.constructor(0, Vec::new());
+
+    runtime.function("tail", vec![0xdeu8, 0xad, 0xbe, 0xef, 1, 2, 3].encode());
+    assert_eq!(runtime.vm.output, vec![1u8, 2, 3].encode());
+
+    runtime.function("tail", vec![0xdeu8, 0xad, 0xbe, 0xef].encode());
+    assert_eq!(runtime.vm.output, Vec::<u8>::new().encode());
+
+    runtime.function_expect_failure("tail", vec![0xdeu8, 0xad].encode());
+
+    let a = vec![1u32, 2, 3, 4, 5];
+
+    runtime.function("window", (a.clone(), 1u32, 4u32).encode());
+    assert_eq!(runtime.vm.output, vec![2u32, 3, 4].encode());
+
+    runtime.function("window", (a.clone(), 5u32, 5u32).encode());
+    assert_eq!(runtime.vm.output, Vec::<u32>::new().encode());
+
+    runtime.function_expect_failure("window", (a.clone(), 3u32, 6u32).encode());
+    runtime.function_expect_failure("window", (a.clone(), 4u32, 3u32).encode());
+
+    runtime.function("sum", (a, 2u32).encode());
+    assert_eq!(runtime.vm.output, 12u32.encode());
+}
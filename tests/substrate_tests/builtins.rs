@@ -78,6 +78,110 @@ fn abi_encode() {
     runtime.heap_verify();
 }
 
+#[test]
+fn abi_encode_returned() {
+    #[derive(Encode)]
+    struct S {
+        f1: i32,
+        f2: Vec<u16>,
+        f3: String,
+        f4: [u8; 3],
+    }
+
+    let mut runtime = build_solidity(
+        r##"
+        struct S {
+            int32 f1;
+            uint16[] f2;
+            string f3;
+            bytes3 f4;
+        }
+
+        enum E { A, B, C }
+
+        contract bar {
+            string s;
+
+            function primitives() public pure returns (bytes) {
+                return abi.encode(int8(-2), uint24(0x123456), int40(-0x1020304050), E.C, true);
+            }
+
+            function dynamic(uint32 len) public pure returns (bytes) {
+                string long = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod";
+                return abi.encode(long, new bytes(len));
+            }
+
+            function arrays() public pure returns (bytes) {
+                uint16[] a = new uint16[](3);
+                a[0] = 1;
+                a[1] = 0xffff;
+                a[2] = 300;
+                int64[2] b = [int64(-1), 0x7fff_ffff_ffff_ffff];
+                return abi.encode(a, b);
+            }
+
+            function structs() public pure returns (bytes) {
+                S x = S({ f1: -50, f2: new uint16[](2), f3: "foo", f4: hex"abcdef" });
+                x.f2[1] = 7;
+                return abi.encode(x, x.f1);
+            }
+
+            function storage() public returns (bytes) {
+                s = "storage string";
+                return abi.encode(s);
+            }
+        }"##,
+    );
+
+    runtime.function("primitives", Vec::new());
+    assert_eq!(
+        runtime.vm.output,
+        (-2i8, 0x123456u32, -0x1020304050i64, 2u8, true)
+            .encode()
+            .encode()
+    );
+
+    let long = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod";
+
+    // the length of the string and of the bytes need a two byte and a four byte compact length
+    for len in [0u32, 17, 17000] {
+        runtime.function("dynamic", len.encode());
+        assert_eq!(
+            runtime.vm.output,
+            (long, vec![0u8; len as usize]).encode().encode()
+        );
+    }
+
+    runtime.function("arrays", Vec::new());
+    assert_eq!(
+        runtime.vm.output,
+        (vec![1u16, 0xffff, 300], [-1i64, 0x7fff_ffff_ffff_ffff])
+            .encode()
+            .encode()
+    );
+
+    runtime.function("structs", Vec::new());
+    assert_eq!(
+        runtime.vm.output,
+        (
+            S {
+                f1: -50,
+                f2: vec![0, 7],
+                f3: String::from("foo"),
+                f4: [0xab, 0xcd, 0xef],
+            },
+            -50i32
+        )
+            .encode()
+            .encode()
+    );
+
+    runtime.function("storage", Vec::new());
+    assert_eq!(runtime.vm.output, ("storage string",).encode().encode());
+
+    runtime.heap_verify();
+}
+
 #[test]
 fn abi_encode_packed() {
     let mut runtime = build_solidity(
@@ -719,3 +823,107 @@ fn my_token() {
     );
     assert_eq!(&runtime.vm.caller[..], &runtime.vm.output[..]);
 }
+
+#[test]
+fn ecrecover() {
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    struct Recover([u8; 32], u8, [u8; 32], [u8; 32]);
+
+    let mut runtime = build_solidity(
+        r##"
+        contract tester {
+            function recover(bytes32 hash, uint8 v, bytes32 r, bytes32 s) public returns (address) {
+                return ecrecover(hash, v, r, s);
+            }
+        }"##,
+    );
+
+    let secret_key = libsecp256k1::SecretKey::parse(&[7u8; 32]).unwrap();
+    let public_key = libsecp256k1::PublicKey::from_secret_key(&secret_key);
+
+    // the account id of an ecdsa key is the blake2_256 of the compressed public key
+    let signer = blake2_rfc::blake2b::blake2b(32, &[], &public_key.serialize_compressed());
+
+    let message_hash = [0xbeu8; 32];
+
+    let (signature, recovery_id) =
+        libsecp256k1::sign(&libsecp256k1::Message::parse(&message_hash), &secret_key);
+
+    let signature = signature.serialize();
+
+    let mut r = [0u8; 32];
+    let mut s = [0u8; 32];
+
+    r.copy_from_slice(&signature[..32]);
+    s.copy_from_slice(&signature[32..]);
+
+    runtime.function(
+        "recover",
+        Recover(message_hash, recovery_id.serialize() + 27, r, s).encode(),
+    );
+
+    assert_eq!(&runtime.vm.output[..], signer.as_bytes());
+
+    // invalid recovery id results in the zero address
+    runtime.function("recover", Recover(message_hash, 5, r, s).encode());
+
+    assert_eq!(runtime.vm.output, [0u8; 32].to_vec());
+}
+
+#[test]
+fn signature_verify() {
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    struct Verify([u8; 32], Vec<u8>, Vec<u8>);
+
+    let mut runtime = build_solidity(
+        r##"
+        contract tester {
+            function verify(address key, bytes message, bytes signature) public returns (bool) {
+                return signatureVerify(key, message, signature);
+            }
+        }"##,
+    );
+
+    let keypair = schnorrkel::MiniSecretKey::from_bytes(&[7u8; 32])
+        .unwrap()
+        .expand_to_keypair(schnorrkel::ExpansionMode::Ed25519);
+
+    let message = b"Hello, World!".to_vec();
+
+    let signature = keypair
+        .sign_simple(b"substrate", &message)
+        .to_bytes()
+        .to_vec();
+
+    runtime.function(
+        "verify",
+        Verify(
+            keypair.public.to_bytes(),
+            message.clone(),
+            signature.clone(),
+        )
+        .encode(),
+    );
+
+    assert_eq!(runtime.vm.output, true.encode());
+
+    runtime.function(
+        "verify",
+        Verify(
+            keypair.public.to_bytes(),
+            b"Hello, World?".to_vec(),
+            signature,
+        )
+        .encode(),
+    );
+
+    assert_eq!(runtime.vm.output, false.encode());
+
+    // signatures must be 64 bytes
+    runtime.function(
+        "verify",
+        Verify(keypair.public.to_bytes(), message, vec![1, 2, 3]).encode(),
+    );
+
+    assert_eq!(runtime.vm.output, false.encode());
+}
@@ -1442,6 +1442,197 @@ fn unchecked_multiplication_overflow() {
     runtime.function("bar", Vec::new());
 }
 
+#[test]
+#[should_panic]
+fn wide_addition_overflow() {
+    let mut runtime = build_solidity_with_overflow_check(
+        r#"
+        contract overflow {
+            function bar(uint128 x) public returns (uint256) {
+                uint256 y = type(uint256).max - x;
+                return y + 2;
+            }
+        }
+        "#,
+    );
+
+    runtime.function("bar", 1u128.encode());
+}
+
+#[test]
+#[should_panic]
+fn wide_subtraction_underflow() {
+    let mut runtime = build_solidity_with_overflow_check(
+        r#"
+        contract underflow {
+            function bar(int128 x) public returns (int128) {
+                return type(int128).min - x;
+            }
+        }
+        "#,
+    );
+
+    runtime.function("bar", 1i128.encode());
+}
+
+#[test]
+#[should_panic]
+fn wide_multiplication_overflow() {
+    let mut runtime = build_solidity_with_overflow_check(
+        r#"
+        contract overflow {
+            function bar(uint128 x) public returns (uint256) {
+                uint256 y = uint256(x) * uint256(x);
+                return y * y;
+            }
+        }
+        "#,
+    );
+
+    runtime.function("bar", (1u128 << 64).encode());
+}
+
+#[test]
+fn wide_multiplication() {
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    struct Args(i128, i128);
+
+    let mut runtime = build_solidity_with_overflow_check(
+        r#"
+        contract mul {
+            function bar(int128 a, int128 b) public returns (int128) {
+                int256 r = int256(a) * int256(b) * int256(b);
+                return int128(r / (int256(b) * int256(b)));
+            }
+
+            function baz(uint128 x) public returns (uint256) {
+                unchecked {
+                    uint256 y = uint256(x) * uint256(x);
+                    return y * y;
+                }
+            }
+        }
+        "#,
+    );
+
+    runtime.function("bar", Args(-3, 1 << 62).encode());
+    assert_eq!(runtime.vm.output, (-3i128).encode());
+
+    runtime.function("baz", (1u128 << 64).encode());
+    assert_eq!(runtime.vm.output, [0u8; 32]);
+}
+
+#[test]
+#[should_panic]
+fn power_overflow() {
+    let mut runtime = build_solidity_with_overflow_check(
+        r#"
+        contract overflow {
+            function bar(uint128 x) public returns (uint256) {
+                return uint256(x) ** 256;
+            }
+        }
+        "#,
+    );
+
+    runtime.function("bar", 2u128.encode());
+}
+
+#[test]
+fn unchecked_power_overflow() {
+    let mut runtime = build_solidity_with_overflow_check(
+        r#"
+        contract overflow {
+            function bar(uint128 x) public returns (uint256) {
+                unchecked {
+                    return uint256(x) ** 255;
+                }
+            }
+
+            function baz(uint128 x) public returns (uint256) {
+                unchecked {
+                    return uint256(x) ** 256;
+                }
+            }
+        }
+        "#,
+    );
+
+    runtime.function("bar", 2u128.encode());
+
+    let mut expected = [0u8; 32];
+    expected[31] = 0x80;
+    assert_eq!(runtime.vm.output, expected);
+
+    runtime.function("baz", 2u128.encode());
+    assert_eq!(runtime.vm.output, [0u8; 32]);
+}
+
+#[test]
+#[should_panic]
+fn checked_by_default_overflow() {
+    let mut runtime = build_solidity(
+        r#"
+        pragma solidity ^0.8.0;
+
+        contract overflow {
+            function bar(uint64 x) public returns (uint64) {
+                return x + 1;
+            }
+        }
+        "#,
+    );
+
+    runtime.function("bar", u64::MAX.encode());
+}
+
+#[test]
+fn checked_by_default_unchecked_block() {
+    let mut runtime = build_solidity(
+        r#"
+        pragma solidity >=0.8.0 <0.9.0;
+
+        contract overflow {
+            function bar(uint64 x) public returns (uint64) {
+                unchecked {
+                    return x + 1;
+                }
+            }
+
+            function baz(uint64 x) public returns (uint64) {
+                return x - 1;
+            }
+        }
+        "#,
+    );
+
+    runtime.function("bar", u64::MAX.encode());
+    assert_eq!(runtime.vm.output, 0u64.encode());
+
+    runtime.function("baz", 1u64.encode());
+    assert_eq!(runtime.vm.output, 0u64.encode());
+
+    runtime.function_expect_failure("baz", 0u64.encode());
+}
+
+#[test]
+fn unchecked_before_0_8() {
+    let mut runtime = build_solidity(
+        r#"
+        pragma solidity ^0.7.0;
+
+        contract overflow {
+            function bar(uint64 x) public returns (uint64) {
+                return x + 1;
+            }
+        }
+        "#,
+    );
+
+    runtime.function("bar", u64::MAX.encode());
+    assert_eq!(runtime.vm.output, 0u64.encode());
+}
+
 #[test]
 fn address_compare() {
     #[derive(Debug, PartialEq, Encode, Decode)]
@@ -330,3 +330,70 @@ fn ext() {
     runtime.function("test1", Vec::new());
     runtime.function("test2", Vec::new());
 }
+
+#[test]
+fn function_types_in_storage_containers() {
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    struct Args(u32, i64);
+
+    let mut runtime = build_solidity(
+        r##"
+        contract ft {
+            struct Handler {
+                function(int64) internal returns (int64) f;
+                function(int64) external returns (int64) ext;
+                uint32 calls;
+            }
+
+            mapping(uint32 => Handler) handlers;
+            mapping(uint32 => function(int64) external returns (int64)) callbacks;
+            function(int64) internal returns (int64)[] ops;
+            function(int64) external returns (int64)[2] exts;
+
+            function double(int64 x) internal returns (int64) {
+                return x * 2;
+            }
+
+            function negate(int64 x) internal returns (int64) {
+                return -x;
+            }
+
+            function inc(int64 x) public returns (int64) {
+                return x + 1;
+            }
+
+            function register(uint32 id) public {
+                handlers[id] = Handler({ f: double, ext: this.inc, calls: 0 });
+                callbacks[id] = this.inc;
+                ops.push(negate);
+                ops.push(double);
+                exts[1] = this.inc;
+            }
+
+            function dispatch(uint32 id, int64 x) public returns (int64) {
+                handlers[id].calls += 1;
+                int64 r = handlers[id].f(x);
+                r = handlers[id].ext(r);
+                r = callbacks[id](r);
+                r = ops[0](r);
+                r = ops[1](r);
+                return exts[1](r);
+            }
+
+            function calls(uint32 id) public view returns (uint32) {
+                return handlers[id].calls;
+            }
+        }"##,
+    );
+
+    runtime.function("register", 7u32.encode());
+
+    runtime.function("dispatch", Args(7, 10).encode());
+
+    // ((((10 * 2) + 1 + 1) * -1) * 2) + 1
+    assert_eq!(runtime.vm.output, (-43i64).encode());
+
+    runtime.function("calls", 7u32.encode());
+
+    assert_eq!(runtime.vm.output, 1u32.encode());
+}
@@ -391,3 +391,110 @@ fn signed_literal_unsigned_cast() {
 
     runtime.function("foo", Vec::new());
 }
+
+#[test]
+fn fixed_point() {
+    let mut runtime = build_solidity(
+        r##"
+        contract oracle {
+            ufixed128x18 price = 1.5;
+
+            function value(ufixed128x18 amount) public view returns (ufixed128x18) {
+                return amount * price;
+            }
+
+            function set_price(ufixed128x18 p) public {
+                price = p;
+            }
+
+            function ratio(fixed64x4 a, fixed64x4 b) public pure returns (fixed64x4) {
+                return a / b;
+            }
+
+            function round_trip(int32 a) public pure returns (int32) {
+                fixed64x2 f = fixed64x2(a) * 0.5;
+
+                assert(f * 2 == fixed64x2(a));
+                assert(fixed64x2(fixed64x4(f)) == f);
+
+                return int32(f);
+            }
+        }"##,
+    );
+
+    runtime.constructor(0, Vec::new());
+
+    runtime.function("value", 2_000_000_000_000_000_000u128.encode());
+
+    assert_eq!(runtime.vm.output, 3_000_000_000_000_000_000u128.encode());
+
+    runtime.function("set_price", 250_000_000_000_000_000u128.encode());
+    runtime.function("value", 2_000_000_000_000_000_000u128.encode());
+
+    assert_eq!(runtime.vm.output, 500_000_000_000_000_000u128.encode());
+
+    runtime.function("ratio", (-75_000i64, 25_000i64).encode());
+
+    assert_eq!(runtime.vm.output, (-30_000i64).encode());
+
+    runtime.function("round_trip", 7i32.encode());
+
+    assert_eq!(runtime.vm.output, 3i32.encode());
+}
+
+#[test]
+fn fixed_point_overflow() {
+    let mut runtime = build_solidity(
+        r##"
+        pragma solidity ^0.8.0;
+
+        contract c {
+            function mul(ufixed64x2 a, ufixed64x2 b) public pure returns (ufixed64x2) {
+                return a * b;
+            }
+
+            function div(fixed64x2 a, fixed64x2 b) public pure returns (fixed64x2) {
+                return a / b;
+            }
+
+            function mul_unchecked(ufixed64x2 a, ufixed64x2 b) public pure returns (ufixed64x2) {
+                unchecked {
+                    return a * b;
+                }
+            }
+
+            function div_unchecked(fixed64x2 a, fixed64x2 b) public pure returns (fixed64x2) {
+                unchecked {
+                    return a / b;
+                }
+            }
+        }"##,
+    );
+
+    runtime.constructor(0, Vec::new());
+
+    // 2^32 * 2^32 does not fit into 64 bits
+    runtime.function_expect_failure("mul", (429_496_729_600u64, 429_496_729_600u64).encode());
+
+    runtime.function("mul", (150u64, 300u64).encode());
+
+    assert_eq!(runtime.vm.output, 450u64.encode());
+
+    runtime.function_expect_failure("div", (i64::MIN, 1i64).encode());
+
+    runtime.function("div", (-300i64, 150i64).encode());
+
+    assert_eq!(runtime.vm.output, (-200i64).encode());
+
+    runtime.function(
+        "mul_unchecked",
+        (429_496_729_600u64, 429_496_729_600u64).encode(),
+    );
+
+    assert_eq!(runtime.vm.output, 0u64.encode());
+
+    // -2^63 * 100 wraps to 0
+    runtime.function("div_unchecked", (i64::MIN, 1i64).encode());
+
+    assert_eq!(runtime.vm.output, 0i64.encode());
+}
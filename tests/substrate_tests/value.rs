@@ -553,3 +553,42 @@ fn send_and_transfer() {
         assert_eq!(account.1, 1011);
     }
 }
+
+#[test]
+fn legacy_payable_fallback() {
+    let mut runtime = build_solidity(
+        r##"
+        pragma solidity ^0.5.0;
+
+        contract c {
+            other o;
+
+            constructor() public {
+                o = new other();
+            }
+
+            function step1() public returns (bool) {
+                return payable(o).send(511);
+            }
+        }
+
+        contract other {
+            function() external payable {
+            }
+        }"##,
+    );
+
+    runtime.constructor(0, Vec::new());
+
+    runtime.function("step1", Vec::new());
+
+    assert_eq!(runtime.vm.output, true.encode());
+
+    for (address, account) in runtime.accounts {
+        if address == runtime.vm.account {
+            continue;
+        }
+
+        assert_eq!(account.1, 1011);
+    }
+}
@@ -1,6 +1,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::build_solidity;
+use parity_scale_codec::Encode;
 
 #[test]
 fn global_constants() {
@@ -32,3 +33,47 @@ fn global_constants() {
 
     runtime.function("test", Vec::new());
 }
+
+#[test]
+fn immutables() {
+    let mut runtime = build_solidity(
+        r##"
+        contract a {
+            uint64 immutable x;
+            bool immutable b = true;
+            int32 immutable y;
+
+            constructor(uint64 v) {
+                x = v;
+                y = -102;
+
+                assembly {
+                    setimmutable(0, "y", sub(0, 2))
+                }
+            }
+
+            function test() public view {
+                assert(x == 0x1234);
+                assert(b);
+                assert(y == -2);
+            }
+
+            function get() public view returns (uint64 r) {
+                assembly {
+                    r := loadimmutable("x")
+                }
+            }
+        }"##,
+    );
+
+    runtime.constructor(0, 0x1234u64.encode());
+
+    // the immutables are not kept in contract storage slots
+    assert_eq!(runtime.store.len(), 1);
+
+    runtime.function("test", Vec::new());
+
+    runtime.function("get", Vec::new());
+
+    assert_eq!(runtime.vm.output, 0x1234u64.encode());
+}
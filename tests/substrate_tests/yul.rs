@@ -108,3 +108,284 @@ contract testing  {
     runtime.function("storage_struct", Val256(U256::from(17)).encode());
     assert_eq!(runtime.vm.output, Val256(U256::from(24)).encode());
 }
+
+#[test]
+fn switch_statement() {
+    let mut runtime = build_solidity(
+        r#"
+contract testing  {
+    function power(uint64 base, uint64 exponent) public pure returns (uint256 ret) {
+        assembly {
+            function power(b, e) -> result {
+                switch e
+                case 0 {
+                    result := 1
+                }
+                case 1 {
+                    result := b
+                }
+                default {
+                    result := power(mul(b, b), div(e, 2))
+                    switch mod(e, 2)
+                    case 1 {
+                        result := mul(b, result)
+                    }
+                }
+            }
+
+            ret := power(base, exponent)
+        }
+    }
+
+    function classify(uint64 a) public pure returns (uint64 ret) {
+        assembly {
+            switch a
+            case 0 { ret := 10 }
+            case 1 { ret := 11 }
+            case 2 { ret := 12 }
+            case 3 { ret := 13 }
+            case 0x61 { ret := 14 }
+            default { ret := 15 }
+        }
+    }
+}
+      "#,
+    );
+
+    runtime.function("power", (3u64, 0u64).encode());
+    assert_eq!(runtime.vm.output, Val256(U256::from(1)).encode());
+
+    runtime.function("power", (3u64, 5u64).encode());
+    assert_eq!(runtime.vm.output, Val256(U256::from(243)).encode());
+
+    runtime.function("power", (2u64, 64u64).encode());
+    assert_eq!(
+        runtime.vm.output,
+        Val256(U256::from(2).pow(U256::from(64))).encode()
+    );
+
+    for (arg, expected) in [
+        (0u64, 10u64),
+        (1, 11),
+        (2, 12),
+        (3, 13),
+        (97, 14),
+        (4, 15),
+        (98, 15),
+    ] {
+        runtime.function("classify", arg.encode());
+        assert_eq!(runtime.vm.output, expected.encode());
+    }
+}
+
+#[test]
+fn memory_and_storage_builtins() {
+    let mut runtime = build_solidity(
+        r#"
+contract testing  {
+    uint256 stored;
+
+    function set(uint256 val) public {
+        assembly {
+            sstore(stored.slot, val)
+        }
+    }
+
+    function get() public view returns (uint256 ret) {
+        assembly {
+            ret := sload(stored.slot)
+        }
+    }
+
+    function getter() public view returns (uint256) {
+        return stored;
+    }
+
+    function memory_access(uint256 val) public pure returns (uint256[] memory arr, uint256 ret) {
+        arr = new uint256[](2);
+        assembly {
+            // skip the length and size fields of the vector
+            let data := add(arr, 8)
+            mstore(data, val)
+            mstore8(add(data, 32), 0x1ff)
+            ret := mload(data)
+        }
+    }
+
+    function hash(bytes memory b) public pure returns (bool) {
+        uint256 h;
+        assembly {
+            h := keccak256(add(b, 8), and(mload(b), 0xffffffff))
+        }
+        return h == uint256(keccak256(b));
+    }
+
+    function call_data(uint256 val) public pure returns (uint256 size, uint256 ret) {
+        assembly {
+            size := calldatasize()
+            ret := calldataload(4)
+        }
+    }
+}
+      "#,
+    );
+
+    runtime.function("set", Val256(U256::from(102)).encode());
+
+    runtime.function("get", Vec::new());
+    assert_eq!(runtime.vm.output, Val256(U256::from(102)).encode());
+
+    runtime.function("getter", Vec::new());
+    assert_eq!(runtime.vm.output, Val256(U256::from(102)).encode());
+
+    runtime.function("memory_access", Val256(U256::from(7)).encode());
+    assert_eq!(
+        runtime.vm.output,
+        (
+            vec![Val256(U256::from(7)), Val256(U256::from(0xff))],
+            Val256(U256::from(7))
+        )
+            .encode()
+    );
+
+    runtime.function("hash", b"Hello, World!".to_vec().encode());
+    assert_eq!(runtime.vm.output, true.encode());
+
+    runtime.function("call_data", Val256(U256::from(300)).encode());
+    assert_eq!(
+        runtime.vm.output,
+        (Val256(U256::from(36)), Val256(U256::from(300))).encode()
+    );
+}
+
+#[test]
+fn calldata_out_of_bounds() {
+    let mut runtime = build_solidity(
+        r#"
+contract testing {
+    function load(uint256 offset) public pure returns (uint256 ret) {
+        assembly {
+            ret := calldataload(offset)
+        }
+    }
+
+    function copy(uint256 offset, uint256 length) public pure returns (uint256 ret) {
+        bytes memory buf = new bytes(64);
+        assembly {
+            // skip the length and size fields of the vector
+            let data := add(buf, 8)
+            calldatacopy(data, offset, length)
+            ret := mload(data)
+        }
+    }
+}
+      "#,
+    );
+
+    runtime.function("load", Val256(U256::from(4)).encode());
+    assert_eq!(runtime.vm.output, Val256(U256::from(4)).encode());
+
+    // offsets which do not fit into 32 bits must not be truncated
+    runtime.function_expect_failure("load", Val256(U256::from(1u64 << 32)).encode());
+    runtime.function_expect_failure(
+        "load",
+        Val256(U256::from(1u64 << 32) + U256::from(4)).encode(),
+    );
+
+    runtime.function(
+        "copy",
+        (Val256(U256::from(4)), Val256(U256::from(64))).encode(),
+    );
+    assert_eq!(runtime.vm.output, Val256(U256::from(4)).encode());
+
+    // offset + length does not fit into 32 bits
+    runtime.function_expect_failure(
+        "copy",
+        (Val256(U256::from(0xffff_ffffu64)), Val256(U256::from(2))).encode(),
+    );
+    runtime.function_expect_failure(
+        "copy",
+        (Val256(U256::from(4)), Val256(U256::from(1u64 << 32))).encode(),
+    );
+    runtime.function_expect_failure(
+        "copy",
+        (Val256(U256::from(1u64 << 32)), Val256(U256::zero())).encode(),
+    );
+}
+
+#[test]
+fn calldata_suffix_assignment() {
+    let mut runtime = build_solidity(
+        r#"
+contract testing {
+    function shrink(uint64[] calldata vl, uint32 len) public pure returns (uint64[] memory) {
+        assembly {
+            vl.length := len
+        }
+        return vl;
+    }
+
+    function swap(uint64[] calldata vl, uint64[] calldata vl2) public pure returns (uint64[] memory) {
+        assembly {
+            function pair(a, b) -> x, y {
+                x := a
+                y := b
+            }
+
+            vl.offset, vl.length := pair(vl2.offset, 1)
+        }
+        return vl;
+    }
+}
+      "#,
+    );
+
+    runtime.function("shrink", (vec![1u64, 2, 3, 4], 2u32).encode());
+    assert_eq!(runtime.vm.output, vec![1u64, 2].encode());
+
+    runtime.function("shrink", (vec![1u64, 2, 3, 4], 0u32).encode());
+    assert_eq!(runtime.vm.output, Vec::<u64>::new().encode());
+
+    runtime.function_expect_failure("shrink", (vec![1u64, 2, 3, 4], 5u32).encode());
+
+    runtime.function("swap", (vec![1u64, 2], vec![3u64, 4]).encode());
+    assert_eq!(runtime.vm.output, vec![3u64].encode());
+}
+
+#[test]
+fn memory_length_assignment() {
+    let mut runtime = build_solidity(
+        r#"
+contract testing {
+    function shrink(uint32 len) public pure returns (uint256, uint64[] memory) {
+        uint64[] memory vl = new uint64[](4);
+        for (uint64 i = 0; i < 4; i++) {
+            vl[i] = i + 10;
+        }
+        assembly {
+            vl.length := len
+        }
+        uint64 sum = 0;
+        for (uint32 i = 0; i < vl.length; i++) {
+            sum += vl[i];
+        }
+        return (vl.length + sum * 100, vl);
+    }
+}
+      "#,
+    );
+
+    runtime.function("shrink", 2u32.encode());
+    assert_eq!(
+        runtime.vm.output,
+        (Val256(U256::from(2102)), vec![10u64, 11]).encode()
+    );
+
+    runtime.function("shrink", 0u32.encode());
+    assert_eq!(
+        runtime.vm.output,
+        (Val256(U256::zero()), Vec::<u64>::new()).encode()
+    );
+
+    runtime.function_expect_failure("shrink", 5u32.encode());
+}
@@ -17,6 +17,9 @@ fn parse_and_codegen(src: &'static str) -> Namespace {
         strength_reduce: false,
         vector_to_slice: false,
         common_subexpression_elimination: false,
+        function_inlining: true,
+        loop_invariant_code_motion: true,
+        dead_code_elimination: true,
         opt_level: OptimizationLevel::Default,
         math_overflow_check: false,
     };
@@ -731,3 +734,60 @@ fn try_catch() {
         "Variable read before being defined"
     );
 }
+
+#[test]
+fn inline_assembly() {
+    let file = r#"
+    contract Test {
+        function yulFunction(uint256 c) public pure returns (uint256 ret) {
+            assembly {
+                function sum(a) -> r {
+                    let x
+                    if gt(a, 2) {
+                        x := a
+                    }
+                    r := add(x, a)
+                }
+
+                ret := sum(c)
+            }
+        }
+
+        function yulBlock(uint256 c) public pure returns (uint256 ret) {
+            uint256 d;
+            assembly {
+                let x
+                switch c
+                case 1 {
+                    x := 5
+                }
+                default {
+                    x := 6
+                }
+                let y
+                mstore(0, y)
+                ret := add(x, d)
+            }
+        }
+    }
+    "#;
+
+    let ns = parse_and_codegen(file);
+    let errors = ns.diagnostics.errors();
+    assert_eq!(errors.len(), 3);
+    assert!(contains_error_message_and_notes(
+        &errors,
+        "Variable 'x' is undefined",
+        1
+    ));
+    assert!(contains_error_message_and_notes(
+        &errors,
+        "Variable 'y' is undefined",
+        1
+    ));
+    assert!(contains_error_message_and_notes(
+        &errors,
+        "Variable 'd' is undefined",
+        1
+    ));
+}